@@ -38,7 +38,7 @@ var bountyEscrowErrors = map[uint32]contractErrorEntry{
 	9:  {"FeeRecipientNotSet", "Fee recipient address has not been configured"},
 	10: {"InvalidBatchSize", "Batch size is invalid (must be between 1 and 20)"},
 	11: {"BatchSizeMismatch", "Number of bounty IDs does not match the number of recipients"},
-	12: {"DuplicateBountyId", "Duplicate bounty ID found in batch"},
+	12: {"DuplicateBountyId", "Bounty ID is already in use or repeated in a batch"},
 	13: {"InvalidAmount", "Bounty amount is invalid (zero, negative, or exceeds available)"},
 	14: {"InvalidDeadline", "Bounty deadline is invalid (in the past or too far in the future)"},
 	// 15 is intentionally absent in the contract enum.
@@ -1,11 +1,50 @@
-use soroban_sdk::{contracttype, symbol_short, Address, Env};
+use soroban_sdk::{contracttype, symbol_short, Address, Bytes, BytesN, Env, String, Symbol, Vec};
+
+use crate::ConfigKey;
 
 pub const EVENT_VERSION_V2: u32 = 2;
 
+/// Claim the next value of the contract-wide event counter.
+///
+/// Every event payload carries the returned `seq`, so indexers can detect
+/// gaps or reordering. Call it exactly once per published event, right where
+/// the payload is built.
+pub fn next_event_seq(env: &Env) -> u64 {
+    let seq: u64 = env
+        .storage()
+        .instance()
+        .get(&ConfigKey::EventSeq)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&ConfigKey::EventSeq, &(seq + 1));
+    seq
+}
+
+/// Which events the contract publishes. Lifecycle, fee and admin events
+/// always go out; `Verbose`, the default, adds the auxiliary ones (depositor
+/// index updates, monitoring metrics and stats snapshots).
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EventLevel {
+    Minimal,
+    Verbose,
+}
+
+/// Whether auxiliary events should be published under the configured level.
+pub fn verbose(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&ConfigKey::EventLevel)
+        .unwrap_or(EventLevel::Verbose)
+        == EventLevel::Verbose
+}
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct BountyEscrowInitialized {
     pub version: u32,
+    pub seq: u64,
     pub admin: Address,
     pub token: Address,
     pub timestamp: u64,
@@ -20,29 +59,100 @@ pub fn emit_bounty_initialized(env: &Env, event: BountyEscrowInitialized) {
 #[derive(Clone, Debug)]
 pub struct FundsLocked {
     pub version: u32,
+    pub seq: u64,
     pub bounty_id: u64,
     pub amount: i128,
     pub depositor: Address,
     pub deadline: u64,
 }
 
+/// Topics are `("f_lock", bounty_id, depositor)` so indexers can subscribe to
+/// a single depositor's locks.
 pub fn emit_funds_locked(env: &Env, event: FundsLocked) {
-    let topics = (symbol_short!("f_lock"), event.bounty_id);
+    let topics = (
+        symbol_short!("f_lock"),
+        event.bounty_id,
+        event.depositor.clone(),
+    );
     env.events().publish(topics, event.clone());
 }
 
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FundsToppedUp {
+    pub version: u32,
+    pub seq: u64,
+    pub bounty_id: u64,
+    pub added_amount: i128,
+    pub new_amount: i128,
+    pub depositor: Address,
+    /// Deadline after the top-up, extended if the call asked for it.
+    pub deadline: u64,
+    pub timestamp: u64,
+}
+
+pub fn emit_funds_topped_up(env: &Env, event: FundsToppedUp) {
+    let topics = (symbol_short!("f_topup"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+/// Published when `merge_escrows` folds the remaining balance of
+/// `source_ids` into `target_id`, leaving the sources Merged.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowsMerged {
+    pub version: u32,
+    pub seq: u64,
+    pub target_id: u64,
+    pub source_ids: Vec<u64>,
+    pub depositor: Address,
+    /// Balance moved out of the sources.
+    pub merged_amount: i128,
+    pub new_amount: i128,
+    pub deadline: u64,
+    pub timestamp: u64,
+}
+
+pub fn emit_escrows_merged(env: &Env, event: EscrowsMerged) {
+    let topics = (symbol_short!("merged"), event.target_id);
+    env.events().publish(topics, event);
+}
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct FundsReleased {
     pub version: u32,
+    pub seq: u64,
     pub bounty_id: u64,
     pub amount: i128,
     pub recipient: Address,
     pub timestamp: u64,
 }
 
+/// Topics are `("f_rel", bounty_id, recipient)` so indexers can subscribe to
+/// a single contributor's payouts.
 pub fn emit_funds_released(env: &Env, event: FundsReleased) {
-    let topics = (symbol_short!("f_rel"), event.bounty_id);
+    let topics = (
+        symbol_short!("f_rel"),
+        event.bounty_id,
+        event.recipient.clone(),
+    );
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FundsReleasedSplit {
+    pub version: u32,
+    pub seq: u64,
+    pub bounty_id: u64,
+    pub total_amount: i128,
+    pub recipients: Vec<(Address, i128)>,
+    pub timestamp: u64,
+}
+
+pub fn emit_funds_released_split(env: &Env, event: FundsReleasedSplit) {
+    let topics = (symbol_short!("f_split"), event.bounty_id);
     env.events().publish(topics, event.clone());
 }
 
@@ -50,6 +160,7 @@ pub fn emit_funds_released(env: &Env, event: FundsReleased) {
 #[derive(Clone, Debug)]
 pub struct FundsRefunded {
     pub version: u32,
+    pub seq: u64,
     pub bounty_id: u64,
     pub amount: i128,
     pub refund_to: Address,
@@ -61,6 +172,49 @@ pub fn emit_funds_refunded(env: &Env, event: FundsRefunded) {
     env.events().publish(topics, event.clone());
 }
 
+// ==================== LIFECYCLE EVENTS ====================
+//
+// Stable schema for off-chain indexers. Every lock/release/refund publishes
+// `(symbol_short!("escrow"), <action>, bounty_id)` where `<action>` is one of
+// `lock`, `release` or `refund`. The payload carries `version` so fields can
+// be added later without breaking existing decoders; consumers should
+// branch on it before reading anything else. The legacy `f_lock` / `f_rel` /
+// `f_ref` events are still emitted alongside these.
+
+/// Current schema version of [`EscrowLifecycleEvent`].
+pub const ESCROW_EVENT_SCHEMA_VERSION: u32 = EVENT_VERSION_V2;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowLifecycleEvent {
+    pub version: u32,
+    pub seq: u64,
+    pub bounty_id: u64,
+    pub depositor: Address,
+    /// Amount moved by this operation (locked, paid out, or refunded).
+    pub amount: i128,
+    pub deadline: u64,
+    /// Escrow status after the operation was applied.
+    pub status: crate::EscrowStatus,
+}
+
+fn emit_escrow_lifecycle(env: &Env, action: Symbol, event: EscrowLifecycleEvent) {
+    let topics = (symbol_short!("escrow"), action, event.bounty_id);
+    env.events().publish(topics, event);
+}
+
+pub fn emit_escrow_locked(env: &Env, event: EscrowLifecycleEvent) {
+    emit_escrow_lifecycle(env, symbol_short!("lock"), event);
+}
+
+pub fn emit_escrow_released(env: &Env, event: EscrowLifecycleEvent) {
+    emit_escrow_lifecycle(env, symbol_short!("release"), event);
+}
+
+pub fn emit_escrow_refunded(env: &Env, event: EscrowLifecycleEvent) {
+    emit_escrow_lifecycle(env, symbol_short!("refund"), event);
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum FeeOperationType {
@@ -71,6 +225,7 @@ pub enum FeeOperationType {
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct FeeCollected {
+    pub seq: u64,
     pub operation_type: FeeOperationType,
     pub amount: i128,
     pub fee_rate: i128,
@@ -86,6 +241,7 @@ pub fn emit_fee_collected(env: &Env, event: FeeCollected) {
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct BatchFundsLocked {
+    pub seq: u64,
     pub count: u32,
     pub total_amount: i128,
     pub timestamp: u64,
@@ -99,6 +255,7 @@ pub fn emit_batch_funds_locked(env: &Env, event: BatchFundsLocked) {
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct FeeConfigUpdated {
+    pub seq: u64,
     pub lock_fee_rate: i128,
     pub release_fee_rate: i128,
     pub fee_recipient: Address,
@@ -114,6 +271,7 @@ pub fn emit_fee_config_updated(env: &Env, event: FeeConfigUpdated) {
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct BatchFundsReleased {
+    pub seq: u64,
     pub count: u32,
     pub total_amount: i128,
     pub timestamp: u64,
@@ -127,6 +285,7 @@ pub fn emit_batch_funds_released(env: &Env, event: BatchFundsReleased) {
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct ApprovalAdded {
+    pub seq: u64,
     pub bounty_id: u64,
     pub contributor: Address,
     pub approver: Address,
@@ -141,6 +300,7 @@ pub fn emit_approval_added(env: &Env, event: ApprovalAdded) {
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ClaimCreated {
+    pub seq: u64,
     pub bounty_id: u64, // use program_id+schedule_id equivalent in program-escrow
     pub recipient: Address,
     pub amount: i128,
@@ -150,6 +310,7 @@ pub struct ClaimCreated {
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ClaimExecuted {
+    pub seq: u64,
     pub bounty_id: u64,
     pub recipient: Address,
     pub amount: i128,
@@ -159,6 +320,7 @@ pub struct ClaimExecuted {
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ClaimCancelled {
+    pub seq: u64,
     pub bounty_id: u64,
     pub recipient: Address,
     pub amount: i128,
@@ -166,6 +328,19 @@ pub struct ClaimCancelled {
     pub cancelled_by: Address,
 }
 
+/// Published when the depositor takes back an escrow whose claim window
+/// lapsed without the contributor claiming.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimReclaimed {
+    pub seq: u64,
+    pub bounty_id: u64,
+    pub recipient: Address,
+    pub amount: i128,
+    pub refund_to: Address,
+    pub reclaimed_at: u64,
+}
+
 pub fn emit_pause_state_changed(env: &Env, event: crate::PauseStateChanged) {
     let topics = (symbol_short!("pause"), event.operation.clone());
     env.events().publish(topics, event);
@@ -174,6 +349,7 @@ pub fn emit_pause_state_changed(env: &Env, event: crate::PauseStateChanged) {
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct EmergencyWithdrawEvent {
+    pub seq: u64,
     pub admin: Address,
     pub recipient: Address,
     pub amount: i128,
@@ -184,3 +360,415 @@ pub fn emit_emergency_withdraw(env: &Env, event: EmergencyWithdrawEvent) {
     let topics = (symbol_short!("em_wtd"),);
     env.events().publish(topics, event.clone());
 }
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ContractPauseToggled {
+    pub seq: u64,
+    pub caller: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_contract_paused(env: &Env, event: ContractPauseToggled) {
+    let topics = (symbol_short!("paused"),);
+    env.events().publish(topics, event.clone());
+}
+
+pub fn emit_contract_unpaused(env: &Env, event: ContractPauseToggled) {
+    let topics = (symbol_short!("unpaused"),);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DisputeRaised {
+    pub seq: u64,
+    pub bounty_id: u64,
+    pub raised_by: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_dispute_raised(env: &Env, event: DisputeRaised) {
+    let topics = (symbol_short!("disp_open"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DisputeResolved {
+    pub seq: u64,
+    pub bounty_id: u64,
+    pub award_to: Address,
+    pub awarded_amount: i128,
+    pub refunded_amount: i128,
+    pub arbiter: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_dispute_resolved(env: &Env, event: DisputeResolved) {
+    let topics = (symbol_short!("disp_res"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+/// Published when a dispute is dropped without a ruling and the escrow
+/// goes back to Locked; see `withdraw_dispute`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeWithdrawn {
+    pub version: u32,
+    pub seq: u64,
+    pub bounty_id: u64,
+    pub withdrawn_by: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_dispute_withdrawn(env: &Env, event: DisputeWithdrawn) {
+    let topics = (symbol_short!("disp_wd"), event.bounty_id);
+    env.events().publish(topics, event);
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AllowlistUpdated {
+    pub seq: u64,
+    pub bounty_id: u64,
+    pub allowed: Vec<Address>,
+    pub updated_by: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_allowlist_updated(env: &Env, event: AllowlistUpdated) {
+    let topics = (symbol_short!("allowlist"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct WorkCompleted {
+    pub seq: u64,
+    pub bounty_id: u64,
+    pub contributor: Address,
+    pub completed_at: u64,
+    pub release_at: u64,
+}
+
+pub fn emit_work_completed(env: &Env, event: WorkCompleted) {
+    let topics = (symbol_short!("completed"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+pub fn emit_auto_released(env: &Env, event: FundsReleased) {
+    let topics = (symbol_short!("auto_rel"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct EscrowCancelled {
+    pub version: u32,
+    pub seq: u64,
+    pub bounty_id: u64,
+    pub depositor: Address,
+    pub contributor: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+pub fn emit_escrow_cancelled(env: &Env, event: EscrowCancelled) {
+    let topics = (symbol_short!("cancel"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RoleUpdated {
+    pub seq: u64,
+    pub role: crate::Role,
+    pub account: Address,
+    pub granted: bool,
+    pub timestamp: u64,
+}
+
+pub fn emit_role_updated(env: &Env, event: RoleUpdated) {
+    let topics = (symbol_short!("role"), event.account.clone());
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Upgraded {
+    pub version: u32,
+    pub seq: u64,
+    /// `get_version` of the code that performed the upgrade.
+    pub previous_contract_version: u32,
+    pub new_wasm_hash: BytesN<32>,
+    pub admin: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_upgraded(env: &Env, event: Upgraded) {
+    let topics = (symbol_short!("upgraded"),);
+    env.events().publish(topics, event.clone());
+}
+
+/// Published when `migrate` moves stored data to a newer layout.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Migrated {
+    pub version: u32,
+    pub seq: u64,
+    pub from_data_version: u32,
+    pub to_data_version: u32,
+    pub timestamp: u64,
+}
+
+pub fn emit_migrated(env: &Env, event: Migrated) {
+    let topics = (symbol_short!("migrated"),);
+    env.events().publish(topics, event);
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MilestoneReleased {
+    pub version: u32,
+    pub seq: u64,
+    pub bounty_id: u64,
+    pub milestone_index: u32,
+    pub amount: i128,
+    pub recipient: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_milestone_released(env: &Env, event: MilestoneReleased) {
+    let topics = (symbol_short!("milestone"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BatchExpiredRefunded {
+    pub seq: u64,
+    pub count: u32,
+    pub total_amount: i128,
+    pub timestamp: u64,
+}
+
+pub fn emit_batch_expired_refunded(env: &Env, event: BatchExpiredRefunded) {
+    let topics = (symbol_short!("b_ref"),);
+    env.events().publish(topics, event.clone());
+}
+
+/// Published whenever an escrow is added to its depositor's index (lock) or
+/// changes status there (release, refund, cancel, dispute resolution).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositorIndexUpdated {
+    pub version: u32,
+    pub seq: u64,
+    pub depositor: Address,
+    pub bounty_id: u64,
+    pub status: crate::EscrowStatus,
+    pub timestamp: u64,
+}
+
+pub fn emit_depositor_index_updated(env: &Env, event: DepositorIndexUpdated) {
+    let topics = (symbol_short!("dep_idx"), event.depositor.clone());
+    env.events().publish(topics, event);
+}
+
+/// Published when the admin force-refunds an escrow ahead of its deadline.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminRefund {
+    pub version: u32,
+    pub seq: u64,
+    pub bounty_id: u64,
+    pub admin: Address,
+    pub refund_to: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+pub fn emit_admin_refund(env: &Env, event: AdminRefund) {
+    let topics = (symbol_short!("adm_ref"), event.bounty_id);
+    env.events().publish(topics, event);
+}
+
+/// Point-in-time copy of `get_aggregate_stats`, published on demand by
+/// `publish_stats_snapshot`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StatsSnapshot {
+    pub version: u32,
+    pub seq: u64,
+    pub stats: crate::AggregateStats,
+    pub timestamp: u64,
+}
+
+pub fn emit_stats_snapshot(env: &Env, event: StatsSnapshot) {
+    let topics = (symbol_short!("stats"),);
+    env.events().publish(topics, event);
+}
+
+/// Published when a third party triggers an expired escrow's refund via
+/// `refund_on_behalf`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundedOnBehalf {
+    pub version: u32,
+    pub seq: u64,
+    pub bounty_id: u64,
+    pub caller: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+pub fn emit_refunded_on_behalf(env: &Env, event: RefundedOnBehalf) {
+    let topics = (symbol_short!("ref_obh"), event.bounty_id);
+    env.events().publish(topics, event);
+}
+
+/// Published when an escrow's depositor role moves to a new address.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositorTransferred {
+    pub version: u32,
+    pub seq: u64,
+    pub bounty_id: u64,
+    pub previous_depositor: Address,
+    pub new_depositor: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_depositor_transferred(env: &Env, event: DepositorTransferred) {
+    let topics = (symbol_short!("dep_xfer"), event.bounty_id);
+    env.events().publish(topics, event);
+}
+
+/// Published when a depositor sets or changes an escrow's note.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowNoteUpdated {
+    pub version: u32,
+    pub seq: u64,
+    pub bounty_id: u64,
+    pub depositor: Address,
+    pub note: String,
+    pub timestamp: u64,
+}
+
+pub fn emit_escrow_note_updated(env: &Env, event: EscrowNoteUpdated) {
+    let topics = (symbol_short!("note"), event.bounty_id);
+    env.events().publish(topics, event);
+}
+
+/// Published when the depositor replaces an escrow's description hash.
+/// `previous` is `None` if the escrow was locked without one.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DescriptionHashUpdated {
+    pub version: u32,
+    pub seq: u64,
+    pub bounty_id: u64,
+    pub depositor: Address,
+    pub previous: Option<Bytes>,
+    pub description_hash: BytesN<32>,
+    pub timestamp: u64,
+}
+
+pub fn emit_description_hash_updated(env: &Env, event: DescriptionHashUpdated) {
+    let topics = (symbol_short!("desc_hash"), event.bounty_id);
+    env.events().publish(topics, event);
+}
+
+/// Published when the arbiter or admin changes a bounty's assigned
+/// contributor. `previous` is `None` if nobody was assigned before.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContributorReassigned {
+    pub version: u32,
+    pub seq: u64,
+    pub bounty_id: u64,
+    pub previous: Option<Address>,
+    pub new_contributor: Address,
+    pub caller: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_contributor_reassigned(env: &Env, event: ContributorReassigned) {
+    let topics = (symbol_short!("reassign"), event.bounty_id);
+    env.events().publish(topics, event);
+}
+
+/// Published when the admin moves funds out of the treasury balance.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryWithdrawn {
+    pub version: u32,
+    pub seq: u64,
+    pub token: Address,
+    pub amount: i128,
+    pub to: Address,
+    /// Treasury balance left after the withdrawal.
+    pub remaining: i128,
+    pub timestamp: u64,
+}
+
+pub fn emit_treasury_withdrawn(env: &Env, event: TreasuryWithdrawn) {
+    let topics = (symbol_short!("treas_wd"), event.to.clone());
+    env.events().publish(topics, event);
+}
+
+/// Published when a burst of refunds trips the anomaly threshold and the
+/// contract pauses itself; see `set_anomaly_threshold`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AnomalyDetected {
+    pub version: u32,
+    pub seq: u64,
+    /// Refunds seen inside the window, the one that tripped it included.
+    pub refund_count: u32,
+    pub window: u64,
+    pub timestamp: u64,
+}
+
+pub fn emit_anomaly_detected(env: &Env, event: AnomalyDetected) {
+    let topics = (symbol_short!("anomaly"),);
+    env.events().publish(topics, event);
+}
+
+/// Published alongside `FundsReleased` when an escrow is paid out at or
+/// after its deadline. Late releases are allowed; this just flags them.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LateRelease {
+    pub version: u32,
+    pub seq: u64,
+    pub bounty_id: u64,
+    pub deadline: u64,
+    pub timestamp: u64,
+}
+
+pub fn emit_late_release(env: &Env, event: LateRelease) {
+    let topics = (symbol_short!("late_rel"), event.bounty_id);
+    env.events().publish(topics, event);
+}
+
+/// Published when the release notifier's `on_release` call fails. The
+/// payout it was told about went through regardless.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NotifierFailed {
+    pub version: u32,
+    pub seq: u64,
+    pub notifier: Address,
+    pub bounty_id: u64,
+    pub contributor: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+pub fn emit_notifier_failed(env: &Env, event: NotifierFailed) {
+    let topics = (symbol_short!("notif_err"), event.bounty_id);
+    env.events().publish(topics, event);
+}
@@ -2,6 +2,7 @@
 #[allow(dead_code)]
 mod events;
 mod invariants;
+mod reentrancy_guard;
 #[cfg(test)]
 mod test_metadata;
 
@@ -11,14 +12,16 @@ mod test_rbac;
 mod traits;
 
 use events::{
-    emit_batch_funds_locked, emit_batch_funds_released, emit_bounty_initialized, emit_funds_locked,
-    emit_funds_refunded, emit_funds_released, BatchFundsLocked, BatchFundsReleased,
-    BountyEscrowInitialized, ClaimCancelled, ClaimCreated, ClaimExecuted, FundsLocked,
-    FundsRefunded, FundsReleased, EVENT_VERSION_V2,
+    emit_batch_funds_locked, emit_batch_funds_released, emit_bounty_initialized,
+    emit_escrow_locked, emit_escrow_refunded, emit_escrow_released, emit_funds_locked,
+    emit_funds_refunded, emit_funds_released, emit_funds_released_split, BatchFundsLocked,
+    BatchFundsReleased, BountyEscrowInitialized, ClaimCancelled, ClaimCreated, ClaimExecuted,
+    EscrowLifecycleEvent, EventLevel, FundsLocked, FundsRefunded, FundsReleased,
+    FundsReleasedSplit, ESCROW_EVENT_SCHEMA_VERSION, EVENT_VERSION_V2,
 };
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, token, vec, Address, Env,
-    Symbol, Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, vec, Address, Bytes,
+    BytesN, Env, IntoVal, Map, Symbol, TryFromVal, Val, Vec,
 };
 
 // ==================== MONITORING MODULE ====================
@@ -37,6 +40,7 @@ mod monitoring {
     #[contracttype]
     #[derive(Clone, Debug)]
     pub struct OperationMetric {
+        pub seq: u64,
         pub operation: Symbol,
         pub caller: Address,
         pub timestamp: u64,
@@ -47,6 +51,7 @@ mod monitoring {
     #[contracttype]
     #[derive(Clone, Debug)]
     pub struct PerformanceMetric {
+        pub seq: u64,
         pub function: Symbol,
         pub duration: u64,
         pub timestamp: u64,
@@ -106,9 +111,13 @@ mod monitoring {
             env.storage().persistent().set(&err_key, &(err_count + 1));
         }
 
+        if !crate::events::verbose(env) {
+            return;
+        }
         env.events().publish(
             (symbol_short!("metric"), symbol_short!("op")),
             OperationMetric {
+                seq: crate::events::next_event_seq(env),
                 operation,
                 caller,
                 timestamp: env.ledger().timestamp(),
@@ -131,9 +140,13 @@ mod monitoring {
             .persistent()
             .set(&time_key, &(total + duration));
 
+        if !crate::events::verbose(env) {
+            return;
+        }
         env.events().publish(
             (symbol_short!("metric"), symbol_short!("perf")),
             PerformanceMetric {
+                seq: crate::events::next_event_seq(env),
                 function,
                 duration,
                 timestamp: env.ledger().timestamp(),
@@ -206,7 +219,7 @@ mod monitoring {
         let total: u64 = env.storage().persistent().get(&time_key).unwrap_or(0);
         let last: u64 = env.storage().persistent().get(&last_key).unwrap_or(0);
 
-        let avg = if count > 0 { total / count } else { 0 };
+        let avg = total.checked_div(count).unwrap_or(0);
 
         PerformanceStats {
             function_name,
@@ -221,7 +234,8 @@ mod monitoring {
 
 // ==================== ANTI-ABUSE MODULE ====================
 mod anti_abuse {
-    use soroban_sdk::{contracttype, symbol_short, Address, Env};
+    use crate::Error;
+    use soroban_sdk::{contracttype, panic_with_error, symbol_short, Address, Env, Vec};
 
     #[contracttype]
     #[derive(Clone, Debug, Eq, PartialEq)]
@@ -239,13 +253,25 @@ mod anti_abuse {
         pub operation_count: u32,
     }
 
+    /// Cap on escrows a depositor may lock within a rolling ledger window.
+    #[contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct LockRateLimit {
+        pub max_locks: u32,
+        pub window_ledgers: u32,
+    }
+
     #[contracttype]
     #[derive(Clone, Debug, Eq, PartialEq)]
     pub enum AntiAbuseKey {
         Config,
         State(Address),
         Whitelist(Address),
-        Admin,
+        // Not `Admin`: it would encode to the same instance key as
+        // `DataKey::Admin` and overwrite the contract admin.
+        AbuseAdmin,
+        LockRateLimit,
+        RecentLocks(Address), // ledger sequences of the depositor's recent locks
     }
 
     pub fn get_config(env: &Env) -> AntiAbuseConfig {
@@ -283,11 +309,69 @@ mod anti_abuse {
     }
 
     pub fn get_admin(env: &Env) -> Option<Address> {
-        env.storage().instance().get(&AntiAbuseKey::Admin)
+        env.storage().instance().get(&AntiAbuseKey::AbuseAdmin)
     }
 
     pub fn set_admin(env: &Env, admin: Address) {
-        env.storage().instance().set(&AntiAbuseKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&AntiAbuseKey::AbuseAdmin, &admin);
+    }
+
+    pub fn get_lock_rate_limit(env: &Env) -> Option<LockRateLimit> {
+        env.storage().instance().get(&AntiAbuseKey::LockRateLimit)
+    }
+
+    pub fn set_lock_rate_limit(env: &Env, limit: Option<LockRateLimit>) {
+        match limit {
+            Some(limit) => env
+                .storage()
+                .instance()
+                .set(&AntiAbuseKey::LockRateLimit, &limit),
+            None => env
+                .storage()
+                .instance()
+                .remove(&AntiAbuseKey::LockRateLimit),
+        }
+    }
+
+    /// Record `count` locks by `address` against the lock rate limit. Returns
+    /// false, without recording any, when they would take the address past
+    /// `max_locks` within the window.
+    pub fn record_locks(env: &Env, address: Address, count: u32) -> bool {
+        let limit = match get_lock_rate_limit(env) {
+            Some(limit) => limit,
+            None => return true,
+        };
+        if is_whitelisted(env, address.clone()) {
+            return true;
+        }
+
+        let now = env.ledger().sequence();
+        let key = AntiAbuseKey::RecentLocks(address);
+        let recent: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        // Keep only locks still inside the window; the ring never holds more
+        // than `max_locks` entries.
+        let mut ring = Vec::new(env);
+        for seq in recent.iter() {
+            if now.saturating_sub(seq) < limit.window_ledgers {
+                ring.push_back(seq);
+            }
+        }
+        if ring.len().saturating_add(count) > limit.max_locks {
+            return false;
+        }
+        for _ in 0..count {
+            ring.push_back(now);
+        }
+        env.storage().persistent().set(&key, &ring);
+        let ttl = limit.window_ledgers.min(env.storage().max_ttl());
+        env.storage().persistent().extend_ttl(&key, ttl, ttl);
+        true
     }
 
     pub fn check_rate_limit(env: &Env, address: Address) {
@@ -318,9 +402,9 @@ mod anti_abuse {
         {
             env.events().publish(
                 (symbol_short!("abuse"), symbol_short!("cooldown")),
-                (address.clone(), now),
+                (address.clone(), now, crate::events::next_event_seq(env)),
             );
-            panic!("Operation in cooldown period");
+            panic_with_error!(env, Error::RateLimited);
         }
 
         // 2. Window check
@@ -337,9 +421,9 @@ mod anti_abuse {
             if state.operation_count >= config.max_operations {
                 env.events().publish(
                     (symbol_short!("abuse"), symbol_short!("limit")),
-                    (address.clone(), now),
+                    (address.clone(), now, crate::events::next_event_seq(env)),
                 );
-                panic!("Rate limit exceeded");
+                panic_with_error!(env, Error::RateLimited);
             }
             state.operation_count += 1;
         }
@@ -354,10 +438,47 @@ mod anti_abuse {
 // ==================== END ANTI-ABUSE MODULE ====================
 
 // ==================== CONSTANTS ====================
-#[allow(dead_code)]
 const BASIS_POINTS: i128 = 10_000;
 const MAX_FEE_RATE: i128 = 5_000; // 50% max fee
+const MAX_RELEASE_FEE_BPS: i128 = 1_000; // 10% cap on the release fee
+const MAX_REFUND_PENALTY_BPS: i128 = 1_000; // 10% cap for set_refund_penalty_bps
+/// Internal ids of namespaced escrows start here, away from the small ids
+/// callers pick for the flat global id space.
+const NAMESPACED_ID_BASE: u64 = 1 << 63;
+/// Deadline sentinel for escrows that never expire: they can be released or
+/// cancelled at any time but are never refundable by time.
+const NO_DEADLINE: u64 = u64::MAX;
 const MAX_BATCH_SIZE: u32 = 20;
+/// Largest page `get_all_escrows` returns, keeping reads within limits.
+const MAX_EXPORT_PAGE_SIZE: u32 = 100;
+/// Entries kept per escrow history; the oldest are dropped beyond this.
+const MAX_ESCROW_HISTORY_LEN: u32 = 50;
+/// Maximum byte length of an `EscrowLabel` title.
+const MAX_LABEL_TITLE_LEN: u32 = 128;
+/// Maximum byte length of an escrow note.
+const MAX_ESCROW_NOTE_LEN: u32 = 256;
+/// Most buckets `list_recent_buckets` returns in one call.
+const MAX_RECENT_BUCKETS: u32 = 100;
+/// Assumed average ledger close time, used to convert deadlines to ledgers.
+const LEDGER_CLOSE_SECONDS: u64 = 5;
+/// Extra lifetime (~30 days) kept after the deadline so refunds stay possible.
+const ESCROW_TTL_GRACE_LEDGERS: u64 = 17_280 * 30;
+/// Logic version of this build, returned by `get_version`. Bump on every
+/// release so clients can tell which code is live after an `upgrade`.
+const CONTRACT_VERSION: u32 = 2;
+/// Storage layout version this build expects. Contracts initialized before
+/// the version was stored count as version 1; `migrate` brings them forward.
+///
+/// * 2 - refund log running totals (`RecordKey::RefundLogTotal`)
+/// * 3 - per-token aggregate stats (`RecordKey::TokenStats`)
+/// * 4 - treasury balance keyed by token (`RecordKey::TreasuryBalance`)
+/// * 5 - refund log entries in the current `RefundHistoryEntry` layout
+/// * 6 - escrow lock times (`RecordKey::CreatedAt`)
+/// * 7 - active TVL totals (`ConfigKey::LockedTvl`, `RecordKey::LockedByDeadline`)
+const DATA_VERSION: u32 = 7;
+/// Most records a single `migrate` call processes, so a large backfill
+/// spreads over several transactions instead of exhausting one.
+const MIGRATION_BATCH_SIZE: u64 = 50;
 
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -365,13 +486,20 @@ const MAX_BATCH_SIZE: u32 = 20;
 pub enum Error {
     AlreadyInitialized = 1,
     NotInitialized = 2,
+    /// No longer returned: reused bounty ids fail with `DuplicateBountyId`.
+    /// Kept so the numeric codes stay stable
     BountyExists = 3,
     BountyNotFound = 4,
+    /// The escrow is not Locked ("not locked"); see `ensure_status` for the
+    /// more specific errors reported for released, refunded and disputed escrows
     FundsNotLocked = 5,
+    /// Refund attempted before the deadline ("not yet expired")
     DeadlineNotPassed = 6,
     Unauthorized = 7,
     InvalidFeeRate = 8,
     FeeRecipientNotSet = 9,
+    /// Returned when a batch is empty or exceeds MAX_BATCH_SIZE, or when an
+    /// admin set is empty, repeats an admin or cannot meet its threshold
     InvalidBatchSize = 10,
     BatchSizeMismatch = 11,
     DuplicateBountyId = 12,
@@ -379,7 +507,8 @@ pub enum Error {
     InvalidAmount = 13,
     /// Returned when deadline is invalid (in the past or too far in the future)
     InvalidDeadline = 14,
-    /// Returned when contract has insufficient funds for the operation
+    /// Returned when contract has insufficient funds for the operation, or
+    /// when the account funding a lock holds less than the amount
     InsufficientFunds = 16,
     /// Returned when refund is attempted without admin approval
     RefundNotApproved = 17,
@@ -391,6 +520,68 @@ pub enum Error {
     /// Returned when refund is blocked by a pending claim/dispute
     NotPaused = 21,
     ClaimPending = 22,
+    /// Returned when a split release lists the same recipient more than once
+    DuplicateRecipient = 23,
+    /// Returned when split release amounts do not sum to the escrow's locked balance
+    SplitAmountMismatch = 24,
+    /// Returned when the contract-wide emergency pause is active
+    ContractPaused = 25,
+    /// Returned when release/refund is attempted while the escrow is under dispute
+    BountyDisputed = 26,
+    /// Returned when resolving or withdrawing a dispute on an escrow that is not in Disputed status
+    NotDisputed = 28,
+    /// Returned when a Partial refund policy is outside 1..=10_000 basis points,
+    /// or when merging escrows whose refund policy or refund address differ
+    InvalidRefundPolicy = 29,
+    /// Returned when refunding an escrow locked with RefundPolicy::None
+    RefundNotAllowed = 30,
+    /// Returned when releasing to an address outside the escrow's contributor allowlist
+    UnauthorizedContributor = 31,
+    /// Returned when claim_auto_release is called before the delay has elapsed
+    AutoReleaseNotReady = 32,
+    /// Returned when a token-moving entry point is re-entered mid-execution
+    ReentrantCall = 33,
+    /// Returned by `cancel_escrow` when no contributor has been assigned
+    ContributorNotAssigned = 34,
+    /// Returned when releasing before the escrow's `release_not_before` time
+    ReleaseTooEarly = 35,
+    /// Returned when a milestone index does not exist on the escrow
+    MilestoneNotFound = 36,
+    /// Returned when releasing a milestone that was already paid out
+    MilestoneAlreadyReleased = 37,
+    /// Returned when releasing a milestone before its unlock_time, or when
+    /// changing a milestone escrow's balance outside `release_milestone`
+    MilestoneLocked = 38,
+    /// Returned when an escrow label title exceeds MAX_LABEL_TITLE_LEN
+    LabelTooLong = 39,
+    /// Returned when operating on an escrow that has already been released
+    AlreadyReleased = 40,
+    /// Returned when operating on an escrow that has already been refunded
+    AlreadyRefunded = 41,
+    /// Returned when a depositor exceeds the configured lock rate limit
+    RateLimited = 42,
+    /// Returned when locking in a token that is not on the allowlist
+    TokenNotAllowed = 43,
+    /// Returned when a lock deadline is closer than the minimum deadline horizon
+    DeadlineTooSoon = 44,
+    /// Returned when an escrow note exceeds MAX_ESCROW_NOTE_LEN
+    NoteTooLong = 45,
+    /// Returned when releasing to someone other than the escrow's assigned contributor
+    ContributorMismatch = 46,
+    /// Returned when the escrow's release condition contract does not report the condition as met,
+    /// or when merging escrows whose reviewer, release time or release condition differ
+    ConditionNotMet = 47,
+    /// Locking would push the total escrow count past the configured cap
+    MaxEscrowsReached = 48,
+    /// Admin action does not exist, was already executed, or lacks the required approvals
+    InsufficientApprovals = 49,
+    /// Net payout after fees would fall below the caller's `min_net`
+    SlippageExceeded = 50,
+    /// Returned when allowance locks are on and the account funding a lock
+    /// has approved this contract for less than the amount
+    InsufficientAllowance = 51,
+    /// Returned when claiming after the claim window has closed
+    ClaimExpired = 52,
 }
 
 #[contracttype]
@@ -401,6 +592,15 @@ pub struct EscrowMetadata {
     pub bounty_type: soroban_sdk::String,
 }
 
+/// UI-facing category tag and title attached at lock time. The tag is a
+/// `Symbol`, so it is bounded to 32 characters by the host.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowLabel {
+    pub tag: Symbol,
+    pub title: soroban_sdk::String,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum EscrowStatus {
@@ -408,6 +608,13 @@ pub enum EscrowStatus {
     Released,
     Refunded,
     PartiallyRefunded,
+    /// Frozen by raise_dispute until the arbiter calls resolve_dispute
+    Disputed,
+    /// Release authorized via authorize_claim; waiting for the contributor to
+    /// claim, or for the depositor to reclaim once the window lapses
+    Claimable,
+    /// Remaining balance moved into another escrow by merge_escrows
+    Merged,
 }
 
 #[contracttype]
@@ -436,11 +643,93 @@ pub enum DataKey {
     RefundApproval(u64),     // bounty_id -> RefundApproval
     ReentrancyGuard,
     MultisigConfig,
-    ReleaseApproval(u64), // bounty_id -> ReleaseApproval
-    PendingClaim(u64),    // bounty_id -> ClaimRecord
-    ClaimWindow,          // u64 seconds (global config)
-    PauseFlags,           // PauseFlags struct
+    ReleaseApproval(u64),              // bounty_id -> ReleaseApproval
+    PendingClaim(u64),                 // bounty_id -> ClaimRecord
+    ClaimWindow,                       // u64 seconds (global config)
+    PauseFlags,                        // PauseFlags struct
     AmountPolicy, // Option<(i128, i128)> — (min_amount, max_amount) set by set_amount_policy
+    TotalFeesCollected, // i128 running total of release fees, refund penalties and forfeits
+    Paused,       // bool — contract-wide emergency switch set by pause()/unpause()
+    Arbiter,      // Address allowed to resolve disputes
+    Dispute(u64), // bounty_id -> DisputeRecord
+    RefundPolicy(u64), // bounty_id -> RefundPolicy (absent means Full)
+    AllowedContributors(u64), // bounty_id -> Vec<Address> (empty/absent means anyone)
+    EscrowToken(u64), // bounty_id -> token Address (absent means the init token)
+    AutoReleaseDelay, // u64 seconds (global config) used by mark_completed
+    AutoRelease(u64), // bounty_id -> AutoReleaseRecord
+    RefundLogCount, // u64 number of entries in the global refund log
+    RefundLog(u64), // sequence -> RefundHistoryEntry
+    DepositorRefunds(Address), // Vec<u64> of refund log sequences by depositor
+    DepositorStats(Address), // DepositorStats maintained on lock/release/refund
+    AssignedContributor(u64), // bounty_id -> Address whose consent cancel_escrow needs and the only allowed recipient
+    Role(Role, Address),      // bool — set while the address holds the role
+    ReleasedTo(u64),          // bounty_id -> contributor Address paid on release
+    ContributorIndex(Address), // Vec<u64> of bounty_ids released to contributor
+    ReleaseNotBefore(u64),    // bounty_id -> u64 earliest release timestamp
+    Milestones(u64),          // bounty_id -> Vec<Milestone>
+    RefundAddress(u64),       // bounty_id -> Address refunds are paid to instead of the depositor
+    EscrowLabel(u64),         // bounty_id -> EscrowLabel set at lock time
+    TagIndex(Symbol),         // Vec<u64> of bounty_ids carrying the tag
+    RefundGracePeriod,        // u64 seconds after the deadline before refunds open
+    EscrowHistory(u64),       // bounty_id -> Vec<EscrowHistoryEntry>
+    ArchivedStats,            // ArchivedStats of escrows dropped from the indexes
+    ArchivedIndex,            // Vec<u64> of bounty_ids removed by archive_settled
+    AllowedTokens,            // Vec<Address> of tokens accepted besides the init token
+    MinDeadlineHorizon,       // u64 seconds a new escrow's deadline must lie in the future
+    EscrowNote(u64),          // bounty_id -> String note maintained by the depositor
+    StatsBucketSize,          // u64 seconds per volume bucket (0 disables bucketing)
+    StatsBucket(u64),         // bucket start timestamp -> BucketStats
+    ReleaseCondition(u64),    // bounty_id -> Address implementing traits::ReleaseCondition
+    StatusCount(EscrowStatus), // u32 live escrows per status, see store_escrow
+    RefundPenaltyBps,         // i128 basis points withheld from deadline refunds for the treasury
+    ReleaseOperator(Address, Address), // (depositor, operator) -> bool release approval
+}
+
+/// Instance-storage settings added after `DataKey` reached the 50-case
+/// limit for contract types.
+#[contracttype]
+pub enum ConfigKey {
+    Relayer,          // Address allowed to lock on a depositor's behalf
+    EventSeq,         // u64 - next `seq` stamped into an event payload
+    MaxEscrows,       // u32 - cap on get_escrow_count, 0 = unlimited
+    Treasury,         // Address credited with fees, penalties and forfeits
+    TreasuryBalance,  // i128 init-token treasury credits before data version 4, see migrate
+    NextNamespacedId, // u64 offset of the next internal id handed to a namespaced escrow
+    Notifier,         // Address implementing traits::ReleaseNotifier
+    ReleaseTimeTotal, // u64 sum of lock-to-release durations, see get_avg_time_to_release
+    ReleaseTimeCount, // u64 releases included in ReleaseTimeTotal
+    Admins,           // Vec<Address> admin set from init_with_admins, absent for a single admin
+    AdminThreshold,   // u32 approvals an admin action needs before it can execute
+    NextActionId,     // u64 id handed to the next proposed admin action
+    DataVersion,      // u32 storage layout version, see DATA_VERSION
+    EventLevel,       // events::EventLevel, Verbose when unset
+    AnomalyThreshold, // (u32, u64) refunds allowed per window before auto-pause
+    RecentRefunds,    // Vec<u64> timestamps of refunds inside the anomaly window
+    DefaultDuration,  // u64 seconds from lock to deadline for lock_funds_simple
+    MigrationCursor,  // u64 progress of the migrate step in progress
+    MigrationStats,   // Map<Address, AggregateStats> rebuilt so far by backfill_token_stats
+    LockedTvl,        // i128 value held by Locked escrows, see get_active_tvl
+    AllowanceLocks,   // bool - locks pull funds through the payer's allowance
+}
+
+/// Persistent-storage keys added after `DataKey` reached the 50-case limit.
+#[contracttype]
+pub enum RecordKey {
+    ApprovalNonce(Address), // depositor -> u64 nonce the next SignedReleaseApproval must carry
+    RefundLogTotal(u64),    // n -> i128 net refunded by the first n refund log entries
+    NamespacedEscrow(Address, u64), // (depositor, depositor's bounty_id) -> internal bounty_id
+    ReleaseLogCount,        // u64 number of entries in the release log
+    ReleaseLog(u64),        // seq -> ReleaseHistoryEntry
+    ContributorReleases(Address), // Vec<u64> release log seqs paid to a contributor
+    AdminAction(u64),       // action_id -> PendingAdminAction awaiting approvals
+    Reviewer(u64),          // bounty_id -> Address that must co-approve releases
+    ReleaseApprovers(u64),  // bounty_id -> Vec<Address> of depositor/reviewer approvals
+    TokenStats(Address),    // token -> AggregateStats kept up to date by store_escrow
+    DescriptionHash(u64),   // bounty_id -> BytesN<32> hash of the off-chain description
+    CreatedAt(u64),         // bounty_id -> u64 ledger timestamp of the lock
+    TreasuryBalance(Address), // token -> i128 credits held in the contract for the treasury
+    LockedByDeadline,       // Map<u64, i128> deadline -> value held by Locked escrows
+    Withheld(u64),          // bounty_id -> i128 refund penalties and forfeits taken from the escrow
 }
 
 #[contracttype]
@@ -450,6 +739,75 @@ pub struct EscrowWithId {
     pub escrow: Escrow,
 }
 
+/// Page of a cursor-paginated query, in ascending bounty_id order.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowCursorPage {
+    pub records: Vec<EscrowWithId>,
+    /// Pass as the cursor to fetch the next page; `None` on the last page.
+    pub next_cursor: Option<u64>,
+}
+
+/// Combined filter for `query_escrows`. Every `None` field, and an empty
+/// `statuses`, means "no constraint"; amount and deadline bounds are inclusive.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowFilter {
+    /// Statuses to match, any of them.
+    pub statuses: Vec<EscrowStatus>,
+    pub min_amount: Option<i128>,
+    pub max_amount: Option<i128>,
+    pub min_deadline: Option<u64>,
+    pub max_deadline: Option<u64>,
+    pub depositor: Option<Address>,
+}
+
+/// Sort key for `query_escrows_by_status_sorted`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SortBy {
+    BountyId,
+    Amount,
+    Deadline,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+/// Single-record view returned by `get_escrow`, including per-escrow policy.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowRecord {
+    pub bounty_id: u64,
+    pub escrow: Escrow,
+    pub refund_policy: RefundPolicy,
+    /// Invite-only recipients; empty means anyone may receive the release.
+    pub allowed_contributors: Vec<Address>,
+    /// Contributor the escrow was released to, once released.
+    pub released_to: Option<Address>,
+    /// Earliest timestamp at which funds may be released, if time-locked.
+    pub release_not_before: Option<u64>,
+    /// Where refunds are paid instead of the depositor, if set at lock time.
+    pub refund_address: Option<Address>,
+    /// Tag set by `lock_funds_with_label`, if any.
+    pub label_tag: Option<Symbol>,
+    /// Title set by `lock_funds_with_label`, if any.
+    pub label_title: Option<soroban_sdk::String>,
+    /// Public note maintained by the depositor via `set_escrow_note`.
+    pub note: Option<soroban_sdk::String>,
+    /// Contributor pre-assigned at lock time or via `assign_contributor`.
+    pub assigned_contributor: Option<Address>,
+    /// Contract consulted before every release, if set at lock time.
+    pub release_condition: Option<Address>,
+    /// 32-byte hash of the off-chain bounty description, see
+    /// `set_description_hash`.
+    pub description_hash: Option<Bytes>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PauseFlags {
@@ -460,20 +818,154 @@ pub struct PauseFlags {
     pub paused_at: u64,
 }
 
+/// Operator roles that can be granted alongside the admin.
+///
+/// The admin implicitly holds every role and is the only account that can
+/// grant or revoke them.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Role {
+    /// May call `pause` / `unpause`
+    Pauser,
+    /// May call `set_fee_bps` / `set_fee_collector`
+    FeeManager,
+    /// May call `resolve_dispute`
+    Arbiter,
+}
+
+/// One tranche of a milestone escrow created by `lock_with_milestones`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Milestone {
+    pub amount: i128,
+    pub unlock_time: u64,
+    pub released: bool,
+}
+
+/// Size distribution of escrows, recomputed on read by `get_extended_stats`.
+///
+/// `min_amount` / `max_amount` are `None` when no escrow was scanned. When
+/// `complete` is false the scan stopped at `max_scan`, so the figures cover
+/// only the first `scanned` escrows in creation order.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExtendedStats {
+    pub scanned: u32,
+    pub complete: bool,
+    /// Sum of the original `amount` of every scanned escrow.
+    pub total_amount: i128,
+    pub min_amount: Option<i128>,
+    pub max_amount: Option<i128>,
+    /// `total_amount / scanned`, rounded down; zero when nothing was scanned.
+    pub average_amount: i128,
+}
+
+/// Per-depositor totals, maintained incrementally so reads are a single lookup.
+///
+/// Buckets follow the same rules as [`AggregateStats`]: a release or refund
+/// moves funds out of `total_locked` into the matching bucket. Refund
+/// penalties and forfeits leave `total_locked` without landing in either.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DepositorStats {
+    pub total_locked: i128,
+    pub total_released: i128,
+    pub total_refunded: i128,
+    pub escrow_count: u32,
+}
+
+/// Totals of escrows removed from the active indexes by `archive_settled`,
+/// folded back into `get_aggregate_stats`.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ArchivedStats {
+    pub total_released: i128,
+    pub total_refunded: i128,
+    pub count_released: u32,
+    pub count_refunded: u32,
+}
+
+/// A depositor's signed intent to release one escrow to one contributor,
+/// submitted by anyone through `release_with_approval`.
+///
+/// The depositor authorizes `release_with_approval` with this struct as the
+/// only argument (`require_auth_for_args`), so the signature covers the
+/// bounty, the contributor, the nonce and the expiry but not the submitter.
+/// `nonce` must equal the depositor's current `get_approval_nonce` and is
+/// consumed on use; the approval stops working after `expires_at` (ledger
+/// timestamp), so an unsubmitted one does not stay valid indefinitely.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignedReleaseApproval {
+    pub bounty_id: u64,
+    pub contributor: Address,
+    pub nonce: u64,
+    pub expires_at: u64,
+}
+
+/// Outcome `simulate_release` predicts for a release.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SimulationResult {
+    pub would_succeed: bool,
+    /// Amount the contributor would receive after the release fee.
+    pub net_amount: i128,
+    pub fee: i128,
+    /// `Error` code the release would fail with, if any.
+    pub error_reason: Option<u32>,
+}
+
+/// Volume locked, released and refunded during one `bucket_size`-second
+/// window starting at `bucket_start`.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BucketStats {
+    pub bucket_start: u64,
+    pub locked: i128,
+    pub released: i128,
+    pub refunded: i128,
+}
+
+/// Snapshot returned by `get_status` for uptime monitoring.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractStatus {
+    pub initialized: bool,
+    /// Contract-wide emergency pause (see `pause`).
+    pub paused: bool,
+    pub admin: Option<Address>,
+    /// Fee rates in basis points currently charged; 0 while fees are disabled.
+    pub lock_fee_bps: i128,
+    pub release_fee_bps: i128,
+    /// Same as `get_escrow_count`.
+    pub escrow_count: u32,
+    /// Same as `get_active_tvl`.
+    pub active_tvl: i128,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct AggregateStats {
     pub total_locked: i128,
+    /// Paid out to contributors, release fees included.
     pub total_released: i128,
     pub total_refunded: i128,
     pub count_locked: u32,
     pub count_released: u32,
     pub count_refunded: u32,
+    /// Running total of release fees, refund penalties and forfeited refunds
+    /// credited to the treasury or fee recipient.
+    pub total_fees_collected: i128,
+    /// Still-held balance per token. `total_locked` and the other amount
+    /// totals add raw units across every token, so they are only meaningful
+    /// while a single asset is in use; read amounts from this map instead.
+    pub total_locked_by_token: Map<Address, i128>,
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PauseStateChanged {
+    pub seq: u64,
     pub operation: Symbol,
     pub paused: bool,
     pub admin: Address,
@@ -498,6 +990,50 @@ pub struct MultisigConfig {
     pub required_signatures: u32,
 }
 
+/// Privileged operation that, under a multi-admin setup, runs only through
+/// `propose_action` / `approve_action` / `execute_action`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AdminAction {
+    /// Replace the fee configuration.
+    UpdateFeeConfig(FeeConfig),
+    /// Replace the contract code with an uploaded WASM.
+    Upgrade(BytesN<32>),
+    /// Set the release fee in basis points.
+    SetFeeBps(i128),
+    /// Set the address that receives collected fees.
+    SetFeeCollector(Address),
+    /// Set the deadline refund penalty in basis points.
+    SetRefundPenaltyBps(i128),
+    /// Designate the treasury.
+    SetTreasury(Address),
+    /// Move (token, amount) of the treasury balance to an address.
+    WithdrawTreasury(Address, i128, Address),
+    /// Drain the contract's balance of a token (init token when `None`)
+    /// to an address.
+    EmergencyWithdraw(Address, Option<Address>),
+    /// Bring stored data up to the current layout.
+    Migrate,
+    /// Approve a refund of (bounty_id, amount) to a recipient other than the
+    /// escrow's refund destination.
+    ApproveRefund(u64, i128, Address, RefundMode),
+    /// Force-refund an escrow to its refund destination.
+    AdminRefund(u64),
+    /// Set the dispute arbiter.
+    SetArbiter(Address),
+    /// Grant (`true`) or revoke (`false`) a role.
+    SetRole(Role, Address, bool),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingAdminAction {
+    pub action: AdminAction,
+    pub proposer: Address,
+    /// Distinct admins that approved, the proposer included.
+    pub approvals: Vec<Address>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ReleaseApproval {
@@ -516,6 +1052,23 @@ pub struct ClaimRecord {
     pub claimed: bool,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AutoReleaseRecord {
+    pub bounty_id: u64,
+    pub contributor: Address,
+    pub completed_at: u64,
+    pub auto_release_after: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeRecord {
+    pub bounty_id: u64,
+    pub raised_by: Address,
+    pub raised_at: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum RefundMode {
@@ -523,6 +1076,49 @@ pub enum RefundMode {
     Partial,
 }
 
+/// Depositor-chosen policy for deadline-based refunds.
+///
+/// - `Full`: the whole remaining balance is refundable after the deadline.
+/// - `Partial(bps)`: only `bps / 10_000` of the remaining balance is refunded;
+///   the rest is forfeited to the treasury (or the fee recipient).
+/// - `None`: the bounty is non-refundable without an admin approval.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RefundPolicy {
+    Full,
+    Partial(u32),
+    None,
+}
+
+/// Why a refund is or is not currently available, see `RefundEligibility`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RefundEligibilityReason {
+    Eligible,
+    /// Deadline plus grace period not reached and no admin approval exists.
+    NotExpired,
+    /// Already released or refunded.
+    AlreadySettled,
+    /// Locked with `RefundPolicy::None`.
+    NonRefundableMode,
+    /// Frozen until the dispute is resolved or withdrawn.
+    Disputed,
+    /// A release is awaiting the contributor's claim.
+    ClaimPending,
+}
+
+/// Detailed result of `get_refund_eligibility_details`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundEligibility {
+    pub eligible: bool,
+    pub reason: RefundEligibilityReason,
+    /// Deadline plus the refund grace period.
+    pub earliest_refund_time: u64,
+    /// Amount a refund would return under the current policy or approval.
+    pub refundable: i128,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RefundApproval {
@@ -543,6 +1139,63 @@ pub struct RefundRecord {
     pub mode: RefundMode,
 }
 
+/// One entry of the contract-wide release log, appended on every payout
+/// to a contributor (full, partial, milestone, split, claim or dispute award).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReleaseHistoryEntry {
+    pub bounty_id: u64,
+    pub contributor: Address,
+    /// Net amount paid to `contributor`, after any release fee.
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+/// One entry of the contract-wide refund log, appended on every refund.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundHistoryEntry {
+    pub bounty_id: u64,
+    pub depositor: Address,
+    /// Address the refund was actually paid to.
+    pub recipient: Address,
+    /// Net amount paid to `recipient`.
+    pub amount: i128,
+    /// Amount taken from the escrow for this refund, before the refund
+    /// penalty; equal to `amount` when no penalty applied.
+    pub gross_amount: i128,
+    pub timestamp: u64,
+    /// True when the refund was forced by the admin via `admin_refund`.
+    pub admin_forced: bool,
+}
+
+/// Kind of lifecycle step recorded in an escrow's history.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EscrowHistoryKind {
+    Locked,
+    ToppedUp,
+    Released,
+    Refunded,
+    Disputed,
+    DisputeResolved,
+    Cancelled,
+    DisputeWithdrawn,
+    Merged,
+}
+
+/// One step in a single escrow's timeline, see `get_escrow_history`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowHistoryEntry {
+    pub kind: EscrowHistoryKind,
+    pub timestamp: u64,
+    /// Ledger sequence the step happened in.
+    pub ledger: u32,
+    /// Signed change to the escrow's `remaining_amount` (negative for payouts).
+    pub amount_delta: i128,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct LockFundsItem {
@@ -565,16 +1218,26 @@ pub struct BountyEscrowContract;
 #[contractimpl]
 impl BountyEscrowContract {
     /// Initialize the contract with the admin address and the token address (XLM).
+    ///
+    /// `token` is any Stellar Asset Contract, including the native one (its
+    /// address comes from `stellar contract id asset --asset native`), so
+    /// XLM bounties need no wrapping. Every transfer goes through the
+    /// standard token interface, so native escrows lock, release and refund
+    /// exactly like any other asset.
     pub fn init(env: Env, admin: Address, token: Address) -> Result<(), Error> {
         if env.storage().instance().has(&DataKey::Admin) {
             return Err(Error::AlreadyInitialized);
         }
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage().instance().set(&DataKey::Token, &token);
+        env.storage()
+            .instance()
+            .set(&ConfigKey::DataVersion, &DATA_VERSION);
 
         emit_bounty_initialized(
             &env,
             BountyEscrowInitialized {
+                seq: events::next_event_seq(&env),
                 version: EVENT_VERSION_V2,
                 admin,
                 token,
@@ -585,706 +1248,948 @@ impl BountyEscrowContract {
         Ok(())
     }
 
-    /// Calculate fee amount based on rate (in basis points)
-    #[allow(dead_code)]
-    fn calculate_fee(amount: i128, fee_rate: i128) -> i128 {
-        if fee_rate == 0 {
-            return 0;
+    /// Initialize like [`Self::init`] and, when given, configure the neutral
+    /// arbiter that resolves disputes in the same call, so escrows locked
+    /// right after deployment are never left without one.
+    pub fn init_with_arbiter(
+        env: Env,
+        admin: Address,
+        token: Address,
+        arbiter: Option<Address>,
+    ) -> Result<(), Error> {
+        Self::init(env.clone(), admin, token)?;
+        if let Some(arbiter) = arbiter {
+            env.storage().instance().set(&DataKey::Arbiter, &arbiter);
         }
-        // Fee = (amount * fee_rate) / BASIS_POINTS
-        // Using checked arithmetic to prevent overflow
-        amount
-            .checked_mul(fee_rate)
-            .and_then(|x| x.checked_div(BASIS_POINTS))
-            .unwrap_or(0)
+        Ok(())
     }
 
-    /// Get fee configuration (internal helper)
-    fn get_fee_config_internal(env: &Env) -> FeeConfig {
-        env.storage()
+    /// Replace the contract code with a previously uploaded WASM (admin only).
+    ///
+    /// Storage is untouched, so escrows, indexes and configuration carry over
+    /// to the new code.
+    ///
+    /// Under a multi-admin setup this fails with `Unauthorized`; propose an
+    /// `AdminAction::Upgrade` instead.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
             .instance()
-            .get(&DataKey::FeeConfig)
-            .unwrap_or_else(|| FeeConfig {
-                lock_fee_rate: 0,
-                release_fee_rate: 0,
-                fee_recipient: env.storage().instance().get(&DataKey::Admin).unwrap(),
-                fee_enabled: false,
-            })
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        Self::ensure_single_admin(&env)?;
+        admin.require_auth();
+
+        Self::apply_upgrade(&env, admin, new_wasm_hash);
+        Ok(())
     }
 
-    /// Update fee configuration (admin only)
-    pub fn update_fee_config(
-        env: Env,
-        lock_fee_rate: Option<i128>,
-        release_fee_rate: Option<i128>,
-        fee_recipient: Option<Address>,
-        fee_enabled: Option<bool>,
-    ) -> Result<(), Error> {
-        if !env.storage().instance().has(&DataKey::Admin) {
-            return Err(Error::NotInitialized);
-        }
+    fn apply_upgrade(env: &Env, admin: Address, new_wasm_hash: BytesN<32>) {
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
 
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        admin.require_auth();
+        events::emit_upgraded(
+            env,
+            events::Upgraded {
+                seq: events::next_event_seq(env),
+                version: EVENT_VERSION_V2,
+                previous_contract_version: CONTRACT_VERSION,
+                new_wasm_hash,
+                admin,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
 
-        let mut fee_config = Self::get_fee_config_internal(&env);
+    // ========================================================================
+    // MULTI-ADMIN ACTIONS
+    // ========================================================================
 
-        if let Some(rate) = lock_fee_rate {
-            if !(0..=MAX_FEE_RATE).contains(&rate) {
-                return Err(Error::InvalidFeeRate);
-            }
-            fee_config.lock_fee_rate = rate;
+    /// Initialize with a set of admins, `threshold` of which must approve
+    /// fee and treasury changes, fund recovery, migrations and upgrades
+    /// (see [`AdminAction`]).
+    ///
+    /// The first admin becomes the primary admin used by every other
+    /// admin-only entrypoint. A single admin behaves exactly like
+    /// [`Self::init`]. Fails with `InvalidBatchSize` for an empty or
+    /// duplicated admin list or a threshold outside `1..=admins.len()`.
+    pub fn init_with_admins(
+        env: Env,
+        admins: Vec<Address>,
+        threshold: u32,
+        token: Address,
+    ) -> Result<(), Error> {
+        if threshold == 0 || threshold > admins.len() {
+            return Err(Error::InvalidBatchSize);
         }
-
-        if let Some(rate) = release_fee_rate {
-            if !(0..=MAX_FEE_RATE).contains(&rate) {
-                return Err(Error::InvalidFeeRate);
+        for (i, admin) in admins.iter().enumerate() {
+            if admins.first_index_of(&admin) != Some(i as u32) {
+                return Err(Error::InvalidBatchSize);
             }
-            fee_config.release_fee_rate = rate;
         }
-
-        if let Some(recipient) = fee_recipient {
-            fee_config.fee_recipient = recipient;
+        Self::init(env.clone(), admins.get(0).unwrap(), token)?;
+        if admins.len() > 1 {
+            env.storage().instance().set(&ConfigKey::Admins, &admins);
+            env.storage()
+                .instance()
+                .set(&ConfigKey::AdminThreshold, &threshold);
         }
+        Ok(())
+    }
 
-        if let Some(enabled) = fee_enabled {
-            fee_config.fee_enabled = enabled;
+    /// View: admins allowed to propose and approve admin actions. A
+    /// single-admin contract reports just its admin.
+    pub fn get_admins(env: Env) -> Result<Vec<Address>, Error> {
+        if let Some(admins) = env.storage().instance().get(&ConfigKey::Admins) {
+            return Ok(admins);
         }
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        Ok(vec![&env, admin])
+    }
 
+    /// View: approvals an admin action needs before it can execute.
+    pub fn get_admin_threshold(env: Env) -> u32 {
         env.storage()
             .instance()
-            .set(&DataKey::FeeConfig, &fee_config);
+            .get(&ConfigKey::AdminThreshold)
+            .unwrap_or(1)
+    }
 
-        events::emit_fee_config_updated(
-            &env,
-            events::FeeConfigUpdated {
-                lock_fee_rate: fee_config.lock_fee_rate,
-                release_fee_rate: fee_config.release_fee_rate,
-                fee_recipient: fee_config.fee_recipient.clone(),
-                fee_enabled: fee_config.fee_enabled,
-                timestamp: env.ledger().timestamp(),
-            },
-        );
-
-        Ok(())
+    /// View: a proposed admin action that has not executed yet.
+    pub fn get_admin_action(env: Env, action_id: u64) -> Option<PendingAdminAction> {
+        env.storage()
+            .persistent()
+            .get(&RecordKey::AdminAction(action_id))
     }
 
-    /// Update pause flags (admin only)
-    pub fn set_paused(
-        env: Env,
-        lock: Option<bool>,
-        release: Option<bool>,
-        refund: Option<bool>,
-        reason: Option<soroban_sdk::String>,
-    ) -> Result<(), Error> {
-        if !env.storage().instance().has(&DataKey::Admin) {
-            return Err(Error::NotInitialized);
-        }
+    /// Propose a privileged action (admin only). The proposal counts as the
+    /// proposer's approval. Returns the id to approve and execute it by.
+    pub fn propose_action(env: Env, proposer: Address, action: AdminAction) -> Result<u64, Error> {
+        Self::ensure_admin_member(&env, &proposer)?;
+        proposer.require_auth();
 
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        admin.require_auth();
+        let action_id: u64 = env
+            .storage()
+            .instance()
+            .get(&ConfigKey::NextActionId)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&ConfigKey::NextActionId, &(action_id + 1));
 
-        let mut flags = Self::get_pause_flags(&env);
-        let timestamp = env.ledger().timestamp();
+        let pending = PendingAdminAction {
+            action,
+            proposer: proposer.clone(),
+            approvals: vec![&env, proposer],
+        };
+        env.storage()
+            .persistent()
+            .set(&RecordKey::AdminAction(action_id), &pending);
+        Ok(action_id)
+    }
 
-        if reason.is_some() {
-            flags.pause_reason = reason.clone();
-        }
+    /// Approve a proposed action (admin only). Approving twice is a no-op.
+    pub fn approve_action(env: Env, approver: Address, action_id: u64) -> Result<(), Error> {
+        Self::ensure_admin_member(&env, &approver)?;
+        approver.require_auth();
 
-        if let Some(paused) = lock {
-            flags.lock_paused = paused;
-            events::emit_pause_state_changed(
-                &env,
-                PauseStateChanged {
-                    operation: symbol_short!("lock"),
-                    paused,
-                    admin: admin.clone(),
-                    reason: reason.clone(),
-                    timestamp,
-                },
-            );
+        let key = RecordKey::AdminAction(action_id);
+        let mut pending: PendingAdminAction = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::InsufficientApprovals)?;
+        if pending.approvals.contains(&approver) {
+            return Ok(());
         }
+        pending.approvals.push_back(approver);
+        env.storage().persistent().set(&key, &pending);
+        Ok(())
+    }
 
-        if let Some(paused) = release {
-            flags.release_paused = paused;
-            events::emit_pause_state_changed(
-                &env,
-                PauseStateChanged {
-                    operation: symbol_short!("release"),
-                    paused,
-                    admin: admin.clone(),
-                    reason: reason.clone(),
-                    timestamp,
-                },
-            );
+    /// Execute a proposed action once it has reached the admin threshold.
+    /// Anyone may call this; the approvals are the authorization. The
+    /// proposal is consumed, so an action executes at most once.
+    pub fn execute_action(env: Env, action_id: u64) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        let key = RecordKey::AdminAction(action_id);
+        let pending: PendingAdminAction = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::InsufficientApprovals)?;
+        if pending.approvals.len() < Self::get_admin_threshold(env.clone()) {
+            return Err(Error::InsufficientApprovals);
         }
+        env.storage().persistent().remove(&key);
 
-        if let Some(paused) = refund {
-            flags.refund_paused = paused;
-            events::emit_pause_state_changed(
-                &env,
-                PauseStateChanged {
-                    operation: symbol_short!("refund"),
-                    paused,
-                    admin: admin.clone(),
-                    reason: reason.clone(),
-                    timestamp,
-                },
-            );
+        match pending.action {
+            AdminAction::UpdateFeeConfig(fee_config) => {
+                Self::validate_fee_config(&fee_config)?;
+                Self::store_fee_config(&env, &fee_config);
+            }
+            AdminAction::Upgrade(new_wasm_hash) => {
+                Self::apply_upgrade(&env, admin, new_wasm_hash);
+            }
+            AdminAction::SetFeeBps(bps) => Self::apply_fee_bps(&env, bps)?,
+            AdminAction::SetFeeCollector(fee_collector) => {
+                Self::apply_fee_collector(&env, fee_collector);
+            }
+            AdminAction::SetRefundPenaltyBps(bps) => Self::apply_refund_penalty_bps(&env, bps)?,
+            AdminAction::SetTreasury(treasury) => {
+                env.storage()
+                    .instance()
+                    .set(&ConfigKey::Treasury, &treasury);
+            }
+            AdminAction::WithdrawTreasury(token, amount, to) => {
+                Self::apply_withdraw_treasury(&env, token, amount, to)?;
+            }
+            AdminAction::EmergencyWithdraw(target, token) => {
+                Self::apply_emergency_withdraw(&env, admin, target, token)?;
+            }
+            AdminAction::Migrate => {
+                Self::apply_migrate(&env);
+            }
+            AdminAction::ApproveRefund(bounty_id, amount, recipient, mode) => {
+                Self::apply_approve_refund(&env, admin, bounty_id, amount, recipient, mode)?;
+            }
+            AdminAction::AdminRefund(bounty_id) => {
+                Self::apply_admin_refund(&env, admin, bounty_id)?;
+            }
+            AdminAction::SetArbiter(arbiter) => {
+                env.storage().instance().set(&DataKey::Arbiter, &arbiter);
+            }
+            AdminAction::SetRole(role, account, granted) => {
+                Self::apply_set_role(&env, role, account, granted);
+            }
         }
+        Ok(())
+    }
 
-        let any_paused = flags.lock_paused || flags.release_paused || flags.refund_paused;
-
-        if any_paused {
-            if flags.paused_at == 0 {
-                flags.paused_at = timestamp;
-            }
+    fn ensure_admin_member(env: &Env, address: &Address) -> Result<(), Error> {
+        if Self::get_admins(env.clone())?.contains(address) {
+            Ok(())
         } else {
-            flags.pause_reason = None;
-            flags.paused_at = 0;
+            Err(Error::Unauthorized)
         }
+    }
 
-        env.storage().instance().set(&DataKey::PauseFlags, &flags);
+    /// Direct calls to multi-admin-gated entrypoints are only allowed when
+    /// the contract has a single admin.
+    fn ensure_single_admin(env: &Env) -> Result<(), Error> {
+        if env.storage().instance().has(&ConfigKey::Admins) {
+            return Err(Error::Unauthorized);
+        }
         Ok(())
     }
 
-    /// Emergency withdraw all funds (admin only, must have lock_paused = true)
-    pub fn emergency_withdraw(env: Env, target: Address) -> Result<(), Error> {
+    /// View: logic version of the code currently deployed.
+    pub fn get_version(_env: Env) -> u32 {
+        CONTRACT_VERSION
+    }
+
+    /// View: storage layout version of the stored data. Lags `DATA_VERSION`
+    /// after an upgrade until `migrate` runs.
+    pub fn get_data_version(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&ConfigKey::DataVersion)
+            .unwrap_or(1)
+    }
+
+    /// Bring stored data up to the layout this code expects (admin only).
+    /// Run after an `upgrade`, with the contract paused, until it returns
+    /// `DATA_VERSION`: each call processes at most `MIGRATION_BATCH_SIZE`
+    /// records and the next one resumes where it stopped. Calling it when
+    /// already current is a no-op. Returns the resulting data version.
+    ///
+    /// Under a multi-admin setup this fails with `Unauthorized`; propose an
+    /// `AdminAction::Migrate` instead.
+    pub fn migrate(env: Env) -> Result<u32, Error> {
         let admin: Address = env
             .storage()
             .instance()
             .get(&DataKey::Admin)
             .ok_or(Error::NotInitialized)?;
+        Self::ensure_single_admin(&env)?;
         admin.require_auth();
 
-        let flags = Self::get_pause_flags(&env);
-        if !flags.lock_paused {
-            return Err(Error::NotPaused);
-        }
-
-        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let token_client = token::TokenClient::new(&env, &token_address);
+        Ok(Self::apply_migrate(&env))
+    }
 
-        let contract_address = env.current_contract_address();
-        let balance = token_client.balance(&contract_address);
+    fn apply_migrate(env: &Env) -> u32 {
+        let from = Self::get_data_version(env.clone());
+        let mut version = from;
+        let mut budget = MIGRATION_BATCH_SIZE;
+        while version < DATA_VERSION && budget > 0 {
+            let finished = match version + 1 {
+                2 => Self::backfill_refund_log_totals(env, &mut budget),
+                3 => Self::backfill_token_stats(env, &mut budget),
+                4 => Self::rekey_treasury_balance(env),
+                5 => Self::rewrite_refund_log_entries(env, &mut budget),
+                6 => Self::backfill_lock_times(env, &mut budget),
+                _ => Self::backfill_active_tvl(env, &mut budget),
+            };
+            if !finished {
+                break;
+            }
+            version += 1;
+            env.storage()
+                .instance()
+                .set(&ConfigKey::DataVersion, &version);
+            env.storage().instance().remove(&ConfigKey::MigrationCursor);
+        }
 
-        if balance > 0 {
-            token_client.transfer(&contract_address, &target, &balance);
-            events::emit_emergency_withdraw(
-                &env,
-                events::EmergencyWithdrawEvent {
-                    admin,
-                    recipient: target,
-                    amount: balance,
+        if version > from {
+            events::emit_migrated(
+                env,
+                events::Migrated {
+                    version: EVENT_VERSION_V2,
+                    seq: events::next_event_seq(env),
+                    from_data_version: from,
+                    to_data_version: version,
                     timestamp: env.ledger().timestamp(),
                 },
             );
         }
-
-        Ok(())
+        version
     }
 
-    /// Get current pause flags
-    pub fn get_pause_flags(env: &Env) -> PauseFlags {
+    /// Position reached by the migration step in progress.
+    fn migration_cursor(env: &Env) -> u64 {
         env.storage()
             .instance()
-            .get(&DataKey::PauseFlags)
-            .unwrap_or(PauseFlags {
-                lock_paused: false,
-                release_paused: false,
-                refund_paused: false,
-                pause_reason: None,
-                paused_at: 0,
-            })
+            .get(&ConfigKey::MigrationCursor)
+            .unwrap_or(0)
     }
 
-    /// Check if an operation is paused
-    fn check_paused(env: &Env, operation: Symbol) -> bool {
-        let flags = Self::get_pause_flags(env);
-        if operation == symbol_short!("lock") {
-            return flags.lock_paused;
-        } else if operation == symbol_short!("release") {
-            return flags.release_paused;
-        } else if operation == symbol_short!("refund") {
-            return flags.refund_paused;
+    /// Move the single init-token treasury balance kept before data version
+    /// 4 under that token's per-token key.
+    fn rekey_treasury_balance(env: &Env) -> bool {
+        let legacy: Option<i128> = env.storage().instance().get(&ConfigKey::TreasuryBalance);
+        if let Some(amount) = legacy {
+            let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+            let key = RecordKey::TreasuryBalance(token_addr);
+            let current: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+            env.storage().persistent().set(&key, &(current + amount));
+            env.storage().instance().remove(&ConfigKey::TreasuryBalance);
         }
-        false
+        true
     }
 
-    /// Get current fee configuration (view function)
-    pub fn get_fee_config(env: Env) -> FeeConfig {
-        Self::get_fee_config_internal(&env)
+    /// Rebuild the running totals behind `sum_refunds_between` for refund
+    /// log entries recorded before the totals were kept. Returns false if
+    /// the budget ran out first.
+    fn backfill_refund_log_totals(env: &Env, budget: &mut u64) -> bool {
+        let count: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RefundLogCount)
+            .unwrap_or(0);
+        let mut seq = Self::migration_cursor(env);
+        let mut total = Self::refund_log_total(env, seq);
+        while seq < count && *budget > 0 {
+            total += Self::read_refund_log_entry(env, seq).amount;
+            env.storage()
+                .persistent()
+                .set(&RecordKey::RefundLogTotal(seq + 1), &total);
+            seq += 1;
+            *budget -= 1;
+        }
+        env.storage()
+            .instance()
+            .set(&ConfigKey::MigrationCursor, &seq);
+        seq == count
     }
 
-    /// Update multisig configuration (admin only)
-    pub fn update_multisig_config(
-        env: Env,
-        threshold_amount: i128,
-        signers: Vec<Address>,
-        required_signatures: u32,
-    ) -> Result<(), Error> {
-        if !env.storage().instance().has(&DataKey::Admin) {
-            return Err(Error::NotInitialized);
+    /// Rebuild the per-token stats from the escrow index. Escrows archived
+    /// before the stats were kept are not counted, and collected fees are
+    /// carried over as they cannot be recovered from the escrows. Stats
+    /// gathered so far are parked in `ConfigKey::MigrationStats` when the
+    /// budget runs out.
+    fn backfill_token_stats(env: &Env, budget: &mut u64) -> bool {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(env));
+        let mut rebuilt: Map<Address, AggregateStats> = env
+            .storage()
+            .instance()
+            .get(&ConfigKey::MigrationStats)
+            .unwrap_or(Map::new(env));
+        let mut position = Self::migration_cursor(env);
+        while position < index.len() as u64 && *budget > 0 {
+            let bounty_id = index.get(position as u32).unwrap();
+            if let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            {
+                let token = Self::escrow_token(env, bounty_id);
+                let mut stats = rebuilt
+                    .get(token.clone())
+                    .unwrap_or_else(|| Self::empty_stats(env));
+                let withheld = Self::withheld_amount(env, bounty_id);
+                Self::tally_token_stats(&mut stats, &token, &escrow, withheld, true);
+                rebuilt.set(token, stats);
+            }
+            position += 1;
+            *budget -= 1;
         }
-
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        admin.require_auth();
-
-        if required_signatures > signers.len() {
-            return Err(Error::InvalidAmount);
+        if position < index.len() as u64 {
+            env.storage()
+                .instance()
+                .set(&ConfigKey::MigrationCursor, &position);
+            env.storage()
+                .instance()
+                .set(&ConfigKey::MigrationStats, &rebuilt);
+            return false;
         }
 
-        let config = MultisigConfig {
-            threshold_amount,
-            signers,
-            required_signatures,
-        };
+        for (token, mut stats) in rebuilt.iter() {
+            stats.total_fees_collected =
+                Self::get_aggregate_stats_by_token(env.clone(), token.clone()).total_fees_collected;
+            env.storage()
+                .persistent()
+                .set(&RecordKey::TokenStats(token), &stats);
+        }
+        env.storage().instance().remove(&ConfigKey::MigrationStats);
+        true
+    }
 
+    /// Rewrite refund log entries stored before `recipient`, `admin_forced`
+    /// or `gross_amount` existed in the current `RefundHistoryEntry` layout.
+    fn rewrite_refund_log_entries(env: &Env, budget: &mut u64) -> bool {
+        let count: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RefundLogCount)
+            .unwrap_or(0);
+        let mut seq = Self::migration_cursor(env);
+        while seq < count && *budget > 0 {
+            let entry = Self::read_refund_log_entry(env, seq);
+            env.storage()
+                .persistent()
+                .set(&DataKey::RefundLog(seq), &entry);
+            seq += 1;
+            *budget -= 1;
+        }
         env.storage()
             .instance()
-            .set(&DataKey::MultisigConfig, &config);
+            .set(&ConfigKey::MigrationCursor, &seq);
+        seq == count
+    }
 
-        Ok(())
+    /// Read refund log entry `seq` in whichever layout it was stored. Fields
+    /// an older layout lacks take the value they had at the time: the
+    /// depositor as recipient, no admin override and no refund penalty.
+    fn read_refund_log_entry(env: &Env, seq: u64) -> RefundHistoryEntry {
+        let fields: Map<Symbol, Val> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RefundLog(seq))
+            .unwrap();
+        let field = |name: &str| fields.get(Symbol::new(env, name));
+        let depositor = Address::try_from_val(env, &field("depositor").unwrap()).unwrap();
+        let amount = i128::try_from_val(env, &field("amount").unwrap()).unwrap();
+        RefundHistoryEntry {
+            bounty_id: u64::try_from_val(env, &field("bounty_id").unwrap()).unwrap(),
+            recipient: field("recipient")
+                .map(|v| Address::try_from_val(env, &v).unwrap())
+                .unwrap_or(depositor.clone()),
+            depositor,
+            amount,
+            gross_amount: field("gross_amount")
+                .map(|v| i128::try_from_val(env, &v).unwrap())
+                .unwrap_or(amount),
+            timestamp: u64::try_from_val(env, &field("timestamp").unwrap()).unwrap(),
+            admin_forced: field("admin_forced")
+                .map(|v| bool::try_from_val(env, &v).unwrap())
+                .unwrap_or(false),
+        }
     }
 
-    /// Get multisig configuration
-    pub fn get_multisig_config(env: Env) -> MultisigConfig {
+    /// Recover the lock time behind `get_avg_time_to_release` for escrows
+    /// locked before it was recorded, from the `Locked` entry of their
+    /// history. Escrows whose history no longer starts at the lock are
+    /// left out of the average.
+    fn backfill_lock_times(env: &Env, budget: &mut u64) -> bool {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(env));
+        let mut position = Self::migration_cursor(env);
+        while position < index.len() as u64 && *budget > 0 {
+            let bounty_id = index.get(position as u32).unwrap();
+            let key = RecordKey::CreatedAt(bounty_id);
+            if !env.storage().persistent().has(&key) {
+                let history: Vec<EscrowHistoryEntry> = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::EscrowHistory(bounty_id))
+                    .unwrap_or(Vec::new(env));
+                if let Some(first) = history.first() {
+                    if first.kind == EscrowHistoryKind::Locked {
+                        env.storage().persistent().set(&key, &first.timestamp);
+                    }
+                }
+            }
+            position += 1;
+            *budget -= 1;
+        }
         env.storage()
             .instance()
-            .get(&DataKey::MultisigConfig)
-            .unwrap_or(MultisigConfig {
-                threshold_amount: i128::MAX,
-                signers: vec![&env],
-                required_signatures: 0,
-            })
+            .set(&ConfigKey::MigrationCursor, &position);
+        position == index.len() as u64
     }
 
-    /// Approve release for large amount (requires multisig)
-    pub fn approve_large_release(
-        env: Env,
-        bounty_id: u64,
-        contributor: Address,
-        approver: Address,
-    ) -> Result<(), Error> {
-        if !env.storage().instance().has(&DataKey::Admin) {
-            return Err(Error::NotInitialized);
+    /// Rebuild the totals behind `get_active_tvl` from the escrow index,
+    /// starting from zero on the first call of the step.
+    fn backfill_active_tvl(env: &Env, budget: &mut u64) -> bool {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(env));
+        let mut position = Self::migration_cursor(env);
+        if position == 0 {
+            env.storage().instance().remove(&ConfigKey::LockedTvl);
+            env.storage()
+                .persistent()
+                .remove(&RecordKey::LockedByDeadline);
         }
-
-        let multisig_config: MultisigConfig = Self::get_multisig_config(env.clone());
-
-        let mut is_signer = false;
-        for signer in multisig_config.signers.iter() {
-            if signer == approver {
-                is_signer = true;
-                break;
+        while position < index.len() as u64 && *budget > 0 {
+            let bounty_id = index.get(position as u32).unwrap();
+            if let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            {
+                Self::shift_active_tvl(env, None, &escrow);
             }
+            position += 1;
+            *budget -= 1;
         }
+        env.storage()
+            .instance()
+            .set(&ConfigKey::MigrationCursor, &position);
+        position == index.len() as u64
+    }
 
-        if !is_signer {
-            return Err(Error::Unauthorized);
+    /// Calculate fee amount based on rate (in basis points).
+    /// Integer division truncates, so fees always round down in the payee's favour.
+    fn calculate_fee(amount: i128, fee_rate: i128) -> i128 {
+        if fee_rate == 0 {
+            return 0;
         }
+        // Fee = (amount * fee_rate) / BASIS_POINTS
+        // Using checked arithmetic to prevent overflow
+        amount
+            .checked_mul(fee_rate)
+            .and_then(|x| x.checked_div(BASIS_POINTS))
+            .unwrap_or(0)
+    }
 
-        approver.require_auth();
+    /// Get fee configuration (internal helper)
+    fn get_fee_config_internal(env: &Env) -> FeeConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::FeeConfig)
+            .unwrap_or_else(|| FeeConfig {
+                lock_fee_rate: 0,
+                release_fee_rate: 0,
+                fee_recipient: env.storage().instance().get(&DataKey::Admin).unwrap(),
+                fee_enabled: false,
+            })
+    }
 
-        let approval_key = DataKey::ReleaseApproval(bounty_id);
-        let mut approval: ReleaseApproval = env
+    /// Designate the treasury (admin only).
+    ///
+    /// Once set, release fees, refund penalties and forfeited partial refunds
+    /// stay in the contract and are credited to `get_treasury_balance` for
+    /// the escrow's token instead of being sent to the fee recipient.
+    ///
+    /// Under a multi-admin setup this fails with `Unauthorized`; propose an
+    /// `AdminAction::SetTreasury` instead.
+    pub fn set_treasury(env: Env, treasury: Address) -> Result<(), Error> {
+        let admin: Address = env
             .storage()
-            .persistent()
-            .get(&approval_key)
-            .unwrap_or(ReleaseApproval {
-                bounty_id,
-                contributor: contributor.clone(),
-                approvals: vec![&env],
-            });
-
-        for existing in approval.approvals.iter() {
-            if existing == approver {
-                return Ok(());
-            }
-        }
-
-        approval.approvals.push_back(approver.clone());
-        env.storage().persistent().set(&approval_key, &approval);
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        Self::ensure_single_admin(&env)?;
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&ConfigKey::Treasury, &treasury);
+        Ok(())
+    }
 
-        events::emit_approval_added(
-            &env,
-            events::ApprovalAdded {
-                bounty_id,
-                contributor: contributor.clone(),
-                approver,
-                timestamp: env.ledger().timestamp(),
-            },
-        );
+    /// View: the treasury address, if one has been set.
+    pub fn get_treasury(env: Env) -> Option<Address> {
+        env.storage().instance().get(&ConfigKey::Treasury)
+    }
 
-        Ok(())
+    /// View: credits in `token` held for the treasury. Not counted in the
+    /// locked side of `get_solvency`.
+    pub fn get_treasury_balance(env: Env, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&RecordKey::TreasuryBalance(token))
+            .unwrap_or(0)
     }
 
-    /// Lock funds for a specific bounty.
-    pub fn lock_funds(
+    /// Move `amount` of the treasury balance in `token` to `to` (admin only).
+    /// Fails with `InsufficientFunds` if `amount` exceeds the tracked balance
+    /// for that token.
+    ///
+    /// Under a multi-admin setup this fails with `Unauthorized`; propose an
+    /// `AdminAction::WithdrawTreasury` instead.
+    pub fn withdraw_treasury(
         env: Env,
-        depositor: Address,
-        bounty_id: u64,
+        token: Address,
         amount: i128,
-        deadline: u64,
+        to: Address,
     ) -> Result<(), Error> {
-        // Apply rate limiting
-        anti_abuse::check_rate_limit(&env, depositor.clone());
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        Self::ensure_single_admin(&env)?;
+        admin.require_auth();
+        Self::apply_withdraw_treasury(&env, token, amount, to)
+    }
 
-        if Self::check_paused(&env, symbol_short!("lock")) {
-            return Err(Error::FundsPaused);
+    fn apply_withdraw_treasury(
+        env: &Env,
+        token: Address,
+        amount: i128,
+        to: Address,
+    ) -> Result<(), Error> {
+        reentrancy_guard::enter(env)?;
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        let balance = Self::get_treasury_balance(env.clone(), token.clone());
+        if amount > balance {
+            return Err(Error::InsufficientFunds);
         }
 
-        let _start = env.ledger().timestamp();
-        let _caller = depositor.clone();
+        let remaining = balance - amount;
+        env.storage()
+            .persistent()
+            .set(&RecordKey::TreasuryBalance(token.clone()), &remaining);
+        token::Client::new(env, &token).transfer(&env.current_contract_address(), &to, &amount);
 
-        // Verify depositor authorization
-        depositor.require_auth();
+        events::emit_treasury_withdrawn(
+            env,
+            events::TreasuryWithdrawn {
+                version: EVENT_VERSION_V2,
+                seq: events::next_event_seq(env),
+                token,
+                amount,
+                to,
+                remaining,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        reentrancy_guard::exit(env);
+        Ok(())
+    }
 
-        if !env.storage().instance().has(&DataKey::Admin) {
-            return Err(Error::NotInitialized);
+    /// Pay a fee, penalty or forfeited amount out of an escrow in
+    /// `token_addr` and return who was credited: the treasury when one is
+    /// set, otherwise the fee recipient.
+    fn credit_treasury(env: &Env, token_addr: &Address, amount: i128) -> Address {
+        if let Some(treasury) = Self::get_treasury(env.clone()) {
+            let balance = Self::get_treasury_balance(env.clone(), token_addr.clone());
+            env.storage().persistent().set(
+                &RecordKey::TreasuryBalance(token_addr.clone()),
+                &(balance + amount),
+            );
+            return treasury;
         }
+        let recipient = Self::get_fee_config_internal(env).fee_recipient;
+        token::Client::new(env, token_addr).transfer(
+            &env.current_contract_address(),
+            &recipient,
+            &amount,
+        );
+        recipient
+    }
 
-        if env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
-            return Err(Error::BountyExists);
+    /// Release fee in force for a `gross` payout (0 while fees are disabled).
+    fn release_fee(env: &Env, gross: i128) -> i128 {
+        let fee_config = Self::get_fee_config_internal(env);
+        if fee_config.fee_enabled {
+            Self::calculate_fee(gross, fee_config.release_fee_rate)
+        } else {
+            0
         }
+    }
 
-        // Enforce min/max amount policy if one has been configured (Issue #62).
-        // When no policy is set this block is skipped entirely, preserving
-        // backward-compatible behaviour for callers that never call set_amount_policy.
-        if let Some((min_amount, max_amount)) = env
-            .storage()
-            .instance()
-            .get::<DataKey, (i128, i128)>(&DataKey::AmountPolicy)
-        {
-            if amount < min_amount {
-                return Err(Error::AmountBelowMinimum);
-            }
-            if amount > max_amount {
-                return Err(Error::AmountAboveMaximum);
-            }
+    /// Withhold the release fee from a `gross` payout in `token_addr` and send
+    /// it to the treasury or fee recipient. Every path that pays a contributor
+    /// goes through here; the contributor receives `gross` minus the returned
+    /// fee.
+    fn collect_release_fee(env: &Env, token_addr: &Address, gross: i128) -> i128 {
+        let fee = Self::release_fee(env, gross);
+        if fee <= 0 {
+            return 0;
         }
-
-        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let client = token::Client::new(&env, &token_addr);
-
-        // Transfer funds from depositor to contract
-        client.transfer(&depositor, &env.current_contract_address(), &amount);
-
-        let escrow = Escrow {
-            depositor: depositor.clone(),
-            amount,
-            status: EscrowStatus::Locked,
-            deadline,
-            refund_history: vec![&env],
-            remaining_amount: amount,
-        };
-        invariants::assert_escrow(&env, &escrow);
-
-        // Extend the TTL of the storage entry to ensure it lives long enough
+        let fee_recipient = Self::credit_treasury(env, token_addr, fee);
+        let token_stats = Self::add_fees_collected(env, token_addr, fee);
         env.storage()
             .persistent()
-            .set(&DataKey::Escrow(bounty_id), &escrow);
+            .set(&RecordKey::TokenStats(token_addr.clone()), &token_stats);
+        events::emit_fee_collected(
+            env,
+            events::FeeCollected {
+                seq: events::next_event_seq(env),
+                operation_type: events::FeeOperationType::Release,
+                amount: fee,
+                fee_rate: Self::get_fee_config_internal(env).release_fee_rate,
+                recipient: fee_recipient,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        fee
+    }
 
-        // Update indexes
-        let mut index: Vec<u64> = env
+    /// Add `amount` to the global and `token_addr` fee totals. Returns the
+    /// updated token stats for the caller to store.
+    fn add_fees_collected(env: &Env, token_addr: &Address, amount: i128) -> AggregateStats {
+        let collected: i128 = env
             .storage()
-            .persistent()
-            .get(&DataKey::EscrowIndex)
-            .unwrap_or(Vec::new(&env));
-        index.push_back(bounty_id);
+            .instance()
+            .get(&DataKey::TotalFeesCollected)
+            .unwrap_or(0);
         env.storage()
-            .persistent()
-            .set(&DataKey::EscrowIndex, &index);
+            .instance()
+            .set(&DataKey::TotalFeesCollected, &(collected + amount));
+        let mut token_stats = Self::get_aggregate_stats_by_token(env.clone(), token_addr.clone());
+        token_stats.total_fees_collected += amount;
+        token_stats
+    }
 
-        let mut depositor_index: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::DepositorIndex(depositor.clone()))
-            .unwrap_or(Vec::new(&env));
-        depositor_index.push_back(bounty_id);
+    /// Withhold a refund penalty or forfeited `amount` from `bounty_id`'s
+    /// balance and credit it to the treasury. It counts towards the fees
+    /// collected rather than as released, so stats only report contributor
+    /// payouts as released. Call before storing the updated escrow.
+    fn withhold_from_refund(
+        env: &Env,
+        bounty_id: u64,
+        depositor: &Address,
+        token_addr: &Address,
+        amount: i128,
+    ) {
+        if amount <= 0 {
+            return;
+        }
+        Self::credit_treasury(env, token_addr, amount);
         env.storage().persistent().set(
-            &DataKey::DepositorIndex(depositor.clone()),
-            &depositor_index,
+            &RecordKey::Withheld(bounty_id),
+            &(Self::withheld_amount(env, bounty_id) + amount),
         );
 
-        // Emit value allows for off-chain indexing
-        emit_funds_locked(
-            &env,
-            FundsLocked {
-                version: EVENT_VERSION_V2,
-                bounty_id,
-                amount,
-                depositor: depositor.clone(),
-                deadline,
-            },
-        );
+        // Released volume is derived from the escrow as what left it without
+        // being refunded or withheld, so the stored token stats drop it here.
+        let mut token_stats = Self::add_fees_collected(env, token_addr, amount);
+        token_stats.total_released -= amount;
+        env.storage()
+            .persistent()
+            .set(&RecordKey::TokenStats(token_addr.clone()), &token_stats);
 
-        Ok(())
+        let key = DataKey::DepositorStats(depositor.clone());
+        let mut stats: DepositorStats = env.storage().persistent().get(&key).unwrap_or_default();
+        stats.total_locked -= amount;
+        env.storage().persistent().set(&key, &stats);
     }
 
-    /// Release funds to the contributor.
-    /// Only the admin (backend) can authorize this.
-    pub fn release_funds(env: Env, bounty_id: u64, contributor: Address) -> Result<(), Error> {
-        if Self::check_paused(&env, symbol_short!("release")) {
-            return Err(Error::FundsPaused);
-        }
-        let _start = env.ledger().timestamp();
-
-        // Ensure contract is initialized
-        if env.storage().instance().has(&DataKey::ReentrancyGuard) {
-            panic!("Reentrancy detected");
-        }
+    /// Refund penalties and forfeits withheld from `bounty_id` so far.
+    fn withheld_amount(env: &Env, bounty_id: u64) -> i128 {
         env.storage()
-            .instance()
-            .set(&DataKey::ReentrancyGuard, &true);
+            .persistent()
+            .get(&RecordKey::Withheld(bounty_id))
+            .unwrap_or(0)
+    }
+
+    /// Update fee configuration (admin only)
+    ///
+    /// Under a multi-admin setup this fails with `Unauthorized`; propose an
+    /// `AdminAction::UpdateFeeConfig` instead.
+    pub fn update_fee_config(
+        env: Env,
+        lock_fee_rate: Option<i128>,
+        release_fee_rate: Option<i128>,
+        fee_recipient: Option<Address>,
+        fee_enabled: Option<bool>,
+    ) -> Result<(), Error> {
         if !env.storage().instance().has(&DataKey::Admin) {
             return Err(Error::NotInitialized);
         }
 
+        Self::ensure_single_admin(&env)?;
         let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
         admin.require_auth();
 
-        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
-            return Err(Error::BountyNotFound);
+        let mut fee_config = Self::get_fee_config_internal(&env);
+
+        if let Some(rate) = lock_fee_rate {
+            fee_config.lock_fee_rate = rate;
         }
 
-        let mut escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
+        if let Some(rate) = release_fee_rate {
+            fee_config.release_fee_rate = rate;
+        }
 
-        if escrow.status != EscrowStatus::Locked {
-            return Err(Error::FundsNotLocked);
+        if let Some(recipient) = fee_recipient {
+            fee_config.fee_recipient = recipient;
         }
 
-        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let client = token::Client::new(&env, &token_addr);
+        if let Some(enabled) = fee_enabled {
+            fee_config.fee_enabled = enabled;
+        }
 
-        // Transfer funds to contributor
-        client.transfer(
-            &env.current_contract_address(),
-            &contributor,
-            &escrow.amount,
-        );
-
-        escrow.status = EscrowStatus::Released;
-        escrow.remaining_amount = 0;
-        invariants::assert_escrow(&env, &escrow);
-        env.storage()
-            .persistent()
-            .set(&DataKey::Escrow(bounty_id), &escrow);
-
-        emit_funds_released(
-            &env,
-            FundsReleased {
-                version: EVENT_VERSION_V2,
-                bounty_id,
-                amount: escrow.amount,
-                recipient: contributor.clone(),
-                timestamp: env.ledger().timestamp(),
-            },
-        );
-
-        // Clear reentrancy guard
-        env.storage().instance().remove(&DataKey::ReentrancyGuard);
+        Self::validate_fee_config(&fee_config)?;
+        Self::store_fee_config(&env, &fee_config);
 
         Ok(())
     }
 
-    /// Set the claim window duration (admin only).
-    /// claim_window: seconds beneficiary has to claim after release is authorized.
-    pub fn set_claim_window(env: Env, claim_window: u64) -> Result<(), Error> {
-        if !env.storage().instance().has(&DataKey::Admin) {
-            return Err(Error::NotInitialized);
-        }
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        admin.require_auth();
-        env.storage()
-            .instance()
-            .set(&DataKey::ClaimWindow, &claim_window);
-        Ok(())
+    /// Set the release fee in basis points (admin or `FeeManager`).
+    ///
+    /// The fee is deducted from each `release_funds` payout at the rate in force
+    /// at release time and sent to the configured fee recipient. Passing 0
+    /// disables the release fee. Values above `MAX_RELEASE_FEE_BPS` (10%) are rejected.
+    ///
+    /// Under a multi-admin setup this fails with `Unauthorized`; propose an
+    /// `AdminAction::SetFeeBps` instead.
+    pub fn set_fee_bps(env: Env, bps: i128, caller: Address) -> Result<(), Error> {
+        Self::require_role(&env, Role::FeeManager, &caller)?;
+        Self::ensure_single_admin(&env)?;
+        Self::apply_fee_bps(&env, bps)
     }
 
-    /// Authorize a release as a pending claim instead of immediate transfer.
-    /// Admin calls this instead of release_funds when claim period is active.
-    /// Beneficiary must call claim() within the window to receive funds.
-    pub fn authorize_claim(env: Env, bounty_id: u64, recipient: Address) -> Result<(), Error> {
-        if Self::check_paused(&env, symbol_short!("release")) {
-            return Err(Error::FundsPaused);
-        }
-        if !env.storage().instance().has(&DataKey::Admin) {
-            return Err(Error::NotInitialized);
-        }
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        admin.require_auth();
-
-        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
-            return Err(Error::BountyNotFound);
-        }
-
-        let escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
-
-        if escrow.status != EscrowStatus::Locked {
-            return Err(Error::FundsNotLocked);
+    fn apply_fee_bps(env: &Env, bps: i128) -> Result<(), Error> {
+        if !(0..=MAX_RELEASE_FEE_BPS).contains(&bps) {
+            return Err(Error::InvalidFeeRate);
         }
 
-        let now = env.ledger().timestamp();
-        let claim_window: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::ClaimWindow)
-            .unwrap_or(0);
-        let claim = ClaimRecord {
-            bounty_id,
-            recipient: recipient.clone(),
-            amount: escrow.amount,
-            expires_at: now.saturating_add(claim_window),
-            claimed: false,
-        };
-
-        env.storage()
-            .persistent()
-            .set(&DataKey::PendingClaim(bounty_id), &claim);
-
-        env.events().publish(
-            (symbol_short!("claim"), symbol_short!("created")),
-            ClaimCreated {
-                bounty_id,
-                recipient,
-                amount: escrow.amount,
-                expires_at: claim.expires_at,
-            },
-        );
+        let mut fee_config = Self::get_fee_config_internal(env);
+        fee_config.release_fee_rate = bps;
+        fee_config.fee_enabled = bps > 0 || fee_config.lock_fee_rate > 0;
+        Self::store_fee_config(env, &fee_config);
         Ok(())
     }
 
-    /// Beneficiary calls this to claim their authorized funds within the window.
-    pub fn claim(env: Env, bounty_id: u64) -> Result<(), Error> {
-        if Self::check_paused(&env, symbol_short!("release")) {
-            return Err(Error::FundsPaused);
-        }
-        if !env
-            .storage()
-            .persistent()
-            .has(&DataKey::PendingClaim(bounty_id))
-        {
-            return Err(Error::BountyNotFound);
-        }
-        let mut claim: ClaimRecord = env
+    /// Set the share of deadline refunds, in basis points, withheld and
+    /// credited to the treasury, or sent to the fee recipient when no treasury
+    /// is set (admin only). It counts towards `total_fees_collected`, not
+    /// released volume. 0, the default, disables it; values
+    /// above `MAX_REFUND_PENALTY_BPS` (10%) are rejected. The penalty rounds
+    /// down, in the depositor's favour. Admin-approved refunds are paid as
+    /// approved.
+    ///
+    /// Under a multi-admin setup this fails with `Unauthorized`; propose an
+    /// `AdminAction::SetRefundPenaltyBps` instead.
+    pub fn set_refund_penalty_bps(env: Env, bps: i128) -> Result<(), Error> {
+        let admin: Address = env
             .storage()
-            .persistent()
-            .get(&DataKey::PendingClaim(bounty_id))
-            .unwrap();
-
-        claim.recipient.require_auth();
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        Self::ensure_single_admin(&env)?;
+        admin.require_auth();
+        Self::apply_refund_penalty_bps(&env, bps)
+    }
 
-        let now = env.ledger().timestamp();
-        if now > claim.expires_at {
-            return Err(Error::DeadlineNotPassed); // reuse or add ClaimExpired error
-        }
-        if claim.claimed {
-            return Err(Error::FundsNotLocked);
+    fn apply_refund_penalty_bps(env: &Env, bps: i128) -> Result<(), Error> {
+        if !(0..=MAX_REFUND_PENALTY_BPS).contains(&bps) {
+            return Err(Error::InvalidFeeRate);
         }
-
-        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let client = token::Client::new(&env, &token_addr);
-        client.transfer(
-            &env.current_contract_address(),
-            &claim.recipient,
-            &claim.amount,
-        );
-
-        // Update escrow status
-        let mut escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
-        escrow.status = EscrowStatus::Released;
         env.storage()
-            .persistent()
-            .set(&DataKey::Escrow(bounty_id), &escrow);
+            .instance()
+            .set(&DataKey::RefundPenaltyBps, &bps);
+        Ok(())
+    }
 
-        claim.claimed = true;
+    /// View: the refund penalty in basis points (0 when unset).
+    pub fn get_refund_penalty_bps(env: Env) -> i128 {
         env.storage()
-            .persistent()
-            .set(&DataKey::PendingClaim(bounty_id), &claim);
+            .instance()
+            .get(&DataKey::RefundPenaltyBps)
+            .unwrap_or(0)
+    }
 
-        env.events().publish(
-            (symbol_short!("claim"), symbol_short!("done")),
-            ClaimExecuted {
-                bounty_id,
-                recipient: claim.recipient.clone(),
-                amount: claim.amount,
-                claimed_at: now,
-            },
-        );
+    /// Set the address that receives collected fees (admin or `FeeManager`).
+    ///
+    /// Under a multi-admin setup this fails with `Unauthorized`; propose an
+    /// `AdminAction::SetFeeCollector` instead.
+    pub fn set_fee_collector(
+        env: Env,
+        fee_collector: Address,
+        caller: Address,
+    ) -> Result<(), Error> {
+        Self::require_role(&env, Role::FeeManager, &caller)?;
+        Self::ensure_single_admin(&env)?;
+        Self::apply_fee_collector(&env, fee_collector);
         Ok(())
     }
 
-    /// Admin can cancel an expired or unwanted pending claim, returning escrow to Locked.
-    pub fn cancel_pending_claim(env: Env, bounty_id: u64) -> Result<(), Error> {
-        if !env.storage().instance().has(&DataKey::Admin) {
-            return Err(Error::NotInitialized);
-        }
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        admin.require_auth();
+    fn apply_fee_collector(env: &Env, fee_collector: Address) {
+        let mut fee_config = Self::get_fee_config_internal(env);
+        fee_config.fee_recipient = fee_collector;
+        Self::store_fee_config(env, &fee_config);
+    }
 
-        if !env
-            .storage()
-            .persistent()
-            .has(&DataKey::PendingClaim(bounty_id))
+    /// Validate fee rates (internal helper). The release fee shares the
+    /// `MAX_RELEASE_FEE_BPS` cap enforced by `set_fee_bps`.
+    fn validate_fee_config(fee_config: &FeeConfig) -> Result<(), Error> {
+        if !(0..=MAX_FEE_RATE).contains(&fee_config.lock_fee_rate)
+            || !(0..=MAX_RELEASE_FEE_BPS).contains(&fee_config.release_fee_rate)
         {
-            return Err(Error::BountyNotFound);
-        }
-        let claim: ClaimRecord = env
-            .storage()
-            .persistent()
-            .get(&DataKey::PendingClaim(bounty_id))
-            .unwrap();
-
-        if claim.claimed {
-            return Err(Error::FundsNotLocked);
+            return Err(Error::InvalidFeeRate);
         }
+        Ok(())
+    }
 
+    fn store_fee_config(env: &Env, fee_config: &FeeConfig) {
         env.storage()
-            .persistent()
-            .remove(&DataKey::PendingClaim(bounty_id));
+            .instance()
+            .set(&DataKey::FeeConfig, fee_config);
 
-        env.events().publish(
-            (symbol_short!("claim"), symbol_short!("cancel")),
-            ClaimCancelled {
-                bounty_id,
-                recipient: claim.recipient,
-                amount: claim.amount,
-                cancelled_at: env.ledger().timestamp(),
-                cancelled_by: admin,
+        events::emit_fee_config_updated(
+            env,
+            events::FeeConfigUpdated {
+                seq: events::next_event_seq(env),
+                lock_fee_rate: fee_config.lock_fee_rate,
+                release_fee_rate: fee_config.release_fee_rate,
+                fee_recipient: fee_config.fee_recipient.clone(),
+                fee_enabled: fee_config.fee_enabled,
+                timestamp: env.ledger().timestamp(),
             },
         );
-        Ok(())
-    }
-
-    /// View: get pending claim for a bounty.
-    pub fn get_pending_claim(env: Env, bounty_id: u64) -> Result<ClaimRecord, Error> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::PendingClaim(bounty_id))
-            .ok_or(Error::BountyNotFound)
     }
 
-    /// Approve a refund before deadline (admin only).
-    /// This allows early refunds with admin approval.
-    pub fn approve_refund(
+    /// Update pause flags (admin only)
+    pub fn set_paused(
         env: Env,
-        bounty_id: u64,
-        amount: i128,
-        recipient: Address,
-        mode: RefundMode,
+        lock: Option<bool>,
+        release: Option<bool>,
+        refund: Option<bool>,
+        reason: Option<soroban_sdk::String>,
     ) -> Result<(), Error> {
         if !env.storage().instance().has(&DataKey::Admin) {
             return Err(Error::NotInitialized);
@@ -1293,53 +2198,219 @@ impl BountyEscrowContract {
         let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
         admin.require_auth();
 
-        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
-            return Err(Error::BountyNotFound);
+        let mut flags = Self::get_pause_flags(&env);
+        let timestamp = env.ledger().timestamp();
+
+        if reason.is_some() {
+            flags.pause_reason = reason.clone();
         }
 
-        let escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
+        if let Some(paused) = lock {
+            flags.lock_paused = paused;
+            events::emit_pause_state_changed(
+                &env,
+                PauseStateChanged {
+                    seq: events::next_event_seq(&env),
+                    operation: symbol_short!("lock"),
+                    paused,
+                    admin: admin.clone(),
+                    reason: reason.clone(),
+                    timestamp,
+                },
+            );
+        }
 
-        if escrow.status != EscrowStatus::Locked && escrow.status != EscrowStatus::PartiallyRefunded
-        {
-            return Err(Error::FundsNotLocked);
+        if let Some(paused) = release {
+            flags.release_paused = paused;
+            events::emit_pause_state_changed(
+                &env,
+                PauseStateChanged {
+                    seq: events::next_event_seq(&env),
+                    operation: symbol_short!("release"),
+                    paused,
+                    admin: admin.clone(),
+                    reason: reason.clone(),
+                    timestamp,
+                },
+            );
         }
 
-        if amount <= 0 || amount > escrow.remaining_amount {
-            return Err(Error::InvalidAmount);
+        if let Some(paused) = refund {
+            flags.refund_paused = paused;
+            events::emit_pause_state_changed(
+                &env,
+                PauseStateChanged {
+                    seq: events::next_event_seq(&env),
+                    operation: symbol_short!("refund"),
+                    paused,
+                    admin: admin.clone(),
+                    reason: reason.clone(),
+                    timestamp,
+                },
+            );
         }
 
-        let approval = RefundApproval {
-            bounty_id,
-            amount,
-            recipient: recipient.clone(),
-            mode: mode.clone(),
-            approved_by: admin.clone(),
-            approved_at: env.ledger().timestamp(),
-        };
+        let any_paused = flags.lock_paused || flags.release_paused || flags.refund_paused;
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::RefundApproval(bounty_id), &approval);
+        if any_paused {
+            if flags.paused_at == 0 {
+                flags.paused_at = timestamp;
+            }
+        } else {
+            flags.pause_reason = None;
+            flags.paused_at = 0;
+        }
 
+        env.storage().instance().set(&DataKey::PauseFlags, &flags);
         Ok(())
     }
 
-    /// Release a partial amount of the locked funds to the contributor.
-    /// Only the admin (backend) can authorize this.
+    /// Emergency withdraw all funds (admin only, must have lock_paused = true)
     ///
-    /// - `payout_amount` must be > 0 and <= `remaining_amount`.
-    /// - `remaining_amount` is decremented by `payout_amount` after each call.
-    /// - When `remaining_amount` reaches 0 the escrow status is set to Released.
-    /// - The bounty stays Locked while any funds remain unreleased.
-    pub fn partial_release(
+    /// Drains the contract's balance of `token`, or of the init token when
+    /// `None`; run it once per asset held.
+    ///
+    /// Under a multi-admin setup this fails with `Unauthorized`; propose an
+    /// `AdminAction::EmergencyWithdraw` instead.
+    pub fn emergency_withdraw(
         env: Env,
-        bounty_id: u64,
-        contributor: Address,
-        payout_amount: i128,
+        target: Address,
+        token: Option<Address>,
+    ) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        Self::ensure_single_admin(&env)?;
+        admin.require_auth();
+        Self::apply_emergency_withdraw(&env, admin, target, token)
+    }
+
+    fn apply_emergency_withdraw(
+        env: &Env,
+        admin: Address,
+        target: Address,
+        token: Option<Address>,
+    ) -> Result<(), Error> {
+        reentrancy_guard::enter(env)?;
+        let flags = Self::get_pause_flags(env);
+        if !flags.lock_paused {
+            return Err(Error::NotPaused);
+        }
+
+        let default_token: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_address = token.unwrap_or(default_token);
+        let token_client = token::TokenClient::new(env, &token_address);
+
+        let contract_address = env.current_contract_address();
+        let balance = token_client.balance(&contract_address);
+
+        if balance > 0 {
+            token_client.transfer(&contract_address, &target, &balance);
+            // The drain takes the treasury's share with it
+            env.storage()
+                .persistent()
+                .remove(&RecordKey::TreasuryBalance(token_address));
+            events::emit_emergency_withdraw(
+                env,
+                events::EmergencyWithdrawEvent {
+                    seq: events::next_event_seq(env),
+                    admin,
+                    recipient: target,
+                    amount: balance,
+                    timestamp: env.ledger().timestamp(),
+                },
+            );
+        }
+
+        reentrancy_guard::exit(env);
+        Ok(())
+    }
+
+    /// Emergency switch: freeze all locks, releases and refunds (admin or `Pauser`).
+    /// Read-only views keep working so operators can inspect state.
+    pub fn pause(env: Env, caller: Address) -> Result<(), Error> {
+        Self::set_contract_paused(&env, true, caller)
+    }
+
+    /// Lift the emergency switch set by `pause` (admin or `Pauser`).
+    pub fn unpause(env: Env, caller: Address) -> Result<(), Error> {
+        Self::set_contract_paused(&env, false, caller)
+    }
+
+    /// View: whether the contract-wide emergency pause is active.
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false)
+    }
+
+    fn set_contract_paused(env: &Env, paused: bool, caller: Address) -> Result<(), Error> {
+        Self::require_role(env, Role::Pauser, &caller)?;
+
+        env.storage().instance().set(&DataKey::Paused, &paused);
+
+        let event = events::ContractPauseToggled {
+            seq: events::next_event_seq(env),
+            caller,
+            timestamp: env.ledger().timestamp(),
+        };
+        if paused {
+            events::emit_contract_paused(env, event);
+        } else {
+            events::emit_contract_unpaused(env, event);
+        }
+        Ok(())
+    }
+
+    /// Fail with ContractPaused while the emergency switch is on
+    fn ensure_not_paused(env: &Env) -> Result<(), Error> {
+        if Self::is_paused(env.clone()) {
+            return Err(Error::ContractPaused);
+        }
+        Ok(())
+    }
+
+    /// Get current pause flags
+    pub fn get_pause_flags(env: &Env) -> PauseFlags {
+        env.storage()
+            .instance()
+            .get(&DataKey::PauseFlags)
+            .unwrap_or(PauseFlags {
+                lock_paused: false,
+                release_paused: false,
+                refund_paused: false,
+                pause_reason: None,
+                paused_at: 0,
+            })
+    }
+
+    /// Check if an operation is paused
+    fn check_paused(env: &Env, operation: Symbol) -> bool {
+        let flags = Self::get_pause_flags(env);
+        if operation == symbol_short!("lock") {
+            return flags.lock_paused;
+        } else if operation == symbol_short!("release") {
+            return flags.release_paused;
+        } else if operation == symbol_short!("refund") {
+            return flags.refund_paused;
+        }
+        false
+    }
+
+    /// Get current fee configuration (view function)
+    pub fn get_fee_config(env: Env) -> FeeConfig {
+        Self::get_fee_config_internal(&env)
+    }
+
+    /// Update multisig configuration (admin only)
+    pub fn update_multisig_config(
+        env: Env,
+        threshold_amount: i128,
+        signers: Vec<Address>,
+        required_signatures: u32,
     ) -> Result<(), Error> {
         if !env.storage().instance().has(&DataKey::Admin) {
             return Err(Error::NotInitialized);
@@ -1348,59 +2419,89 @@ impl BountyEscrowContract {
         let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
         admin.require_auth();
 
-        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
-            return Err(Error::BountyNotFound);
+        if required_signatures > signers.len() {
+            return Err(Error::InvalidAmount);
         }
 
-        let mut escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
+        let config = MultisigConfig {
+            threshold_amount,
+            signers,
+            required_signatures,
+        };
 
-        if escrow.status != EscrowStatus::Locked {
-            return Err(Error::FundsNotLocked);
-        }
+        env.storage()
+            .instance()
+            .set(&DataKey::MultisigConfig, &config);
 
-        // Guard: zero or negative payout makes no sense and would corrupt state
-        if payout_amount <= 0 {
-            return Err(Error::InvalidAmount);
+        Ok(())
+    }
+
+    /// Get multisig configuration
+    pub fn get_multisig_config(env: Env) -> MultisigConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::MultisigConfig)
+            .unwrap_or(MultisigConfig {
+                threshold_amount: i128::MAX,
+                signers: vec![&env],
+                required_signatures: 0,
+            })
+    }
+
+    /// Approve release for large amount (requires multisig)
+    pub fn approve_large_release(
+        env: Env,
+        bounty_id: u64,
+        contributor: Address,
+        approver: Address,
+    ) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
         }
 
-        // Guard: prevent overpayment — payout cannot exceed what is still owed
-        if payout_amount > escrow.remaining_amount {
-            return Err(Error::InsufficientFunds);
+        let multisig_config: MultisigConfig = Self::get_multisig_config(env.clone());
+
+        let mut is_signer = false;
+        for signer in multisig_config.signers.iter() {
+            if signer == approver {
+                is_signer = true;
+                break;
+            }
         }
 
-        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let client = token::Client::new(&env, &token_addr);
+        if !is_signer {
+            return Err(Error::Unauthorized);
+        }
 
-        // Transfer only the requested partial amount to the contributor
-        client.transfer(
-            &env.current_contract_address(),
-            &contributor,
-            &payout_amount,
-        );
+        approver.require_auth();
 
-        // Decrement remaining; this is always an exact integer subtraction — no rounding
-        escrow.remaining_amount -= payout_amount;
+        let approval_key = DataKey::ReleaseApproval(bounty_id);
+        let mut approval: ReleaseApproval = env
+            .storage()
+            .persistent()
+            .get(&approval_key)
+            .unwrap_or(ReleaseApproval {
+                bounty_id,
+                contributor: contributor.clone(),
+                approvals: vec![&env],
+            });
 
-        // Automatically transition to Released once fully paid out
-        if escrow.remaining_amount == 0 {
-            escrow.status = EscrowStatus::Released;
+        for existing in approval.approvals.iter() {
+            if existing == approver {
+                return Ok(());
+            }
         }
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Escrow(bounty_id), &escrow);
+        approval.approvals.push_back(approver.clone());
+        env.storage().persistent().set(&approval_key, &approval);
 
-        events::emit_funds_released(
+        events::emit_approval_added(
             &env,
-            FundsReleased {
-                version: EVENT_VERSION_V2,
+            events::ApprovalAdded {
+                seq: events::next_event_seq(&env),
                 bounty_id,
-                amount: payout_amount,
-                recipient: contributor.clone(),
+                contributor: contributor.clone(),
+                approver,
                 timestamp: env.ledger().timestamp(),
             },
         );
@@ -1408,230 +2509,5552 @@ impl BountyEscrowContract {
         Ok(())
     }
 
-    /// Refund funds to the original depositor if the deadline has passed.
-    /// Refunds the full remaining_amount (accounts for any prior partial releases).
-    pub fn refund(env: Env, bounty_id: u64) -> Result<(), Error> {
-        if Self::check_paused(&env, symbol_short!("refund")) {
-            return Err(Error::FundsPaused);
+    /// Lock funds for a specific bounty.
+    ///
+    /// Escrow records are never deleted, so a `bounty_id` can only be locked
+    /// once: reusing one, even after it was released or refunded, fails with
+    /// `DuplicateBountyId`. Retried transactions therefore cannot overwrite a
+    /// settled escrow.
+    ///
+    /// The depositor must authorize the call: it owns the escrow and the
+    /// funds are pulled from it. See [`Self::lock_funds_by_relayer`] for
+    /// locking on a depositor's behalf. A balance below `amount` fails up
+    /// front with `InsufficientFunds`. The pull is a plain authorized
+    /// transfer needing no token allowance unless allowance locks are on
+    /// (see [`Self::set_allowance_locks`]).
+    ///
+    /// A `deadline` of `u64::MAX` means no deadline: the escrow never
+    /// becomes refundable by time.
+    pub fn lock_funds(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        amount: i128,
+        deadline: u64,
+    ) -> Result<(), Error> {
+        let token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Token)
+            .ok_or(Error::NotInitialized)?;
+        Self::lock_funds_internal(
+            env,
+            depositor.clone(),
+            bounty_id,
+            amount,
+            deadline,
+            token,
+            depositor,
+        )
+    }
+
+    /// [`Self::lock_funds`] with the deadline set `get_default_duration`
+    /// seconds from now. Fails with `InvalidDeadline` until the admin sets a
+    /// default duration.
+    pub fn lock_funds_simple(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        amount: i128,
+    ) -> Result<(), Error> {
+        let duration = Self::get_default_duration(env.clone());
+        if duration == 0 {
+            return Err(Error::InvalidDeadline);
+        }
+        let deadline = env.ledger().timestamp().saturating_add(duration);
+        Self::lock_funds(env, depositor, bounty_id, amount, deadline)
+    }
+
+    /// Lock funds on `depositor`'s behalf as the relayer set with
+    /// [`Self::set_relayer`]. The relayer authorizes the call and supplies
+    /// the funds (for example after collecting them off-chain); `depositor`
+    /// still owns the escrow and receives refunds. Any other caller fails
+    /// with `Unauthorized`.
+    pub fn lock_funds_by_relayer(
+        env: Env,
+        relayer: Address,
+        depositor: Address,
+        bounty_id: u64,
+        amount: i128,
+        deadline: u64,
+    ) -> Result<(), Error> {
+        if Self::get_relayer(env.clone()) != Some(relayer.clone()) {
+            return Err(Error::Unauthorized);
+        }
+        let token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Token)
+            .ok_or(Error::NotInitialized)?;
+        Self::lock_funds_internal(env, depositor, bounty_id, amount, deadline, token, relayer)
+    }
+
+    /// Set or clear (`None`) the relayer trusted to lock on depositors'
+    /// behalf (admin only).
+    pub fn set_relayer(env: Env, relayer: Option<Address>) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        match relayer {
+            Some(relayer) => env.storage().instance().set(&ConfigKey::Relayer, &relayer),
+            None => env.storage().instance().remove(&ConfigKey::Relayer),
+        }
+        Ok(())
+    }
+
+    /// View: the configured relayer, if any.
+    pub fn get_relayer(env: Env) -> Option<Address> {
+        env.storage().instance().get(&ConfigKey::Relayer)
+    }
+
+    /// Set or clear (`None`) the contract told about every payout through
+    /// [`traits::ReleaseNotifier`] (admin only).
+    pub fn set_notifier(env: Env, notifier: Option<Address>) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        match notifier {
+            Some(notifier) => env
+                .storage()
+                .instance()
+                .set(&ConfigKey::Notifier, &notifier),
+            None => env.storage().instance().remove(&ConfigKey::Notifier),
+        }
+        Ok(())
+    }
+
+    /// View: the configured release notifier, if any.
+    pub fn get_notifier(env: Env) -> Option<Address> {
+        env.storage().instance().get(&ConfigKey::Notifier)
+    }
+
+    /// Choose which events are published (admin only). See
+    /// [`EventLevel`]; lock, release and refund events are emitted
+    /// at every level.
+    pub fn set_event_level(env: Env, level: EventLevel) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        env.storage().instance().set(&ConfigKey::EventLevel, &level);
+        Ok(())
+    }
+
+    pub fn get_event_level(env: Env) -> EventLevel {
+        env.storage()
+            .instance()
+            .get(&ConfigKey::EventLevel)
+            .unwrap_or(EventLevel::Verbose)
+    }
+
+    /// Best-effort `on_release` call to the notifier, if one is set. A
+    /// failing notifier never reverts the payout; it is reported with a
+    /// `NotifierFailed` event instead.
+    fn notify_release(env: &Env, bounty_id: u64, contributor: &Address, amount: i128) {
+        let Some(notifier) = Self::get_notifier(env.clone()) else {
+            return;
+        };
+        let client = traits::ReleaseNotifierClient::new(env, &notifier);
+        if let Ok(Ok(())) = client.try_on_release(&bounty_id, contributor, &amount) {
+            return;
+        }
+        events::emit_notifier_failed(
+            env,
+            events::NotifierFailed {
+                version: EVENT_VERSION_V2,
+                seq: events::next_event_seq(env),
+                notifier,
+                bounty_id,
+                contributor: contributor.clone(),
+                amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    // ==================== DEPOSITOR-NAMESPACED ESCROWS ====================
+    //
+    // The flat methods share one global bounty_id space across depositors.
+    // The `_namespaced` variants key an escrow by `(depositor, bounty_id)`
+    // instead: each one is stored under a fresh internal id from
+    // NAMESPACED_ID_BASE upwards, which is what events, history and the
+    // flat views report for it.
+
+    /// Lock funds under `bounty_id` in `depositor`'s own id space and return
+    /// the internal id the escrow is stored under. Same checks as
+    /// [`Self::lock_funds`]; fails with `DuplicateBountyId` if `depositor` already
+    /// used `bounty_id`.
+    pub fn lock_funds_namespaced(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        amount: i128,
+        deadline: u64,
+    ) -> Result<u64, Error> {
+        let key = RecordKey::NamespacedEscrow(depositor.clone(), bounty_id);
+        if env.storage().persistent().has(&key) {
+            return Err(Error::DuplicateBountyId);
+        }
+
+        // Skip any id a flat lock already claimed
+        let mut offset: u64 = env
+            .storage()
+            .instance()
+            .get(&ConfigKey::NextNamespacedId)
+            .unwrap_or(0);
+        let mut internal_id = NAMESPACED_ID_BASE + offset;
+        while env
+            .storage()
+            .persistent()
+            .has(&DataKey::Escrow(internal_id))
+        {
+            offset += 1;
+            internal_id = NAMESPACED_ID_BASE + offset;
+        }
+
+        Self::lock_funds(env.clone(), depositor, internal_id, amount, deadline)?;
+        env.storage()
+            .instance()
+            .set(&ConfigKey::NextNamespacedId, &(offset + 1));
+        env.storage().persistent().set(&key, &internal_id);
+        Ok(internal_id)
+    }
+
+    /// View: the internal id of `depositor`'s namespaced `bounty_id`.
+    pub fn get_namespaced_id(env: Env, depositor: Address, bounty_id: u64) -> Result<u64, Error> {
+        env.storage()
+            .persistent()
+            .get(&RecordKey::NamespacedEscrow(depositor, bounty_id))
+            .ok_or(Error::BountyNotFound)
+    }
+
+    /// Compound-key variant of [`Self::get_escrow_info`].
+    pub fn get_escrow_namespaced(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+    ) -> Result<Escrow, Error> {
+        let internal_id = Self::get_namespaced_id(env.clone(), depositor, bounty_id)?;
+        Self::get_escrow_info(env, internal_id)
+    }
+
+    /// Compound-key variant of [`Self::release_funds`].
+    pub fn release_funds_namespaced(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        contributor: Address,
+    ) -> Result<(), Error> {
+        let internal_id = Self::get_namespaced_id(env.clone(), depositor, bounty_id)?;
+        Self::release_funds(env, internal_id, contributor)
+    }
+
+    /// Compound-key variant of [`Self::refund`].
+    pub fn refund_namespaced(env: Env, depositor: Address, bounty_id: u64) -> Result<(), Error> {
+        let internal_id = Self::get_namespaced_id(env.clone(), depositor, bounty_id)?;
+        Self::refund(env, internal_id)
+    }
+
+    /// Lock funds for a bounty denominated in `token` instead of the init token.
+    /// Release, refund and top-up for this bounty all settle in the same token.
+    /// Pass the native asset contract address to escrow XLM directly.
+    pub fn lock_funds_with_token(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        amount: i128,
+        deadline: u64,
+        token: Address,
+    ) -> Result<(), Error> {
+        Self::lock_funds_internal(
+            env,
+            depositor.clone(),
+            bounty_id,
+            amount,
+            deadline,
+            token,
+            depositor,
+        )
+    }
+
+    /// Allow escrows to be locked in `token` (admin only). The init token is
+    /// always accepted; with an empty allowlist it is the only one.
+    pub fn add_allowed_token(env: Env, token: Address) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        let mut tokens = Self::get_allowed_tokens(env.clone());
+        if !tokens.contains(&token) {
+            tokens.push_back(token);
+            env.storage()
+                .instance()
+                .set(&DataKey::AllowedTokens, &tokens);
+        }
+        Ok(())
+    }
+
+    /// Stop accepting `token` for new locks (admin only). Existing escrows in
+    /// that token can still be released and refunded.
+    pub fn remove_allowed_token(env: Env, token: Address) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        let mut tokens = Self::get_allowed_tokens(env.clone());
+        if let Some(i) = tokens.first_index_of(&token) {
+            tokens.remove(i);
+            env.storage()
+                .instance()
+                .set(&DataKey::AllowedTokens, &tokens);
+        }
+        Ok(())
+    }
+
+    /// View: tokens accepted by `lock_funds_with_token` in addition to the
+    /// init token.
+    pub fn get_allowed_tokens(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::AllowedTokens)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Central escrow state-machine check: `Ok` when the escrow's status is
+    /// one of `accepted`, otherwise the error describing why it is not.
+    ///
+    /// | status              | error             |
+    /// |---------------------|-------------------|
+    /// | `Released`          | `AlreadyReleased` |
+    /// | `Refunded`          | `AlreadyRefunded` |
+    /// | `Disputed`          | `BountyDisputed`  |
+    /// | `Claimable`         | `ClaimPending`    |
+    /// | anything else       | `FundsNotLocked`  |
+    ///
+    /// `FundsNotLocked` is the "not locked" error, and refunds before the
+    /// deadline fail with `DeadlineNotPassed`, the "not yet expired" error.
+    /// Both keep their original codes so existing clients need no changes.
+    fn ensure_status(escrow: &Escrow, accepted: &[EscrowStatus]) -> Result<(), Error> {
+        if accepted.contains(&escrow.status) {
+            return Ok(());
+        }
+        Err(match escrow.status {
+            EscrowStatus::Released => Error::AlreadyReleased,
+            EscrowStatus::Refunded => Error::AlreadyRefunded,
+            EscrowStatus::Disputed => Error::BountyDisputed,
+            EscrowStatus::Claimable => Error::ClaimPending,
+            _ => Error::FundsNotLocked,
+        })
+    }
+
+    /// Milestone escrows are paid out only through `release_milestone`, so
+    /// their balance must keep matching the unreleased milestones.
+    fn ensure_not_milestone(env: &Env, bounty_id: u64) -> Result<(), Error> {
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Milestones(bounty_id))
+        {
+            return Err(Error::MilestoneLocked);
+        }
+        Ok(())
+    }
+
+    /// Token a new lock is denominated in: `token`, or the init token when
+    /// `None`. Fails with TokenNotAllowed unless it is the init token or on
+    /// the token allowlist.
+    fn lock_token(env: &Env, token: Option<Address>) -> Result<Address, Error> {
+        let default_token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Token)
+            .ok_or(Error::NotInitialized)?;
+        let token = token.unwrap_or(default_token.clone());
+        if token != default_token && !Self::get_allowed_tokens(env.clone()).contains(&token) {
+            return Err(Error::TokenNotAllowed);
+        }
+        Ok(token)
+    }
+
+    /// Record the token of a new escrow. Init-token escrows store nothing,
+    /// matching escrows locked before per-bounty tokens existed.
+    fn set_escrow_token(env: &Env, bounty_id: u64, token: &Address) {
+        let default_token: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        if *token != default_token {
+            env.storage()
+                .persistent()
+                .set(&DataKey::EscrowToken(bounty_id), token);
+        }
+    }
+
+    /// Token an escrow is denominated in. Escrows locked before per-bounty
+    /// tokens existed have no entry and fall back to the init token, which
+    /// never changes after `init`, so they need no migration.
+    fn escrow_token(env: &Env, bounty_id: u64) -> Address {
+        env.storage()
+            .persistent()
+            .get(&DataKey::EscrowToken(bounty_id))
+            .unwrap_or_else(|| env.storage().instance().get(&DataKey::Token).unwrap())
+    }
+
+    /// Build the `("escrow", <action>, bounty_id)` payload from the escrow's
+    /// post-operation state.
+    fn lifecycle_event(
+        env: &Env,
+        bounty_id: u64,
+        escrow: &Escrow,
+        amount: i128,
+    ) -> EscrowLifecycleEvent {
+        EscrowLifecycleEvent {
+            seq: events::next_event_seq(env),
+            version: ESCROW_EVENT_SCHEMA_VERSION,
+            bounty_id,
+            depositor: escrow.depositor.clone(),
+            amount,
+            deadline: escrow.deadline,
+            status: escrow.status.clone(),
+        }
+    }
+
+    /// Ledgers needed to keep an escrow alive until `deadline` plus a grace
+    /// period, capped at the network's maximum TTL.
+    fn ttl_for_deadline(env: &Env, deadline: u64) -> u32 {
+        let secs = deadline.saturating_sub(env.ledger().timestamp());
+        let ledgers = (secs / LEDGER_CLOSE_SECONDS).saturating_add(ESCROW_TTL_GRACE_LEDGERS);
+        ledgers.min(env.storage().max_ttl() as u64) as u32
+    }
+
+    /// Lock-time variant of `extend_escrow_ttl`: a fresh escrow only has the
+    /// entries the lock itself wrote, so skip probing the optional per-escrow
+    /// keys. Call it once the lock's history entry is in place.
+    fn extend_new_escrow_ttl(env: &Env, bounty_id: u64, ledgers: u32) {
+        env.storage().instance().extend_ttl(ledgers, ledgers);
+        let storage = env.storage().persistent();
+        storage.extend_ttl(&DataKey::Escrow(bounty_id), ledgers, ledgers);
+        storage.extend_ttl(&DataKey::EscrowHistory(bounty_id), ledgers, ledgers);
+        storage.extend_ttl(&RecordKey::CreatedAt(bounty_id), ledgers, ledgers);
+        if storage.has(&DataKey::EscrowToken(bounty_id)) {
+            storage.extend_ttl(&DataKey::EscrowToken(bounty_id), ledgers, ledgers);
+        }
+    }
+
+    /// Extend the escrow entry, any per-escrow settings stored alongside it,
+    /// and the contract instance, which the escrow is unusable without.
+    fn extend_escrow_ttl(env: &Env, bounty_id: u64, ledgers: u32) {
+        env.storage().instance().extend_ttl(ledgers, ledgers);
+        let storage = env.storage().persistent();
+        storage.extend_ttl(&DataKey::Escrow(bounty_id), ledgers, ledgers);
+        for key in [
+            DataKey::Metadata(bounty_id),
+            DataKey::EscrowToken(bounty_id),
+            DataKey::RefundPolicy(bounty_id),
+            DataKey::RefundAddress(bounty_id),
+            DataKey::ReleaseNotBefore(bounty_id),
+            DataKey::AllowedContributors(bounty_id),
+            DataKey::Milestones(bounty_id),
+            DataKey::EscrowLabel(bounty_id),
+            DataKey::EscrowHistory(bounty_id),
+            DataKey::EscrowNote(bounty_id),
+            DataKey::AssignedContributor(bounty_id),
+            DataKey::ReleaseCondition(bounty_id),
+        ] {
+            if storage.has(&key) {
+                storage.extend_ttl(&key, ledgers, ledgers);
+            }
+        }
+        for key in [
+            RecordKey::Reviewer(bounty_id),
+            RecordKey::ReleaseApprovers(bounty_id),
+            RecordKey::DescriptionHash(bounty_id),
+            RecordKey::CreatedAt(bounty_id),
+        ] {
+            if storage.has(&key) {
+                storage.extend_ttl(&key, ledgers, ledgers);
+            }
+        }
+    }
+
+    /// Extend the storage TTL of an escrow by `ledgers` (capped at the
+    /// network maximum) so long-dated escrows are not archived.
+    ///
+    /// Anyone may call this; it only pays rent. Reads do not bump TTLs, so
+    /// views stay read-only and callers decide when to pay for extension.
+    pub fn bump_escrow_ttl(env: Env, bounty_id: u64, ledgers: u32) -> Result<(), Error> {
+        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyNotFound);
+        }
+        let ledgers = ledgers.min(env.storage().max_ttl());
+        Self::extend_escrow_ttl(&env, bounty_id, ledgers);
+        Ok(())
+    }
+
+    /// Signal indexers that an escrow entered its depositor's index or moved
+    /// to a new status there.
+    fn emit_depositor_index(env: &Env, bounty_id: u64, escrow: &Escrow) {
+        if !events::verbose(env) {
+            return;
+        }
+        events::emit_depositor_index_updated(
+            env,
+            events::DepositorIndexUpdated {
+                seq: events::next_event_seq(env),
+                version: EVENT_VERSION_V2,
+                depositor: escrow.depositor.clone(),
+                bounty_id,
+                status: escrow.status.clone(),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    /// Append a step to the escrow's history, dropping the oldest entry once
+    /// `MAX_ESCROW_HISTORY_LEN` is reached.
+    fn record_history(env: &Env, bounty_id: u64, kind: EscrowHistoryKind, amount_delta: i128) {
+        let key = DataKey::EscrowHistory(bounty_id);
+        let mut history: Vec<EscrowHistoryEntry> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        if history.len() >= MAX_ESCROW_HISTORY_LEN {
+            history.pop_front();
+        }
+        history.push_back(EscrowHistoryEntry {
+            kind,
+            timestamp: env.ledger().timestamp(),
+            ledger: env.ledger().sequence(),
+            amount_delta,
+        });
+        env.storage().persistent().set(&key, &history);
+    }
+
+    /// View: the escrow's lifecycle (lock, top-ups, releases, refunds,
+    /// disputes), oldest first. Only the latest `MAX_ESCROW_HISTORY_LEN`
+    /// entries are kept.
+    pub fn get_escrow_history(env: Env, bounty_id: u64) -> Result<Vec<EscrowHistoryEntry>, Error> {
+        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyNotFound);
+        }
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowHistory(bounty_id))
+            .unwrap_or(Vec::new(&env)))
+    }
+
+    /// View: the token a bounty is denominated in.
+    pub fn get_escrow_token(env: Env, bounty_id: u64) -> Result<Address, Error> {
+        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyNotFound);
+        }
+        Ok(Self::escrow_token(&env, bounty_id))
+    }
+
+    /// Name a funding shortfall instead of surfacing the token's own panic:
+    /// `InsufficientFunds` when `payer` holds less than `amount`, and
+    /// `InsufficientAllowance` when allowance locks are on and `payer` has
+    /// approved this contract for less. The transfer still has the final say.
+    fn check_lock_funding(
+        env: &Env,
+        client: &token::Client,
+        payer: &Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        if client.balance(payer) < amount {
+            return Err(Error::InsufficientFunds);
+        }
+        if Self::get_allowance_locks(env.clone())
+            && client.allowance(payer, &env.current_contract_address()) < amount
+        {
+            return Err(Error::InsufficientAllowance);
+        }
+        Ok(())
+    }
+
+    /// Move `amount` from `payer` into the contract, spending the payer's
+    /// allowance when allowance locks are on.
+    fn pull_lock_funds(env: &Env, client: &token::Client, payer: &Address, amount: i128) {
+        let contract_address = env.current_contract_address();
+        if Self::get_allowance_locks(env.clone()) {
+            client.transfer_from(&contract_address, payer, &contract_address, &amount);
+        } else {
+            client.transfer(payer, &contract_address, &amount);
+        }
+    }
+
+    /// Make locks pull funds with `transfer_from` against the payer's
+    /// allowance to this contract instead of a direct transfer (admin only).
+    /// Depositors then cap what the escrow may take by `approve`-ing it in
+    /// advance, and a lock above that cap fails with `InsufficientAllowance`.
+    /// Off by default.
+    pub fn set_allowance_locks(env: Env, enabled: bool) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&ConfigKey::AllowanceLocks, &enabled);
+        Ok(())
+    }
+
+    /// View: whether locks pull funds through the payer's allowance.
+    pub fn get_allowance_locks(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&ConfigKey::AllowanceLocks)
+            .unwrap_or(false)
+    }
+
+    /// Create an escrow owned by `depositor`, funded by `payer` (the
+    /// depositor itself except for relayed locks), who must authorize.
+    #[allow(clippy::too_many_arguments)]
+    fn lock_funds_internal(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        amount: i128,
+        deadline: u64,
+        token_addr: Address,
+        payer: Address,
+    ) -> Result<(), Error> {
+        reentrancy_guard::enter(&env)?;
+        // Apply rate limiting
+        anti_abuse::check_rate_limit(&env, depositor.clone());
+        if !anti_abuse::record_locks(&env, depositor.clone(), 1) {
+            return Err(Error::RateLimited);
+        }
+
+        Self::ensure_not_paused(&env)?;
+        if Self::check_paused(&env, symbol_short!("lock")) {
+            return Err(Error::FundsPaused);
+        }
+
+        // Verify the funding account's authorization
+        payer.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+
+        Self::validate_new_escrow(&env, bounty_id, amount, deadline)?;
+        Self::ensure_escrow_capacity(&env, 1)?;
+        Self::lock_token(&env, Some(token_addr.clone()))?;
+
+        let client = token::Client::new(&env, &token_addr);
+        Self::check_lock_funding(&env, &client, &payer, amount)?;
+        Self::pull_lock_funds(&env, &client, &payer, amount);
+
+        let escrow = Escrow {
+            depositor: depositor.clone(),
+            amount,
+            status: EscrowStatus::Locked,
+            deadline,
+            refund_history: vec![&env],
+            remaining_amount: amount,
+        };
+        invariants::assert_escrow(&env, &escrow);
+
+        // The token goes in first so store_escrow credits the right asset
+        Self::set_escrow_token(&env, bounty_id, &token_addr);
+
+        Self::store_escrow(&env, bounty_id, &escrow);
+
+        Self::index_new_escrow(&env, bounty_id, &depositor);
+        Self::record_depositor_lock(&env, &depositor, amount, true);
+        Self::record_bucket_volume(&env, amount, 0, 0);
+
+        // Emit value allows for off-chain indexing
+        emit_funds_locked(
+            &env,
+            FundsLocked {
+                seq: events::next_event_seq(&env),
+                version: EVENT_VERSION_V2,
+                bounty_id,
+                amount,
+                depositor: depositor.clone(),
+                deadline,
+            },
+        );
+        Self::record_history(&env, bounty_id, EscrowHistoryKind::Locked, amount);
+        // Extend the TTL of the storage entries to ensure they live long enough
+        Self::extend_new_escrow_ttl(&env, bounty_id, Self::ttl_for_deadline(&env, deadline));
+        emit_escrow_locked(
+            &env,
+            Self::lifecycle_event(&env, bounty_id, &escrow, amount),
+        );
+        Self::emit_depositor_index(&env, bounty_id, &escrow);
+
+        reentrancy_guard::exit(&env);
+        Ok(())
+    }
+
+    /// Add more tokens to an existing Locked escrow (original depositor only).
+    ///
+    /// Both `amount` and `remaining_amount` grow by `additional_amount`, so
+    /// amount-range queries and aggregate stats immediately reflect the new total.
+    ///
+    /// A `new_deadline` later than the current one extends the deadline in
+    /// the same call, so deadline queries see both changes together; an
+    /// earlier or equal one is ignored.
+    ///
+    /// The top-up is funded like a lock: it fails with `InsufficientFunds` or,
+    /// under allowance locks, `InsufficientAllowance` before moving tokens.
+    pub fn top_up(
+        env: Env,
+        bounty_id: u64,
+        additional_amount: i128,
+        new_deadline: Option<u64>,
+    ) -> Result<(), Error> {
+        reentrancy_guard::enter(&env)?;
+        Self::ensure_not_paused(&env)?;
+        if Self::check_paused(&env, symbol_short!("lock")) {
+            return Err(Error::FundsPaused);
+        }
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+
+        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyNotFound);
+        }
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .unwrap();
+
+        escrow.depositor.require_auth();
+
+        Self::ensure_status(&escrow, &[EscrowStatus::Locked])?;
+        Self::ensure_not_milestone(&env, bounty_id)?;
+
+        if additional_amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        escrow.amount = escrow
+            .amount
+            .checked_add(additional_amount)
+            .ok_or(Error::InvalidAmount)?;
+        escrow.remaining_amount = escrow
+            .remaining_amount
+            .checked_add(additional_amount)
+            .ok_or(Error::InvalidAmount)?;
+
+        let token_addr = Self::escrow_token(&env, bounty_id);
+        let client = token::Client::new(&env, &token_addr);
+        Self::check_lock_funding(&env, &client, &escrow.depositor, additional_amount)?;
+        Self::pull_lock_funds(&env, &client, &escrow.depositor, additional_amount);
+        let extended = new_deadline.filter(|deadline| *deadline > escrow.deadline);
+        if let Some(deadline) = extended {
+            escrow.deadline = deadline;
+        }
+        invariants::assert_escrow(&env, &escrow);
+        Self::store_escrow(&env, bounty_id, &escrow);
+        if let Some(deadline) = extended {
+            Self::extend_escrow_ttl(&env, bounty_id, Self::ttl_for_deadline(&env, deadline));
+        }
+
+        Self::record_depositor_lock(&env, &escrow.depositor, additional_amount, false);
+        Self::record_bucket_volume(&env, additional_amount, 0, 0);
+        Self::record_history(
+            &env,
+            bounty_id,
+            EscrowHistoryKind::ToppedUp,
+            additional_amount,
+        );
+
+        events::emit_funds_topped_up(
+            &env,
+            events::FundsToppedUp {
+                seq: events::next_event_seq(&env),
+                version: EVENT_VERSION_V2,
+                bounty_id,
+                added_amount: additional_amount,
+                new_amount: escrow.amount,
+                depositor: escrow.depositor.clone(),
+                deadline: escrow.deadline,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        reentrancy_guard::exit(&env);
+        Ok(())
+    }
+
+    /// Fold several Locked escrows of one depositor into `target_id`
+    /// (depositor only), e.g. small bounties for the same piece of work.
+    ///
+    /// The remaining balance of every source moves onto the target's
+    /// `amount` and `remaining_amount`, and the target takes the latest
+    /// deadline among them. Sources end `Merged` with nothing left to settle;
+    /// their `amount` keeps only what they paid out before the merge, so
+    /// aggregate stats count each token once. The target keeps its own
+    /// settings (refund policy, assigned contributor, ...).
+    ///
+    /// All escrows must be Locked, share the depositor and token, and carry
+    /// no pending auto-release (`ClaimPending`) or milestones
+    /// (`MilestoneLocked`). Sources must also match the target's terms,
+    /// so merging never loosens the rules on the funds moved:
+    /// * a different refund policy or refund address fails with
+    ///   `InvalidRefundPolicy`;
+    /// * a different contributor allowlist, or an assigned contributor other
+    ///   than the target's, fails with `ContributorMismatch`;
+    /// * a different reviewer, release time or release condition fails with
+    ///   `ConditionNotMet`.
+    pub fn merge_escrows(env: Env, source_ids: Vec<u64>, target_id: u64) -> Result<(), Error> {
+        reentrancy_guard::enter(&env)?;
+        Self::ensure_not_paused(&env)?;
+        if source_ids.is_empty() || source_ids.len() > MAX_BATCH_SIZE {
+            return Err(Error::InvalidBatchSize);
+        }
+
+        let mut target = Self::get_escrow_info(env.clone(), target_id)?;
+        target.depositor.require_auth();
+        Self::ensure_status(&target, &[EscrowStatus::Locked])?;
+        Self::ensure_mergeable(&env, target_id)?;
+        let token_addr = Self::escrow_token(&env, target_id);
+        let contributor = Self::get_assigned_contributor(env.clone(), target_id);
+
+        let mut sources = Vec::new(&env);
+        let mut merged_amount: i128 = 0;
+        let mut deadline = target.deadline;
+        for (i, source_id) in source_ids.iter().enumerate() {
+            if source_id == target_id || source_ids.first_index_of(source_id) != Some(i as u32) {
+                return Err(Error::DuplicateBountyId);
+            }
+            let source = Self::get_escrow_info(env.clone(), source_id)?;
+            if source.depositor != target.depositor {
+                return Err(Error::Unauthorized);
+            }
+            Self::ensure_status(&source, &[EscrowStatus::Locked])?;
+            if Self::escrow_token(&env, source_id) != token_addr {
+                return Err(Error::TokenNotAllowed);
+            }
+            if let Some(assigned) = Self::get_assigned_contributor(env.clone(), source_id) {
+                if contributor.as_ref() != Some(&assigned) {
+                    return Err(Error::ContributorMismatch);
+                }
+            }
+            Self::ensure_same_terms(&env, source_id, target_id)?;
+            Self::ensure_mergeable(&env, source_id)?;
+            merged_amount = merged_amount
+                .checked_add(source.remaining_amount)
+                .ok_or(Error::InvalidAmount)?;
+            deadline = deadline.max(source.deadline);
+            sources.push_back((source_id, source));
+        }
+
+        for (source_id, mut source) in sources.iter() {
+            let moved = source.remaining_amount;
+            source.amount -= moved;
+            source.remaining_amount = 0;
+            source.status = EscrowStatus::Merged;
+            invariants::assert_escrow(&env, &source);
+            Self::store_escrow(&env, source_id, &source);
+            Self::record_history(&env, source_id, EscrowHistoryKind::Merged, -moved);
+            Self::emit_depositor_index(&env, source_id, &source);
+        }
+
+        target.amount = target
+            .amount
+            .checked_add(merged_amount)
+            .ok_or(Error::InvalidAmount)?;
+        target.remaining_amount += merged_amount;
+        target.deadline = deadline;
+        invariants::assert_escrow(&env, &target);
+        Self::store_escrow(&env, target_id, &target);
+        Self::extend_escrow_ttl(&env, target_id, Self::ttl_for_deadline(&env, deadline));
+        Self::record_history(&env, target_id, EscrowHistoryKind::Merged, merged_amount);
+
+        events::emit_escrows_merged(
+            &env,
+            events::EscrowsMerged {
+                version: EVENT_VERSION_V2,
+                seq: events::next_event_seq(&env),
+                target_id,
+                source_ids,
+                depositor: target.depositor.clone(),
+                merged_amount,
+                new_amount: target.amount,
+                deadline,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Self::emit_depositor_index(&env, target_id, &target);
+
+        reentrancy_guard::exit(&env);
+        Ok(())
+    }
+
+    /// Reject merging an escrow with a pending auto-release, which would pay
+    /// out the merged funds, or with milestones, whose balance must keep
+    /// matching them.
+    fn ensure_mergeable(env: &Env, bounty_id: u64) -> Result<(), Error> {
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::AutoRelease(bounty_id))
+        {
+            return Err(Error::ClaimPending);
+        }
+        Self::ensure_not_milestone(env, bounty_id)
+    }
+
+    /// Reject a merge source whose refund or release terms differ from the
+    /// target's; see [`Self::merge_escrows`] for the errors.
+    fn ensure_same_terms(env: &Env, source_id: u64, target_id: u64) -> Result<(), Error> {
+        let storage = env.storage().persistent();
+        if Self::get_refund_policy(env.clone(), source_id)
+            != Self::get_refund_policy(env.clone(), target_id)
+            || storage.get::<DataKey, Address>(&DataKey::RefundAddress(source_id))
+                != storage.get::<DataKey, Address>(&DataKey::RefundAddress(target_id))
+        {
+            return Err(Error::InvalidRefundPolicy);
+        }
+        if Self::get_allowed_contributors(env.clone(), source_id)
+            != Self::get_allowed_contributors(env.clone(), target_id)
+        {
+            return Err(Error::ContributorMismatch);
+        }
+        let same_release_rules = Self::get_reviewer(env.clone(), source_id)
+            == Self::get_reviewer(env.clone(), target_id)
+            && storage.get::<DataKey, u64>(&DataKey::ReleaseNotBefore(source_id))
+                == storage.get::<DataKey, u64>(&DataKey::ReleaseNotBefore(target_id))
+            && storage.get::<DataKey, Address>(&DataKey::ReleaseCondition(source_id))
+                == storage.get::<DataKey, Address>(&DataKey::ReleaseCondition(target_id));
+        if !same_release_rules {
+            return Err(Error::ConditionNotMet);
+        }
+        Ok(())
+    }
+
+    /// Lock funds with an explicit refund policy (see `RefundPolicy`).
+    /// Plain `lock_funds` is equivalent to `RefundPolicy::Full`.
+    pub fn lock_funds_with_refund_policy(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        amount: i128,
+        deadline: u64,
+        refund_policy: RefundPolicy,
+    ) -> Result<(), Error> {
+        if let RefundPolicy::Partial(bps) = refund_policy {
+            if bps == 0 || bps as i128 > BASIS_POINTS {
+                return Err(Error::InvalidRefundPolicy);
+            }
+        }
+        Self::lock_funds(env.clone(), depositor, bounty_id, amount, deadline)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::RefundPolicy(bounty_id), &refund_policy);
+        Ok(())
+    }
+
+    /// Lock funds that cannot be released before `release_not_before`.
+    ///
+    /// The release time is independent of `deadline`, which only governs
+    /// when the depositor may refund.
+    pub fn lock_funds_with_release_time(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        amount: i128,
+        deadline: u64,
+        release_not_before: u64,
+    ) -> Result<(), Error> {
+        Self::lock_funds(env.clone(), depositor, bounty_id, amount, deadline)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::ReleaseNotBefore(bounty_id), &release_not_before);
+        Ok(())
+    }
+
+    /// Lock funds whose refunds are paid to `refund_address` instead of the
+    /// depositor, e.g. a cold wallet when funding from a hot wallet.
+    ///
+    /// Requires the depositor's auth, like `lock_funds`. Admin-approved
+    /// refunds still go to the recipient named in the approval.
+    pub fn lock_funds_with_refund_address(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        amount: i128,
+        deadline: u64,
+        refund_address: Address,
+    ) -> Result<(), Error> {
+        Self::lock_funds(env.clone(), depositor, bounty_id, amount, deadline)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::RefundAddress(bounty_id), &refund_address);
+        Ok(())
+    }
+
+    /// Lock funds with a category `tag` and display `title`, indexed for
+    /// `query_escrows_by_tag`. Titles longer than `MAX_LABEL_TITLE_LEN`
+    /// bytes are rejected with `LabelTooLong`.
+    pub fn lock_funds_with_label(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        amount: i128,
+        deadline: u64,
+        tag: Symbol,
+        title: soroban_sdk::String,
+    ) -> Result<(), Error> {
+        if title.len() > MAX_LABEL_TITLE_LEN {
+            return Err(Error::LabelTooLong);
+        }
+        Self::lock_funds(env.clone(), depositor, bounty_id, amount, deadline)?;
+
+        let index_key = DataKey::TagIndex(tag.clone());
+        let mut index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&index_key)
+            .unwrap_or(Vec::new(&env));
+        index.push_back(bounty_id);
+        env.storage().persistent().set(&index_key, &index);
+        env.storage().persistent().set(
+            &DataKey::EscrowLabel(bounty_id),
+            &EscrowLabel { tag, title },
+        );
+        Ok(())
+    }
+
+    /// Where depositor refunds for this escrow are paid: the refund address
+    /// chosen at lock time, or the depositor when none was set.
+    fn refund_destination(env: &Env, bounty_id: u64, escrow: &Escrow) -> Address {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RefundAddress(bounty_id))
+            .unwrap_or_else(|| escrow.depositor.clone())
+    }
+
+    /// View: the refund policy chosen at lock time.
+    pub fn get_refund_policy(env: Env, bounty_id: u64) -> RefundPolicy {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RefundPolicy(bounty_id))
+            .unwrap_or(RefundPolicy::Full)
+    }
+
+    /// Portion of `remaining` the depositor may reclaim after the deadline
+    fn refundable_amount(policy: &RefundPolicy, remaining: i128) -> i128 {
+        match policy {
+            RefundPolicy::Full => remaining,
+            RefundPolicy::Partial(bps) => remaining * (*bps as i128) / BASIS_POINTS,
+            RefundPolicy::None => 0,
+        }
+    }
+
+    /// Lock funds that may only be released to a contributor on `allowed`
+    /// (invite-only bounties). An empty list leaves the escrow unrestricted.
+    ///
+    /// The allowlist is fixed for the escrow's lifetime, so contributors
+    /// working from it cannot be cut out by the depositor later.
+    pub fn lock_funds_with_allowlist(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        amount: i128,
+        deadline: u64,
+        allowed: Vec<Address>,
+    ) -> Result<(), Error> {
+        if allowed.len() > MAX_BATCH_SIZE {
+            return Err(Error::InvalidBatchSize);
+        }
+        Self::lock_funds(env.clone(), depositor.clone(), bounty_id, amount, deadline)?;
+        if allowed.is_empty() {
+            return Ok(());
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::AllowedContributors(bounty_id), &allowed);
+
+        events::emit_allowlist_updated(
+            &env,
+            events::AllowlistUpdated {
+                seq: events::next_event_seq(&env),
+                bounty_id,
+                allowed,
+                updated_by: depositor,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// View: the contributor allowlist for a bounty (empty means unrestricted).
+    pub fn get_allowed_contributors(env: Env, bounty_id: u64) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AllowedContributors(bounty_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Fail with ContributorMismatch if the escrow has an assigned contributor
+    /// other than `contributor`, or with UnauthorizedContributor if it has an
+    /// allowlist that does not include `contributor`.
+    fn ensure_contributor_allowed(
+        env: &Env,
+        bounty_id: u64,
+        contributor: &Address,
+    ) -> Result<(), Error> {
+        let assigned: Option<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AssignedContributor(bounty_id));
+        if assigned.is_some_and(|a| a != *contributor) {
+            return Err(Error::ContributorMismatch);
+        }
+        Self::ensure_on_allowlist(env, bounty_id, contributor)
+    }
+
+    /// Fail with UnauthorizedContributor if the escrow has an allowlist that
+    /// does not include `contributor`.
+    fn ensure_on_allowlist(env: &Env, bounty_id: u64, contributor: &Address) -> Result<(), Error> {
+        let allowed: Option<Vec<Address>> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AllowedContributors(bounty_id));
+        match allowed {
+            Some(list) if !list.contains(contributor) => Err(Error::UnauthorizedContributor),
+            _ => Ok(()),
+        }
+    }
+
+    /// Fail with ConditionNotMet unless the escrow's release condition
+    /// contract, if any, reports the condition as met. A condition contract
+    /// that errors counts as not met.
+    fn ensure_release_condition_met(env: &Env, bounty_id: u64) -> Result<(), Error> {
+        let condition: Option<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ReleaseCondition(bounty_id));
+        let Some(condition) = condition else {
+            return Ok(());
+        };
+        let client = traits::ReleaseConditionClient::new(env, &condition);
+        match client.try_check_condition(&bounty_id) {
+            Ok(Ok(true)) => Ok(()),
+            _ => Err(Error::ConditionNotMet),
+        }
+    }
+
+    /// Fail with InsufficientApprovals unless, for an escrow with a reviewer,
+    /// both the depositor and the reviewer have called `approve_release`.
+    fn ensure_release_approved(env: &Env, bounty_id: u64) -> Result<(), Error> {
+        let Some(reviewer) = Self::get_reviewer(env.clone(), bounty_id) else {
+            return Ok(());
+        };
+        let depositor = Self::get_escrow_info(env.clone(), bounty_id)?.depositor;
+        let approvers = Self::get_release_approvals(env.clone(), bounty_id);
+        if approvers.contains(&depositor) && approvers.contains(&reviewer) {
+            Ok(())
+        } else {
+            Err(Error::InsufficientApprovals)
+        }
+    }
+
+    /// Fail with ReleaseTooEarly while the escrow's release time lock is active.
+    fn ensure_release_time_reached(env: &Env, bounty_id: u64) -> Result<(), Error> {
+        let not_before: Option<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ReleaseNotBefore(bounty_id));
+        match not_before {
+            Some(ts) if env.ledger().timestamp() < ts => Err(Error::ReleaseTooEarly),
+            _ => Ok(()),
+        }
+    }
+
+    /// Release the escrow to its assigned contributor (admin only), for
+    /// directly commissioned work where the recipient is known up front.
+    /// Fails with `ContributorNotAssigned` when nobody is assigned.
+    pub fn release_to_assigned(env: Env, bounty_id: u64) -> Result<(), Error> {
+        let contributor = Self::get_assigned_contributor(env.clone(), bounty_id)
+            .ok_or(Error::ContributorNotAssigned)?;
+        Self::release_funds(env, bounty_id, contributor)
+    }
+
+    /// Release funds to the contributor.
+    /// Only the admin (backend) can authorize this.
+    /// If a contributor is assigned, it must be the recipient
+    /// (`ContributorMismatch` otherwise).
+    ///
+    /// The contributor may be the depositor itself (self-release). That is
+    /// still a release, not a refund: it ignores the deadline and counts
+    /// towards `count_released` and the depositor's `total_released`.
+    ///
+    /// A passed deadline does not block release: until someone refunds it,
+    /// the escrow can still be paid out, and a `LateRelease` event is
+    /// published next to `FundsReleased`.
+    pub fn release_funds(env: Env, bounty_id: u64, contributor: Address) -> Result<(), Error> {
+        reentrancy_guard::enter(&env)?;
+        Self::ensure_not_paused(&env)?;
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(Error::FundsPaused);
+        }
+        let _start = env.ledger().timestamp();
+
+        // Ensure contract is initialized
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        Self::release_funds_internal(&env, bounty_id, contributor)?;
+        reentrancy_guard::exit(&env);
+        Ok(())
+    }
+
+    /// [`Self::release_funds`] that fails with `SlippageExceeded` when the
+    /// contributor's net payout, after the fee in force at execution, would be
+    /// below `min_net`. Protects the recipient from a fee change landing
+    /// between submission and execution.
+    pub fn release_funds_with_min_net(
+        env: Env,
+        bounty_id: u64,
+        contributor: Address,
+        min_net: i128,
+    ) -> Result<(), Error> {
+        let (_, gross, fee) = Self::prepare_release(&env, bounty_id, &contributor)?;
+        if gross - fee < min_net {
+            return Err(Error::SlippageExceeded);
+        }
+        Self::release_funds(env, bounty_id, contributor)
+    }
+
+    /// Release an escrow as a release operator the depositor approved with
+    /// [`Self::set_release_operator`]. Same checks and payout as
+    /// [`Self::release_funds`]; fails with `Unauthorized` when `operator` is
+    /// not approved by the escrow's depositor or `contributor` is the
+    /// depositor, since operators release work, not refunds.
+    pub fn release_funds_by_operator(
+        env: Env,
+        bounty_id: u64,
+        contributor: Address,
+        operator: Address,
+    ) -> Result<(), Error> {
+        reentrancy_guard::enter(&env)?;
+        Self::ensure_not_paused(&env)?;
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(Error::FundsPaused);
+        }
+        operator.require_auth();
+
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+        if contributor == escrow.depositor
+            || !Self::is_release_operator(env.clone(), escrow.depositor, operator)
+        {
+            return Err(Error::Unauthorized);
+        }
+
+        Self::release_funds_internal(&env, bounty_id, contributor)?;
+        reentrancy_guard::exit(&env);
+        Ok(())
+    }
+
+    /// Release an escrow on the strength of a depositor-signed
+    /// [`SignedReleaseApproval`], so a relayer can submit the transaction. Fails
+    /// with `Unauthorized` when the approval names a different bounty or
+    /// contributor, pays the depositor, has expired, or carries a stale
+    /// (already used) nonce. Otherwise the same checks and payout as
+    /// [`Self::release_funds`].
+    pub fn release_with_approval(
+        env: Env,
+        bounty_id: u64,
+        contributor: Address,
+        approval: SignedReleaseApproval,
+    ) -> Result<(), Error> {
+        reentrancy_guard::enter(&env)?;
+        Self::ensure_not_paused(&env)?;
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(Error::FundsPaused);
+        }
+        if approval.bounty_id != bounty_id
+            || approval.contributor != contributor
+            || env.ledger().timestamp() > approval.expires_at
+        {
+            return Err(Error::Unauthorized);
+        }
+
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+        if contributor == escrow.depositor {
+            return Err(Error::Unauthorized);
+        }
+        let nonce_key = RecordKey::ApprovalNonce(escrow.depositor.clone());
+        let nonce: u64 = env.storage().persistent().get(&nonce_key).unwrap_or(0);
+        if approval.nonce != nonce {
+            return Err(Error::Unauthorized);
+        }
+        escrow
+            .depositor
+            .require_auth_for_args((approval,).into_val(&env));
+        env.storage().persistent().set(&nonce_key, &(nonce + 1));
+
+        Self::release_funds_internal(&env, bounty_id, contributor)?;
+        reentrancy_guard::exit(&env);
+        Ok(())
+    }
+
+    /// View: the nonce `depositor`'s next [`SignedReleaseApproval`] must carry.
+    pub fn get_approval_nonce(env: Env, depositor: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&RecordKey::ApprovalNonce(depositor))
+            .unwrap_or(0)
+    }
+
+    /// Approve or revoke `operator` to release any of `depositor`'s escrows
+    /// (depositor only), like an allowance limited to releases. A depositor
+    /// cannot approve itself (`Unauthorized`).
+    pub fn set_release_operator(
+        env: Env,
+        depositor: Address,
+        operator: Address,
+        approved: bool,
+    ) -> Result<(), Error> {
+        depositor.require_auth();
+        if approved && operator == depositor {
+            return Err(Error::Unauthorized);
+        }
+        let key = DataKey::ReleaseOperator(depositor, operator);
+        if approved {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        Ok(())
+    }
+
+    /// View: whether `operator` may release `depositor`'s escrows.
+    pub fn is_release_operator(env: Env, depositor: Address, operator: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ReleaseOperator(depositor, operator))
+            .unwrap_or(false)
+    }
+
+    /// Checks shared by `release_funds` and `simulate_release`. Returns the
+    /// escrow, the gross amount a release takes from it and the release fee
+    /// withheld from that amount.
+    fn prepare_release(
+        env: &Env,
+        bounty_id: u64,
+        contributor: &Address,
+    ) -> Result<(Escrow, i128, i128), Error> {
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+
+        Self::ensure_status(&escrow, &[EscrowStatus::Locked])?;
+        Self::ensure_contributor_allowed(env, bounty_id, contributor)?;
+        Self::ensure_release_time_reached(env, bounty_id)?;
+        Self::ensure_release_condition_met(env, bounty_id)?;
+        Self::ensure_release_approved(env, bounty_id)?;
+
+        // Pay out the unreleased balance (accounts for prior partial releases),
+        // minus the release fee in force right now.
+        let gross = escrow.remaining_amount;
+        let fee = Self::release_fee(env, gross);
+        Ok((escrow, gross, fee))
+    }
+
+    /// View: preview `release_funds(bounty_id, contributor)` without moving
+    /// funds. Runs the same pause and escrow checks and fee calculation;
+    /// authorization is not simulated.
+    pub fn simulate_release(env: Env, bounty_id: u64, contributor: Address) -> SimulationResult {
+        let outcome = Self::ensure_not_paused(&env).and_then(|_| {
+            if Self::check_paused(&env, symbol_short!("release")) {
+                return Err(Error::FundsPaused);
+            }
+            Self::prepare_release(&env, bounty_id, &contributor)
+        });
+        match outcome {
+            Ok((_, gross, fee)) => SimulationResult {
+                would_succeed: true,
+                net_amount: gross - fee,
+                fee,
+                error_reason: None,
+            },
+            Err(err) => SimulationResult {
+                would_succeed: false,
+                net_amount: 0,
+                fee: 0,
+                error_reason: Some(err as u32),
+            },
+        }
+    }
+
+    /// Pay out a Locked escrow in full to `contributor`. Callers own the
+    /// reentrancy guard, pause checks and authorization.
+    fn release_funds_internal(
+        env: &Env,
+        bounty_id: u64,
+        contributor: Address,
+    ) -> Result<(), Error> {
+        let (mut escrow, gross, _) = Self::prepare_release(env, bounty_id, &contributor)?;
+
+        let token_addr = Self::escrow_token(env, bounty_id);
+        let client = token::Client::new(env, &token_addr);
+        let payout = gross - Self::collect_release_fee(env, &token_addr, gross);
+
+        client.transfer(&env.current_contract_address(), &contributor, &payout);
+        Self::record_depositor_outflow(env, &escrow.depositor, gross, 0);
+        Self::record_history(env, bounty_id, EscrowHistoryKind::Released, -gross);
+        Self::record_release_to(env, bounty_id, &contributor);
+
+        escrow.status = EscrowStatus::Released;
+        escrow.remaining_amount = 0;
+        invariants::assert_escrow(env, &escrow);
+        Self::store_escrow(env, bounty_id, &escrow);
+        Self::record_release(env, bounty_id, &contributor, payout);
+
+        emit_funds_released(
+            env,
+            FundsReleased {
+                seq: events::next_event_seq(env),
+                version: EVENT_VERSION_V2,
+                bounty_id,
+                amount: payout,
+                recipient: contributor.clone(),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        emit_escrow_released(env, Self::lifecycle_event(env, bounty_id, &escrow, payout));
+        let now = env.ledger().timestamp();
+        if escrow.deadline != NO_DEADLINE && now >= escrow.deadline {
+            events::emit_late_release(
+                env,
+                events::LateRelease {
+                    version: EVENT_VERSION_V2,
+                    seq: events::next_event_seq(env),
+                    bounty_id,
+                    deadline: escrow.deadline,
+                    timestamp: now,
+                },
+            );
+        }
+        Self::emit_depositor_index(env, bounty_id, &escrow);
+        Ok(())
+    }
+
+    /// Set how many seconds after an escrow's deadline refunds stay closed
+    /// (admin only), leaving time for an in-flight release to land.
+    pub fn set_refund_grace_period(env: Env, grace_period: u64) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::RefundGracePeriod, &grace_period);
+        Ok(())
+    }
+
+    /// View: the refund grace period in seconds (0 when unset).
+    pub fn get_refund_grace_period(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::RefundGracePeriod)
+            .unwrap_or(0)
+    }
+
+    /// Require new escrows to have a deadline at least `horizon` seconds after
+    /// the lock (admin only). 0, the default, only rejects past deadlines.
+    pub fn set_min_deadline_horizon(env: Env, horizon: u64) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::MinDeadlineHorizon, &horizon);
+        Ok(())
+    }
+
+    /// View: the minimum deadline horizon in seconds (0 when unset).
+    pub fn get_min_deadline_horizon(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MinDeadlineHorizon)
+            .unwrap_or(0)
+    }
+
+    /// Set how many seconds after the lock `lock_funds_simple` places the
+    /// deadline (admin only). 0, the default, disables `lock_funds_simple`.
+    pub fn set_default_duration(env: Env, duration: u64) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&ConfigKey::DefaultDuration, &duration);
+        Ok(())
+    }
+
+    /// View: the default lock duration in seconds (0 when unset).
+    pub fn get_default_duration(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&ConfigKey::DefaultDuration)
+            .unwrap_or(0)
+    }
+
+    /// Checks every new escrow passes before any tokens move, shared by the
+    /// single and batch lock paths: the id is unused, the deadline is
+    /// acceptable and the amount respects the amount policy, if any.
+    fn validate_new_escrow(
+        env: &Env,
+        bounty_id: u64,
+        amount: i128,
+        deadline: u64,
+    ) -> Result<(), Error> {
+        if env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::DuplicateBountyId);
+        }
+        Self::check_lock_deadline(env, deadline)?;
+
+        // Enforce min/max amount policy if one has been configured (Issue #62).
+        // When no policy is set this block is skipped entirely, preserving
+        // backward-compatible behaviour for callers that never call set_amount_policy.
+        if let Some((min_amount, max_amount)) = env
+            .storage()
+            .instance()
+            .get::<DataKey, (i128, i128)>(&DataKey::AmountPolicy)
+        {
+            if amount < min_amount {
+                return Err(Error::AmountBelowMinimum);
+            }
+            if amount > max_amount {
+                return Err(Error::AmountAboveMaximum);
+            }
+        }
+        Ok(())
+    }
+
+    /// Add a new escrow to the global and per-depositor indexes and stamp
+    /// its lock time.
+    fn index_new_escrow(env: &Env, bounty_id: u64, depositor: &Address) {
+        env.storage()
+            .persistent()
+            .set(&RecordKey::CreatedAt(bounty_id), &env.ledger().timestamp());
+
+        let mut index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(env));
+        index.push_back(bounty_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::EscrowIndex, &index);
+
+        let key = DataKey::DepositorIndex(depositor.clone());
+        let mut depositor_index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        depositor_index.push_back(bounty_id);
+        env.storage().persistent().set(&key, &depositor_index);
+    }
+
+    /// Reject a lock deadline that has passed (`InvalidDeadline`) or falls
+    /// inside the minimum deadline horizon (`DeadlineTooSoon`).
+    fn check_lock_deadline(env: &Env, deadline: u64) -> Result<(), Error> {
+        let now = env.ledger().timestamp();
+        if deadline < now {
+            return Err(Error::InvalidDeadline);
+        }
+        let horizon = Self::get_min_deadline_horizon(env.clone());
+        if deadline < now.saturating_add(horizon) {
+            return Err(Error::DeadlineTooSoon);
+        }
+        Ok(())
+    }
+
+    /// Earliest timestamp at which a deadline refund is allowed
+    /// (`u64::MAX` for escrows without a deadline).
+    fn refund_opens_at(env: &Env, escrow: &Escrow) -> u64 {
+        escrow
+            .deadline
+            .saturating_add(Self::get_refund_grace_period(env.clone()))
+    }
+
+    /// Whether the deadline (plus grace period) has passed, so the time
+    /// path of a refund is open. Never true for escrows without a deadline.
+    fn refund_time_reached(env: &Env, escrow: &Escrow) -> bool {
+        escrow.deadline != NO_DEADLINE
+            && env.ledger().timestamp() >= Self::refund_opens_at(env, escrow)
+    }
+
+    /// Set the claim window duration (admin only).
+    /// claim_window: seconds beneficiary has to claim after release is authorized.
+    pub fn set_claim_window(env: Env, claim_window: u64) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::ClaimWindow, &claim_window);
+        Ok(())
+    }
+
+    /// Authorize a release as a pending claim instead of immediate transfer.
+    /// Admin calls this instead of release_funds when claim period is active.
+    /// Beneficiary must call claim() within the window to receive funds;
+    /// afterwards the depositor may take them back with reclaim_unclaimed.
+    ///
+    /// Moves the escrow to `Claimable`. Authorizing again replaces the
+    /// pending claim.
+    pub fn authorize_claim(env: Env, bounty_id: u64, recipient: Address) -> Result<(), Error> {
+        Self::ensure_not_paused(&env)?;
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(Error::FundsPaused);
+        }
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyNotFound);
+        }
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .unwrap();
+
+        Self::ensure_status(&escrow, &[EscrowStatus::Locked, EscrowStatus::Claimable])?;
+        Self::ensure_contributor_allowed(&env, bounty_id, &recipient)?;
+
+        let now = env.ledger().timestamp();
+        let claim_window: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ClaimWindow)
+            .unwrap_or(0);
+        let claim = ClaimRecord {
+            bounty_id,
+            recipient: recipient.clone(),
+            amount: escrow.remaining_amount,
+            expires_at: now.saturating_add(claim_window),
+            claimed: false,
+        };
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::PendingClaim(bounty_id), &claim);
+        escrow.status = EscrowStatus::Claimable;
+        Self::store_escrow(&env, bounty_id, &escrow);
+
+        env.events().publish(
+            (symbol_short!("claim"), symbol_short!("created")),
+            ClaimCreated {
+                seq: events::next_event_seq(&env),
+                bounty_id,
+                recipient,
+                amount: claim.amount,
+                expires_at: claim.expires_at,
+            },
+        );
+        Ok(())
+    }
+
+    /// Beneficiary calls this to claim their authorized funds within the window.
+    pub fn claim(env: Env, bounty_id: u64) -> Result<(), Error> {
+        reentrancy_guard::enter(&env)?;
+        Self::ensure_not_paused(&env)?;
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(Error::FundsPaused);
+        }
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::PendingClaim(bounty_id))
+        {
+            return Err(Error::BountyNotFound);
+        }
+        let mut claim: ClaimRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingClaim(bounty_id))
+            .unwrap();
+
+        claim.recipient.require_auth();
+
+        let now = env.ledger().timestamp();
+        if now > claim.expires_at {
+            return Err(Error::ClaimExpired);
+        }
+        if claim.claimed {
+            return Err(Error::AlreadyReleased);
+        }
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .unwrap();
+        Self::ensure_status(&escrow, &[EscrowStatus::Claimable])?;
+        Self::ensure_release_time_reached(&env, bounty_id)?;
+        Self::ensure_release_condition_met(&env, bounty_id)?;
+        Self::ensure_release_approved(&env, bounty_id)?;
+
+        // Pay what the escrow still holds; the claim amount is only the
+        // balance at authorization time.
+        let amount = escrow.remaining_amount;
+        let token_addr = Self::escrow_token(&env, bounty_id);
+        let client = token::Client::new(&env, &token_addr);
+        let payout = amount - Self::collect_release_fee(&env, &token_addr, amount);
+        client.transfer(&env.current_contract_address(), &claim.recipient, &payout);
+
+        // Update escrow status
+        Self::record_depositor_outflow(&env, &escrow.depositor, amount, 0);
+        Self::record_release_to(&env, bounty_id, &claim.recipient);
+        Self::record_history(&env, bounty_id, EscrowHistoryKind::Released, -amount);
+        escrow.status = EscrowStatus::Released;
+        escrow.remaining_amount = 0;
+        invariants::assert_escrow(&env, &escrow);
+        Self::store_escrow(&env, bounty_id, &escrow);
+
+        claim.amount = amount;
+        claim.claimed = true;
+        env.storage()
+            .persistent()
+            .set(&DataKey::PendingClaim(bounty_id), &claim);
+        Self::record_release(&env, bounty_id, &claim.recipient, payout);
+
+        env.events().publish(
+            (symbol_short!("claim"), symbol_short!("done")),
+            ClaimExecuted {
+                seq: events::next_event_seq(&env),
+                bounty_id,
+                recipient: claim.recipient.clone(),
+                amount: payout,
+                claimed_at: now,
+            },
+        );
+        emit_escrow_released(
+            &env,
+            Self::lifecycle_event(&env, bounty_id, &escrow, payout),
+        );
+        Self::emit_depositor_index(&env, bounty_id, &escrow);
+        reentrancy_guard::exit(&env);
+        Ok(())
+    }
+
+    /// Admin can cancel an expired or unwanted pending claim, returning escrow to Locked.
+    pub fn cancel_pending_claim(env: Env, bounty_id: u64) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::PendingClaim(bounty_id))
+        {
+            return Err(Error::BountyNotFound);
+        }
+        let claim: ClaimRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingClaim(bounty_id))
+            .unwrap();
+
+        if claim.claimed {
+            return Err(Error::AlreadyReleased);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingClaim(bounty_id));
+        if let Some(mut escrow) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+        {
+            if escrow.status == EscrowStatus::Claimable {
+                escrow.status = EscrowStatus::Locked;
+                Self::store_escrow(&env, bounty_id, &escrow);
+            }
+        }
+
+        env.events().publish(
+            (symbol_short!("claim"), symbol_short!("cancel")),
+            ClaimCancelled {
+                seq: events::next_event_seq(&env),
+                bounty_id,
+                recipient: claim.recipient,
+                amount: claim.amount,
+                cancelled_at: env.ledger().timestamp(),
+                cancelled_by: admin,
+            },
+        );
+        Ok(())
+    }
+
+    /// Depositor takes back a `Claimable` escrow once its claim window has
+    /// lapsed unclaimed, forfeiting the contributor's claim. Funds go to the
+    /// refund address if one was set, otherwise to the depositor.
+    pub fn reclaim_unclaimed(env: Env, bounty_id: u64) -> Result<(), Error> {
+        reentrancy_guard::enter(&env)?;
+        Self::ensure_not_paused(&env)?;
+        if Self::check_paused(&env, symbol_short!("refund")) {
+            return Err(Error::FundsPaused);
+        }
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+        escrow.depositor.require_auth();
+        Self::ensure_status(&escrow, &[EscrowStatus::Claimable])?;
+        let claim: ClaimRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingClaim(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+        let now = env.ledger().timestamp();
+        if now <= claim.expires_at {
+            return Err(Error::DeadlineNotPassed);
+        }
+
+        let amount = escrow.remaining_amount;
+        let refund_to = Self::refund_destination(&env, bounty_id, &escrow);
+        let client = token::Client::new(&env, &Self::escrow_token(&env, bounty_id));
+        client.transfer(&env.current_contract_address(), &refund_to, &amount);
+
+        escrow.remaining_amount = 0;
+        escrow.status = EscrowStatus::Refunded;
+        escrow.refund_history.push_back(RefundRecord {
+            amount,
+            recipient: refund_to.clone(),
+            timestamp: now,
+            mode: RefundMode::Full,
+        });
+        Self::record_refund(
+            &env,
+            &RefundHistoryEntry {
+                bounty_id,
+                depositor: escrow.depositor.clone(),
+                recipient: refund_to.clone(),
+                amount,
+                gross_amount: amount,
+                timestamp: now,
+                admin_forced: false,
+            },
+        );
+        Self::record_depositor_outflow(&env, &escrow.depositor, 0, amount);
+        Self::record_history(&env, bounty_id, EscrowHistoryKind::Refunded, -amount);
+        invariants::assert_escrow(&env, &escrow);
+        Self::store_escrow(&env, bounty_id, &escrow);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingClaim(bounty_id));
+
+        env.events().publish(
+            (symbol_short!("claim"), symbol_short!("reclaim")),
+            events::ClaimReclaimed {
+                seq: events::next_event_seq(&env),
+                bounty_id,
+                recipient: claim.recipient,
+                amount,
+                refund_to,
+                reclaimed_at: now,
+            },
+        );
+        emit_escrow_refunded(
+            &env,
+            Self::lifecycle_event(&env, bounty_id, &escrow, amount),
+        );
+        Self::emit_depositor_index(&env, bounty_id, &escrow);
+
+        reentrancy_guard::exit(&env);
+        Ok(())
+    }
+
+    /// View: get pending claim for a bounty.
+    pub fn get_pending_claim(env: Env, bounty_id: u64) -> Result<ClaimRecord, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PendingClaim(bounty_id))
+            .ok_or(Error::BountyNotFound)
+    }
+
+    /// Set the delay after `mark_completed` before anyone may trigger the
+    /// auto-release (admin only).
+    pub fn set_auto_release_delay(env: Env, delay: u64) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::AutoReleaseDelay, &delay);
+        Ok(())
+    }
+
+    /// Record that `contributor` completed the work (admin only).
+    ///
+    /// Starts the auto-release window: once `completed_at + auto_release_after`
+    /// has passed, anyone can call `claim_auto_release`. The depositor can raise
+    /// a dispute in the meantime, and `release_funds` still works immediately.
+    pub fn mark_completed(env: Env, bounty_id: u64, contributor: Address) -> Result<(), Error> {
+        Self::ensure_not_paused(&env)?;
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyNotFound);
+        }
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .unwrap();
+        Self::ensure_status(&escrow, &[EscrowStatus::Locked])?;
+        Self::ensure_contributor_allowed(&env, bounty_id, &contributor)?;
+
+        let record = AutoReleaseRecord {
+            bounty_id,
+            contributor,
+            completed_at: env.ledger().timestamp(),
+            auto_release_after: env
+                .storage()
+                .instance()
+                .get(&DataKey::AutoReleaseDelay)
+                .unwrap_or(0),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::AutoRelease(bounty_id), &record);
+
+        events::emit_work_completed(
+            &env,
+            events::WorkCompleted {
+                seq: events::next_event_seq(&env),
+                bounty_id,
+                contributor: record.contributor.clone(),
+                completed_at: record.completed_at,
+                release_at: record
+                    .completed_at
+                    .saturating_add(record.auto_release_after),
+            },
+        );
+        Ok(())
+    }
+
+    /// Pay the recorded contributor once the auto-release window has elapsed.
+    /// Callable by anyone.
+    pub fn claim_auto_release(env: Env, bounty_id: u64) -> Result<(), Error> {
+        reentrancy_guard::enter(&env)?;
+        Self::ensure_not_paused(&env)?;
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(Error::FundsPaused);
+        }
+        let record: AutoReleaseRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AutoRelease(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+
+        let now = env.ledger().timestamp();
+        if now
+            < record
+                .completed_at
+                .saturating_add(record.auto_release_after)
+        {
+            return Err(Error::AutoReleaseNotReady);
+        }
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .unwrap();
+        Self::ensure_status(&escrow, &[EscrowStatus::Locked])?;
+        Self::ensure_release_time_reached(&env, bounty_id)?;
+        Self::ensure_release_condition_met(&env, bounty_id)?;
+        Self::ensure_release_approved(&env, bounty_id)?;
+
+        let gross = escrow.remaining_amount;
+        let token_addr = Self::escrow_token(&env, bounty_id);
+        let payout = gross - Self::collect_release_fee(&env, &token_addr, gross);
+        let client = token::Client::new(&env, &token_addr);
+        client.transfer(
+            &env.current_contract_address(),
+            &record.contributor,
+            &payout,
+        );
+        Self::record_depositor_outflow(&env, &escrow.depositor, gross, 0);
+        Self::record_release_to(&env, bounty_id, &record.contributor);
+        Self::record_history(&env, bounty_id, EscrowHistoryKind::Released, -gross);
+
+        escrow.status = EscrowStatus::Released;
+        escrow.remaining_amount = 0;
+        invariants::assert_escrow(&env, &escrow);
+        Self::store_escrow(&env, bounty_id, &escrow);
+        Self::record_release(&env, bounty_id, &record.contributor, payout);
+
+        events::emit_auto_released(
+            &env,
+            FundsReleased {
+                seq: events::next_event_seq(&env),
+                version: EVENT_VERSION_V2,
+                bounty_id,
+                amount: payout,
+                recipient: record.contributor,
+                timestamp: now,
+            },
+        );
+        emit_escrow_released(
+            &env,
+            Self::lifecycle_event(&env, bounty_id, &escrow, payout),
+        );
+        Self::emit_depositor_index(&env, bounty_id, &escrow);
+        reentrancy_guard::exit(&env);
+        Ok(())
+    }
+
+    /// View: the pending auto-release for a bounty, if work was marked completed.
+    pub fn get_auto_release(env: Env, bounty_id: u64) -> Option<AutoReleaseRecord> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AutoRelease(bounty_id))
+    }
+
+    /// Approve a refund before deadline (admin only).
+    /// This allows early refunds with admin approval.
+    ///
+    /// Under a multi-admin setup, approving a refund to anyone other than the
+    /// escrow's refund destination fails with `Unauthorized`; propose an
+    /// `AdminAction::ApproveRefund` instead.
+    pub fn approve_refund(
+        env: Env,
+        bounty_id: u64,
+        amount: i128,
+        recipient: Address,
+        mode: RefundMode,
+    ) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        let escrow = Self::get_escrow_info(env.clone(), bounty_id)?;
+        if recipient != Self::refund_destination(&env, bounty_id, &escrow) {
+            Self::ensure_single_admin(&env)?;
+        }
+        Self::apply_approve_refund(&env, admin, bounty_id, amount, recipient, mode)
+    }
+
+    fn apply_approve_refund(
+        env: &Env,
+        admin: Address,
+        bounty_id: u64,
+        amount: i128,
+        recipient: Address,
+        mode: RefundMode,
+    ) -> Result<(), Error> {
+        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyNotFound);
+        }
+
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .unwrap();
+
+        Self::ensure_status(
+            &escrow,
+            &[EscrowStatus::Locked, EscrowStatus::PartiallyRefunded],
+        )?;
+
+        if amount <= 0 || amount > escrow.remaining_amount {
+            return Err(Error::InvalidAmount);
+        }
+
+        let approval = RefundApproval {
+            bounty_id,
+            amount,
+            recipient: recipient.clone(),
+            mode: mode.clone(),
+            approved_by: admin.clone(),
+            approved_at: env.ledger().timestamp(),
+        };
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::RefundApproval(bounty_id), &approval);
+
+        Ok(())
+    }
+
+    /// Release a partial amount of the locked funds to the contributor.
+    /// Only the admin (backend) can authorize this.
+    ///
+    /// - `amount` must be > 0 and <= `remaining_amount`.
+    /// - `remaining_amount` is decremented by `amount` after each call; the
+    ///   contributor receives `amount` less the release fee.
+    /// - When `remaining_amount` reaches 0 the escrow status is set to Released.
+    /// - The bounty stays Locked while any funds remain unreleased.
+    /// - Each call emits its own `FundsReleased` event for the partial amount.
+    pub fn release_partial(
+        env: Env,
+        bounty_id: u64,
+        contributor: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        reentrancy_guard::enter(&env)?;
+        Self::ensure_not_paused(&env)?;
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(Error::FundsPaused);
+        }
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyNotFound);
+        }
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .unwrap();
+
+        Self::ensure_status(&escrow, &[EscrowStatus::Locked])?;
+        Self::ensure_not_milestone(&env, bounty_id)?;
+        Self::ensure_contributor_allowed(&env, bounty_id, &contributor)?;
+        Self::ensure_release_time_reached(&env, bounty_id)?;
+        Self::ensure_release_condition_met(&env, bounty_id)?;
+        Self::ensure_release_approved(&env, bounty_id)?;
+
+        // Guard: zero or negative payout makes no sense and would corrupt state
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        // Guard: prevent overpayment — payout cannot exceed what is still owed
+        if amount > escrow.remaining_amount {
+            return Err(Error::InsufficientFunds);
+        }
+
+        let token_addr = Self::escrow_token(&env, bounty_id);
+        let client = token::Client::new(&env, &token_addr);
+
+        // Transfer only the requested partial amount, less the release fee
+        let payout = amount - Self::collect_release_fee(&env, &token_addr, amount);
+        client.transfer(&env.current_contract_address(), &contributor, &payout);
+
+        // Decrement remaining; this is always an exact integer subtraction — no rounding
+        escrow.remaining_amount -= amount;
+        Self::record_depositor_outflow(&env, &escrow.depositor, amount, 0);
+        Self::record_history(&env, bounty_id, EscrowHistoryKind::Released, -amount);
+
+        // Automatically transition to Released once fully paid out
+        if escrow.remaining_amount == 0 {
+            escrow.status = EscrowStatus::Released;
+            Self::record_release_to(&env, bounty_id, &contributor);
+        } else {
+            Self::index_contributor(&env, bounty_id, &contributor);
+        }
+
+        invariants::assert_escrow(&env, &escrow);
+        Self::store_escrow(&env, bounty_id, &escrow);
+        Self::record_release(&env, bounty_id, &contributor, payout);
+
+        events::emit_funds_released(
+            &env,
+            FundsReleased {
+                seq: events::next_event_seq(&env),
+                version: EVENT_VERSION_V2,
+                bounty_id,
+                amount: payout,
+                recipient: contributor.clone(),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        emit_escrow_released(
+            &env,
+            Self::lifecycle_event(&env, bounty_id, &escrow, payout),
+        );
+        if escrow.status == EscrowStatus::Released {
+            Self::emit_depositor_index(&env, bounty_id, &escrow);
+        }
+
+        reentrancy_guard::exit(&env);
+        Ok(())
+    }
+
+    /// Lock the sum of `milestones` as one escrow, releasable tranche by tranche.
+    ///
+    /// Each milestone is `(amount, unlock_time)`. The escrow's refund deadline
+    /// is the latest unlock time, so whatever has not been released by then
+    /// can be refunded to the depositor with [`Self::refund`].
+    pub fn lock_with_milestones(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        milestones: Vec<(i128, u64)>,
+    ) -> Result<(), Error> {
+        if milestones.is_empty() || milestones.len() > MAX_BATCH_SIZE {
+            return Err(Error::InvalidBatchSize);
+        }
+
+        let mut total: i128 = 0;
+        let mut deadline: u64 = 0;
+        let mut stored = Vec::new(&env);
+        for (amount, unlock_time) in milestones.iter() {
+            if amount <= 0 {
+                return Err(Error::InvalidAmount);
+            }
+            total = total.checked_add(amount).ok_or(Error::InvalidAmount)?;
+            deadline = deadline.max(unlock_time);
+            stored.push_back(Milestone {
+                amount,
+                unlock_time,
+                released: false,
+            });
+        }
+
+        Self::lock_funds(env.clone(), depositor, bounty_id, total, deadline)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Milestones(bounty_id), &stored);
+        Ok(())
+    }
+
+    /// Pay out one milestone once its unlock_time has passed (admin only).
+    ///
+    /// The escrow moves to `Released` when the last milestone is paid.
+    /// `top_up`, `release_partial` and `merge_escrows` reject milestone
+    /// escrows with `MilestoneLocked`, so that payment empties the escrow.
+    pub fn release_milestone(
+        env: Env,
+        bounty_id: u64,
+        milestone_index: u32,
+        contributor: Address,
+    ) -> Result<(), Error> {
+        reentrancy_guard::enter(&env)?;
+        Self::ensure_not_paused(&env)?;
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(Error::FundsPaused);
+        }
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+        Self::ensure_status(&escrow, &[EscrowStatus::Locked])?;
+        Self::ensure_contributor_allowed(&env, bounty_id, &contributor)?;
+        Self::ensure_release_time_reached(&env, bounty_id)?;
+        Self::ensure_release_condition_met(&env, bounty_id)?;
+        Self::ensure_release_approved(&env, bounty_id)?;
+
+        let mut milestones: Vec<Milestone> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Milestones(bounty_id))
+            .ok_or(Error::MilestoneNotFound)?;
+        let mut milestone = milestones
+            .get(milestone_index)
+            .ok_or(Error::MilestoneNotFound)?;
+        if milestone.released {
+            return Err(Error::MilestoneAlreadyReleased);
+        }
+        let now = env.ledger().timestamp();
+        if now < milestone.unlock_time {
+            return Err(Error::MilestoneLocked);
+        }
+        if milestone.amount > escrow.remaining_amount {
+            return Err(Error::InsufficientFunds);
+        }
+
+        let token_addr = Self::escrow_token(&env, bounty_id);
+        let payout =
+            milestone.amount - Self::collect_release_fee(&env, &token_addr, milestone.amount);
+        let client = token::Client::new(&env, &token_addr);
+        client.transfer(&env.current_contract_address(), &contributor, &payout);
+
+        milestone.released = true;
+        milestones.set(milestone_index, milestone.clone());
+        escrow.remaining_amount -= milestone.amount;
+        Self::record_depositor_outflow(&env, &escrow.depositor, milestone.amount, 0);
+        Self::record_history(
+            &env,
+            bounty_id,
+            EscrowHistoryKind::Released,
+            -milestone.amount,
+        );
+        if milestones.iter().all(|m| m.released) {
+            escrow.status = EscrowStatus::Released;
+            Self::record_release_to(&env, bounty_id, &contributor);
+        } else {
+            Self::index_contributor(&env, bounty_id, &contributor);
+        }
+        invariants::assert_escrow(&env, &escrow);
+        Self::store_escrow(&env, bounty_id, &escrow);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Milestones(bounty_id), &milestones);
+        Self::record_release(&env, bounty_id, &contributor, payout);
+
+        events::emit_milestone_released(
+            &env,
+            events::MilestoneReleased {
+                seq: events::next_event_seq(&env),
+                version: EVENT_VERSION_V2,
+                bounty_id,
+                milestone_index,
+                amount: payout,
+                recipient: contributor,
+                timestamp: now,
+            },
+        );
+        emit_escrow_released(
+            &env,
+            Self::lifecycle_event(&env, bounty_id, &escrow, payout),
+        );
+        if escrow.status == EscrowStatus::Released {
+            Self::emit_depositor_index(&env, bounty_id, &escrow);
+        }
+
+        reentrancy_guard::exit(&env);
+        Ok(())
+    }
+
+    /// View: the milestones of a bounty (empty for non-milestone escrows).
+    pub fn get_milestones(env: Env, bounty_id: u64) -> Vec<Milestone> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Milestones(bounty_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Legacy alias for [`Self::release_partial`], kept for existing integrations.
+    pub fn partial_release(
+        env: Env,
+        bounty_id: u64,
+        contributor: Address,
+        payout_amount: i128,
+    ) -> Result<(), Error> {
+        Self::release_partial(env, bounty_id, contributor, payout_amount)
+    }
+
+    /// Release the locked balance of a single bounty to several contributors.
+    /// Only the admin (backend) can authorize this.
+    ///
+    /// - `recipients` is a list of (contributor, amount) pairs; it must not be
+    ///   empty, exceed `MAX_BATCH_SIZE`, or repeat a contributor.
+    /// - Every amount must be > 0 and the amounts must sum exactly to the
+    ///   escrow's `remaining_amount`. The release fee is withheld from each
+    ///   share.
+    /// - All transfers happen in one transaction, so any failure reverts all of them.
+    pub fn release_funds_split(
+        env: Env,
+        bounty_id: u64,
+        recipients: Vec<(Address, i128)>,
+    ) -> Result<(), Error> {
+        reentrancy_guard::enter(&env)?;
+        Self::ensure_not_paused(&env)?;
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(Error::FundsPaused);
+        }
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        let count = recipients.len();
+        if count == 0 || count > MAX_BATCH_SIZE {
+            return Err(Error::InvalidBatchSize);
+        }
+
+        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyNotFound);
+        }
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .unwrap();
+
+        Self::ensure_status(&escrow, &[EscrowStatus::Locked])?;
+        Self::ensure_release_time_reached(&env, bounty_id)?;
+        Self::ensure_release_condition_met(&env, bounty_id)?;
+        Self::ensure_release_approved(&env, bounty_id)?;
+
+        // Validate every share before moving any tokens (all-or-nothing approach)
+        let mut total: i128 = 0;
+        for (i, (recipient, amount)) in recipients.iter().enumerate() {
+            if amount <= 0 {
+                return Err(Error::InvalidAmount);
+            }
+            Self::ensure_contributor_allowed(&env, bounty_id, &recipient)?;
+            for (other, _) in recipients.iter().skip(i + 1) {
+                if other == recipient {
+                    return Err(Error::DuplicateRecipient);
+                }
+            }
+            total = total.checked_add(amount).ok_or(Error::InvalidAmount)?;
+        }
+
+        if total != escrow.remaining_amount {
+            return Err(Error::SplitAmountMismatch);
+        }
+
+        let token_addr = Self::escrow_token(&env, bounty_id);
+        let client = token::Client::new(&env, &token_addr);
+        let contract_address = env.current_contract_address();
+
+        // Each share pays its own release fee; `paid` holds the net amounts.
+        let mut paid = Vec::new(&env);
+        let mut total_paid: i128 = 0;
+        for (recipient, amount) in recipients.iter() {
+            let payout = amount - Self::collect_release_fee(&env, &token_addr, amount);
+            client.transfer(&contract_address, &recipient, &payout);
+            total_paid += payout;
+            paid.push_back((recipient, payout));
+        }
+        Self::record_depositor_outflow(&env, &escrow.depositor, total, 0);
+        Self::record_history(&env, bounty_id, EscrowHistoryKind::Released, -total);
+
+        escrow.status = EscrowStatus::Released;
+        escrow.remaining_amount = 0;
+        invariants::assert_escrow(&env, &escrow);
+        Self::store_escrow(&env, bounty_id, &escrow);
+        for (recipient, payout) in paid.iter() {
+            Self::index_contributor(&env, bounty_id, &recipient);
+            Self::record_release(&env, bounty_id, &recipient, payout);
+        }
+
+        emit_funds_released_split(
+            &env,
+            FundsReleasedSplit {
+                seq: events::next_event_seq(&env),
+                version: EVENT_VERSION_V2,
+                bounty_id,
+                total_amount: total_paid,
+                recipients: paid,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        emit_escrow_released(
+            &env,
+            Self::lifecycle_event(&env, bounty_id, &escrow, total_paid),
+        );
+        Self::emit_depositor_index(&env, bounty_id, &escrow);
+
+        reentrancy_guard::exit(&env);
+        Ok(())
+    }
+
+    /// [`Self::release_funds_split`] with shares in basis points instead of
+    /// token amounts. The shares must each be > 0 and sum to 10_000
+    /// (`SplitAmountMismatch` otherwise). Amounts are taken from the escrow's
+    /// `remaining_amount` rounding down, and the last recipient also receives
+    /// the rounding remainder, so the whole balance is paid out. On small
+    /// balances a share that rounds down to nothing is folded into that
+    /// remainder, and its recipient is left out of the payout.
+    pub fn release_funds_split_bps(
+        env: Env,
+        bounty_id: u64,
+        recipients: Vec<(Address, u32)>,
+    ) -> Result<(), Error> {
+        if recipients.is_empty() {
+            return Err(Error::InvalidBatchSize);
+        }
+        let remaining = Self::get_escrow_info(env.clone(), bounty_id)?.remaining_amount;
+
+        let mut total_bps: i128 = 0;
+        let mut allocated: i128 = 0;
+        let mut amounts = Vec::new(&env);
+        let last = recipients.len() - 1;
+        for (i, (recipient, bps)) in recipients.iter().enumerate() {
+            if bps == 0 {
+                return Err(Error::InvalidAmount);
+            }
+            total_bps += bps as i128;
+            let amount = if i as u32 == last {
+                remaining - allocated
+            } else {
+                remaining * bps as i128 / BASIS_POINTS
+            };
+            if amount == 0 && i as u32 != last {
+                continue;
+            }
+            allocated += amount;
+            amounts.push_back((recipient, amount));
+        }
+        if total_bps != BASIS_POINTS {
+            return Err(Error::SplitAmountMismatch);
+        }
+
+        Self::release_funds_split(env, bounty_id, amounts)
+    }
+
+    /// Refund funds to the original depositor if the deadline has passed.
+    /// Refunds the full remaining_amount (accounts for any prior partial releases).
+    pub fn refund(env: Env, bounty_id: u64) -> Result<(), Error> {
+        reentrancy_guard::enter(&env)?;
+        Self::ensure_not_paused(&env)?;
+        if Self::check_paused(&env, symbol_short!("refund")) {
+            return Err(Error::FundsPaused);
+        }
+
+        Self::refund_internal(env.clone(), bounty_id)?;
+
+        reentrancy_guard::exit(&env);
+        Ok(())
+    }
+
+    /// Refund an expired escrow on the depositor's behalf. Anyone may call
+    /// this, so funds never depend on the depositor coming back online.
+    ///
+    /// Only the plain deadline path is allowed: the escrow must use
+    /// `RefundPolicy::Full` and have no pending admin approval, so the funds
+    /// always go to the depositor or its refund address. Emits
+    /// `RefundedOnBehalf` naming `caller`.
+    pub fn refund_on_behalf(env: Env, bounty_id: u64, caller: Address) -> Result<(), Error> {
+        reentrancy_guard::enter(&env)?;
+        Self::ensure_not_paused(&env)?;
+        if Self::check_paused(&env, symbol_short!("refund")) {
+            return Err(Error::FundsPaused);
+        }
+        caller.require_auth();
+
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+        Self::ensure_status(
+            &escrow,
+            &[EscrowStatus::Locked, EscrowStatus::PartiallyRefunded],
+        )?;
+        let now = env.ledger().timestamp();
+        if !Self::refund_time_reached(&env, &escrow) {
+            return Err(Error::DeadlineNotPassed);
+        }
+        if Self::get_refund_policy(env.clone(), bounty_id) != RefundPolicy::Full
+            || env
+                .storage()
+                .persistent()
+                .has(&DataKey::RefundApproval(bounty_id))
+        {
+            return Err(Error::RefundNotAllowed);
+        }
+
+        let amount = Self::refund_internal(env.clone(), bounty_id)?;
+        events::emit_refunded_on_behalf(
+            &env,
+            events::RefundedOnBehalf {
+                seq: events::next_event_seq(&env),
+                version: EVENT_VERSION_V2,
+                bounty_id,
+                caller,
+                amount,
+                timestamp: now,
+            },
+        );
+
+        reentrancy_guard::exit(&env);
+        Ok(())
+    }
+
+    /// Validate and execute a single refund, returning the amount paid out.
+    /// All checks run before any transfer, so an `Err` leaves state untouched.
+    /// Callers own the reentrancy guard and pause checks.
+    fn refund_internal(env: Env, bounty_id: u64) -> Result<i128, Error> {
+        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyNotFound);
+        }
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .unwrap();
+
+        Self::ensure_status(
+            &escrow,
+            &[EscrowStatus::Locked, EscrowStatus::PartiallyRefunded],
+        )?;
+
+        // GUARD 1: Block refund if there is a pending claim (Issue #391 fix)
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::PendingClaim(bounty_id))
+        {
+            let claim: ClaimRecord = env
+                .storage()
+                .persistent()
+                .get(&DataKey::PendingClaim(bounty_id))
+                .unwrap();
+            if !claim.claimed {
+                return Err(Error::ClaimPending);
+            }
+        }
+
+        // GUARD 2: Block refund once work is marked completed, so a refund
+        // cannot race the scheduled auto-release
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::AutoRelease(bounty_id))
+        {
+            return Err(Error::ClaimPending);
+        }
+
+        let now = env.ledger().timestamp();
+        let approval_key = DataKey::RefundApproval(bounty_id);
+        let approval: Option<RefundApproval> = env.storage().persistent().get(&approval_key);
+
+        // Refund is allowed if:
+        // 1. Deadline has passed (returns full amount to depositor)
+        // 2. An administrative approval exists (can be early, partial, and to custom recipient)
+        if !Self::refund_time_reached(&env, &escrow) && approval.is_none() {
+            return Err(Error::DeadlineNotPassed);
+        }
+
+        // Admin approvals override the escrow's refund policy; the standard
+        // deadline path honours it and forfeits any non-refundable portion.
+        // The refund penalty is withheld on the deadline path only and, like
+        // the policy's non-refundable portion, goes to the treasury.
+        let (refund_amount, refund_to, is_full, forfeited, penalty) =
+            if let Some(app) = approval.clone() {
+                let full = app.mode == RefundMode::Full || app.amount >= escrow.remaining_amount;
+                (app.amount, app.recipient, full, 0, 0)
+            } else {
+                let policy = Self::get_refund_policy(env.clone(), bounty_id);
+                if policy == RefundPolicy::None {
+                    return Err(Error::RefundNotAllowed);
+                }
+                let refundable = Self::refundable_amount(&policy, escrow.remaining_amount);
+                let penalty = refundable * Self::get_refund_penalty_bps(env.clone()) / BASIS_POINTS;
+                (
+                    refundable - penalty,
+                    Self::refund_destination(&env, bounty_id, &escrow),
+                    true,
+                    escrow.remaining_amount - refundable + penalty,
+                    penalty,
+                )
+            };
+
+        if refund_amount < 0
+            || refund_amount + forfeited > escrow.remaining_amount
+            || (refund_amount == 0 && forfeited == 0)
+        {
+            return Err(Error::InvalidAmount);
+        }
+
+        let token_addr = Self::escrow_token(&env, bounty_id);
+        let client = token::Client::new(&env, &token_addr);
+
+        // Transfer the calculated refund amount to the designated recipient
+        if refund_amount > 0 {
+            client.transfer(&env.current_contract_address(), &refund_to, &refund_amount);
+        }
+        Self::withhold_from_refund(&env, bounty_id, &escrow.depositor, &token_addr, forfeited);
+
+        invariants::assert_escrow(&env, &escrow);
+        let previous_status = escrow.status.clone();
+        // Update escrow state: subtract the amount refunded and forfeited
+        escrow.remaining_amount -= refund_amount + forfeited;
+        if is_full || escrow.remaining_amount == 0 {
+            escrow.status = EscrowStatus::Refunded;
+        } else {
+            escrow.status = EscrowStatus::PartiallyRefunded;
+        }
+
+        // Add to refund history
+        escrow.refund_history.push_back(RefundRecord {
+            amount: refund_amount,
+            recipient: refund_to.clone(),
+            timestamp: now,
+            mode: if is_full {
+                RefundMode::Full
+            } else {
+                RefundMode::Partial
+            },
+        });
+        Self::record_refund(
+            &env,
+            &RefundHistoryEntry {
+                bounty_id,
+                depositor: escrow.depositor.clone(),
+                recipient: refund_to.clone(),
+                amount: refund_amount,
+                gross_amount: refund_amount + penalty,
+                timestamp: now,
+                admin_forced: false,
+            },
+        );
+        Self::record_depositor_outflow(&env, &escrow.depositor, 0, refund_amount);
+        Self::record_history(
+            &env,
+            bounty_id,
+            EscrowHistoryKind::Refunded,
+            -(refund_amount + forfeited),
+        );
+
+        // Save updated escrow
+        Self::store_escrow(&env, bounty_id, &escrow);
+
+        // Remove approval after successful execution
+        if approval.is_some() {
+            env.storage().persistent().remove(&approval_key);
+        }
+
+        emit_funds_refunded(
+            &env,
+            FundsRefunded {
+                seq: events::next_event_seq(&env),
+                version: EVENT_VERSION_V2,
+                bounty_id,
+                amount: refund_amount,
+                refund_to: refund_to.clone(),
+                timestamp: now,
+            },
+        );
+        emit_escrow_refunded(
+            &env,
+            Self::lifecycle_event(&env, bounty_id, &escrow, refund_amount),
+        );
+        if escrow.status != previous_status {
+            Self::emit_depositor_index(&env, bounty_id, &escrow);
+        }
+
+        Ok(refund_amount)
+    }
+
+    /// Refund up to `limit` escrows whose deadline has passed, oldest first,
+    /// and return how many were refunded.
+    ///
+    /// Each escrow goes through the same path as [`Self::refund`], so refund
+    /// policies, pending admin approvals, history and depositor stats are all
+    /// honoured. Escrows that `refund` would reject (pending claim, dispute,
+    /// `RefundPolicy::None`, ...) are skipped rather than failing the batch.
+    /// A large backlog may need several calls; keep calling until it
+    /// returns fewer than `limit`. The batch stops early if one of its
+    /// refunds trips the anomaly auto-pause (see `set_anomaly_threshold`).
+    pub fn refund_expired_batch(env: Env, limit: u32) -> Result<u32, Error> {
+        reentrancy_guard::enter(&env)?;
+        Self::ensure_not_paused(&env)?;
+        if Self::check_paused(&env, symbol_short!("refund")) {
+            return Err(Error::FundsPaused);
+        }
+
+        let now = env.ledger().timestamp();
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+
+        let mut count: u32 = 0;
+        let mut total_amount: i128 = 0;
+        for bounty_id in index.iter() {
+            if count >= limit {
+                break;
+            }
+            let escrow: Escrow = match env.storage().persistent().get(&DataKey::Escrow(bounty_id)) {
+                Some(e) => e,
+                None => continue,
+            };
+            let refundable_status = Self::ensure_status(
+                &escrow,
+                &[EscrowStatus::Locked, EscrowStatus::PartiallyRefunded],
+            );
+            if refundable_status.is_err() || !Self::refund_time_reached(&env, &escrow) {
+                continue;
+            }
+            if let Ok(amount) = Self::refund_internal(env.clone(), bounty_id) {
+                count += 1;
+                total_amount += amount;
+                // A refund that trips the anomaly threshold pauses the
+                // contract; the rest of the batch must wait for the admin.
+                if Self::is_paused(env.clone()) {
+                    break;
+                }
+            }
+        }
+
+        events::emit_batch_expired_refunded(
+            &env,
+            events::BatchExpiredRefunded {
+                seq: events::next_event_seq(&env),
+                count,
+                total_amount,
+                timestamp: now,
+            },
+        );
+
+        reentrancy_guard::exit(&env);
+        Ok(count)
+    }
+
+    /// Refund part of an expired escrow to the depositor (or its refund
+    /// address), leaving the rest in escrow for later refunds.
+    ///
+    /// Requires the depositor's auth and a passed deadline. The escrow becomes
+    /// `PartiallyRefunded`, and `Refunded` once `remaining_amount` reaches
+    /// zero. A `PartiallyRefunded` escrow can no longer be released; the rest
+    /// only goes back through `refund` or `refund_partial`. Only escrows with `RefundPolicy::Full` support partial refunds;
+    /// others must go through `refund`, which applies the policy. The refund
+    /// penalty (see `set_refund_penalty_bps`) is withheld from `amount` and
+    /// credited to the treasury.
+    pub fn refund_partial(env: Env, bounty_id: u64, amount: i128) -> Result<(), Error> {
+        reentrancy_guard::enter(&env)?;
+        Self::ensure_not_paused(&env)?;
+        if Self::check_paused(&env, symbol_short!("refund")) {
+            return Err(Error::FundsPaused);
+        }
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+        escrow.depositor.require_auth();
+
+        Self::ensure_status(
+            &escrow,
+            &[EscrowStatus::Locked, EscrowStatus::PartiallyRefunded],
+        )?;
+        let now = env.ledger().timestamp();
+        if !Self::refund_time_reached(&env, &escrow) {
+            return Err(Error::DeadlineNotPassed);
+        }
+        if Self::get_refund_policy(env.clone(), bounty_id) != RefundPolicy::Full {
+            return Err(Error::RefundNotAllowed);
+        }
+        if let Some(claim) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, ClaimRecord>(&DataKey::PendingClaim(bounty_id))
+        {
+            if !claim.claimed {
+                return Err(Error::ClaimPending);
+            }
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::AutoRelease(bounty_id))
+        {
+            return Err(Error::ClaimPending);
+        }
+        if amount <= 0 || amount > escrow.remaining_amount {
+            return Err(Error::InvalidAmount);
+        }
+
+        // The refund penalty applies here as on the deadline path of `refund`
+        let penalty = amount * Self::get_refund_penalty_bps(env.clone()) / BASIS_POINTS;
+        let net_amount = amount - penalty;
+
+        let refund_to = Self::refund_destination(&env, bounty_id, &escrow);
+        let token_addr = Self::escrow_token(&env, bounty_id);
+        let client = token::Client::new(&env, &token_addr);
+        if net_amount > 0 {
+            client.transfer(&env.current_contract_address(), &refund_to, &net_amount);
+        }
+        Self::withhold_from_refund(&env, bounty_id, &escrow.depositor, &token_addr, penalty);
+
+        let previous_status = escrow.status.clone();
+        escrow.remaining_amount -= amount;
+        escrow.status = if escrow.remaining_amount == 0 {
+            EscrowStatus::Refunded
+        } else {
+            EscrowStatus::PartiallyRefunded
+        };
+        escrow.refund_history.push_back(RefundRecord {
+            amount: net_amount,
+            recipient: refund_to.clone(),
+            timestamp: now,
+            mode: RefundMode::Partial,
+        });
+        Self::record_refund(
+            &env,
+            &RefundHistoryEntry {
+                bounty_id,
+                depositor: escrow.depositor.clone(),
+                recipient: refund_to.clone(),
+                amount: net_amount,
+                gross_amount: amount,
+                timestamp: now,
+                admin_forced: false,
+            },
+        );
+        Self::record_depositor_outflow(&env, &escrow.depositor, 0, net_amount);
+        Self::record_history(&env, bounty_id, EscrowHistoryKind::Refunded, -amount);
+        invariants::assert_escrow(&env, &escrow);
+        Self::store_escrow(&env, bounty_id, &escrow);
+
+        emit_funds_refunded(
+            &env,
+            FundsRefunded {
+                seq: events::next_event_seq(&env),
+                version: EVENT_VERSION_V2,
+                bounty_id,
+                amount: net_amount,
+                refund_to,
+                timestamp: now,
+            },
+        );
+        emit_escrow_refunded(
+            &env,
+            Self::lifecycle_event(&env, bounty_id, &escrow, net_amount),
+        );
+        if escrow.status != previous_status {
+            Self::emit_depositor_index(&env, bounty_id, &escrow);
+        }
+
+        reentrancy_guard::exit(&env);
+        Ok(())
+    }
+
+    /// Force-refund an escrow to its depositor (or refund address) before its
+    /// deadline. Admin only; meant for stuck bounties, e.g. when the
+    /// contributor has disappeared.
+    ///
+    /// The full remaining amount is returned regardless of deadline, grace
+    /// period or refund policy. Disputed escrows must go through
+    /// `resolve_dispute`, and a pending claim must be cancelled first. The
+    /// refund is logged with `admin_forced` set and emits `AdminRefund`.
+    ///
+    /// Under a multi-admin setup this fails with `Unauthorized`; propose an
+    /// `AdminAction::AdminRefund` instead.
+    pub fn admin_refund(env: Env, bounty_id: u64) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        Self::ensure_single_admin(&env)?;
+        admin.require_auth();
+        Self::apply_admin_refund(&env, admin, bounty_id)
+    }
+
+    fn apply_admin_refund(env: &Env, admin: Address, bounty_id: u64) -> Result<(), Error> {
+        reentrancy_guard::enter(env)?;
+        Self::ensure_not_paused(env)?;
+        if Self::check_paused(env, symbol_short!("refund")) {
+            return Err(Error::FundsPaused);
+        }
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+        Self::ensure_status(
+            &escrow,
+            &[EscrowStatus::Locked, EscrowStatus::PartiallyRefunded],
+        )?;
+        if let Some(claim) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, ClaimRecord>(&DataKey::PendingClaim(bounty_id))
+        {
+            if !claim.claimed {
+                return Err(Error::ClaimPending);
+            }
+        }
+        let amount = escrow.remaining_amount;
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let now = env.ledger().timestamp();
+        let refund_to = Self::refund_destination(env, bounty_id, &escrow);
+        let client = token::Client::new(env, &Self::escrow_token(env, bounty_id));
+        client.transfer(&env.current_contract_address(), &refund_to, &amount);
+
+        escrow.remaining_amount = 0;
+        escrow.status = EscrowStatus::Refunded;
+        escrow.refund_history.push_back(RefundRecord {
+            amount,
+            recipient: refund_to.clone(),
+            timestamp: now,
+            mode: RefundMode::Full,
+        });
+        Self::record_refund(
+            env,
+            &RefundHistoryEntry {
+                bounty_id,
+                depositor: escrow.depositor.clone(),
+                recipient: refund_to.clone(),
+                amount,
+                gross_amount: amount,
+                timestamp: now,
+                admin_forced: true,
+            },
+        );
+        Self::record_depositor_outflow(env, &escrow.depositor, 0, amount);
+        Self::record_history(env, bounty_id, EscrowHistoryKind::Refunded, -amount);
+        invariants::assert_escrow(env, &escrow);
+        Self::store_escrow(env, bounty_id, &escrow);
+
+        events::emit_admin_refund(
+            env,
+            events::AdminRefund {
+                seq: events::next_event_seq(env),
+                version: EVENT_VERSION_V2,
+                bounty_id,
+                admin,
+                refund_to: refund_to.clone(),
+                amount,
+                timestamp: now,
+            },
+        );
+        emit_funds_refunded(
+            env,
+            FundsRefunded {
+                seq: events::next_event_seq(env),
+                version: EVENT_VERSION_V2,
+                bounty_id,
+                amount,
+                refund_to,
+                timestamp: now,
+            },
+        );
+        emit_escrow_refunded(env, Self::lifecycle_event(env, bounty_id, &escrow, amount));
+        Self::emit_depositor_index(env, bounty_id, &escrow);
+
+        reentrancy_guard::exit(env);
+        Ok(())
+    }
+
+    /// Hand an active escrow over to `new_depositor` (current depositor only),
+    /// e.g. when a DAO reassigns a bounty's sponsor.
+    ///
+    /// The escrow moves between the depositors' indexes and stats, and later
+    /// refunds go to `new_depositor` unless a refund address was set at lock
+    /// time.
+    pub fn transfer_depositor(
+        env: Env,
+        bounty_id: u64,
+        new_depositor: Address,
+    ) -> Result<(), Error> {
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+        escrow.depositor.require_auth();
+        Self::ensure_status(
+            &escrow,
+            &[EscrowStatus::Locked, EscrowStatus::PartiallyRefunded],
+        )?;
+        let previous = escrow.depositor.clone();
+        if previous == new_depositor {
+            return Ok(());
+        }
+
+        let old_key = DataKey::DepositorIndex(previous.clone());
+        let mut old_index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&old_key)
+            .unwrap_or(Vec::new(&env));
+        if let Some(pos) = old_index.first_index_of(bounty_id) {
+            old_index.remove(pos);
+        }
+        env.storage().persistent().set(&old_key, &old_index);
+
+        let new_key = DataKey::DepositorIndex(new_depositor.clone());
+        let mut new_index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&new_key)
+            .unwrap_or(Vec::new(&env));
+        new_index.push_back(bounty_id);
+        env.storage().persistent().set(&new_key, &new_index);
+
+        // The still-held balance follows the escrow to its new depositor.
+        let stats_key = DataKey::DepositorStats(previous.clone());
+        let mut stats: DepositorStats = env
+            .storage()
+            .persistent()
+            .get(&stats_key)
+            .unwrap_or_default();
+        stats.total_locked -= escrow.remaining_amount;
+        stats.escrow_count = stats.escrow_count.saturating_sub(1);
+        env.storage().persistent().set(&stats_key, &stats);
+        Self::record_depositor_lock(&env, &new_depositor, escrow.remaining_amount, true);
+
+        escrow.depositor = new_depositor.clone();
+        Self::store_escrow(&env, bounty_id, &escrow);
+
+        events::emit_depositor_transferred(
+            &env,
+            events::DepositorTransferred {
+                seq: events::next_event_seq(&env),
+                version: EVENT_VERSION_V2,
+                bounty_id,
+                previous_depositor: previous,
+                new_depositor,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Self::emit_depositor_index(&env, bounty_id, &escrow);
+        Ok(())
+    }
+
+    /// Lock funds that may only be released while `condition_contract`
+    /// reports the bounty's condition as met (for example an oracle that
+    /// confirms a PR merged). The contract must implement
+    /// [`traits::ReleaseCondition`]; it is called on every release path.
+    pub fn lock_funds_with_condition(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        amount: i128,
+        deadline: u64,
+        condition_contract: Address,
+    ) -> Result<(), Error> {
+        Self::lock_funds(env.clone(), depositor, bounty_id, amount, deadline)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::ReleaseCondition(bounty_id), &condition_contract);
+        Ok(())
+    }
+
+    /// Lock funds together with the hash of the bounty's off-chain
+    /// description, so anyone can later check the text was not changed.
+    /// The hash is shown by `get_escrow`; see `set_description_hash`.
+    pub fn lock_funds_with_description(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        amount: i128,
+        deadline: u64,
+        description_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        Self::lock_funds(env.clone(), depositor, bounty_id, amount, deadline)?;
+        env.storage()
+            .persistent()
+            .set(&RecordKey::DescriptionHash(bounty_id), &description_hash);
+        Ok(())
+    }
+
+    /// Replace the description hash of a Locked escrow (depositor only),
+    /// e.g. after agreeing on revised acceptance criteria. Settled escrows
+    /// keep the hash they ended with.
+    pub fn set_description_hash(
+        env: Env,
+        bounty_id: u64,
+        description_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        let escrow = Self::get_escrow_info(env.clone(), bounty_id)?;
+        escrow.depositor.require_auth();
+        Self::ensure_status(&escrow, &[EscrowStatus::Locked])?;
+
+        let key = RecordKey::DescriptionHash(bounty_id);
+        let previous: Option<BytesN<32>> = env.storage().persistent().get(&key);
+        let previous = previous.map(Bytes::from);
+        env.storage().persistent().set(&key, &description_hash);
+
+        events::emit_description_hash_updated(
+            &env,
+            events::DescriptionHashUpdated {
+                version: EVENT_VERSION_V2,
+                seq: events::next_event_seq(&env),
+                bounty_id,
+                depositor: escrow.depositor,
+                previous,
+                description_hash,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Lock funds whose releases need sign-off from both the depositor and
+    /// `reviewer`, each given through [`Self::approve_release`]. Escrows
+    /// without a reviewer release on the usual authorization alone.
+    pub fn lock_funds_with_reviewer(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        amount: i128,
+        deadline: u64,
+        reviewer: Address,
+    ) -> Result<(), Error> {
+        Self::lock_funds(env.clone(), depositor, bounty_id, amount, deadline)?;
+        env.storage()
+            .persistent()
+            .set(&RecordKey::Reviewer(bounty_id), &reviewer);
+        Ok(())
+    }
+
+    /// Approve releasing a reviewed escrow, as its depositor or reviewer.
+    /// Once both have approved, every release path may pay it out.
+    /// Approving twice is a no-op.
+    pub fn approve_release(env: Env, bounty_id: u64, approver: Address) -> Result<(), Error> {
+        let escrow = Self::get_escrow_info(env.clone(), bounty_id)?;
+        if approver != escrow.depositor
+            && Self::get_reviewer(env.clone(), bounty_id) != Some(approver.clone())
+        {
+            return Err(Error::Unauthorized);
+        }
+        approver.require_auth();
+
+        let mut approvers = Self::get_release_approvals(env.clone(), bounty_id);
+        if !approvers.contains(&approver) {
+            approvers.push_back(approver);
+            env.storage()
+                .persistent()
+                .set(&RecordKey::ReleaseApprovers(bounty_id), &approvers);
+        }
+        Ok(())
+    }
+
+    /// View: reviewer whose approval releases of this escrow need, if any.
+    pub fn get_reviewer(env: Env, bounty_id: u64) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&RecordKey::Reviewer(bounty_id))
+    }
+
+    /// View: who has approved releasing this escrow so far.
+    pub fn get_release_approvals(env: Env, bounty_id: u64) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&RecordKey::ReleaseApprovers(bounty_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Lock funds for work commissioned from a known `contributor`. The
+    /// contributor is assigned as with [`Self::assign_contributor`], so
+    /// releases may only pay them and [`Self::release_to_assigned`] can be
+    /// used without naming a recipient.
+    pub fn lock_funds_for_contributor(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        amount: i128,
+        deadline: u64,
+        contributor: Address,
+    ) -> Result<(), Error> {
+        Self::lock_funds(env.clone(), depositor, bounty_id, amount, deadline)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::AssignedContributor(bounty_id), &contributor);
+        Ok(())
+    }
+
+    /// Record the contributor working on a Locked bounty (depositor only).
+    ///
+    /// The assigned contributor is the counter-party whose consent
+    /// [`Self::cancel_escrow`] requires, and the only address releases may
+    /// pay. The depositor can assign once; replacing an assigned contributor
+    /// fails with `Unauthorized` and goes through
+    /// [`Self::reassign_contributor`], so the depositor cannot redirect a
+    /// release the admin is about to make.
+    pub fn assign_contributor(env: Env, bounty_id: u64, contributor: Address) -> Result<(), Error> {
+        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyNotFound);
+        }
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .unwrap();
+        escrow.depositor.require_auth();
+
+        Self::ensure_status(&escrow, &[EscrowStatus::Locked])?;
+        let key = DataKey::AssignedContributor(bounty_id);
+        match env.storage().persistent().get::<DataKey, Address>(&key) {
+            Some(assigned) if assigned != contributor => Err(Error::Unauthorized),
+            Some(_) => Ok(()),
+            None => {
+                env.storage().persistent().set(&key, &contributor);
+                Ok(())
+            }
+        }
+    }
+
+    /// View: the contributor assigned to a bounty, if any.
+    pub fn get_assigned_contributor(env: Env, bounty_id: u64) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AssignedContributor(bounty_id))
+    }
+
+    /// Point a Locked or Disputed bounty at a different contributor, e.g.
+    /// when arbitration decides someone else did the work.
+    ///
+    /// `caller` must be the configured arbiter, the admin, or hold
+    /// `Role::Arbiter`. Later releases may only pay `new_contributor`, who
+    /// must be on the escrow's contributor allowlist if it has one
+    /// (`UnauthorizedContributor` otherwise). The contributor index only
+    /// lists released bounties, so nothing there changes until the release
+    /// happens.
+    pub fn reassign_contributor(
+        env: Env,
+        bounty_id: u64,
+        new_contributor: Address,
+        caller: Address,
+    ) -> Result<(), Error> {
+        Self::ensure_not_paused(&env)?;
+        let arbiter: Option<Address> = env.storage().instance().get(&DataKey::Arbiter);
+        if arbiter.as_ref() == Some(&caller) {
+            caller.require_auth();
+        } else {
+            Self::require_role(&env, Role::Arbiter, &caller)?;
+        }
+
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+        Self::ensure_status(&escrow, &[EscrowStatus::Locked, EscrowStatus::Disputed])?;
+        Self::ensure_on_allowlist(&env, bounty_id, &new_contributor)?;
+
+        let key = DataKey::AssignedContributor(bounty_id);
+        let previous: Option<Address> = env.storage().persistent().get(&key);
+        env.storage().persistent().set(&key, &new_contributor);
+
+        events::emit_contributor_reassigned(
+            &env,
+            events::ContributorReassigned {
+                version: EVENT_VERSION_V2,
+                seq: events::next_event_seq(&env),
+                bounty_id,
+                previous,
+                new_contributor,
+                caller,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Cancel a Locked bounty by mutual consent, returning the full remaining
+    /// balance to the depositor.
+    ///
+    /// Requires authorization from both the depositor and the assigned
+    /// contributor. Unlike [`Self::refund`] this works before the deadline and
+    /// ignores the bounty's refund policy.
+    pub fn cancel_escrow(env: Env, bounty_id: u64) -> Result<(), Error> {
+        reentrancy_guard::enter(&env)?;
+        Self::ensure_not_paused(&env)?;
+        if Self::check_paused(&env, symbol_short!("refund")) {
+            return Err(Error::FundsPaused);
+        }
+
+        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyNotFound);
+        }
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .unwrap();
+
+        Self::ensure_status(&escrow, &[EscrowStatus::Locked])?;
+
+        let contributor: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AssignedContributor(bounty_id))
+            .ok_or(Error::ContributorNotAssigned)?;
+        escrow.depositor.require_auth();
+        contributor.require_auth();
+
+        // A pending claim or scheduled auto-release must be settled or
+        // cancelled first, as with refund
+        if let Some(claim) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, ClaimRecord>(&DataKey::PendingClaim(bounty_id))
+        {
+            if !claim.claimed {
+                return Err(Error::ClaimPending);
+            }
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::AutoRelease(bounty_id))
+        {
+            return Err(Error::ClaimPending);
+        }
+
+        let amount = escrow.remaining_amount;
+        let now = env.ledger().timestamp();
+        let refund_to = Self::refund_destination(&env, bounty_id, &escrow);
+        let client = token::Client::new(&env, &Self::escrow_token(&env, bounty_id));
+        client.transfer(&env.current_contract_address(), &refund_to, &amount);
+
+        escrow.remaining_amount = 0;
+        escrow.status = EscrowStatus::Refunded;
+        escrow.refund_history.push_back(RefundRecord {
+            amount,
+            recipient: refund_to.clone(),
+            timestamp: now,
+            mode: RefundMode::Full,
+        });
+        Self::record_refund(
+            &env,
+            &RefundHistoryEntry {
+                bounty_id,
+                depositor: escrow.depositor.clone(),
+                recipient: refund_to.clone(),
+                amount,
+                gross_amount: amount,
+                timestamp: now,
+                admin_forced: false,
+            },
+        );
+        Self::record_depositor_outflow(&env, &escrow.depositor, 0, amount);
+        Self::record_history(&env, bounty_id, EscrowHistoryKind::Cancelled, -amount);
+        invariants::assert_escrow(&env, &escrow);
+        Self::store_escrow(&env, bounty_id, &escrow);
+
+        events::emit_escrow_cancelled(
+            &env,
+            events::EscrowCancelled {
+                seq: events::next_event_seq(&env),
+                version: EVENT_VERSION_V2,
+                bounty_id,
+                depositor: escrow.depositor.clone(),
+                contributor,
+                amount,
+                timestamp: now,
+            },
+        );
+        emit_escrow_refunded(
+            &env,
+            Self::lifecycle_event(&env, bounty_id, &escrow, amount),
+        );
+        Self::emit_depositor_index(&env, bounty_id, &escrow);
+
+        reentrancy_guard::exit(&env);
+        Ok(())
+    }
+
+    /// Set the neutral arbiter allowed to resolve disputes (admin only).
+    ///
+    /// Under a multi-admin setup this fails with `Unauthorized`; propose an
+    /// `AdminAction::SetArbiter` instead.
+    pub fn set_arbiter(env: Env, arbiter: Address) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        Self::ensure_single_admin(&env)?;
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Arbiter, &arbiter);
+        Ok(())
+    }
+
+    /// View: the configured arbiter, if any.
+    pub fn get_arbiter(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Arbiter)
+    }
+
+    /// Grant `role` to `account` (admin only).
+    ///
+    /// Under a multi-admin setup this and `revoke_role` fail with
+    /// `Unauthorized`; propose an `AdminAction::SetRole` instead.
+    pub fn grant_role(env: Env, role: Role, account: Address) -> Result<(), Error> {
+        Self::set_role(&env, role, account, true)
+    }
+
+    /// Revoke `role` from `account` (admin only). Revoking a role the account
+    /// does not hold is a no-op.
+    pub fn revoke_role(env: Env, role: Role, account: Address) -> Result<(), Error> {
+        Self::set_role(&env, role, account, false)
+    }
+
+    /// View: whether `account` holds `role`. The admin holds every role,
+    /// except `Arbiter` under a multi-admin setup, where the primary admin
+    /// must not settle disputes on its own.
+    pub fn has_role(env: Env, role: Role, account: Address) -> bool {
+        let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        let implicit = role != Role::Arbiter || Self::ensure_single_admin(&env).is_ok();
+        (implicit && admin == Some(account.clone()))
+            || env
+                .storage()
+                .persistent()
+                .has(&DataKey::Role(role, account))
+    }
+
+    fn set_role(env: &Env, role: Role, account: Address, granted: bool) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        Self::ensure_single_admin(env)?;
+        admin.require_auth();
+        Self::apply_set_role(env, role, account, granted);
+        Ok(())
+    }
+
+    fn apply_set_role(env: &Env, role: Role, account: Address, granted: bool) {
+        let key = DataKey::Role(role.clone(), account.clone());
+        if granted {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        events::emit_role_updated(
+            env,
+            events::RoleUpdated {
+                seq: events::next_event_seq(env),
+                role,
+                account,
+                granted,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    /// Require `caller`'s auth and that it is the admin or holds `role`.
+    fn require_role(env: &Env, role: Role, caller: &Address) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        caller.require_auth();
+        if !Self::has_role(env.clone(), role, caller.clone()) {
+            return Err(Error::Unauthorized);
+        }
+        Ok(())
+    }
+
+    /// Freeze a Locked or Claimable escrow pending arbitration.
+    ///
+    /// `caller` must be the depositor or the recipient of the bounty's pending
+    /// claim. Raising a dispute cancels the pending claim: the arbiter's ruling
+    /// decides the payout instead, and after a withdrawn dispute the admin may
+    /// authorize a new claim. While Disputed, `release_funds` and `refund`
+    /// fail with BountyDisputed.
+    pub fn raise_dispute(env: Env, bounty_id: u64, caller: Address) -> Result<(), Error> {
+        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyNotFound);
+        }
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .unwrap();
+
+        let pending_claim = env
+            .storage()
+            .persistent()
+            .get::<DataKey, ClaimRecord>(&DataKey::PendingClaim(bounty_id))
+            .filter(|claim| !claim.claimed);
+        let is_claimant = pending_claim
+            .as_ref()
+            .map(|claim| claim.recipient == caller)
+            .unwrap_or(false);
+        if caller != escrow.depositor && !is_claimant {
+            return Err(Error::Unauthorized);
+        }
+        caller.require_auth();
+
+        Self::ensure_status(&escrow, &[EscrowStatus::Locked, EscrowStatus::Claimable])?;
+
+        let now = env.ledger().timestamp();
+        if let Some(claim) = pending_claim {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::PendingClaim(bounty_id));
+            env.events().publish(
+                (symbol_short!("claim"), symbol_short!("cancel")),
+                ClaimCancelled {
+                    seq: events::next_event_seq(&env),
+                    bounty_id,
+                    recipient: claim.recipient,
+                    amount: claim.amount,
+                    cancelled_at: now,
+                    cancelled_by: caller.clone(),
+                },
+            );
+        }
+        escrow.status = EscrowStatus::Disputed;
+        Self::record_history(&env, bounty_id, EscrowHistoryKind::Disputed, 0);
+        Self::store_escrow(&env, bounty_id, &escrow);
+        env.storage().persistent().set(
+            &DataKey::Dispute(bounty_id),
+            &DisputeRecord {
+                bounty_id,
+                raised_by: caller.clone(),
+                raised_at: now,
+            },
+        );
+
+        events::emit_dispute_raised(
+            &env,
+            events::DisputeRaised {
+                seq: events::next_event_seq(&env),
+                bounty_id,
+                raised_by: caller,
+                timestamp: now,
+            },
+        );
+        Ok(())
+    }
+
+    /// Drop a dispute without a ruling and put the escrow back to Locked, for
+    /// when the parties settle it between themselves.
+    ///
+    /// `caller` is either the arbiter (the configured one, an `Arbiter` role
+    /// holder, or the admin), or one of the disputants. In the latter case
+    /// both disputants must authorize: the depositor, and the contributor who
+    /// raised the dispute or else the assigned contributor, when there is one.
+    pub fn withdraw_dispute(env: Env, bounty_id: u64, caller: Address) -> Result<(), Error> {
+        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyNotFound);
+        }
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .unwrap();
+
+        // The pending claim is cancelled when a dispute is raised, so a
+        // claimant who raised it is only known from the dispute record
+        let counterparty = env
+            .storage()
+            .persistent()
+            .get::<DataKey, DisputeRecord>(&DataKey::Dispute(bounty_id))
+            .map(|dispute| dispute.raised_by)
+            .filter(|raised_by| *raised_by != escrow.depositor)
+            .or_else(|| Self::get_assigned_contributor(env.clone(), bounty_id));
+        let arbiter: Option<Address> = env.storage().instance().get(&DataKey::Arbiter);
+        if arbiter.as_ref() == Some(&caller) {
+            caller.require_auth();
+        } else if caller == escrow.depositor || counterparty.as_ref() == Some(&caller) {
+            escrow.depositor.require_auth();
+            if let Some(other) = &counterparty {
+                other.require_auth();
+            }
+        } else {
+            Self::require_role(&env, Role::Arbiter, &caller)?;
+        }
+
+        if escrow.status != EscrowStatus::Disputed {
+            return Err(Error::NotDisputed);
+        }
+
+        escrow.status = EscrowStatus::Locked;
+        Self::record_history(&env, bounty_id, EscrowHistoryKind::DisputeWithdrawn, 0);
+        Self::store_escrow(&env, bounty_id, &escrow);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Dispute(bounty_id));
+
+        events::emit_dispute_withdrawn(
+            &env,
+            events::DisputeWithdrawn {
+                version: EVENT_VERSION_V2,
+                seq: events::next_event_seq(&env),
+                bounty_id,
+                withdrawn_by: caller,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Self::emit_depositor_index(&env, bounty_id, &escrow);
+        Ok(())
+    }
+
+    /// Resolve a dispute (the configured arbiter, an `Arbiter` role holder, or
+    /// the admin).
+    ///
+    /// `amount` of the remaining balance goes to `award_to`, less the release
+    /// fee; whatever is left is refunded to the depositor. The escrow ends Released when anything was
+    /// awarded, or Refunded when `amount` is zero. A `release` lifecycle event
+    /// carries the net award and a `refund` one the refunded balance.
+    pub fn resolve_dispute(
+        env: Env,
+        bounty_id: u64,
+        award_to: Address,
+        amount: i128,
+        caller: Address,
+    ) -> Result<(), Error> {
+        reentrancy_guard::enter(&env)?;
+        let arbiter: Option<Address> = env.storage().instance().get(&DataKey::Arbiter);
+        if arbiter.as_ref() == Some(&caller) {
+            caller.require_auth();
+        } else {
+            Self::require_role(&env, Role::Arbiter, &caller)?;
+        }
+
+        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyNotFound);
+        }
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .unwrap();
+
+        if escrow.status != EscrowStatus::Disputed {
+            return Err(Error::NotDisputed);
+        }
+        if amount < 0 || amount > escrow.remaining_amount {
+            return Err(Error::InvalidAmount);
+        }
+
+        let token_addr = Self::escrow_token(&env, bounty_id);
+        let client = token::Client::new(&env, &token_addr);
+        let contract_address = env.current_contract_address();
+        let now = env.ledger().timestamp();
+
+        let refund_amount = escrow.remaining_amount - amount;
+        let award = amount - Self::collect_release_fee(&env, &token_addr, amount);
+        if award > 0 {
+            client.transfer(&contract_address, &award_to, &award);
+        }
+        if refund_amount > 0 {
+            let refund_to = Self::refund_destination(&env, bounty_id, &escrow);
+            client.transfer(&contract_address, &refund_to, &refund_amount);
+            escrow.refund_history.push_back(RefundRecord {
+                amount: refund_amount,
+                recipient: refund_to.clone(),
+                timestamp: now,
+                mode: if amount == 0 {
+                    RefundMode::Full
+                } else {
+                    RefundMode::Partial
+                },
+            });
+            Self::record_refund(
+                &env,
+                &RefundHistoryEntry {
+                    bounty_id,
+                    depositor: escrow.depositor.clone(),
+                    recipient: refund_to.clone(),
+                    amount: refund_amount,
+                    gross_amount: refund_amount,
+                    timestamp: now,
+                    admin_forced: false,
+                },
+            );
+        }
+
+        Self::record_depositor_outflow(&env, &escrow.depositor, amount, refund_amount);
+        Self::record_history(
+            &env,
+            bounty_id,
+            EscrowHistoryKind::DisputeResolved,
+            -(amount + refund_amount),
+        );
+        escrow.remaining_amount = 0;
+        escrow.status = if amount > 0 {
+            EscrowStatus::Released
+        } else {
+            EscrowStatus::Refunded
+        };
+        invariants::assert_escrow(&env, &escrow);
+        Self::store_escrow(&env, bounty_id, &escrow);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Dispute(bounty_id));
+        if amount > 0 {
+            Self::record_release_to(&env, bounty_id, &award_to);
+            Self::record_release(&env, bounty_id, &award_to, award);
+        }
+
+        events::emit_dispute_resolved(
+            &env,
+            events::DisputeResolved {
+                seq: events::next_event_seq(&env),
+                bounty_id,
+                award_to,
+                awarded_amount: award,
+                refunded_amount: refund_amount,
+                arbiter: caller,
+                timestamp: now,
+            },
+        );
+        if amount > 0 {
+            emit_escrow_released(&env, Self::lifecycle_event(&env, bounty_id, &escrow, award));
+        }
+        if refund_amount > 0 {
+            emit_escrow_refunded(
+                &env,
+                Self::lifecycle_event(&env, bounty_id, &escrow, refund_amount),
+            );
+        }
+        Self::emit_depositor_index(&env, bounty_id, &escrow);
+        reentrancy_guard::exit(&env);
+        Ok(())
+    }
+
+    /// View: the open dispute for a bounty, if any.
+    pub fn get_dispute(env: Env, bounty_id: u64) -> Option<DisputeRecord> {
+        env.storage().persistent().get(&DataKey::Dispute(bounty_id))
+    }
+
+    /// view function to get escrow info
+    pub fn get_escrow_info(env: Env, bounty_id: u64) -> Result<Escrow, Error> {
+        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyNotFound);
+        }
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .unwrap())
+    }
+
+    /// View: whether `bounty_id` is taken, with a single storage lookup.
+    /// Settled escrows still count, since ids are never reused.
+    pub fn escrow_exists(env: Env, bounty_id: u64) -> bool {
+        env.storage().persistent().has(&DataKey::Escrow(bounty_id))
+    }
+
+    /// View: fetch a single escrow by id together with its refund policy.
+    /// Returns `None` for unknown ids instead of failing.
+    pub fn get_escrow(env: Env, bounty_id: u64) -> Option<EscrowRecord> {
+        let escrow = env
+            .storage()
+            .persistent()
+            .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))?;
+        let label: Option<EscrowLabel> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowLabel(bounty_id));
+        Some(EscrowRecord {
+            bounty_id,
+            escrow,
+            refund_policy: Self::get_refund_policy(env.clone(), bounty_id),
+            allowed_contributors: Self::get_allowed_contributors(env.clone(), bounty_id),
+            released_to: env
+                .storage()
+                .persistent()
+                .get(&DataKey::ReleasedTo(bounty_id)),
+            release_not_before: env
+                .storage()
+                .persistent()
+                .get(&DataKey::ReleaseNotBefore(bounty_id)),
+            refund_address: env
+                .storage()
+                .persistent()
+                .get(&DataKey::RefundAddress(bounty_id)),
+            label_tag: label.as_ref().map(|label| label.tag.clone()),
+            label_title: label.map(|label| label.title),
+            note: env
+                .storage()
+                .persistent()
+                .get(&DataKey::EscrowNote(bounty_id)),
+            assigned_contributor: Self::get_assigned_contributor(env.clone(), bounty_id),
+            release_condition: env
+                .storage()
+                .persistent()
+                .get(&DataKey::ReleaseCondition(bounty_id)),
+            description_hash: env
+                .storage()
+                .persistent()
+                .get::<_, BytesN<32>>(&RecordKey::DescriptionHash(bounty_id))
+                .map(Bytes::from),
+        })
+    }
+
+    /// Set or replace the public note on an escrow (depositor only), e.g. a
+    /// link to acceptance criteria. Unlike the label it can change at any
+    /// time and never affects funds; an empty note clears it. Notes longer
+    /// than `MAX_ESCROW_NOTE_LEN` bytes are rejected with `NoteTooLong`.
+    pub fn set_escrow_note(
+        env: Env,
+        bounty_id: u64,
+        note: soroban_sdk::String,
+    ) -> Result<(), Error> {
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+        escrow.depositor.require_auth();
+        if note.len() > MAX_ESCROW_NOTE_LEN {
+            return Err(Error::NoteTooLong);
+        }
+
+        let key = DataKey::EscrowNote(bounty_id);
+        if note.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &note);
+        }
+
+        events::emit_escrow_note_updated(
+            &env,
+            events::EscrowNoteUpdated {
+                seq: events::next_event_seq(&env),
+                version: EVENT_VERSION_V2,
+                bounty_id,
+                depositor: escrow.depositor,
+                note,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Remember who an escrow was released to and index it by contributor.
+    fn record_release_to(env: &Env, bounty_id: u64, contributor: &Address) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::ReleasedTo(bounty_id), contributor);
+        Self::index_contributor(env, bounty_id, contributor);
+    }
+
+    /// Add `bounty_id` to the escrows paid to `contributor`, once per bounty
+    /// however many payments it received.
+    fn index_contributor(env: &Env, bounty_id: u64, contributor: &Address) {
+        let key = DataKey::ContributorIndex(contributor.clone());
+        let mut index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        if index.contains(bounty_id) {
+            return;
+        }
+        index.push_back(bounty_id);
+        env.storage().persistent().set(&key, &index);
+    }
+
+    /// view function to get contract balance of the token
+    pub fn get_balance(env: Env) -> Result<i128, Error> {
+        if !env.storage().instance().has(&DataKey::Token) {
+            return Err(Error::NotInitialized);
+        }
+        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let client = token::Client::new(&env, &token_addr);
+        Ok(client.balance(&env.current_contract_address()))
+    }
+
+    /// View: `(token balance held by the contract, sum of remaining escrow
+    /// amounts)` for the init token. A solvent contract has
+    /// `balance >= locked`; see `get_token_solvency` for other tokens.
+    pub fn get_solvency(env: Env) -> Result<(i128, i128), Error> {
+        let token_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Token)
+            .ok_or(Error::NotInitialized)?;
+        Ok(Self::get_token_solvency(env, token_addr))
+    }
+
+    /// View: solvency for escrows denominated in `token`. The locked total
+    /// covers every escrow still holding funds, including disputed and
+    /// partially refunded ones.
+    pub fn get_token_solvency(env: Env, token: Address) -> (i128, i128) {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+        let mut locked: i128 = 0;
+        for bounty_id in index.iter() {
+            if let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            {
+                if escrow.remaining_amount > 0 && Self::escrow_token(&env, bounty_id) == token {
+                    locked += escrow.remaining_amount;
+                }
+            }
+        }
+        let balance = token::Client::new(&env, &token).balance(&env.current_contract_address());
+        (balance, locked)
+    }
+
+    /// Query escrows with filtering and pagination
+    /// Pass 0 for min values and i128::MAX/u64::MAX for max values to disable those filters
+    pub fn query_escrows_by_status(
+        env: Env,
+        status: EscrowStatus,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<EscrowWithId> {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+        let mut results = Vec::new(&env);
+        let mut count = 0u32;
+        let mut skipped = 0u32;
+
+        for i in 0..index.len() {
+            if count >= limit {
+                break;
+            }
+
+            let bounty_id = index.get(i).unwrap();
+            if let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            {
+                if escrow.status == status {
+                    if skipped < offset {
+                        skipped += 1;
+                        continue;
+                    }
+                    results.push_back(EscrowWithId { bounty_id, escrow });
+                    count += 1;
+                }
+            }
+        }
+        results
+    }
+
+    /// Keyset-paginated variant of `query_escrows_by_status`: up to `limit`
+    /// escrows in `status` with a bounty_id above `after_bounty_id` (`None`
+    /// starts from the lowest), in ascending bounty_id order.
+    ///
+    /// Pages never skip or repeat an escrow when others are locked between
+    /// calls, and no matching records are read just to be skipped.
+    pub fn query_escrows_by_status_cursor(
+        env: Env,
+        status: EscrowStatus,
+        after_bounty_id: Option<u64>,
+        limit: u32,
+    ) -> EscrowCursorPage {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+        // Lowest `limit` matching ids, kept sorted; `more` records whether a
+        // further match exists beyond them.
+        let mut ids: Vec<u64> = Vec::new(&env);
+        let mut more = false;
+
+        for bounty_id in index.iter() {
+            if after_bounty_id.is_some_and(|after| bounty_id <= after) {
+                continue;
+            }
+            let beyond_page = match ids.last() {
+                _ if ids.len() < limit => false,
+                Some(last) => bounty_id > last,
+                None => true,
+            };
+            if beyond_page && more {
+                continue;
+            }
+            let matches = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+                .is_some_and(|escrow| escrow.status == status);
+            if !matches {
+                continue;
+            }
+            if beyond_page {
+                more = true;
+                continue;
+            }
+            let mut pos = ids.len();
+            while pos > 0 && ids.get(pos - 1).unwrap() > bounty_id {
+                pos -= 1;
+            }
+            ids.insert(pos, bounty_id);
+            if ids.len() > limit {
+                ids.pop_back();
+                more = true;
+            }
+        }
+
+        let mut records = Vec::new(&env);
+        for bounty_id in ids.iter() {
+            let escrow: Escrow = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Escrow(bounty_id))
+                .unwrap();
+            records.push_back(EscrowWithId { bounty_id, escrow });
+        }
+        EscrowCursorPage {
+            records,
+            next_cursor: if more { ids.last() } else { None },
+        }
+    }
+
+    /// Query escrows whose status is any of `statuses`, in lock order. An
+    /// empty `statuses` matches every escrow. Pagination applies to the union.
+    pub fn query_escrows_by_statuses(
+        env: Env,
+        statuses: Vec<EscrowStatus>,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<EscrowWithId> {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+        let mut results = Vec::new(&env);
+        let mut skipped = 0u32;
+
+        for bounty_id in index.iter() {
+            if results.len() >= limit {
+                break;
+            }
+            if let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            {
+                if !statuses.is_empty() && !statuses.contains(&escrow.status) {
+                    continue;
+                }
+                if skipped < offset {
+                    skipped += 1;
+                    continue;
+                }
+                results.push_back(EscrowWithId { bounty_id, escrow });
+            }
+        }
+        results
+    }
+
+    /// View: number of indexed escrows currently in `status`, read from a
+    /// counter kept up to date on every transition rather than by scanning.
+    /// Matches `query_escrows_by_status` over the full index.
+    pub fn get_escrow_count_by_status(env: Env, status: EscrowStatus) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::StatusCount(status))
+            .unwrap_or(0)
+    }
+
+    /// Persist `escrow`, moving it between the per-status counters behind
+    /// `get_escrow_count_by_status` when its status changed.
+    fn store_escrow(env: &Env, bounty_id: u64, escrow: &Escrow) {
+        let key = DataKey::Escrow(bounty_id);
+        let previous_escrow = env.storage().persistent().get::<DataKey, Escrow>(&key);
+        let token = Self::escrow_token(env, bounty_id);
+        let stats_key = RecordKey::TokenStats(token.clone());
+        let mut stats = env
+            .storage()
+            .persistent()
+            .get(&stats_key)
+            .unwrap_or_else(|| Self::empty_stats(env));
+        let withheld = Self::withheld_amount(env, bounty_id);
+        if let Some(old) = &previous_escrow {
+            Self::tally_token_stats(&mut stats, &token, old, withheld, false);
+        }
+        Self::tally_token_stats(&mut stats, &token, escrow, withheld, true);
+        env.storage().persistent().set(&stats_key, &stats);
+        Self::shift_active_tvl(env, previous_escrow.as_ref(), escrow);
+
+        let previous = previous_escrow.map(|e| e.status);
+        if previous.as_ref() != Some(&escrow.status) {
+            if escrow.status == EscrowStatus::Released {
+                Self::record_time_to_release(env, bounty_id);
+            }
+            Self::shift_status_count(env, previous, Some(escrow.status.clone()));
+        }
+        // Leaving Locked settles the escrow some other way, which supersedes
+        // a scheduled auto-release. A dispute can be withdrawn, so it keeps
+        // the schedule until resolved.
+        if !matches!(escrow.status, EscrowStatus::Locked | EscrowStatus::Disputed) {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::AutoRelease(bounty_id));
+        }
+        env.storage().persistent().set(&key, escrow);
+    }
+
+    /// Move a Locked escrow's remaining amount between the totals behind
+    /// `get_active_tvl` as it is stored: in when it is locked, out when it
+    /// leaves Locked, and across deadlines when its deadline moves.
+    fn shift_active_tvl(env: &Env, from: Option<&Escrow>, to: &Escrow) {
+        let locked = |e: &Escrow| {
+            (e.status == EscrowStatus::Locked).then_some((e.deadline, e.remaining_amount))
+        };
+        let before = from.and_then(locked);
+        let after = locked(to);
+        if before == after {
+            return;
+        }
+
+        let mut total: i128 = env
+            .storage()
+            .instance()
+            .get(&ConfigKey::LockedTvl)
+            .unwrap_or(0);
+        let mut by_deadline: Map<u64, i128> = env
+            .storage()
+            .persistent()
+            .get(&RecordKey::LockedByDeadline)
+            .unwrap_or(Map::new(env));
+        for (side, sign) in [(before, -1), (after, 1)] {
+            if let Some((deadline, amount)) = side {
+                total += sign * amount;
+                let sum = by_deadline.get(deadline).unwrap_or(0) + sign * amount;
+                if sum == 0 {
+                    by_deadline.remove(deadline);
+                } else {
+                    by_deadline.set(deadline, sum);
+                }
+            }
+        }
+        env.storage().instance().set(&ConfigKey::LockedTvl, &total);
+        env.storage()
+            .persistent()
+            .set(&RecordKey::LockedByDeadline, &by_deadline);
+    }
+
+    /// Add the lock-to-release duration of an escrow that just became
+    /// Released to the running totals behind `get_avg_time_to_release`.
+    /// Escrows locked before lock times were recorded are left out.
+    fn record_time_to_release(env: &Env, bounty_id: u64) {
+        let created_at: Option<u64> = env
+            .storage()
+            .persistent()
+            .get(&RecordKey::CreatedAt(bounty_id));
+        let Some(created_at) = created_at else {
+            return;
+        };
+        let storage = env.storage().instance();
+        let elapsed = env.ledger().timestamp().saturating_sub(created_at);
+        let total: u64 = storage.get(&ConfigKey::ReleaseTimeTotal).unwrap_or(0);
+        let count: u64 = storage.get(&ConfigKey::ReleaseTimeCount).unwrap_or(0);
+        storage.set(&ConfigKey::ReleaseTimeTotal, &total.saturating_add(elapsed));
+        storage.set(&ConfigKey::ReleaseTimeCount, &(count + 1));
+    }
+
+    /// View: average seconds between lock and full release over all released
+    /// escrows, rounded down; 0 before the first release.
+    pub fn get_avg_time_to_release(env: Env) -> u64 {
+        let storage = env.storage().instance();
+        let count: u64 = storage.get(&ConfigKey::ReleaseTimeCount).unwrap_or(0);
+        if count == 0 {
+            return 0;
+        }
+        let total: u64 = storage.get(&ConfigKey::ReleaseTimeTotal).unwrap_or(0);
+        total / count
+    }
+
+    /// Move one escrow between status counters; `None` on either side means
+    /// it is entering or leaving the index.
+    fn shift_status_count(env: &Env, from: Option<EscrowStatus>, to: Option<EscrowStatus>) {
+        let storage = env.storage().instance();
+        if let Some(status) = from {
+            let key = DataKey::StatusCount(status);
+            let count: u32 = storage.get(&key).unwrap_or(0);
+            storage.set(&key, &count.saturating_sub(1));
+        }
+        if let Some(status) = to {
+            let key = DataKey::StatusCount(status);
+            let count: u32 = storage.get(&key).unwrap_or(0);
+            storage.set(&key, &(count + 1));
+        }
+    }
+
+    /// Total number of escrows in `status`; the size of the full result set
+    /// `query_escrows_by_status` pages through. Alias for
+    /// [`Self::get_escrow_count_by_status`], reading the same counter.
+    pub fn count_escrows_by_status(env: Env, status: EscrowStatus) -> u32 {
+        Self::get_escrow_count_by_status(env, status)
+    }
+
+    /// Query escrows matching every constraint set in `filter`, with pagination
+    /// applied to the combined result.
+    pub fn query_escrows(
+        env: Env,
+        filter: EscrowFilter,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<EscrowWithId> {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+        let mut results = Vec::new(&env);
+        let mut count = 0u32;
+        let mut skipped = 0u32;
+
+        for i in 0..index.len() {
+            if count >= limit {
+                break;
+            }
+
+            let bounty_id = index.get(i).unwrap();
+            if let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            {
+                if Self::matches_filter(&escrow, &filter) {
+                    if skipped < offset {
+                        skipped += 1;
+                        continue;
+                    }
+                    results.push_back(EscrowWithId { bounty_id, escrow });
+                    count += 1;
+                }
+            }
+        }
+        results
+    }
+
+    fn matches_filter(escrow: &Escrow, filter: &EscrowFilter) -> bool {
+        if !filter.statuses.is_empty() && !filter.statuses.contains(&escrow.status) {
+            return false;
+        }
+        if filter.min_amount.is_some_and(|min| escrow.amount < min)
+            || filter.max_amount.is_some_and(|max| escrow.amount > max)
+        {
+            return false;
+        }
+        if filter.min_deadline.is_some_and(|min| escrow.deadline < min)
+            || filter.max_deadline.is_some_and(|max| escrow.deadline > max)
+        {
+            return false;
+        }
+        if let Some(depositor) = &filter.depositor {
+            if escrow.depositor != *depositor {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Query escrows by status in a deterministic order.
+    /// All matches are sorted first, then `offset`/`limit` are applied, so page
+    /// boundaries stay stable across calls.
+    pub fn query_escrows_by_status_sorted(
+        env: Env,
+        status: EscrowStatus,
+        sort_by: SortBy,
+        order: SortOrder,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<EscrowWithId> {
+        let matches = Self::query_escrows_by_status(env.clone(), status, 0, u32::MAX);
+        let sorted = Self::sort_escrows(&env, matches, sort_by, order);
+        Self::paginate(&env, sorted, offset, limit)
+    }
+
+    /// Stable bottom-up merge sort over query results. Every match has to be
+    /// ordered before a page can be cut, so this sees the full result set,
+    /// not one page; merging keeps it at O(n log n) comparisons.
+    fn sort_escrows(
+        env: &Env,
+        items: Vec<EscrowWithId>,
+        sort_by: SortBy,
+        order: SortOrder,
+    ) -> Vec<EscrowWithId> {
+        let key = |item: &EscrowWithId| -> i128 {
+            match sort_by {
+                SortBy::BountyId => item.bounty_id as i128,
+                SortBy::Amount => item.escrow.amount,
+                SortBy::Deadline => item.escrow.deadline as i128,
+            }
+        };
+        let len = items.len();
+        let mut sorted = items;
+        let mut width: u32 = 1;
+        while width < len {
+            let mut merged: Vec<EscrowWithId> = Vec::new(env);
+            let mut start: u32 = 0;
+            while start < len {
+                let mid = start.saturating_add(width).min(len);
+                let end = mid.saturating_add(width).min(len);
+                let (mut i, mut j) = (start, mid);
+                while i < mid && j < end {
+                    let left = sorted.get(i).unwrap();
+                    let right = sorted.get(j).unwrap();
+                    // Ties take the left run, which keeps the sort stable
+                    let take_left = match order {
+                        SortOrder::Ascending => key(&left) <= key(&right),
+                        SortOrder::Descending => key(&left) >= key(&right),
+                    };
+                    if take_left {
+                        merged.push_back(left);
+                        i += 1;
+                    } else {
+                        merged.push_back(right);
+                        j += 1;
+                    }
+                }
+                for k in i..mid {
+                    merged.push_back(sorted.get(k).unwrap());
+                }
+                for k in j..end {
+                    merged.push_back(sorted.get(k).unwrap());
+                }
+                start = end;
+            }
+            sorted = merged;
+            width = width.saturating_mul(2);
+        }
+        sorted
+    }
+
+    /// Apply offset/limit to an already-filtered result set
+    fn paginate(env: &Env, items: Vec<EscrowWithId>, offset: u32, limit: u32) -> Vec<EscrowWithId> {
+        let mut page = Vec::new(env);
+        let start = offset.min(items.len());
+        let end = offset.saturating_add(limit).min(items.len());
+        for i in start..end {
+            page.push_back(items.get(i).unwrap());
+        }
+        page
+    }
+
+    /// Query escrows with amount range filtering
+    pub fn query_escrows_by_amount(
+        env: Env,
+        min_amount: i128,
+        max_amount: i128,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<EscrowWithId> {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+        let mut results = Vec::new(&env);
+        let mut count = 0u32;
+        let mut skipped = 0u32;
+
+        for i in 0..index.len() {
+            if count >= limit {
+                break;
+            }
+
+            let bounty_id = index.get(i).unwrap();
+            if let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            {
+                if escrow.amount >= min_amount && escrow.amount <= max_amount {
+                    if skipped < offset {
+                        skipped += 1;
+                        continue;
+                    }
+                    results.push_back(EscrowWithId { bounty_id, escrow });
+                    count += 1;
+                }
+            }
+        }
+        results
+    }
+
+    /// Query escrows with deadline range filtering. Escrows without a
+    /// deadline (`u64::MAX`) only match ranges whose `max_deadline` is
+    /// `u64::MAX`.
+    pub fn query_escrows_by_deadline(
+        env: Env,
+        min_deadline: u64,
+        max_deadline: u64,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<EscrowWithId> {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+        let mut results = Vec::new(&env);
+        let mut count = 0u32;
+        let mut skipped = 0u32;
+
+        for i in 0..index.len() {
+            if count >= limit {
+                break;
+            }
+
+            let bounty_id = index.get(i).unwrap();
+            if let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            {
+                if escrow.deadline >= min_deadline && escrow.deadline <= max_deadline {
+                    if skipped < offset {
+                        skipped += 1;
+                        continue;
+                    }
+                    results.push_back(EscrowWithId { bounty_id, escrow });
+                    count += 1;
+                }
+            }
+        }
+        results
+    }
+
+    /// Query escrows in `status` whose deadline lies in
+    /// `min_deadline..=max_deadline`, e.g. only Locked escrows expiring in a
+    /// window. `offset` and `limit` apply to the matching escrows.
+    pub fn query_by_deadline_and_status(
+        env: Env,
+        min_deadline: u64,
+        max_deadline: u64,
+        status: EscrowStatus,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<EscrowWithId> {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+        let mut results = Vec::new(&env);
+        let mut skipped = 0u32;
+
+        for bounty_id in index.iter() {
+            if results.len() >= limit {
+                break;
+            }
+            let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            else {
+                continue;
+            };
+            if escrow.status != status
+                || escrow.deadline < min_deadline
+                || escrow.deadline > max_deadline
+            {
+                continue;
+            }
+            if skipped < offset {
+                skipped += 1;
+                continue;
+            }
+            results.push_back(EscrowWithId { bounty_id, escrow });
+        }
+        results
+    }
+
+    /// Query escrows in `status` whose locked amount lies in
+    /// `min_amount..=max_amount`, e.g. only high-value Locked escrows.
+    /// `offset` and `limit` apply to the matching escrows.
+    pub fn query_by_amount_and_status(
+        env: Env,
+        min_amount: i128,
+        max_amount: i128,
+        status: EscrowStatus,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<EscrowWithId> {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+        let mut results = Vec::new(&env);
+        let mut skipped = 0u32;
+
+        for bounty_id in index.iter() {
+            if results.len() >= limit {
+                break;
+            }
+            let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            else {
+                continue;
+            };
+            if escrow.status != status || escrow.amount < min_amount || escrow.amount > max_amount {
+                continue;
+            }
+            if skipped < offset {
+                skipped += 1;
+                continue;
+            }
+            results.push_back(EscrowWithId { bounty_id, escrow });
+        }
+        results
+    }
+
+    /// View: the Locked escrow with the earliest deadline, for spotting stuck
+    /// funds; ties go to the escrow locked first. `None` when nothing is
+    /// locked.
+    pub fn get_oldest_locked_escrow(env: Env) -> Option<EscrowRecord> {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+        let mut oldest: Option<(u64, u64)> = None;
+        for bounty_id in index.iter() {
+            if let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            {
+                if escrow.status != EscrowStatus::Locked {
+                    continue;
+                }
+                match oldest {
+                    Some((_, deadline)) if deadline <= escrow.deadline => {}
+                    _ => oldest = Some((bounty_id, escrow.deadline)),
+                }
+            }
+        }
+        oldest.and_then(|(bounty_id, _)| Self::get_escrow(env, bounty_id))
+    }
+
+    /// Locked escrows expiring within the next `window_seconds`, i.e. with
+    /// `now < deadline <= now + window_seconds` where `now` is the ledger
+    /// timestamp. Intended for "expiring soon" dashboards.
+    pub fn get_escrows_near_deadline(
+        env: Env,
+        window_seconds: u64,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<EscrowRecord> {
+        let now = env.ledger().timestamp();
+        let horizon = now.saturating_add(window_seconds);
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+        let mut results = Vec::new(&env);
+        let mut count = 0u32;
+        let mut skipped = 0u32;
+
+        for bounty_id in index.iter() {
+            if count >= limit {
+                break;
+            }
+            let Some(record) = Self::get_escrow(env.clone(), bounty_id) else {
+                continue;
+            };
+            let deadline = record.escrow.deadline;
+            if record.escrow.status != EscrowStatus::Locked
+                || deadline == NO_DEADLINE
+                || deadline <= now
+                || deadline > horizon
+            {
+                continue;
+            }
+            if skipped < offset {
+                skipped += 1;
+                continue;
+            }
+            results.push_back(record);
+            count += 1;
+        }
+        results
+    }
+
+    /// Query escrows denominated in a given token
+    pub fn query_escrows_by_token(
+        env: Env,
+        token: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<EscrowWithId> {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+        let mut results = Vec::new(&env);
+        let mut count = 0u32;
+        let mut skipped = 0u32;
+
+        for i in 0..index.len() {
+            if count >= limit {
+                break;
+            }
+
+            let bounty_id = index.get(i).unwrap();
+            if let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            {
+                if Self::escrow_token(&env, bounty_id) == token {
+                    if skipped < offset {
+                        skipped += 1;
+                        continue;
+                    }
+                    results.push_back(EscrowWithId { bounty_id, escrow });
+                    count += 1;
+                }
+            }
+        }
+        results
+    }
+
+    /// Query escrows by depositor
+    pub fn query_escrows_by_depositor(
+        env: Env,
+        depositor: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<EscrowWithId> {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DepositorIndex(depositor))
+            .unwrap_or(Vec::new(&env));
+        let mut results = Vec::new(&env);
+        let start = offset.min(index.len());
+        let end = (offset + limit).min(index.len());
+
+        for i in start..end {
+            let bounty_id = index.get(i).unwrap();
+            if let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            {
+                results.push_back(EscrowWithId { bounty_id, escrow });
+            }
+        }
+        results
+    }
+
+    /// Total number of escrows `query_escrows_by_depositor` can return for
+    /// `depositor`.
+    pub fn count_escrows_by_depositor(env: Env, depositor: Address) -> u32 {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DepositorIndex(depositor))
+            .unwrap_or(Vec::new(&env));
+        let mut count = 0u32;
+        for bounty_id in index.iter() {
+            if env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Query `depositor`'s escrows currently in `status`, in lock order.
+    ///
+    /// Pagination applies to the filtered result, so `offset` counts matching
+    /// escrows only. Named without the `escrows_` infix to stay within the
+    /// 32-character limit on contract function names.
+    pub fn query_by_depositor_and_status(
+        env: Env,
+        depositor: Address,
+        status: EscrowStatus,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<EscrowRecord> {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DepositorIndex(depositor))
+            .unwrap_or(Vec::new(&env));
+        let mut results = Vec::new(&env);
+        let mut skipped = 0u32;
+
+        for i in 0..index.len() {
+            if results.len() >= limit {
+                break;
+            }
+            let bounty_id = index.get(i).unwrap();
+            let matches = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+                .is_some_and(|escrow| escrow.status == status);
+            if !matches {
+                continue;
+            }
+            if skipped < offset {
+                skipped += 1;
+                continue;
+            }
+            if let Some(record) = Self::get_escrow(env.clone(), bounty_id) {
+                results.push_back(record);
+            }
+        }
+        results
+    }
+
+    /// Query escrows that paid `contributor`, in order of their first payment
+    /// to it. Partial, milestone, split and dispute payouts all count.
+    pub fn query_escrows_by_contributor(
+        env: Env,
+        contributor: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<EscrowRecord> {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ContributorIndex(contributor))
+            .unwrap_or(Vec::new(&env));
+        let mut results = Vec::new(&env);
+        let start = offset.min(index.len());
+        let end = offset.saturating_add(limit).min(index.len());
+
+        for i in start..end {
+            if let Some(record) = Self::get_escrow(env.clone(), index.get(i).unwrap()) {
+                results.push_back(record);
+            }
+        }
+        results
+    }
+
+    /// Query escrows locked with `tag` via `lock_funds_with_label`, in lock order.
+    pub fn query_escrows_by_tag(
+        env: Env,
+        tag: Symbol,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<EscrowRecord> {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TagIndex(tag))
+            .unwrap_or(Vec::new(&env));
+        let mut results = Vec::new(&env);
+        let start = offset.min(index.len());
+        let end = offset.saturating_add(limit).min(index.len());
+
+        for i in start..end {
+            if let Some(record) = Self::get_escrow(env.clone(), index.get(i).unwrap()) {
+                results.push_back(record);
+            }
+        }
+        results
+    }
+
+    fn empty_stats(env: &Env) -> AggregateStats {
+        AggregateStats {
+            total_locked: 0,
+            total_released: 0,
+            total_refunded: 0,
+            count_locked: 0,
+            count_released: 0,
+            count_refunded: 0,
+            total_fees_collected: 0,
+            total_locked_by_token: Map::new(env),
+        }
+    }
+
+    /// Add (`add == true`) or take back one escrow's share of a token's
+    /// stats, bucketed the same way `get_aggregate_stats` does. `withheld` is
+    /// the escrow's [`Self::withheld_amount`].
+    fn tally_token_stats(
+        stats: &mut AggregateStats,
+        token: &Address,
+        escrow: &Escrow,
+        withheld: i128,
+        add: bool,
+    ) {
+        let sign: i128 = if add { 1 } else { -1 };
+        let refunded: i128 = escrow.refund_history.iter().map(|r| r.amount).sum();
+        let released = escrow.amount - escrow.remaining_amount - refunded - withheld;
+        stats.total_released += sign * released;
+        stats.total_refunded += sign * refunded;
+
+        let held = stats.total_locked_by_token.get(token.clone()).unwrap_or(0)
+            + sign * escrow.remaining_amount;
+        if held == 0 {
+            stats.total_locked_by_token.remove(token.clone());
+        } else {
+            stats.total_locked_by_token.set(token.clone(), held);
+        }
+
+        let count = match escrow.status {
+            EscrowStatus::Locked | EscrowStatus::Disputed | EscrowStatus::Claimable => {
+                stats.total_locked += sign * escrow.remaining_amount;
+                &mut stats.count_locked
+            }
+            EscrowStatus::Released => &mut stats.count_released,
+            EscrowStatus::Refunded | EscrowStatus::PartiallyRefunded => {
+                stats.total_locked += sign * escrow.remaining_amount;
+                &mut stats.count_refunded
+            }
+            // Its balance is counted by the escrow it was merged into
+            EscrowStatus::Merged => return,
+        };
+        *count = if add {
+            *count + 1
+        } else {
+            count.saturating_sub(1)
+        };
+    }
+
+    /// View: `get_aggregate_stats` restricted to escrows held in `token`.
+    /// Kept up to date on every escrow write, so unlike the global view it
+    /// does not walk the index, and released and refunded totals survive
+    /// archiving. `total_fees_collected` counts release fees, refund
+    /// penalties and forfeits in this token.
+    pub fn get_aggregate_stats_by_token(env: Env, token: Address) -> AggregateStats {
+        env.storage()
+            .persistent()
+            .get(&RecordKey::TokenStats(token))
+            .unwrap_or_else(|| Self::empty_stats(&env))
+    }
+
+    /// Get aggregate statistics. Counts cover every token, and so do the
+    /// amount totals, which only add up meaningfully for a single asset;
+    /// use `get_aggregate_stats_by_token` to read amounts per token.
+    pub fn get_aggregate_stats(env: Env) -> AggregateStats {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+        let mut stats = AggregateStats {
+            total_locked: 0,
+            total_released: 0,
+            total_refunded: 0,
+            count_locked: 0,
+            count_released: 0,
+            count_refunded: 0,
+            total_fees_collected: env
+                .storage()
+                .instance()
+                .get(&DataKey::TotalFeesCollected)
+                .unwrap_or(0),
+            total_locked_by_token: Map::new(&env),
+        };
+        let archived = Self::get_archived_stats(&env);
+        stats.total_released += archived.total_released;
+        stats.total_refunded += archived.total_refunded;
+        stats.count_released += archived.count_released;
+        stats.count_refunded += archived.count_refunded;
+
+        for i in 0..index.len() {
+            let bounty_id = index.get(i).unwrap();
+            if let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            {
+                // Split each escrow's original amount into the portion still held,
+                // the portion refunded, the portion withheld from refunds, and the
+                // portion paid out (which includes any partial releases made while
+                // the escrow was still Locked).
+                let refunded: i128 = escrow.refund_history.iter().map(|r| r.amount).sum();
+                let released = escrow.amount
+                    - escrow.remaining_amount
+                    - refunded
+                    - Self::withheld_amount(&env, bounty_id);
+                stats.total_released += released;
+                stats.total_refunded += refunded;
+                if escrow.remaining_amount > 0 {
+                    let token = Self::escrow_token(&env, bounty_id);
+                    let held = stats.total_locked_by_token.get(token.clone()).unwrap_or(0);
+                    stats
+                        .total_locked_by_token
+                        .set(token, held + escrow.remaining_amount);
+                }
+                match escrow.status {
+                    EscrowStatus::Locked | EscrowStatus::Disputed | EscrowStatus::Claimable => {
+                        stats.total_locked += escrow.remaining_amount;
+                        stats.count_locked += 1;
+                    }
+                    EscrowStatus::Released => {
+                        stats.count_released += 1;
+                    }
+                    EscrowStatus::Refunded | EscrowStatus::PartiallyRefunded => {
+                        stats.total_locked += escrow.remaining_amount;
+                        stats.count_refunded += 1;
+                    }
+                    EscrowStatus::Merged => {}
+                }
+            }
+        }
+        stats
+    }
+
+    /// View: contract health in one call. Safe before `init`, when it
+    /// reports an uninitialized contract with zero fees and counts.
+    pub fn get_status(env: Env) -> ContractStatus {
+        let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        let (lock_fee_bps, release_fee_bps) = match admin {
+            Some(_) => {
+                let fees = Self::get_fee_config_internal(&env);
+                if fees.fee_enabled {
+                    (fees.lock_fee_rate, fees.release_fee_rate)
+                } else {
+                    (0, 0)
+                }
+            }
+            None => (0, 0),
+        };
+        ContractStatus {
+            initialized: admin.is_some(),
+            paused: Self::is_paused(env.clone()),
+            admin,
+            lock_fee_bps,
+            release_fee_bps,
+            escrow_count: Self::get_escrow_count(env.clone()),
+            active_tvl: Self::get_active_tvl(env),
+        }
+    }
+
+    /// View: value still held by Locked escrows whose deadline has not
+    /// passed. Unlike `total_locked` in `get_aggregate_stats`, expired
+    /// escrows awaiting a refund are left out. Escrows without a deadline
+    /// always count.
+    ///
+    /// Read from totals `store_escrow` keeps per deadline, so the cost grows
+    /// with the number of distinct passed deadlines still Locked, not with
+    /// the number of escrows.
+    pub fn get_active_tvl(env: Env) -> i128 {
+        let locked: i128 = env
+            .storage()
+            .instance()
+            .get(&ConfigKey::LockedTvl)
+            .unwrap_or(0);
+        let by_deadline: Map<u64, i128> = env
+            .storage()
+            .persistent()
+            .get(&RecordKey::LockedByDeadline)
+            .unwrap_or(Map::new(&env));
+        let now = env.ledger().timestamp();
+        let mut expired = 0i128;
+        // Keys iterate in ascending order and NO_DEADLINE sorts last.
+        for (deadline, amount) in by_deadline.iter() {
+            if deadline > now {
+                break;
+            }
+            expired += amount;
+        }
+        locked - expired
+    }
+
+    fn get_archived_stats(env: &Env) -> ArchivedStats {
+        env.storage()
+            .instance()
+            .get(&DataKey::ArchivedStats)
+            .unwrap_or_default()
+    }
+
+    /// Page through every escrow: archived ones in archive order, then the
+    /// rest in lock order. Pages are read straight off the two indexes, so
+    /// escrows locked while an export runs only ever land after it; only an
+    /// `archive_settled` call in between reorders the sequence.
+    ///
+    /// `limit` is capped at `MAX_EXPORT_PAGE_SIZE`; an `offset` past the end
+    /// yields an empty vector.
+    pub fn get_all_escrows(env: Env, offset: u32, limit: u32) -> Vec<EscrowRecord> {
+        let mut results = Vec::new(&env);
+        let mut skip = offset;
+        let mut remaining = limit.min(MAX_EXPORT_PAGE_SIZE);
+        for key in [DataKey::ArchivedIndex, DataKey::EscrowIndex] {
+            if remaining == 0 {
+                break;
+            }
+            let index: Vec<u64> = env
+                .storage()
+                .persistent()
+                .get(&key)
+                .unwrap_or(Vec::new(&env));
+            if skip >= index.len() {
+                skip -= index.len();
+                continue;
+            }
+            let end = skip.saturating_add(remaining).min(index.len());
+            for i in skip..end {
+                if let Some(record) = Self::get_escrow(env.clone(), index.get(i).unwrap()) {
+                    results.push_back(record);
+                }
+            }
+            remaining -= end - skip;
+            skip = 0;
         }
+        results
+    }
 
-        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
-            return Err(Error::BountyNotFound);
-        }
+    /// Drop up to `limit` Released or Refunded escrows settled before ledger
+    /// `older_than_ledger` from the escrow and depositor indexes (admin only),
+    /// so status and depositor scans stay short. Returns the number archived.
+    ///
+    /// The escrow records themselves are kept, so `get_escrow` and
+    /// `get_all_escrows` still return them and the bounty id stays taken.
+    /// Their totals move into `ArchivedStats`, keeping `get_aggregate_stats`
+    /// and `get_escrow_count` unchanged. The
+    /// settlement ledger comes from the escrow's history; escrows without one
+    /// are left alone.
+    pub fn archive_settled(env: Env, older_than_ledger: u32, limit: u32) -> Result<u32, Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
 
-        let mut escrow: Escrow = env
+        let index: Vec<u64> = env
             .storage()
             .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
-
-        if escrow.status != EscrowStatus::Locked && escrow.status != EscrowStatus::PartiallyRefunded
-        {
-            return Err(Error::FundsNotLocked);
-        }
-
-        // GUARD 1: Block refund if there is a pending claim (Issue #391 fix)
-        if env
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+        let mut kept = Vec::new(&env);
+        let mut archived_ids: Vec<u64> = env
             .storage()
             .persistent()
-            .has(&DataKey::PendingClaim(bounty_id))
-        {
-            let claim: ClaimRecord = env
+            .get(&DataKey::ArchivedIndex)
+            .unwrap_or(Vec::new(&env));
+        let mut archived = Self::get_archived_stats(&env);
+        let mut count = 0u32;
+
+        for bounty_id in index.iter() {
+            if count >= limit || !Self::is_archivable(&env, bounty_id, older_than_ledger) {
+                kept.push_back(bounty_id);
+                continue;
+            }
+            let escrow: Escrow = env
                 .storage()
                 .persistent()
-                .get(&DataKey::PendingClaim(bounty_id))
+                .get(&DataKey::Escrow(bounty_id))
                 .unwrap();
-            if !claim.claimed {
-                return Err(Error::ClaimPending);
+            let refunded: i128 = escrow.refund_history.iter().map(|r| r.amount).sum();
+            archived.total_released += escrow.amount
+                - escrow.remaining_amount
+                - refunded
+                - Self::withheld_amount(&env, bounty_id);
+            archived.total_refunded += refunded;
+            if escrow.status == EscrowStatus::Released {
+                archived.count_released += 1;
+            } else {
+                archived.count_refunded += 1;
             }
-        }
+            Self::shift_status_count(&env, Some(escrow.status), None);
 
-        let now = env.ledger().timestamp();
-        let approval_key = DataKey::RefundApproval(bounty_id);
-        let approval: Option<RefundApproval> = env.storage().persistent().get(&approval_key);
+            let key = DataKey::DepositorIndex(escrow.depositor);
+            let mut depositor_index: Vec<u64> = env
+                .storage()
+                .persistent()
+                .get(&key)
+                .unwrap_or(Vec::new(&env));
+            if let Some(pos) = depositor_index.first_index_of(bounty_id) {
+                depositor_index.remove(pos);
+                env.storage().persistent().set(&key, &depositor_index);
+            }
+            archived_ids.push_back(bounty_id);
+            count += 1;
+        }
 
-        // Refund is allowed if:
-        // 1. Deadline has passed (returns full amount to depositor)
-        // 2. An administrative approval exists (can be early, partial, and to custom recipient)
-        if now < escrow.deadline && approval.is_none() {
-            return Err(Error::DeadlineNotPassed);
+        if count > 0 {
+            env.storage().persistent().set(&DataKey::EscrowIndex, &kept);
+            env.storage()
+                .persistent()
+                .set(&DataKey::ArchivedIndex, &archived_ids);
+            env.storage()
+                .instance()
+                .set(&DataKey::ArchivedStats, &archived);
         }
+        Ok(count)
+    }
 
-        let (refund_amount, refund_to, is_full) = if let Some(app) = approval.clone() {
-            let full = app.mode == RefundMode::Full || app.amount >= escrow.remaining_amount;
-            (app.amount, app.recipient, full)
-        } else {
-            // Standard refund after deadline
-            (escrow.remaining_amount, escrow.depositor.clone(), true)
-        };
+    /// Whether `bounty_id` is Released or Refunded and its last history step
+    /// (the settlement) happened before `older_than_ledger`.
+    fn is_archivable(env: &Env, bounty_id: u64, older_than_ledger: u32) -> bool {
+        let settled = env
+            .storage()
+            .persistent()
+            .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            .is_some_and(|escrow| {
+                matches!(
+                    escrow.status,
+                    EscrowStatus::Released | EscrowStatus::Refunded
+                )
+            });
+        if !settled {
+            return false;
+        }
+        env.storage()
+            .persistent()
+            .get::<DataKey, Vec<EscrowHistoryEntry>>(&DataKey::EscrowHistory(bounty_id))
+            .and_then(|history| history.last())
+            .is_some_and(|entry| entry.ledger < older_than_ledger)
+    }
 
-        if refund_amount <= 0 || refund_amount > escrow.remaining_amount {
-            return Err(Error::InvalidAmount);
+    /// Publish the current `get_aggregate_stats` as a single `StatsSnapshot`
+    /// event, so monitoring can consume periodic snapshots (e.g. from an
+    /// operator cron) instead of polling. Callable by anyone; it only reads
+    /// state. Returns the stats; under `EventLevel::Minimal` the event is
+    /// not published, like the other monitoring events.
+    pub fn publish_stats_snapshot(env: Env) -> AggregateStats {
+        let stats = Self::get_aggregate_stats(env.clone());
+        if events::verbose(&env) {
+            events::emit_stats_snapshot(
+                &env,
+                events::StatsSnapshot {
+                    seq: events::next_event_seq(&env),
+                    version: EVENT_VERSION_V2,
+                    stats: stats.clone(),
+                    timestamp: env.ledger().timestamp(),
+                },
+            );
         }
+        stats
+    }
 
-        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let client = token::Client::new(&env, &token_addr);
+    /// Start bucketing lock/release/refund volume into `bucket_size`-second
+    /// windows (admin only); 0, the default, stops recording. Buckets
+    /// written under a previous size keep their old boundaries.
+    pub fn set_bucket_size(env: Env, bucket_size: u64) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::StatsBucketSize, &bucket_size);
+        Ok(())
+    }
 
-        // Transfer the calculated refund amount to the designated recipient
-        client.transfer(&env.current_contract_address(), &refund_to, &refund_amount);
+    /// View: the stats bucket size in seconds (0 when bucketing is off).
+    pub fn get_bucket_size(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::StatsBucketSize)
+            .unwrap_or(0)
+    }
 
-        invariants::assert_escrow(&env, &escrow);
-        // Update escrow state: subtract the amount exactly refunded
-        escrow.remaining_amount -= refund_amount;
-        if is_full || escrow.remaining_amount == 0 {
-            escrow.status = EscrowStatus::Refunded;
-        } else {
-            escrow.status = EscrowStatus::PartiallyRefunded;
+    /// View: volume for the bucket containing `bucket_start`. Any timestamp
+    /// inside the window works; the result carries the aligned start.
+    pub fn get_stats_bucket(env: Env, bucket_start: u64) -> BucketStats {
+        let size = Self::get_bucket_size(env.clone());
+        if size == 0 {
+            return BucketStats {
+                bucket_start,
+                ..Default::default()
+            };
         }
-
-        // Add to refund history
-        escrow.refund_history.push_back(RefundRecord {
-            amount: refund_amount,
-            recipient: refund_to.clone(),
-            timestamp: now,
-            mode: if is_full {
-                RefundMode::Full
-            } else {
-                RefundMode::Partial
-            },
-        });
-
-        // Save updated escrow
+        let start = bucket_start - bucket_start % size;
         env.storage()
             .persistent()
-            .set(&DataKey::Escrow(bounty_id), &escrow);
+            .get(&DataKey::StatsBucket(start))
+            .unwrap_or(BucketStats {
+                bucket_start: start,
+                ..Default::default()
+            })
+    }
 
-        // Remove approval after successful execution
-        if approval.is_some() {
-            env.storage().persistent().remove(&approval_key);
+    /// View: the last `count` buckets up to and including the current one,
+    /// oldest first, with empty windows included as zeroes. `count` is
+    /// capped at `MAX_RECENT_BUCKETS`.
+    pub fn list_recent_buckets(env: Env, count: u32) -> Vec<BucketStats> {
+        let mut buckets = Vec::new(&env);
+        let size = Self::get_bucket_size(env.clone());
+        if size == 0 {
+            return buckets;
         }
-
-        emit_funds_refunded(
-            &env,
-            FundsRefunded {
-                version: EVENT_VERSION_V2,
-                bounty_id,
-                amount: refund_amount,
-                refund_to: refund_to.clone(),
-                timestamp: now,
-            },
-        );
-
-        Ok(())
+        let now = env.ledger().timestamp();
+        let mut start = now - now % size;
+        for _ in 0..count.min(MAX_RECENT_BUCKETS) {
+            buckets.push_front(Self::get_stats_bucket(env.clone(), start));
+            if start < size {
+                break;
+            }
+            start -= size;
+        }
+        buckets
     }
 
-    /// view function to get escrow info
-    pub fn get_escrow_info(env: Env, bounty_id: u64) -> Result<Escrow, Error> {
-        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
-            return Err(Error::BountyNotFound);
+    /// Add volume to the current stats bucket, if bucketing is enabled.
+    fn record_bucket_volume(env: &Env, locked: i128, released: i128, refunded: i128) {
+        let size = Self::get_bucket_size(env.clone());
+        if size == 0 {
+            return;
         }
-        Ok(env
-            .storage()
+        let now = env.ledger().timestamp();
+        let mut bucket = Self::get_stats_bucket(env.clone(), now);
+        bucket.locked += locked;
+        bucket.released += released;
+        bucket.refunded += refunded;
+        env.storage()
             .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap())
+            .set(&DataKey::StatsBucket(bucket.bucket_start), &bucket);
     }
 
-    /// view function to get contract balance of the token
-    pub fn get_balance(env: Env) -> Result<i128, Error> {
-        if !env.storage().instance().has(&DataKey::Token) {
-            return Err(Error::NotInitialized);
-        }
-        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let client = token::Client::new(&env, &token_addr);
-        Ok(client.balance(&env.current_contract_address()))
+    /// View: lock/release/refund totals for escrows created by `depositor`.
+    pub fn get_depositor_stats(env: Env, depositor: Address) -> DepositorStats {
+        env.storage()
+            .persistent()
+            .get(&DataKey::DepositorStats(depositor))
+            .unwrap_or_default()
     }
 
-    /// Query escrows with filtering and pagination
-    /// Pass 0 for min values and i128::MAX/u64::MAX for max values to disable those filters
-    pub fn query_escrows_by_status(
-        env: Env,
-        status: EscrowStatus,
-        offset: u32,
-        limit: u32,
-    ) -> Vec<EscrowWithId> {
+    /// View: min / max / average escrow size over at most `max_scan` escrows.
+    pub fn get_extended_stats(env: Env, max_scan: u32) -> ExtendedStats {
         let index: Vec<u64> = env
             .storage()
             .persistent()
             .get(&DataKey::EscrowIndex)
             .unwrap_or(Vec::new(&env));
-        let mut results = Vec::new(&env);
-        let mut count = 0u32;
-        let mut skipped = 0u32;
-
-        for i in 0..index.len() {
-            if count >= limit {
-                break;
-            }
+        let mut stats = ExtendedStats {
+            scanned: 0,
+            complete: index.len() <= max_scan,
+            total_amount: 0,
+            min_amount: None,
+            max_amount: None,
+            average_amount: 0,
+        };
 
+        for i in 0..index.len().min(max_scan) {
             let bounty_id = index.get(i).unwrap();
             if let Some(escrow) = env
                 .storage()
                 .persistent()
                 .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
             {
-                if escrow.status == status {
-                    if skipped < offset {
-                        skipped += 1;
-                        continue;
-                    }
-                    results.push_back(EscrowWithId { bounty_id, escrow });
-                    count += 1;
-                }
+                stats.scanned += 1;
+                stats.total_amount += escrow.amount;
+                stats.min_amount = Some(
+                    stats
+                        .min_amount
+                        .map_or(escrow.amount, |m| m.min(escrow.amount)),
+                );
+                stats.max_amount = Some(
+                    stats
+                        .max_amount
+                        .map_or(escrow.amount, |m| m.max(escrow.amount)),
+                );
             }
         }
-        results
+        if stats.scanned > 0 {
+            stats.average_amount = stats.total_amount / stats.scanned as i128;
+        }
+        stats
+    }
+
+    fn record_depositor_lock(env: &Env, depositor: &Address, amount: i128, new_escrow: bool) {
+        let key = DataKey::DepositorStats(depositor.clone());
+        let mut stats: DepositorStats = env.storage().persistent().get(&key).unwrap_or_default();
+        stats.total_locked += amount;
+        if new_escrow {
+            stats.escrow_count += 1;
+        }
+        env.storage().persistent().set(&key, &stats);
+    }
+
+    fn record_depositor_outflow(env: &Env, depositor: &Address, released: i128, refunded: i128) {
+        if released == 0 && refunded == 0 {
+            return;
+        }
+        let key = DataKey::DepositorStats(depositor.clone());
+        let mut stats: DepositorStats = env.storage().persistent().get(&key).unwrap_or_default();
+        stats.total_locked -= released + refunded;
+        stats.total_released += released;
+        stats.total_refunded += refunded;
+        env.storage().persistent().set(&key, &stats);
+        Self::record_bucket_volume(env, 0, released, refunded);
+    }
+
+    /// Get total count of escrows
+    pub fn get_escrow_count(env: Env) -> u32 {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+        let archived = Self::get_archived_stats(&env);
+        index.len() + archived.count_released + archived.count_refunded
+    }
+
+    /// Cap the total number of escrows this contract will ever hold (admin
+    /// only). The cap is checked against `get_escrow_count`, which includes
+    /// settled and archived escrows, so it bounds lifetime storage growth
+    /// rather than the live set. 0, the default, means unlimited.
+    pub fn set_max_escrows(env: Env, max_escrows: u32) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&ConfigKey::MaxEscrows, &max_escrows);
+        Ok(())
+    }
+
+    /// View: the escrow cap (0 when unlimited).
+    pub fn get_max_escrows(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&ConfigKey::MaxEscrows)
+            .unwrap_or(0)
+    }
+
+    /// Fail with `MaxEscrowsReached` if creating `new_escrows` more would
+    /// exceed the configured cap.
+    fn ensure_escrow_capacity(env: &Env, new_escrows: u32) -> Result<(), Error> {
+        let max_escrows = Self::get_max_escrows(env.clone());
+        if max_escrows == 0 {
+            return Ok(());
+        }
+        let count = Self::get_escrow_count(env.clone());
+        if count.saturating_add(new_escrows) > max_escrows {
+            return Err(Error::MaxEscrowsReached);
+        }
+        Ok(())
+    }
+
+    /// Set the minimum and maximum allowed lock amount (admin only).
+    ///
+    /// Once set, any call to lock_funds with an amount outside [min_amount, max_amount]
+    /// will be rejected with AmountBelowMinimum or AmountAboveMaximum respectively.
+    /// The policy can be updated at any time by the admin; new limits take effect
+    /// immediately for subsequent lock_funds calls.
+    ///
+    /// Passing min_amount == max_amount restricts locking to a single exact value.
+    /// min_amount must not exceed max_amount — the call panics if this invariant
+    /// is violated.
+    pub fn set_amount_policy(
+        env: Env,
+        caller: Address,
+        min_amount: i128,
+        max_amount: i128,
+    ) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if caller != admin {
+            return Err(Error::Unauthorized);
+        }
+        admin.require_auth();
+
+        if min_amount > max_amount {
+            return Err(Error::InvalidAmount);
+        }
+
+        // Persist the policy so lock_funds can enforce it on every subsequent call.
+        env.storage()
+            .instance()
+            .set(&DataKey::AmountPolicy, &(min_amount, max_amount));
+
+        Ok(())
     }
 
-    /// Query escrows with amount range filtering
-    pub fn query_escrows_by_amount(
-        env: Env,
-        min_amount: i128,
-        max_amount: i128,
-        offset: u32,
-        limit: u32,
-    ) -> Vec<EscrowWithId> {
-        let index: Vec<u64> = env
+    /// Configure lock amount bounds (admin only). A `max` of zero means no
+    /// upper bound.
+    ///
+    /// Limits apply to new locks only; existing escrows are never re-validated.
+    pub fn set_amount_limits(env: Env, min: i128, max: i128) -> Result<(), Error> {
+        let admin: Address = env
             .storage()
-            .persistent()
-            .get(&DataKey::EscrowIndex)
-            .unwrap_or(Vec::new(&env));
-        let mut results = Vec::new(&env);
-        let mut count = 0u32;
-        let mut skipped = 0u32;
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
 
-        for i in 0..index.len() {
-            if count >= limit {
-                break;
-            }
+        if min < 0 || max < 0 || (max != 0 && min > max) {
+            return Err(Error::InvalidAmount);
+        }
+        let upper = if max == 0 { i128::MAX } else { max };
+        env.storage()
+            .instance()
+            .set(&DataKey::AmountPolicy, &(min, upper));
+        Ok(())
+    }
 
-            let bounty_id = index.get(i).unwrap();
-            if let Some(escrow) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
-            {
-                if escrow.amount >= min_amount && escrow.amount <= max_amount {
-                    if skipped < offset {
-                        skipped += 1;
-                        continue;
-                    }
-                    results.push_back(EscrowWithId { bounty_id, escrow });
-                    count += 1;
-                }
-            }
+    /// View: the `(min, max)` lock amount bounds, with `max == 0` meaning
+    /// unbounded. Returns `(0, 0)` when no limits are configured.
+    pub fn get_amount_limits(env: Env) -> (i128, i128) {
+        match env
+            .storage()
+            .instance()
+            .get::<DataKey, (i128, i128)>(&DataKey::AmountPolicy)
+        {
+            Some((min, i128::MAX)) => (min, 0),
+            Some(limits) => limits,
+            None => (0, 0),
         }
-        results
     }
 
-    /// Query escrows with deadline range filtering
-    pub fn query_escrows_by_deadline(
+    /// Get escrow IDs by status
+    pub fn get_escrow_ids_by_status(
         env: Env,
-        min_deadline: u64,
-        max_deadline: u64,
+        status: EscrowStatus,
         offset: u32,
         limit: u32,
-    ) -> Vec<EscrowWithId> {
+    ) -> Vec<u64> {
         let index: Vec<u64> = env
             .storage()
             .persistent()
@@ -1645,19 +8068,18 @@ impl BountyEscrowContract {
             if count >= limit {
                 break;
             }
-
             let bounty_id = index.get(i).unwrap();
             if let Some(escrow) = env
                 .storage()
                 .persistent()
                 .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
             {
-                if escrow.deadline >= min_deadline && escrow.deadline <= max_deadline {
+                if escrow.status == status {
                     if skipped < offset {
                         skipped += 1;
                         continue;
                     }
-                    results.push_back(EscrowWithId { bounty_id, escrow });
+                    results.push_back(bounty_id);
                     count += 1;
                 }
             }
@@ -1665,13 +8087,14 @@ impl BountyEscrowContract {
         results
     }
 
-    /// Query escrows by depositor
-    pub fn query_escrows_by_depositor(
+    /// Ids-only variant of `query_escrows_by_depositor`, with the same
+    /// membership, order and pagination.
+    pub fn get_escrow_ids_by_depositor(
         env: Env,
         depositor: Address,
         offset: u32,
         limit: u32,
-    ) -> Vec<EscrowWithId> {
+    ) -> Vec<u64> {
         let index: Vec<u64> = env
             .storage()
             .persistent()
@@ -1679,217 +8102,457 @@ impl BountyEscrowContract {
             .unwrap_or(Vec::new(&env));
         let mut results = Vec::new(&env);
         let start = offset.min(index.len());
-        let end = (offset + limit).min(index.len());
+        let end = offset.saturating_add(limit).min(index.len());
 
         for i in start..end {
             let bounty_id = index.get(i).unwrap();
-            if let Some(escrow) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
-            {
-                results.push_back(EscrowWithId { bounty_id, escrow });
+            if env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+                results.push_back(bounty_id);
             }
         }
         results
     }
 
-    /// Get aggregate statistics
-    pub fn get_aggregate_stats(env: Env) -> AggregateStats {
-        let index: Vec<u64> = env
+    pub fn set_anti_abuse_admin(env: Env, admin: Address) -> Result<(), Error> {
+        let current: Address = env
             .storage()
-            .persistent()
-            .get(&DataKey::EscrowIndex)
-            .unwrap_or(Vec::new(&env));
-        let mut stats = AggregateStats {
-            total_locked: 0,
-            total_released: 0,
-            total_refunded: 0,
-            count_locked: 0,
-            count_released: 0,
-            count_refunded: 0,
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        current.require_auth();
+        anti_abuse::set_admin(&env, admin);
+        Ok(())
+    }
+
+    pub fn get_anti_abuse_admin(env: Env) -> Option<Address> {
+        anti_abuse::get_admin(&env)
+    }
+
+    pub fn set_whitelist(
+        env: Env,
+        whitelisted_address: Address,
+        whitelisted: bool,
+    ) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        anti_abuse::set_whitelist(&env, whitelisted_address, whitelisted);
+        Ok(())
+    }
+
+    /// Limit each depositor to `max_locks` locks per `window_ledgers` ledgers
+    /// (admin only). Passing 0 for either value removes the limit, which is
+    /// the default. Whitelisted addresses are exempt.
+    pub fn set_rate_limit(env: Env, max_locks: u32, window_ledgers: u32) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        let limit = if max_locks == 0 || window_ledgers == 0 {
+            None
+        } else {
+            Some(anti_abuse::LockRateLimit {
+                max_locks,
+                window_ledgers,
+            })
         };
+        anti_abuse::set_lock_rate_limit(&env, limit);
+        Ok(())
+    }
 
-        for i in 0..index.len() {
-            let bounty_id = index.get(i).unwrap();
-            if let Some(escrow) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
-            {
-                match escrow.status {
-                    EscrowStatus::Locked => {
-                        stats.total_locked += escrow.amount;
-                        stats.count_locked += 1;
-                    }
-                    EscrowStatus::Released => {
-                        stats.total_released += escrow.amount;
-                        stats.count_released += 1;
-                    }
-                    EscrowStatus::Refunded | EscrowStatus::PartiallyRefunded => {
-                        stats.total_refunded += escrow.amount;
-                        stats.count_refunded += 1;
-                    }
-                }
+    /// View: the lock rate limit as `(max_locks, window_ledgers)`; `(0, 0)`
+    /// when unlimited.
+    pub fn get_rate_limit(env: Env) -> (u32, u32) {
+        anti_abuse::get_lock_rate_limit(&env)
+            .map(|limit| (limit.max_locks, limit.window_ledgers))
+            .unwrap_or((0, 0))
+    }
+
+    /// Retrieves the refund history for a specific bounty.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `bounty_id` - The bounty to query
+    ///
+    /// # Returns
+    /// * `Ok(Vec<RefundRecord>)` - The refund history
+    /// * `Err(Error::BountyNotFound)` - Bounty doesn't exist
+    pub fn get_refund_history(env: Env, bounty_id: u64) -> Result<Vec<RefundRecord>, Error> {
+        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyNotFound);
+        }
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .unwrap();
+        Ok(escrow.refund_history)
+    }
+
+    /// Auto-pause the contract when more than `max_refunds` refunds land
+    /// within `window` seconds (admin only), as a brake on a bug or exploit
+    /// draining escrows. The admin investigates and calls `unpause`.
+    /// `max_refunds` of 0, the default, disables the check.
+    pub fn set_anomaly_threshold(env: Env, max_refunds: u32, window: u64) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&ConfigKey::AnomalyThreshold, &(max_refunds, window));
+        env.storage().instance().remove(&ConfigKey::RecentRefunds);
+        Ok(())
+    }
+
+    /// View: `(max_refunds, window)` set by `set_anomaly_threshold`.
+    pub fn get_anomaly_threshold(env: Env) -> (u32, u64) {
+        env.storage()
+            .instance()
+            .get(&ConfigKey::AnomalyThreshold)
+            .unwrap_or((0, 0))
+    }
+
+    /// Count a refund towards the anomaly threshold and pause the contract
+    /// once it is exceeded.
+    fn track_refund_anomaly(env: &Env) {
+        let (max_refunds, window) = Self::get_anomaly_threshold(env.clone());
+        if max_refunds == 0 {
+            return;
+        }
+        let now = env.ledger().timestamp();
+        let recent: Vec<u64> = env
+            .storage()
+            .instance()
+            .get(&ConfigKey::RecentRefunds)
+            .unwrap_or(Vec::new(env));
+        let mut window_refunds = Vec::new(env);
+        for ts in recent.iter() {
+            if now.saturating_sub(ts) < window {
+                window_refunds.push_back(ts);
             }
         }
-        stats
+        window_refunds.push_back(now);
+
+        if window_refunds.len() <= max_refunds {
+            env.storage()
+                .instance()
+                .set(&ConfigKey::RecentRefunds, &window_refunds);
+            return;
+        }
+
+        env.storage().instance().remove(&ConfigKey::RecentRefunds);
+        env.storage().instance().set(&DataKey::Paused, &true);
+        events::emit_contract_paused(
+            env,
+            events::ContractPauseToggled {
+                seq: events::next_event_seq(env),
+                caller: env.current_contract_address(),
+                timestamp: now,
+            },
+        );
+        events::emit_anomaly_detected(
+            env,
+            events::AnomalyDetected {
+                version: EVENT_VERSION_V2,
+                seq: events::next_event_seq(env),
+                refund_count: window_refunds.len(),
+                window,
+                timestamp: now,
+            },
+        );
     }
 
-    /// Get total count of escrows
-    pub fn get_escrow_count(env: Env) -> u32 {
-        let index: Vec<u64> = env
+    /// Append a refund to the global log and the depositor's refund index.
+    fn record_refund(env: &Env, entry: &RefundHistoryEntry) {
+        let seq: u64 = env
             .storage()
             .persistent()
-            .get(&DataKey::EscrowIndex)
-            .unwrap_or(Vec::new(&env));
-        index.len()
+            .get(&DataKey::RefundLogCount)
+            .unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&DataKey::RefundLog(seq), entry);
+        env.storage()
+            .persistent()
+            .set(&DataKey::RefundLogCount, &(seq + 1));
+        let total = Self::refund_log_total(env, seq) + entry.amount;
+        env.storage()
+            .persistent()
+            .set(&RecordKey::RefundLogTotal(seq + 1), &total);
+
+        let key = DataKey::DepositorRefunds(entry.depositor.clone());
+        let mut seqs: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        seqs.push_back(seq);
+        env.storage().persistent().set(&key, &seqs);
+
+        Self::track_refund_anomaly(env);
     }
 
-    /// Set the minimum and maximum allowed lock amount (admin only).
-    ///
-    /// Once set, any call to lock_funds with an amount outside [min_amount, max_amount]
-    /// will be rejected with AmountBelowMinimum or AmountAboveMaximum respectively.
-    /// The policy can be updated at any time by the admin; new limits take effect
-    /// immediately for subsequent lock_funds calls.
-    ///
-    /// Passing min_amount == max_amount restricts locking to a single exact value.
-    /// min_amount must not exceed max_amount — the call panics if this invariant
-    /// is violated.
-    pub fn set_amount_policy(
-        env: Env,
-        caller: Address,
-        min_amount: i128,
-        max_amount: i128,
-    ) -> Result<(), Error> {
-        if !env.storage().instance().has(&DataKey::Admin) {
-            return Err(Error::NotInitialized);
-        }
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if caller != admin {
-            return Err(Error::Unauthorized);
+    /// Net amount refunded by the first `count` entries of the refund log.
+    fn refund_log_total(env: &Env, count: u64) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&RecordKey::RefundLogTotal(count))
+            .unwrap_or(0)
+    }
+
+    /// Number of refund log entries with a timestamp at or before `ts`.
+    /// The log is appended in ledger order, so this is a binary search.
+    fn refunds_up_to(env: &Env, ts: u64) -> u64 {
+        let (mut lo, mut hi) = (
+            0u64,
+            env.storage()
+                .persistent()
+                .get(&DataKey::RefundLogCount)
+                .unwrap_or(0u64),
+        );
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let entry: RefundHistoryEntry = env
+                .storage()
+                .persistent()
+                .get(&DataKey::RefundLog(mid))
+                .unwrap();
+            if entry.timestamp <= ts {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
         }
-        admin.require_auth();
+        lo
+    }
 
-        if min_amount > max_amount {
-            panic!("invalid policy: min_amount cannot exceed max_amount");
+    /// View: net amount refunded with a timestamp in `[from_ts, to_ts]`.
+    ///
+    /// Windowed counterpart of the lifetime `total_refunded` stat. Running
+    /// totals are kept per refund log entry, so this costs a binary search
+    /// over the log rather than a scan of the window.
+    pub fn sum_refunds_between(env: Env, from_ts: u64, to_ts: u64) -> i128 {
+        if from_ts > to_ts {
+            return 0;
         }
+        let end = Self::refunds_up_to(&env, to_ts);
+        let start = match from_ts.checked_sub(1) {
+            Some(before) => Self::refunds_up_to(&env, before),
+            None => 0,
+        };
+        Self::refund_log_total(&env, end) - Self::refund_log_total(&env, start)
+    }
 
-        // Persist the policy so lock_funds can enforce it on every subsequent call.
+    /// Append a payout to the global release log and the contributor's
+    /// release index, then tell the release notifier about it. Call only
+    /// after the escrow's new state is stored, so nothing is left to write
+    /// once control passes to the notifier.
+    fn record_release(env: &Env, bounty_id: u64, contributor: &Address, amount: i128) {
+        let seq: u64 = env
+            .storage()
+            .persistent()
+            .get(&RecordKey::ReleaseLogCount)
+            .unwrap_or(0);
+        env.storage().persistent().set(
+            &RecordKey::ReleaseLog(seq),
+            &ReleaseHistoryEntry {
+                bounty_id,
+                contributor: contributor.clone(),
+                amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
         env.storage()
-            .instance()
-            .set(&DataKey::AmountPolicy, &(min_amount, max_amount));
+            .persistent()
+            .set(&RecordKey::ReleaseLogCount, &(seq + 1));
 
-        Ok(())
+        let key = RecordKey::ContributorReleases(contributor.clone());
+        let mut seqs: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        seqs.push_back(seq);
+        env.storage().persistent().set(&key, &seqs);
+
+        Self::notify_release(env, bounty_id, contributor, amount);
     }
 
-    /// Get escrow IDs by status
-    pub fn get_escrow_ids_by_status(
+    /// Page through every payout made by the contract, oldest first.
+    ///
+    /// An `offset` past the end yields an empty vector.
+    pub fn get_release_history(env: Env, offset: u32, limit: u32) -> Vec<ReleaseHistoryEntry> {
+        let count: u64 = env
+            .storage()
+            .persistent()
+            .get(&RecordKey::ReleaseLogCount)
+            .unwrap_or(0);
+        let start = (offset as u64).min(count);
+        let end = (offset as u64).saturating_add(limit as u64).min(count);
+        let mut page = Vec::new(&env);
+        for seq in start..end {
+            if let Some(entry) = env.storage().persistent().get(&RecordKey::ReleaseLog(seq)) {
+                page.push_back(entry);
+            }
+        }
+        page
+    }
+
+    /// Page through the payouts made to `contributor`, oldest first.
+    pub fn get_contributor_release_history(
         env: Env,
-        status: EscrowStatus,
+        contributor: Address,
         offset: u32,
         limit: u32,
-    ) -> Vec<u64> {
-        let index: Vec<u64> = env
+    ) -> Vec<ReleaseHistoryEntry> {
+        let seqs: Vec<u64> = env
             .storage()
             .persistent()
-            .get(&DataKey::EscrowIndex)
+            .get(&RecordKey::ContributorReleases(contributor))
             .unwrap_or(Vec::new(&env));
-        let mut results = Vec::new(&env);
-        let mut count = 0u32;
-        let mut skipped = 0u32;
-
-        for i in 0..index.len() {
-            if count >= limit {
-                break;
-            }
-            let bounty_id = index.get(i).unwrap();
-            if let Some(escrow) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
-            {
-                if escrow.status == status {
-                    if skipped < offset {
-                        skipped += 1;
-                        continue;
-                    }
-                    results.push_back(bounty_id);
-                    count += 1;
-                }
+        let start = offset.min(seqs.len());
+        let end = offset.saturating_add(limit).min(seqs.len());
+        let mut page = Vec::new(&env);
+        for i in start..end {
+            let seq = seqs.get(i).unwrap();
+            if let Some(entry) = env.storage().persistent().get(&RecordKey::ReleaseLog(seq)) {
+                page.push_back(entry);
             }
         }
-        results
+        page
     }
 
-    pub fn set_anti_abuse_admin(env: Env, admin: Address) -> Result<(), Error> {
-        let current: Address = env
+    /// Page through every refund made by the contract, oldest first.
+    ///
+    /// An `offset` past the end yields an empty vector.
+    pub fn get_refund_history_paged(env: Env, offset: u32, limit: u32) -> Vec<RefundHistoryEntry> {
+        let count: u64 = env
             .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(Error::NotInitialized)?;
-        current.require_auth();
-        anti_abuse::set_admin(&env, admin);
-        Ok(())
-    }
-
-    pub fn get_anti_abuse_admin(env: Env) -> Option<Address> {
-        anti_abuse::get_admin(&env)
+            .persistent()
+            .get(&DataKey::RefundLogCount)
+            .unwrap_or(0);
+        let start = (offset as u64).min(count);
+        let end = (offset as u64).saturating_add(limit as u64).min(count);
+        let mut page = Vec::new(&env);
+        for seq in start..end {
+            if let Some(entry) = env.storage().persistent().get(&DataKey::RefundLog(seq)) {
+                page.push_back(entry);
+            }
+        }
+        page
     }
 
-    pub fn set_whitelist(
+    /// Page through the refunds of escrows created by `depositor`, oldest first.
+    ///
+    /// An `offset` past the end yields an empty vector.
+    pub fn get_refund_history_by_depositor(
         env: Env,
-        whitelisted_address: Address,
-        whitelisted: bool,
-    ) -> Result<(), Error> {
-        let admin: Address = env
+        depositor: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<RefundHistoryEntry> {
+        let seqs: Vec<u64> = env
             .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(Error::NotInitialized)?;
-        admin.require_auth();
-        anti_abuse::set_whitelist(&env, whitelisted_address, whitelisted);
-        Ok(())
+            .persistent()
+            .get(&DataKey::DepositorRefunds(depositor))
+            .unwrap_or(Vec::new(&env));
+        let start = offset.min(seqs.len());
+        let end = offset.saturating_add(limit).min(seqs.len());
+        let mut page = Vec::new(&env);
+        for i in start..end {
+            let seq = seqs.get(i).unwrap();
+            if let Some(entry) = env.storage().persistent().get(&DataKey::RefundLog(seq)) {
+                page.push_back(entry);
+            }
+        }
+        page
     }
 
-    /// Retrieves the refund history for a specific bounty.
-    ///
-    /// # Arguments
-    /// * `env` - The contract environment
-    /// * `bounty_id` - The bounty to query
-    ///
-    /// # Returns
-    /// * `Ok(Vec<RefundRecord>)` - The refund history
-    /// * `Err(Error::BountyNotFound)` - Bounty doesn't exist
-    pub fn get_refund_history(env: Env, bounty_id: u64) -> Result<Vec<RefundRecord>, Error> {
-        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
-            return Err(Error::BountyNotFound);
-        }
+    /// Explain whether `bounty_id` can be refunded right now, and if not, why.
+    pub fn get_refund_eligibility_details(
+        env: Env,
+        bounty_id: u64,
+    ) -> Result<RefundEligibility, Error> {
         let escrow: Escrow = env
             .storage()
             .persistent()
             .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
-        Ok(escrow.refund_history)
+            .ok_or(Error::BountyNotFound)?;
+        let has_approval = env
+            .storage()
+            .persistent()
+            .has(&DataKey::RefundApproval(bounty_id));
+        Ok(Self::refund_eligibility(
+            &env,
+            bounty_id,
+            &escrow,
+            has_approval,
+        ))
+    }
+
+    fn refund_eligibility(
+        env: &Env,
+        bounty_id: u64,
+        escrow: &Escrow,
+        has_approval: bool,
+    ) -> RefundEligibility {
+        let earliest_refund_time = Self::refund_opens_at(env, escrow);
+        let policy = Self::get_refund_policy(env.clone(), bounty_id);
+        let reason = match escrow.status {
+            EscrowStatus::Released | EscrowStatus::Refunded | EscrowStatus::Merged => {
+                RefundEligibilityReason::AlreadySettled
+            }
+            EscrowStatus::Disputed => RefundEligibilityReason::Disputed,
+            EscrowStatus::Claimable => RefundEligibilityReason::ClaimPending,
+            // An admin approval overrides both the deadline and the policy
+            _ if has_approval => RefundEligibilityReason::Eligible,
+            _ if policy == RefundPolicy::None => RefundEligibilityReason::NonRefundableMode,
+            _ if !Self::refund_time_reached(env, escrow) => RefundEligibilityReason::NotExpired,
+            _ => RefundEligibilityReason::Eligible,
+        };
+        // Without an approval only the policy's refundable share is reclaimable
+        let refundable = if has_approval {
+            escrow.remaining_amount
+        } else {
+            Self::refundable_amount(&policy, escrow.remaining_amount)
+        };
+        RefundEligibility {
+            eligible: reason == RefundEligibilityReason::Eligible,
+            reason,
+            earliest_refund_time,
+            refundable,
+        }
     }
 
-    /// Gets refund eligibility information for a bounty.
+    /// Gets refund eligibility information for a bounty. Prefer
+    /// `get_refund_eligibility_details`, which also reports why a refund is
+    /// unavailable; this tuple form is kept for existing integrations.
     ///
     /// # Arguments
     /// * `env` - The contract environment
     /// * `bounty_id` - The bounty to query
     ///
     /// # Returns
-    /// * `Ok((bool, bool, i128, Option<RefundApproval>))` - Tuple containing:
+    /// * `Ok((bool, bool, i128, Option<RefundApproval>, u64))` - Tuple containing:
     ///   - can_refund: Whether refund is possible
     ///   - deadline_passed: Whether the deadline has passed
-    ///   - remaining: Remaining amount in escrow
+    ///   - refundable: Amount the depositor can reclaim under the escrow's refund policy
     ///   - approval: Optional refund approval if exists
+    ///   - refundable_at: Timestamp from which a deadline refund is allowed
+    ///     (deadline plus the refund grace period)
     /// * `Err(Error::BountyNotFound)` - Bounty doesn't exist
     pub fn get_refund_eligibility(
         env: Env,
         bounty_id: u64,
-    ) -> Result<(bool, bool, i128, Option<RefundApproval>), Error> {
+    ) -> Result<(bool, bool, i128, Option<RefundApproval>, u64), Error> {
         if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
             return Err(Error::BountyNotFound);
         }
@@ -1900,7 +8563,7 @@ impl BountyEscrowContract {
             .unwrap();
 
         let now = env.ledger().timestamp();
-        let deadline_passed = now >= escrow.deadline;
+        let deadline_passed = escrow.deadline != NO_DEADLINE && now >= escrow.deadline;
 
         let approval = if env
             .storage()
@@ -1917,38 +8580,82 @@ impl BountyEscrowContract {
             None
         };
 
-        // can_refund is true if:
-        // 1. Status is Locked or PartiallyRefunded AND
-        // 2. (deadline has passed OR there's an approval)
-        let can_refund = (escrow.status == EscrowStatus::Locked
-            || escrow.status == EscrowStatus::PartiallyRefunded)
-            && (deadline_passed || approval.is_some());
-
+        let details = Self::refund_eligibility(&env, bounty_id, &escrow, approval.is_some());
         Ok((
-            can_refund,
+            details.eligible,
             deadline_passed,
-            escrow.remaining_amount,
+            details.refundable,
             approval,
+            details.earliest_refund_time,
         ))
     }
 
+    /// Query escrows that `get_refund_eligibility` reports as refundable at
+    /// the current ledger time, honouring each escrow's refund policy, the
+    /// refund grace period and admin approvals. `offset` and `limit` apply
+    /// to the eligible escrows.
+    pub fn query_refund_eligible(env: Env, offset: u32, limit: u32) -> Vec<EscrowWithId> {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+        let mut results = Vec::new(&env);
+        let mut skipped = 0u32;
+
+        for bounty_id in index.iter() {
+            if results.len() >= limit {
+                break;
+            }
+            let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            else {
+                continue;
+            };
+            let has_approval = env
+                .storage()
+                .persistent()
+                .has(&DataKey::RefundApproval(bounty_id));
+            if !Self::refund_eligibility(&env, bounty_id, &escrow, has_approval).eligible {
+                continue;
+            }
+            if skipped < offset {
+                skipped += 1;
+                continue;
+            }
+            results.push_back(EscrowWithId { bounty_id, escrow });
+        }
+        results
+    }
+
     /// Batch lock funds for multiple bounties in a single transaction.
     /// This improves gas efficiency by reducing transaction overhead.
     ///
     /// # Arguments
     /// * `items` - Vector of LockFundsItem containing bounty_id, depositor, amount, and deadline
+    /// * `token` - Token every item is locked in; `None` means the init token
     ///
     /// # Returns
     /// Number of successfully locked bounties
     ///
     /// # Errors
     /// * InvalidBatchSize - if batch size exceeds MAX_BATCH_SIZE or is zero
-    /// * BountyExists - if any bounty_id already exists
+    /// * DuplicateBountyId - if any bounty_id already exists or is repeated
     /// * NotInitialized - if contract is not initialized
+    /// * TokenNotAllowed - if `token` is neither the init token nor allowed
+    /// * RateLimited - if a depositor's items would exceed its lock rate limit
     ///
     /// # Note
     /// This operation is atomic - if any item fails, the entire transaction reverts.
-    pub fn batch_lock_funds(env: Env, items: Vec<LockFundsItem>) -> Result<u32, Error> {
+    pub fn batch_lock_funds(
+        env: Env,
+        items: Vec<LockFundsItem>,
+        token: Option<Address>,
+    ) -> Result<u32, Error> {
+        reentrancy_guard::enter(&env)?;
+        Self::ensure_not_paused(&env)?;
         if Self::check_paused(&env, symbol_short!("lock")) {
             return Err(Error::FundsPaused);
         }
@@ -1964,27 +8671,29 @@ impl BountyEscrowContract {
         if !env.storage().instance().has(&DataKey::Admin) {
             return Err(Error::NotInitialized);
         }
+        Self::ensure_escrow_capacity(&env, batch_size)?;
 
-        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_addr = Self::lock_token(&env, token)?;
         let client = token::Client::new(&env, &token_addr);
-        let contract_address = env.current_contract_address();
         let timestamp = env.ledger().timestamp();
 
         // Validate all items before processing (all-or-nothing approach)
+        let mut depositor_totals: Map<Address, i128> = Map::new(&env);
+        let mut depositor_counts: Map<Address, u32> = Map::new(&env);
         for item in items.iter() {
-            // Check if bounty already exists
-            if env
-                .storage()
-                .persistent()
-                .has(&DataKey::Escrow(item.bounty_id))
-            {
-                return Err(Error::BountyExists);
-            }
-
-            // Validate amount
+            // Unlike single locks, batches never create empty escrows
             if item.amount <= 0 {
                 return Err(Error::InvalidAmount);
             }
+            Self::validate_new_escrow(&env, item.bounty_id, item.amount, item.deadline)?;
+            let total = depositor_totals
+                .get(item.depositor.clone())
+                .unwrap_or(0)
+                .checked_add(item.amount)
+                .ok_or(Error::InvalidAmount)?;
+            depositor_totals.set(item.depositor.clone(), total);
+            let count = depositor_counts.get(item.depositor.clone()).unwrap_or(0);
+            depositor_counts.set(item.depositor.clone(), count + 1);
 
             // Check for duplicate bounty_ids in the batch
             let mut count = 0u32;
@@ -2015,11 +8724,24 @@ impl BountyEscrowContract {
             }
         }
 
+        // Each escrow counts against its depositor's lock rate limit
+        for (depositor, count) in depositor_counts.iter() {
+            anti_abuse::check_rate_limit(&env, depositor.clone());
+            if !anti_abuse::record_locks(&env, depositor, count) {
+                return Err(Error::RateLimited);
+            }
+        }
+
+        // Each depositor must cover its items across the whole batch
+        for (depositor, total) in depositor_totals.iter() {
+            Self::check_lock_funding(&env, &client, &depositor, total)?;
+        }
+
         // Process all items (atomic - all succeed or all fail)
         let mut locked_count = 0u32;
         for item in items.iter() {
             // Transfer funds from depositor to contract
-            client.transfer(&item.depositor, &contract_address, &item.amount);
+            Self::pull_lock_funds(&env, &client, &item.depositor, item.amount);
 
             // Create escrow record
             let escrow = Escrow {
@@ -2032,14 +8754,17 @@ impl BountyEscrowContract {
             };
 
             // Store escrow
-            env.storage()
-                .persistent()
-                .set(&DataKey::Escrow(item.bounty_id), &escrow);
+            Self::set_escrow_token(&env, item.bounty_id, &token_addr);
+            Self::store_escrow(&env, item.bounty_id, &escrow);
+            Self::index_new_escrow(&env, item.bounty_id, &item.depositor);
+            Self::record_depositor_lock(&env, &item.depositor, item.amount, true);
+            Self::record_bucket_volume(&env, item.amount, 0, 0);
 
             // Emit individual event for each locked bounty
             emit_funds_locked(
                 &env,
                 FundsLocked {
+                    seq: events::next_event_seq(&env),
                     version: EVENT_VERSION_V2,
                     bounty_id: item.bounty_id,
                     amount: item.amount,
@@ -2047,6 +8772,17 @@ impl BountyEscrowContract {
                     deadline: item.deadline,
                 },
             );
+            Self::record_history(&env, item.bounty_id, EscrowHistoryKind::Locked, item.amount);
+            Self::extend_new_escrow_ttl(
+                &env,
+                item.bounty_id,
+                Self::ttl_for_deadline(&env, item.deadline),
+            );
+            emit_escrow_locked(
+                &env,
+                Self::lifecycle_event(&env, item.bounty_id, &escrow, item.amount),
+            );
+            Self::emit_depositor_index(&env, item.bounty_id, &escrow);
 
             locked_count += 1;
         }
@@ -2055,15 +8791,140 @@ impl BountyEscrowContract {
         emit_batch_funds_locked(
             &env,
             BatchFundsLocked {
+                seq: events::next_event_seq(&env),
                 count: locked_count,
                 total_amount: items.iter().map(|i| i.amount).sum(),
                 timestamp,
             },
         );
 
+        reentrancy_guard::exit(&env);
         Ok(locked_count)
     }
 
+    /// Lock several bounties for one depositor, pulling the summed amount in a
+    /// single token transfer.
+    ///
+    /// # Arguments
+    /// * `depositor` - Address funding every entry
+    /// * `entries` - Vector of (bounty_id, amount, deadline) tuples
+    /// * `token` - Token every entry is locked in; `None` means the init token
+    ///
+    /// # Returns
+    /// Number of escrows created
+    ///
+    /// # Errors
+    /// * InvalidBatchSize - if batch size exceeds MAX_BATCH_SIZE or is zero
+    /// * DuplicateBountyId - if an id is already used or repeated
+    /// * InvalidAmount - if any amount is zero or negative
+    /// * InvalidDeadline - if any deadline is already in the past
+    /// * TokenNotAllowed - if `token` is neither the init token nor allowed
+    /// * RateLimited - if the entries would exceed the lock rate limit
+    ///
+    /// # Note
+    /// This operation is atomic - if any entry fails, the entire transaction reverts.
+    pub fn lock_funds_batch(
+        env: Env,
+        depositor: Address,
+        entries: Vec<(u64, i128, u64)>,
+        token: Option<Address>,
+    ) -> Result<u32, Error> {
+        reentrancy_guard::enter(&env)?;
+        Self::ensure_not_paused(&env)?;
+        if Self::check_paused(&env, symbol_short!("lock")) {
+            return Err(Error::FundsPaused);
+        }
+        let batch_size = entries.len();
+        if batch_size == 0 || batch_size > MAX_BATCH_SIZE {
+            return Err(Error::InvalidBatchSize);
+        }
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        Self::ensure_escrow_capacity(&env, batch_size)?;
+        let token_addr = Self::lock_token(&env, token)?;
+
+        depositor.require_auth();
+
+        let now = env.ledger().timestamp();
+
+        // Validate all entries before moving any tokens (all-or-nothing approach)
+        let mut total_amount: i128 = 0;
+        for (i, (bounty_id, amount, deadline)) in entries.iter().enumerate() {
+            for (other_id, _, _) in entries.iter().skip(i + 1) {
+                if other_id == bounty_id {
+                    return Err(Error::DuplicateBountyId);
+                }
+            }
+            if amount <= 0 {
+                return Err(Error::InvalidAmount);
+            }
+            Self::validate_new_escrow(&env, bounty_id, amount, deadline)?;
+            total_amount = total_amount
+                .checked_add(amount)
+                .ok_or(Error::InvalidAmount)?;
+        }
+
+        // Every escrow in the batch counts against the lock rate limit
+        anti_abuse::check_rate_limit(&env, depositor.clone());
+        if !anti_abuse::record_locks(&env, depositor.clone(), entries.len()) {
+            return Err(Error::RateLimited);
+        }
+
+        let client = token::Client::new(&env, &token_addr);
+        Self::check_lock_funding(&env, &client, &depositor, total_amount)?;
+        Self::pull_lock_funds(&env, &client, &depositor, total_amount);
+
+        for (bounty_id, amount, deadline) in entries.iter() {
+            let escrow = Escrow {
+                depositor: depositor.clone(),
+                amount,
+                status: EscrowStatus::Locked,
+                deadline,
+                refund_history: vec![&env],
+                remaining_amount: amount,
+            };
+            invariants::assert_escrow(&env, &escrow);
+            Self::set_escrow_token(&env, bounty_id, &token_addr);
+            Self::store_escrow(&env, bounty_id, &escrow);
+            Self::index_new_escrow(&env, bounty_id, &depositor);
+            Self::record_depositor_lock(&env, &depositor, amount, true);
+            Self::record_bucket_volume(&env, amount, 0, 0);
+
+            emit_funds_locked(
+                &env,
+                FundsLocked {
+                    seq: events::next_event_seq(&env),
+                    version: EVENT_VERSION_V2,
+                    bounty_id,
+                    amount,
+                    depositor: depositor.clone(),
+                    deadline,
+                },
+            );
+            Self::record_history(&env, bounty_id, EscrowHistoryKind::Locked, amount);
+            Self::extend_new_escrow_ttl(&env, bounty_id, Self::ttl_for_deadline(&env, deadline));
+            emit_escrow_locked(
+                &env,
+                Self::lifecycle_event(&env, bounty_id, &escrow, amount),
+            );
+            Self::emit_depositor_index(&env, bounty_id, &escrow);
+        }
+
+        emit_batch_funds_locked(
+            &env,
+            BatchFundsLocked {
+                seq: events::next_event_seq(&env),
+                count: batch_size,
+                total_amount,
+                timestamp: now,
+            },
+        );
+
+        reentrancy_guard::exit(&env);
+        Ok(batch_size)
+    }
+
     /// Batch release funds to multiple contributors in a single transaction.
     /// This improves gas efficiency by reducing transaction overhead.
     ///
@@ -2076,12 +8937,15 @@ impl BountyEscrowContract {
     /// # Errors
     /// * InvalidBatchSize - if batch size exceeds MAX_BATCH_SIZE or is zero
     /// * BountyNotFound - if any bounty_id doesn't exist
-    /// * FundsNotLocked - if any bounty is not in Locked status
+    /// * AlreadyReleased / AlreadyRefunded / FundsNotLocked - if any bounty
+    ///   is not in Locked status (see `ensure_status`)
     /// * Unauthorized - if caller is not admin
     ///
     /// # Note
     /// This operation is atomic - if any item fails, the entire transaction reverts.
     pub fn batch_release_funds(env: Env, items: Vec<ReleaseFundsItem>) -> Result<u32, Error> {
+        reentrancy_guard::enter(&env)?;
+        Self::ensure_not_paused(&env)?;
         if Self::check_paused(&env, symbol_short!("release")) {
             return Err(Error::FundsPaused);
         }
@@ -2101,8 +8965,6 @@ impl BountyEscrowContract {
         let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
         admin.require_auth();
 
-        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let client = token::Client::new(&env, &token_addr);
         let contract_address = env.current_contract_address();
         let timestamp = env.ledger().timestamp();
 
@@ -2125,9 +8987,12 @@ impl BountyEscrowContract {
                 .unwrap();
 
             // Check if funds are locked
-            if escrow.status != EscrowStatus::Locked {
-                return Err(Error::FundsNotLocked);
-            }
+            Self::ensure_status(&escrow, &[EscrowStatus::Locked])?;
+
+            Self::ensure_contributor_allowed(&env, item.bounty_id, &item.contributor)?;
+            Self::ensure_release_time_reached(&env, item.bounty_id)?;
+            Self::ensure_release_condition_met(&env, item.bounty_id)?;
+            Self::ensure_release_approved(&env, item.bounty_id)?;
 
             // Check for duplicate bounty_ids in the batch
             let mut count = 0u32;
@@ -2141,7 +9006,7 @@ impl BountyEscrowContract {
             }
 
             total_amount = total_amount
-                .checked_add(escrow.amount)
+                .checked_add(escrow.remaining_amount)
                 .ok_or(Error::InvalidAmount)?;
         }
 
@@ -2154,26 +9019,39 @@ impl BountyEscrowContract {
                 .get(&DataKey::Escrow(item.bounty_id))
                 .unwrap();
 
-            // Transfer funds to contributor
-            client.transfer(&contract_address, &item.contributor, &escrow.amount);
+            // Transfer the unreleased balance to contributor in the escrow's token
+            let gross = escrow.remaining_amount;
+            let token_addr = Self::escrow_token(&env, item.bounty_id);
+            let payout = gross - Self::collect_release_fee(&env, &token_addr, gross);
+            let client = token::Client::new(&env, &token_addr);
+            client.transfer(&contract_address, &item.contributor, &payout);
+            Self::record_depositor_outflow(&env, &escrow.depositor, gross, 0);
+            Self::record_release_to(&env, item.bounty_id, &item.contributor);
+            Self::record_history(&env, item.bounty_id, EscrowHistoryKind::Released, -gross);
 
             // Update escrow status
             escrow.status = EscrowStatus::Released;
-            env.storage()
-                .persistent()
-                .set(&DataKey::Escrow(item.bounty_id), &escrow);
+            escrow.remaining_amount = 0;
+            Self::store_escrow(&env, item.bounty_id, &escrow);
+            Self::record_release(&env, item.bounty_id, &item.contributor, payout);
 
             // Emit individual event for each released bounty
             emit_funds_released(
                 &env,
                 FundsReleased {
+                    seq: events::next_event_seq(&env),
                     version: EVENT_VERSION_V2,
                     bounty_id: item.bounty_id,
-                    amount: escrow.amount,
+                    amount: payout,
                     recipient: item.contributor.clone(),
                     timestamp,
                 },
             );
+            emit_escrow_released(
+                &env,
+                Self::lifecycle_event(&env, item.bounty_id, &escrow, payout),
+            );
+            Self::emit_depositor_index(&env, item.bounty_id, &escrow);
 
             released_count += 1;
         }
@@ -2182,12 +9060,14 @@ impl BountyEscrowContract {
         emit_batch_funds_released(
             &env,
             BatchFundsReleased {
+                seq: events::next_event_seq(&env),
                 count: released_count,
                 total_amount,
                 timestamp,
             },
         );
 
+        reentrancy_guard::exit(&env);
         Ok(released_count)
     }
     pub fn update_metadata(
@@ -2261,15 +9141,15 @@ impl traits::EscrowInterface for BountyEscrowContract {
 
 // Implement the UpgradeInterface trait for version compatibility
 impl traits::UpgradeInterface for BountyEscrowContract {
-    /// Get contract version
-    fn get_version(env: &Env) -> u32 {
-        1 // Current version
+    /// Get contract version; same as the `get_version` entry point.
+    fn get_version(_env: &Env) -> u32 {
+        CONTRACT_VERSION
     }
 
     /// Set contract version (admin only)
-    fn set_version(env: &Env, _new_version: u32) -> Result<(), soroban_sdk::String> {
+    fn set_version(_env: &Env, _new_version: u32) -> Result<(), soroban_sdk::String> {
         // Version management - reserved for future use
-        // Currently, version is hardcoded to 1
+        // Currently, the version is fixed by CONTRACT_VERSION
         Ok(())
     }
 }
@@ -2312,15 +9192,15 @@ mod escrow_status_transition_tests {
     //
     // FROM        | TO          | EXPECTED RESULT
     // ------------|-------------|----------------
-    // Locked      | Locked      | Err (invalid - BountyExists)
+    // Locked      | Locked      | Err (invalid - DuplicateBountyId)
     // Locked      | Released    | Ok (allowed)
     // Locked      | Refunded    | Ok (allowed)
-    // Released    | Locked      | Err (invalid - BountyExists)
-    // Released    | Released    | Err (invalid - FundsNotLocked)
-    // Released    | Refunded    | Err (invalid - FundsNotLocked)
-    // Refunded    | Locked      | Err (invalid - BountyExists)
-    // Refunded    | Released    | Err (invalid - FundsNotLocked)
-    // Refunded    | Refunded    | Err (invalid - FundsNotLocked)
+    // Released    | Locked      | Err (invalid - DuplicateBountyId)
+    // Released    | Released    | Err (invalid - AlreadyReleased)
+    // Released    | Refunded    | Err (invalid - AlreadyReleased)
+    // Refunded    | Locked      | Err (invalid - DuplicateBountyId)
+    // Refunded    | Released    | Err (invalid - AlreadyRefunded)
+    // Refunded    | Refunded    | Err (invalid - AlreadyRefunded)
 
     /// Construct a fresh Escrow instance with the specified status.
     fn create_escrow_with_status(
@@ -2346,7 +9226,7 @@ mod escrow_status_transition_tests {
         contract_id: Address,
         client: BountyEscrowContractClient<'static>,
         token_admin: token::StellarAssetClient<'static>,
-        admin: Address,
+        _admin: Address,
         depositor: Address,
         contributor: Address,
     }
@@ -2360,7 +9240,9 @@ mod escrow_status_transition_tests {
             let depositor = Address::generate(&env);
             let contributor = Address::generate(&env);
 
-            let token_id = env.register_stellar_asset_contract(admin.clone());
+            let token_id = env
+                .register_stellar_asset_contract_v2(admin.clone())
+                .address();
             let token_admin = token::StellarAssetClient::new(&env, &token_id);
 
             let contract_id = env.register_contract(None, BountyEscrowContract);
@@ -2373,7 +9255,7 @@ mod escrow_status_transition_tests {
                 contract_id,
                 client,
                 token_admin,
-                admin,
+                _admin: admin,
                 depositor,
                 contributor,
             }
@@ -2425,7 +9307,7 @@ mod escrow_status_transition_tests {
                 label: "Locked to Locked (Lock)",
                 from: EscrowStatus::Locked,
                 action: TransitionAction::Lock,
-                expected_result: Err(Error::BountyExists),
+                expected_result: Err(Error::DuplicateBountyId),
             },
             TransitionTestCase {
                 label: "Locked to Released (Release)",
@@ -2443,37 +9325,37 @@ mod escrow_status_transition_tests {
                 label: "Released to Locked (Lock)",
                 from: EscrowStatus::Released,
                 action: TransitionAction::Lock,
-                expected_result: Err(Error::BountyExists),
+                expected_result: Err(Error::DuplicateBountyId),
             },
             TransitionTestCase {
                 label: "Released to Released (Release)",
                 from: EscrowStatus::Released,
                 action: TransitionAction::Release,
-                expected_result: Err(Error::FundsNotLocked),
+                expected_result: Err(Error::AlreadyReleased),
             },
             TransitionTestCase {
                 label: "Released to Refunded (Refund)",
                 from: EscrowStatus::Released,
                 action: TransitionAction::Refund,
-                expected_result: Err(Error::FundsNotLocked),
+                expected_result: Err(Error::AlreadyReleased),
             },
             TransitionTestCase {
                 label: "Refunded to Locked (Lock)",
                 from: EscrowStatus::Refunded,
                 action: TransitionAction::Lock,
-                expected_result: Err(Error::BountyExists),
+                expected_result: Err(Error::DuplicateBountyId),
             },
             TransitionTestCase {
                 label: "Refunded to Released (Release)",
                 from: EscrowStatus::Refunded,
                 action: TransitionAction::Release,
-                expected_result: Err(Error::FundsNotLocked),
+                expected_result: Err(Error::AlreadyRefunded),
             },
             TransitionTestCase {
                 label: "Refunded to Refunded (Refund)",
                 from: EscrowStatus::Refunded,
                 action: TransitionAction::Refund,
-                expected_result: Err(Error::FundsNotLocked),
+                expected_result: Err(Error::AlreadyRefunded),
             },
         ];
 
@@ -2529,8 +9411,8 @@ mod escrow_status_transition_tests {
                             case.label
                         );
                         assert_eq!(
-                            result.unwrap_err().unwrap(),
-                            case.expected_result.unwrap_err(),
+                            Some(result.unwrap_err().unwrap()),
+                            case.expected_result.err(),
                             "Transition '{}' failed: mismatched error variant",
                             case.label
                         );
@@ -2552,8 +9434,8 @@ mod escrow_status_transition_tests {
                             case.label
                         );
                         assert_eq!(
-                            result.unwrap_err().unwrap(),
-                            case.expected_result.unwrap_err(),
+                            Some(result.unwrap_err().unwrap()),
+                            case.expected_result.err(),
                             "Transition '{}' failed: mismatched error variant",
                             case.label
                         );
@@ -2616,8 +9498,8 @@ mod escrow_status_transition_tests {
         );
         assert_eq!(
             result.unwrap_err().unwrap(),
-            Error::BountyExists,
-            "Expected BountyExists when attempting to Lock Released escrow."
+            Error::DuplicateBountyId,
+            "Expected DuplicateBountyId when attempting to Lock Released escrow."
         );
         let stored = setup.client.get_escrow_info(&bounty_id);
         assert_eq!(
@@ -2643,8 +9525,8 @@ mod escrow_status_transition_tests {
         );
         assert_eq!(
             result.unwrap_err().unwrap(),
-            Error::FundsNotLocked,
-            "Expected FundsNotLocked error variant"
+            Error::AlreadyRefunded,
+            "Expected AlreadyRefunded error variant"
         );
         let stored = setup.client.get_escrow_info(&bounty_id);
         assert_eq!(
@@ -2690,8 +9572,8 @@ mod escrow_status_transition_tests {
         );
         assert_eq!(
             result.unwrap_err().unwrap(),
-            Error::FundsNotLocked,
-            "Expected FundsNotLocked on idempotent attempt"
+            Error::AlreadyReleased,
+            "Expected AlreadyReleased on idempotent attempt"
         );
     }
 
@@ -2719,4 +9601,8 @@ mod escrow_status_transition_tests {
 #[cfg(test)]
 mod test_query_filters;
 #[cfg(test)]
+mod test_reentrancy;
+#[cfg(test)]
 mod test_status_transitions;
+#[cfg(test)]
+mod test_upgrade;
@@ -0,0 +1,21 @@
+use crate::{DataKey, Error};
+use soroban_sdk::Env;
+
+/// Mark a token-moving entry point as in progress.
+///
+/// Fails with `ReentrantCall` if the flag is already set. Error paths revert
+/// the whole invocation, so only successful paths need to call `exit`.
+pub(crate) fn enter(env: &Env) -> Result<(), Error> {
+    if env.storage().instance().has(&DataKey::ReentrancyGuard) {
+        return Err(Error::ReentrantCall);
+    }
+    env.storage()
+        .instance()
+        .set(&DataKey::ReentrancyGuard, &true);
+    Ok(())
+}
+
+/// Clear the in-progress flag set by `enter`.
+pub(crate) fn exit(env: &Env) {
+    env.storage().instance().remove(&DataKey::ReentrancyGuard);
+}
@@ -1,7 +1,7 @@
 use super::*;
 use soroban_sdk::{
     // Added Ledger as _ to put the trait in scope for set_timestamp
-    testutils::{Address as _, Ledger as _},
+    testutils::{storage::Persistent as _, Address as _, Ledger as _},
     token,
     vec,
     Address,
@@ -92,7 +92,7 @@ fn test_lock_funds_success() {
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #3)")]
+#[should_panic(expected = "Error(Contract, #12)")]
 fn test_lock_funds_duplicate() {
     let setup = TestSetup::new();
     let bounty_id = 1;
@@ -164,7 +164,7 @@ fn test_release_funds_success() {
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #5)")]
+#[should_panic(expected = "Error(Contract, #40)")]
 fn test_release_funds_already_released() {
     let setup = TestSetup::new();
     let bounty_id = 1;
@@ -247,6 +247,131 @@ fn test_get_balance() {
     assert_eq!(setup.escrow.get_balance(), amount);
 }
 
+#[test]
+fn test_get_solvency_matches_locked_total() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    assert_eq!(setup.escrow.get_solvency(), (0, 0));
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2, &2_500, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &3, &700, &deadline);
+    assert_eq!(setup.escrow.get_solvency(), (4_200, 4_200));
+
+    setup.escrow.release_funds(&2, &setup.contributor);
+    setup.escrow.partial_release(&3, &setup.contributor, &200);
+    assert_eq!(setup.escrow.get_solvency(), (1_500, 1_500));
+}
+
+#[test]
+fn test_get_token_solvency_is_scoped_per_token() {
+    let setup = TestSetup::new();
+    let (other, other_admin) = create_token_contract(&setup.env, &setup._admin);
+    other_admin.mint(&setup.depositor, &10_000);
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup.escrow.add_allowed_token(&other.address);
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds_with_token(&setup.depositor, &2, &3_000, &deadline, &other.address);
+
+    assert_eq!(setup.escrow.get_solvency(), (1_000, 1_000));
+    assert_eq!(
+        setup.escrow.get_token_solvency(&other.address),
+        (3_000, 3_000)
+    );
+
+    // Tokens sent directly to the contract show up as surplus
+    other_admin.mint(&setup.escrow.address, &50);
+    let (balance, locked) = setup.escrow.get_token_solvency(&other.address);
+    assert!(balance >= locked);
+    assert_eq!(balance - locked, 50);
+}
+
+#[test]
+fn test_lock_with_token_outside_allowlist_is_rejected() {
+    let setup = TestSetup::new();
+    let (other, other_admin) = create_token_contract(&setup.env, &setup._admin);
+    other_admin.mint(&setup.depositor, &10_000);
+    let deadline = setup.env.ledger().timestamp() + 1000;
+
+    assert_eq!(setup.escrow.get_allowed_tokens().len(), 0);
+    let res = setup.escrow.try_lock_funds_with_token(
+        &setup.depositor,
+        &1,
+        &1_000,
+        &deadline,
+        &other.address,
+    );
+    assert_eq!(res.unwrap_err().unwrap(), Error::TokenNotAllowed);
+
+    // The init token is always accepted, even through the explicit-token entry point
+    setup.escrow.lock_funds_with_token(
+        &setup.depositor,
+        &2,
+        &1_000,
+        &deadline,
+        &setup.token.address,
+    );
+    assert_eq!(other.balance(&setup.depositor), 10_000);
+}
+
+#[test]
+fn test_allowed_token_can_be_locked_until_removed() {
+    let setup = TestSetup::new();
+    let (other, other_admin) = create_token_contract(&setup.env, &setup._admin);
+    other_admin.mint(&setup.depositor, &10_000);
+    let deadline = setup.env.ledger().timestamp() + 1000;
+
+    setup.escrow.add_allowed_token(&other.address);
+    setup.escrow.add_allowed_token(&other.address);
+    assert_eq!(
+        setup.escrow.get_allowed_tokens(),
+        vec![&setup.env, other.address.clone()]
+    );
+
+    setup
+        .escrow
+        .lock_funds_with_token(&setup.depositor, &1, &1_000, &deadline, &other.address);
+    assert_eq!(setup.escrow.get_escrow_token(&1), other.address);
+
+    setup.escrow.remove_allowed_token(&other.address);
+    assert_eq!(setup.escrow.get_allowed_tokens().len(), 0);
+    let res = setup.escrow.try_lock_funds_with_token(
+        &setup.depositor,
+        &2,
+        &1_000,
+        &deadline,
+        &other.address,
+    );
+    assert_eq!(res.unwrap_err().unwrap(), Error::TokenNotAllowed);
+
+    // Escrows already locked in a delisted token still settle
+    setup.escrow.release_funds(&1, &setup.contributor);
+    assert_eq!(other.balance(&setup.contributor), 1_000);
+}
+
+#[test]
+fn test_allowed_token_management_requires_admin() {
+    let setup = TestSetup::new();
+    let (other, _) = create_token_contract(&setup.env, &setup._admin);
+    setup.env.set_auths(&[]);
+    assert!(setup.escrow.try_add_allowed_token(&other.address).is_err());
+    assert!(setup
+        .escrow
+        .try_remove_allowed_token(&other.address)
+        .is_err());
+}
+
 // =============================================================================
 // Partial Payout Rounding and Small Amount Tests (Issue #354)
 // =============================================================================
@@ -464,7 +589,7 @@ fn test_partial_release_bounty_not_found() {
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #5)")]
+#[should_panic(expected = "Error(Contract, #40)")]
 fn test_partial_release_on_already_released_bounty_panics() {
     let setup = TestSetup::new();
     let bounty_id = 50;
@@ -546,7 +671,7 @@ fn test_claim_within_window_transfers_funds() {
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #6)")]
+#[should_panic(expected = "Error(Contract, #52)")]
 fn test_claim_after_window_expires_panics() {
     let setup = TestSetup::new();
     let bounty_id = 101_u64;
@@ -566,6 +691,35 @@ fn test_claim_after_window_expires_panics() {
     setup.escrow.claim(&bounty_id);
 }
 
+#[test]
+fn test_claim_after_partial_release_pays_remaining_balance() {
+    let setup = TestSetup::new();
+    let bounty_id = 105_u64;
+    let deadline = setup.env.ledger().timestamp() + 10_000;
+    // A second escrow whose funds the claim must not dip into.
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &106, &1_000, &deadline);
+
+    setup
+        .escrow
+        .release_partial(&bounty_id, &setup.contributor, &400);
+    setup.escrow.set_claim_window(&500_u64);
+    setup.escrow.authorize_claim(&bounty_id, &setup.contributor);
+    assert_eq!(setup.escrow.get_pending_claim(&bounty_id).amount, 600);
+
+    setup.escrow.claim(&bounty_id);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+    assert_eq!(setup.token.balance(&setup.escrow.address), 1_000);
+    let info = setup.escrow.get_escrow_info(&bounty_id);
+    assert_eq!(info.status, EscrowStatus::Released);
+    assert_eq!(info.remaining_amount, 0);
+    assert_eq!(setup.escrow.get_escrow_info(&106).remaining_amount, 1_000);
+}
+
 #[test]
 fn test_cancel_pending_claim_restores_escrow() {
     let setup = TestSetup::new();
@@ -656,7 +810,7 @@ fn test_cancel_claim_then_use_release_funds_normally() {
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #5)")]
+#[should_panic(expected = "Error(Contract, #40)")]
 fn test_claim_twice_panics() {
     let setup = TestSetup::new();
     let bounty_id = 105_u64;
@@ -703,7 +857,7 @@ fn test_claim_does_not_affect_other_bounties() {
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #6)")]
+#[should_panic(expected = "Error(Contract, #52)")]
 fn test_authorize_claim_zero_window_expires_immediately() {
     let setup = TestSetup::new();
     let bounty_id = 108_u64;
@@ -753,7 +907,7 @@ fn test_authorize_claim_on_nonexistent_bounty() {
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #5)")]
+#[should_panic(expected = "Error(Contract, #40)")]
 fn test_authorize_claim_on_released_bounty() {
     let setup = TestSetup::new();
     let bounty_id = 110_u64;
@@ -768,7 +922,7 @@ fn test_authorize_claim_on_released_bounty() {
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #5)")]
+#[should_panic(expected = "Error(Contract, #41)")]
 fn test_authorize_claim_on_refunded_bounty() {
     let setup = TestSetup::new();
     let bounty_id = 111_u64;
@@ -885,7 +1039,7 @@ fn test_batch_lock_funds_success() {
 
     setup.token_admin.mint(&setup.depositor, &10_000);
 
-    let count = setup.escrow.batch_lock_funds(&items);
+    let count = setup.escrow.batch_lock_funds(&items, &None);
     assert_eq!(count, 3);
 
     for i in 1..=3 {
@@ -896,12 +1050,71 @@ fn test_batch_lock_funds_success() {
     assert_eq!(setup.escrow.get_balance(), 6000);
 }
 
+#[test]
+fn test_batch_lock_funds_indexes_escrows_and_applies_amount_policy() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    let other_depositor = Address::generate(&setup.env);
+    setup.token_admin.mint(&other_depositor, &5_000);
+
+    setup.escrow.set_amount_policy(&setup._admin, &100, &2_000);
+    let oversized = vec![
+        &setup.env,
+        LockFundsItem {
+            bounty_id: 1,
+            depositor: setup.depositor.clone(),
+            amount: 2_001,
+            deadline,
+        },
+    ];
+    assert_eq!(
+        setup
+            .escrow
+            .try_batch_lock_funds(&oversized, &None)
+            .unwrap_err()
+            .unwrap(),
+        Error::AmountAboveMaximum
+    );
+
+    let items = vec![
+        &setup.env,
+        LockFundsItem {
+            bounty_id: 1,
+            depositor: setup.depositor.clone(),
+            amount: 1_000,
+            deadline,
+        },
+        LockFundsItem {
+            bounty_id: 2,
+            depositor: other_depositor.clone(),
+            amount: 500,
+            deadline,
+        },
+    ];
+    setup.escrow.batch_lock_funds(&items, &None);
+
+    assert_eq!(
+        setup
+            .escrow
+            .get_escrow_ids_by_depositor(&setup.depositor, &0, &10),
+        vec![&setup.env, 1_u64]
+    );
+    assert_eq!(
+        setup
+            .escrow
+            .get_escrow_ids_by_depositor(&other_depositor, &0, &10),
+        vec![&setup.env, 2_u64]
+    );
+    assert_eq!(setup.escrow.get_all_escrows(&0, &10).len(), 2);
+    assert_eq!(setup.escrow.get_aggregate_stats().total_locked, 1_500);
+}
+
 #[test]
 #[should_panic(expected = "Error(Contract, #10)")]
 fn test_batch_lock_funds_empty() {
     let setup = TestSetup::new();
     let items: Vec<LockFundsItem> = Vec::new(&setup.env);
-    setup.escrow.batch_lock_funds(&items);
+    setup.escrow.batch_lock_funds(&items, &None);
 }
 
 #[test]
@@ -920,7 +1133,7 @@ fn test_batch_lock_funds_single_item() {
     ];
 
     setup.token_admin.mint(&setup.depositor, &1000);
-    let count = setup.escrow.batch_lock_funds(&items);
+    let count = setup.escrow.batch_lock_funds(&items, &None);
     assert_eq!(count, 1);
 
     let escrow = setup.escrow.get_escrow_info(&1);
@@ -945,7 +1158,7 @@ fn test_batch_lock_funds_exceeds_max_batch_size() {
     }
 
     setup.token_admin.mint(&setup.depositor, &10_000);
-    setup.escrow.batch_lock_funds(&items);
+    setup.escrow.batch_lock_funds(&items, &None);
 }
 
 #[test]
@@ -964,12 +1177,12 @@ fn test_batch_lock_funds_at_max_batch_size() {
     }
 
     setup.token_admin.mint(&setup.depositor, &10_000);
-    let count = setup.escrow.batch_lock_funds(&items);
+    let count = setup.escrow.batch_lock_funds(&items, &None);
     assert_eq!(count, 20);
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #3)")]
+#[should_panic(expected = "Error(Contract, #12)")]
 fn test_batch_lock_funds_duplicate_bounty_id() {
     let setup = TestSetup::new();
     let deadline = setup.env.ledger().timestamp() + 1000;
@@ -994,7 +1207,7 @@ fn test_batch_lock_funds_duplicate_bounty_id() {
         },
     ];
 
-    setup.escrow.batch_lock_funds(&items);
+    setup.escrow.batch_lock_funds(&items, &None);
 }
 
 #[test]
@@ -1019,7 +1232,7 @@ fn test_batch_lock_funds_duplicate_in_batch() {
         },
     ];
 
-    setup.escrow.batch_lock_funds(&items);
+    setup.escrow.batch_lock_funds(&items, &None);
 }
 
 #[test]
@@ -1051,7 +1264,7 @@ fn test_batch_lock_funds_triple_duplicate_in_batch() {
     ];
 
     setup.token_admin.mint(&setup.depositor, &10000);
-    setup.escrow.batch_lock_funds(&items);
+    setup.escrow.batch_lock_funds(&items, &None);
 }
 
 #[test]
@@ -1083,7 +1296,7 @@ fn test_batch_lock_funds_non_adjacent_duplicates() {
     ];
 
     setup.token_admin.mint(&setup.depositor, &10000);
-    setup.escrow.batch_lock_funds(&items);
+    setup.escrow.batch_lock_funds(&items, &None);
 }
 
 #[test]
@@ -1102,7 +1315,7 @@ fn test_batch_lock_funds_zero_amount() {
         },
     ];
 
-    setup.escrow.batch_lock_funds(&items);
+    setup.escrow.batch_lock_funds(&items, &None);
 }
 
 #[test]
@@ -1121,7 +1334,7 @@ fn test_batch_lock_funds_negative_amount() {
         },
     ];
 
-    setup.escrow.batch_lock_funds(&items);
+    setup.escrow.batch_lock_funds(&items, &None);
 }
 
 #[test]
@@ -1147,11 +1360,11 @@ fn test_batch_lock_funds_mixed_valid_invalid_amounts() {
     ];
 
     setup.token_admin.mint(&setup.depositor, &2000);
-    setup.escrow.batch_lock_funds(&items);
+    setup.escrow.batch_lock_funds(&items, &None);
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #3)")]
+#[should_panic(expected = "Error(Contract, #12)")]
 fn test_batch_lock_funds_first_valid_second_exists() {
     let setup = TestSetup::new();
     let deadline = setup.env.ledger().timestamp() + 1000;
@@ -1177,11 +1390,11 @@ fn test_batch_lock_funds_first_valid_second_exists() {
     ];
 
     setup.token_admin.mint(&setup.depositor, &5000);
-    setup.escrow.batch_lock_funds(&items);
+    setup.escrow.batch_lock_funds(&items, &None);
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #3)")]
+#[should_panic(expected = "Error(Contract, #12)")]
 fn test_batch_operations_atomicity() {
     let setup = TestSetup::new();
     let deadline = setup.env.ledger().timestamp() + 1000;
@@ -1206,7 +1419,7 @@ fn test_batch_operations_atomicity() {
         },
     ];
 
-    setup.escrow.batch_lock_funds(&items);
+    setup.escrow.batch_lock_funds(&items, &None);
 }
 
 #[test]
@@ -1308,7 +1521,7 @@ fn test_batch_release_funds_exceeds_max_batch_size() {
         });
     }
     setup.token_admin.mint(&setup.depositor, &10_000);
-    setup.escrow.batch_lock_funds(&lock_items);
+    setup.escrow.batch_lock_funds(&lock_items, &None);
 
     let mut release_items = Vec::new(&setup.env);
     for i in 1..=21 {
@@ -1339,7 +1552,7 @@ fn test_batch_release_funds_not_found() {
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #5)")]
+#[should_panic(expected = "Error(Contract, #40)")]
 fn test_batch_release_funds_already_released() {
     let setup = TestSetup::new();
     let deadline = setup.env.ledger().timestamp() + 1000;
@@ -1424,7 +1637,7 @@ fn test_batch_release_funds_first_valid_second_not_found() {
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #5)")]
+#[should_panic(expected = "Error(Contract, #41)")]
 fn test_batch_release_funds_mixed_locked_and_refunded() {
     let setup = TestSetup::new();
     let deadline = setup.env.ledger().timestamp() + 100;
@@ -1505,7 +1718,7 @@ fn test_batch_operations_large_batch() {
 
     setup.token_admin.mint(&setup.depositor, &10_000);
 
-    let count = setup.escrow.batch_lock_funds(&items);
+    let count = setup.escrow.batch_lock_funds(&items, &None);
     assert_eq!(count, 10);
 
     for i in 1..=10 {
@@ -1558,7 +1771,7 @@ fn test_batch_operations_multiple_depositors() {
         },
     ];
 
-    let count = setup.escrow.batch_lock_funds(&items);
+    let count = setup.escrow.batch_lock_funds(&items, &None);
     assert_eq!(count, 3);
 
     let escrow1 = setup.escrow.get_escrow_info(&1);
@@ -1620,3 +1833,5104 @@ fn test_batch_release_funds_to_multiple_contributors() {
     assert_eq!(setup.token.balance(&contributor3), 3000);
     assert_eq!(setup.escrow.get_balance(), 0);
 }
+
+// =============================================================================
+// release_partial: staged payouts and aggregate stats
+// =============================================================================
+
+#[test]
+fn test_release_partial_updates_aggregate_stats() {
+    let setup = TestSetup::new();
+    let bounty_id = 200_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+    setup
+        .escrow
+        .release_partial(&bounty_id, &setup.contributor, &250);
+
+    let stats = setup.escrow.get_aggregate_stats();
+    assert_eq!(stats.total_locked, 750);
+    assert_eq!(stats.total_released, 250);
+    assert_eq!(stats.count_locked, 1);
+    assert_eq!(stats.count_released, 0);
+
+    setup
+        .escrow
+        .release_partial(&bounty_id, &setup.contributor, &750);
+
+    let stats = setup.escrow.get_aggregate_stats();
+    assert_eq!(stats.total_locked, 0);
+    assert_eq!(stats.total_released, 1_000);
+    assert_eq!(stats.count_locked, 0);
+    assert_eq!(stats.count_released, 1);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&bounty_id).status,
+        EscrowStatus::Released
+    );
+}
+
+#[test]
+fn test_release_partial_rejects_zero_and_excess_amounts() {
+    let setup = TestSetup::new();
+    let bounty_id = 201_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &500, &deadline);
+
+    let zero = setup
+        .escrow
+        .try_release_partial(&bounty_id, &setup.contributor, &0);
+    assert_eq!(zero.unwrap_err().unwrap(), Error::InvalidAmount);
+
+    let excess = setup
+        .escrow
+        .try_release_partial(&bounty_id, &setup.contributor, &501);
+    assert_eq!(excess.unwrap_err().unwrap(), Error::InsufficientFunds);
+}
+
+#[test]
+fn test_release_funds_after_partial_pays_only_remainder() {
+    let setup = TestSetup::new();
+    let bounty_id = 202_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+    setup
+        .escrow
+        .release_partial(&bounty_id, &setup.contributor, &400);
+    setup.escrow.release_funds(&bounty_id, &setup.contributor);
+
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+    assert_eq!(setup.token.balance(&setup.escrow.address), 0);
+}
+
+#[test]
+fn test_refund_eligibility_after_partial_release_reports_remainder() {
+    let setup = TestSetup::new();
+    let bounty_id = 203_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+    setup
+        .escrow
+        .release_partial(&bounty_id, &setup.contributor, &600);
+
+    setup.env.ledger().set_timestamp(deadline + 1);
+    let (can_refund, deadline_passed, remaining, _, _) =
+        setup.escrow.get_refund_eligibility(&bounty_id);
+    assert!(can_refund);
+    assert!(deadline_passed);
+    assert_eq!(remaining, 400);
+}
+
+#[test]
+fn test_release_partial_blocked_when_release_paused() {
+    let setup = TestSetup::new();
+    let bounty_id = 204_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+    setup.escrow.set_paused(&None, &Some(true), &None, &None);
+
+    let result = setup
+        .escrow
+        .try_release_partial(&bounty_id, &setup.contributor, &100);
+    assert_eq!(result.unwrap_err().unwrap(), Error::FundsPaused);
+}
+
+// =============================================================================
+// release_funds_split: one bounty, several winners
+// =============================================================================
+
+#[test]
+fn test_release_funds_split_pays_each_recipient() {
+    let setup = TestSetup::new();
+    let bounty_id = 210_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+
+    let winner1 = Address::generate(&setup.env);
+    let winner2 = Address::generate(&setup.env);
+    let recipients = vec![
+        &setup.env,
+        (winner1.clone(), 600_i128),
+        (winner2.clone(), 400_i128),
+    ];
+    setup.escrow.release_funds_split(&bounty_id, &recipients);
+
+    assert_eq!(setup.token.balance(&winner1), 600);
+    assert_eq!(setup.token.balance(&winner2), 400);
+    let stored = setup.escrow.get_escrow_info(&bounty_id);
+    assert_eq!(stored.status, EscrowStatus::Released);
+    assert_eq!(stored.remaining_amount, 0);
+}
+
+#[test]
+fn test_release_funds_split_rejects_sum_mismatch() {
+    let setup = TestSetup::new();
+    let bounty_id = 211_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+
+    let recipients = vec![
+        &setup.env,
+        (Address::generate(&setup.env), 500_i128),
+        (Address::generate(&setup.env), 499_i128),
+    ];
+    let result = setup
+        .escrow
+        .try_release_funds_split(&bounty_id, &recipients);
+    assert_eq!(result.unwrap_err().unwrap(), Error::SplitAmountMismatch);
+    assert_eq!(setup.token.balance(&setup.escrow.address), 1_000);
+}
+
+#[test]
+fn test_release_funds_split_rejects_empty_and_duplicates() {
+    let setup = TestSetup::new();
+    let bounty_id = 212_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+
+    let empty: Vec<(Address, i128)> = Vec::new(&setup.env);
+    let result = setup.escrow.try_release_funds_split(&bounty_id, &empty);
+    assert_eq!(result.unwrap_err().unwrap(), Error::InvalidBatchSize);
+
+    let winner = Address::generate(&setup.env);
+    let duplicates = vec![
+        &setup.env,
+        (winner.clone(), 500_i128),
+        (winner.clone(), 500_i128),
+    ];
+    let result = setup
+        .escrow
+        .try_release_funds_split(&bounty_id, &duplicates);
+    assert_eq!(result.unwrap_err().unwrap(), Error::DuplicateRecipient);
+}
+
+#[test]
+fn test_release_funds_split_bps_gives_remainder_to_last() {
+    let setup = TestSetup::new();
+    let bounty_id = 213_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_001, &deadline);
+
+    let a = Address::generate(&setup.env);
+    let b = Address::generate(&setup.env);
+    let c = Address::generate(&setup.env);
+    let shares = vec![
+        &setup.env,
+        (a.clone(), 3_333_u32),
+        (b.clone(), 3_333_u32),
+        (c.clone(), 3_334_u32),
+    ];
+    setup.escrow.release_funds_split_bps(&bounty_id, &shares);
+
+    assert_eq!(setup.token.balance(&a), 333);
+    assert_eq!(setup.token.balance(&b), 333);
+    assert_eq!(setup.token.balance(&c), 335);
+    assert_eq!(setup.token.balance(&setup.escrow.address), 0);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&bounty_id).status,
+        EscrowStatus::Released
+    );
+}
+
+#[test]
+fn test_release_funds_split_bps_folds_empty_shares_into_remainder() {
+    let setup = TestSetup::new();
+    let bounty_id = 215_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &5, &deadline);
+
+    // 1 bps of 5 rounds down to nothing; the last recipient takes it all
+    let a = Address::generate(&setup.env);
+    let b = Address::generate(&setup.env);
+    let shares = vec![&setup.env, (a.clone(), 1_u32), (b.clone(), 9_999_u32)];
+    setup.escrow.release_funds_split_bps(&bounty_id, &shares);
+
+    assert_eq!(setup.token.balance(&a), 0);
+    assert_eq!(setup.token.balance(&b), 5);
+    assert_eq!(setup.token.balance(&setup.escrow.address), 0);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&bounty_id).status,
+        EscrowStatus::Released
+    );
+}
+
+#[test]
+fn test_release_funds_split_bps_rejects_bad_shares() {
+    let setup = TestSetup::new();
+    let bounty_id = 214_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+
+    let a = Address::generate(&setup.env);
+    let b = Address::generate(&setup.env);
+    let short = vec![&setup.env, (a.clone(), 5_000_u32), (b.clone(), 4_000_u32)];
+    let result = setup.escrow.try_release_funds_split_bps(&bounty_id, &short);
+    assert_eq!(result.unwrap_err().unwrap(), Error::SplitAmountMismatch);
+
+    let duplicates = vec![&setup.env, (a.clone(), 5_000_u32), (a.clone(), 5_000_u32)];
+    let result = setup
+        .escrow
+        .try_release_funds_split_bps(&bounty_id, &duplicates);
+    assert_eq!(result.unwrap_err().unwrap(), Error::DuplicateRecipient);
+}
+
+// =============================================================================
+// Release fee (set_fee_bps / set_fee_collector)
+// =============================================================================
+
+#[test]
+fn test_release_fee_rounds_down_and_goes_to_collector() {
+    let setup = TestSetup::new();
+    let collector = Address::generate(&setup.env);
+    let deadline = setup.env.ledger().timestamp() + 1000;
+
+    setup.escrow.set_fee_collector(&collector, &setup._admin);
+    setup.escrow.set_fee_bps(&250, &setup._admin);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &220, &999, &deadline);
+    setup.escrow.release_funds(&220, &setup.contributor);
+
+    // 999 * 250 / 10_000 = 24.975 -> 24
+    assert_eq!(setup.token.balance(&collector), 24);
+    assert_eq!(setup.token.balance(&setup.contributor), 975);
+    assert_eq!(setup.escrow.get_aggregate_stats().total_fees_collected, 24);
+}
+
+#[test]
+fn test_release_fee_uses_rate_at_release_time() {
+    let setup = TestSetup::new();
+    let collector = Address::generate(&setup.env);
+    let deadline = setup.env.ledger().timestamp() + 1000;
+
+    setup.escrow.set_fee_collector(&collector, &setup._admin);
+    setup.escrow.set_fee_bps(&100, &setup._admin);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &221, &10_000, &deadline);
+
+    // Fee changes after the escrow was locked; the new rate applies
+    setup.escrow.set_fee_bps(&500, &setup._admin);
+    setup.escrow.release_funds(&221, &setup.contributor);
+
+    assert_eq!(setup.token.balance(&collector), 500);
+    assert_eq!(setup.token.balance(&setup.contributor), 9_500);
+}
+
+#[test]
+fn test_set_fee_bps_rejects_values_above_cap() {
+    let setup = TestSetup::new();
+    let result = setup.escrow.try_set_fee_bps(&1_001, &setup._admin);
+    assert_eq!(result.unwrap_err().unwrap(), Error::InvalidFeeRate);
+
+    setup.escrow.set_fee_bps(&1_000, &setup._admin);
+    assert_eq!(setup.escrow.get_fee_config().release_fee_rate, 1_000);
+}
+
+/// Charge a 5% release fee to a fresh collector and lock 1_000 under `bounty_id`.
+fn setup_release_fee(setup: &TestSetup, bounty_id: u64) -> Address {
+    let collector = Address::generate(&setup.env);
+    let deadline = setup.env.ledger().timestamp() + 10_000;
+    setup.escrow.set_fee_collector(&collector, &setup._admin);
+    setup.escrow.set_fee_bps(&500, &setup._admin);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+    collector
+}
+
+fn assert_release_fee(setup: &TestSetup, collector: &Address, fee: i128) {
+    assert_eq!(setup.token.balance(collector), fee);
+    assert_eq!(setup.escrow.get_aggregate_stats().total_fees_collected, fee);
+}
+
+#[test]
+fn test_release_partial_charges_release_fee() {
+    let setup = TestSetup::new();
+    let collector = setup_release_fee(&setup, 222);
+
+    setup.escrow.release_partial(&222, &setup.contributor, &400);
+    assert_eq!(setup.token.balance(&setup.contributor), 380);
+    assert_eq!(setup.escrow.get_escrow_info(&222).remaining_amount, 600);
+    assert_release_fee(&setup, &collector, 20);
+}
+
+#[test]
+fn test_release_milestone_charges_release_fee() {
+    let setup = TestSetup::new();
+    let collector = Address::generate(&setup.env);
+    setup.escrow.set_fee_collector(&collector, &setup._admin);
+    setup.escrow.set_fee_bps(&500, &setup._admin);
+    let now = lock_three_milestones(&setup, 223);
+
+    setup.env.ledger().set_timestamp(now + 100);
+    setup.escrow.release_milestone(&223, &0, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 285);
+    assert_release_fee(&setup, &collector, 15);
+}
+
+#[test]
+fn test_release_funds_split_charges_fee_per_share() {
+    let setup = TestSetup::new();
+    let collector = setup_release_fee(&setup, 224);
+    let other = Address::generate(&setup.env);
+
+    setup.escrow.release_funds_split(
+        &224,
+        &vec![
+            &setup.env,
+            (setup.contributor.clone(), 600_i128),
+            (other.clone(), 400_i128),
+        ],
+    );
+    assert_eq!(setup.token.balance(&setup.contributor), 570);
+    assert_eq!(setup.token.balance(&other), 380);
+    assert_release_fee(&setup, &collector, 50);
+}
+
+#[test]
+fn test_release_funds_split_bps_charges_fee_per_share() {
+    let setup = TestSetup::new();
+    let collector = setup_release_fee(&setup, 225);
+    let other = Address::generate(&setup.env);
+
+    setup.escrow.release_funds_split_bps(
+        &225,
+        &vec![
+            &setup.env,
+            (setup.contributor.clone(), 7_500_u32),
+            (other.clone(), 2_500_u32),
+        ],
+    );
+    assert_eq!(setup.token.balance(&setup.contributor), 713);
+    assert_eq!(setup.token.balance(&other), 238);
+    // 750 * 5% = 37 and 250 * 5% = 12, each rounded down
+    assert_release_fee(&setup, &collector, 49);
+}
+
+#[test]
+fn test_batch_release_funds_charges_release_fee() {
+    let setup = TestSetup::new();
+    let collector = setup_release_fee(&setup, 226);
+    let deadline = setup.env.ledger().timestamp() + 10_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &227, &2_000, &deadline);
+
+    setup.escrow.batch_release_funds(&vec![
+        &setup.env,
+        ReleaseFundsItem {
+            bounty_id: 226,
+            contributor: setup.contributor.clone(),
+        },
+        ReleaseFundsItem {
+            bounty_id: 227,
+            contributor: setup.contributor.clone(),
+        },
+    ]);
+    assert_eq!(setup.token.balance(&setup.contributor), 2_850);
+    assert_release_fee(&setup, &collector, 150);
+}
+
+#[test]
+fn test_claim_charges_release_fee() {
+    let setup = TestSetup::new();
+    let collector = setup_release_fee(&setup, 228);
+
+    setup.escrow.set_claim_window(&500_u64);
+    setup.escrow.authorize_claim(&228, &setup.contributor);
+    setup.escrow.claim(&228);
+    assert_eq!(setup.token.balance(&setup.contributor), 950);
+    assert_release_fee(&setup, &collector, 50);
+}
+
+#[test]
+fn test_claim_auto_release_charges_release_fee() {
+    let setup = TestSetup::new();
+    let collector = setup_release_fee(&setup, 229);
+
+    setup.escrow.set_auto_release_delay(&500);
+    setup.escrow.mark_completed(&229, &setup.contributor);
+    setup
+        .env
+        .ledger()
+        .set_timestamp(setup.env.ledger().timestamp() + 500);
+    setup.escrow.claim_auto_release(&229);
+    assert_eq!(setup.token.balance(&setup.contributor), 950);
+    assert_release_fee(&setup, &collector, 50);
+}
+
+#[test]
+fn test_resolve_dispute_charges_fee_on_award_only() {
+    let setup = TestSetup::new();
+    let collector = setup_release_fee(&setup, 230);
+    let arbiter = Address::generate(&setup.env);
+    setup.escrow.set_arbiter(&arbiter);
+    let depositor_before = setup.token.balance(&setup.depositor);
+
+    setup.escrow.raise_dispute(&230, &setup.depositor);
+    setup
+        .escrow
+        .resolve_dispute(&230, &setup.contributor, &600, &arbiter);
+    assert_eq!(setup.token.balance(&setup.contributor), 570);
+    assert_eq!(
+        setup.token.balance(&setup.depositor),
+        depositor_before + 400
+    );
+    assert_release_fee(&setup, &collector, 30);
+}
+
+// =============================================================================
+// top_up: raising a locked bounty's reward
+// =============================================================================
+
+#[test]
+fn test_top_up_increases_amount_and_stats() {
+    let setup = TestSetup::new();
+    let bounty_id = 230_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+
+    setup.escrow.top_up(&bounty_id, &500, &None);
+
+    let stored = setup.escrow.get_escrow_info(&bounty_id);
+    assert_eq!(stored.amount, 1_500);
+    assert_eq!(stored.remaining_amount, 1_500);
+    assert_eq!(setup.token.balance(&setup.escrow.address), 1_500);
+    assert_eq!(setup.escrow.get_aggregate_stats().total_locked, 1_500);
+
+    let matches = setup
+        .escrow
+        .query_escrows_by_amount(&1_200, &2_000, &0, &10);
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches.get(0).unwrap().bounty_id, bounty_id);
+}
+
+#[test]
+fn test_top_up_can_extend_deadline() {
+    let setup = TestSetup::new();
+    let bounty_id = 232_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+
+    setup
+        .escrow
+        .top_up(&bounty_id, &500, &Some(deadline + 4_000));
+
+    let stored = setup.escrow.get_escrow_info(&bounty_id);
+    assert_eq!(stored.amount, 1_500);
+    assert_eq!(stored.deadline, deadline + 4_000);
+    let by_amount = setup
+        .escrow
+        .query_escrows_by_amount(&1_500, &1_500, &0, &10);
+    assert_eq!(by_amount.get(0).unwrap().bounty_id, bounty_id);
+    assert_eq!(
+        setup
+            .escrow
+            .query_escrows_by_deadline(&deadline, &(deadline + 3_999), &0, &10)
+            .len(),
+        0
+    );
+    let by_deadline =
+        setup
+            .escrow
+            .query_escrows_by_deadline(&(deadline + 4_000), &(deadline + 4_000), &0, &10);
+    assert_eq!(by_deadline.get(0).unwrap().bounty_id, bounty_id);
+
+    // An earlier deadline never shortens the escrow.
+    setup.escrow.top_up(&bounty_id, &100, &Some(deadline));
+    let stored = setup.escrow.get_escrow_info(&bounty_id);
+    assert_eq!(stored.amount, 1_600);
+    assert_eq!(stored.deadline, deadline + 4_000);
+}
+
+#[test]
+fn test_top_up_rejects_non_locked_and_invalid_amounts() {
+    let setup = TestSetup::new();
+    let bounty_id = 231_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+
+    let zero = setup.escrow.try_top_up(&bounty_id, &0, &None);
+    assert_eq!(zero.unwrap_err().unwrap(), Error::InvalidAmount);
+
+    // Overflow is rejected before any tokens move.
+    let overflow = setup.escrow.try_top_up(&bounty_id, &i128::MAX, &None);
+    assert_eq!(overflow.unwrap_err().unwrap(), Error::InvalidAmount);
+    assert_eq!(setup.token.balance(&setup.escrow.address), 1_000);
+
+    setup.escrow.release_funds(&bounty_id, &setup.contributor);
+    let released = setup.escrow.try_top_up(&bounty_id, &100, &None);
+    assert_eq!(released.unwrap_err().unwrap(), Error::AlreadyReleased);
+}
+
+#[test]
+fn test_get_escrow_returns_record_or_none() {
+    let setup = TestSetup::new();
+    let bounty_id = 240_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+
+    let record = setup.escrow.get_escrow(&bounty_id).unwrap();
+    assert_eq!(record.bounty_id, bounty_id);
+    assert_eq!(record.escrow.depositor, setup.depositor);
+    assert_eq!(record.escrow.amount, 1_000);
+    assert_eq!(record.escrow.deadline, deadline);
+    assert_eq!(record.escrow.status, EscrowStatus::Locked);
+
+    assert_eq!(setup.escrow.get_escrow(&999), None);
+}
+
+// =============================================================================
+// Refund policy chosen at lock time
+// =============================================================================
+
+#[test]
+fn test_refund_policy_partial_forfeits_remainder_to_treasury() {
+    let setup = TestSetup::new();
+    let treasury = Address::generate(&setup.env);
+    setup.escrow.set_fee_collector(&treasury, &setup._admin);
+    setup.escrow.set_bucket_size(&100_000);
+    let deadline = setup.env.ledger().timestamp() + 1000;
+
+    setup.escrow.lock_funds_with_refund_policy(
+        &setup.depositor,
+        &250,
+        &1_000,
+        &deadline,
+        &RefundPolicy::Partial(7_500),
+    );
+
+    setup.env.ledger().set_timestamp(deadline + 1);
+    let (can_refund, _, refundable, _, _) = setup.escrow.get_refund_eligibility(&250);
+    assert!(can_refund);
+    assert_eq!(refundable, 750);
+
+    let before = setup.token.balance(&setup.depositor);
+    setup.escrow.refund(&250);
+    assert_eq!(setup.token.balance(&setup.depositor), before + 750);
+    assert_eq!(setup.token.balance(&treasury), 250);
+    assert_eq!(setup.token.balance(&setup.escrow.address), 0);
+
+    // The forfeit never reaches a contributor, so no stats count it as released
+    let stats = setup.escrow.get_aggregate_stats();
+    assert_eq!(stats.total_released, 0);
+    assert_eq!(stats.total_refunded, 750);
+    assert_eq!(stats.total_fees_collected, 250);
+    let depositor_stats = setup.escrow.get_depositor_stats(&setup.depositor);
+    assert_eq!(depositor_stats.total_locked, 0);
+    assert_eq!(depositor_stats.total_released, 0);
+    let bucket = setup
+        .escrow
+        .get_stats_bucket(&setup.env.ledger().timestamp());
+    assert_eq!(bucket.locked, 1_000);
+    assert_eq!(bucket.released, 0);
+    assert_eq!(bucket.refunded, 750);
+}
+
+#[test]
+fn test_refund_policy_none_blocks_deadline_refund() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+
+    setup.escrow.lock_funds_with_refund_policy(
+        &setup.depositor,
+        &251,
+        &1_000,
+        &deadline,
+        &RefundPolicy::None,
+    );
+
+    setup.env.ledger().set_timestamp(deadline + 1);
+    let (can_refund, deadline_passed, _, _, _) = setup.escrow.get_refund_eligibility(&251);
+    assert!(!can_refund);
+    assert!(deadline_passed);
+
+    let result = setup.escrow.try_refund(&251);
+    assert_eq!(result.unwrap_err().unwrap(), Error::RefundNotAllowed);
+}
+
+#[test]
+fn test_refund_policy_exposed_in_single_record_view() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &252, &1_000, &deadline);
+    assert_eq!(
+        setup.escrow.get_escrow(&252).unwrap().refund_policy,
+        RefundPolicy::Full
+    );
+
+    let invalid = setup.escrow.try_lock_funds_with_refund_policy(
+        &setup.depositor,
+        &253,
+        &1_000,
+        &deadline,
+        &RefundPolicy::Partial(10_001),
+    );
+    assert_eq!(invalid.unwrap_err().unwrap(), Error::InvalidRefundPolicy);
+}
+
+// =============================================================================
+// Contributor allowlist (invite-only bounties)
+// =============================================================================
+
+#[test]
+fn test_allowlist_rejects_unlisted_contributor() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    let invited = Address::generate(&setup.env);
+    setup.escrow.lock_funds_with_allowlist(
+        &setup.depositor,
+        &260,
+        &1_000,
+        &deadline,
+        &vec![&setup.env, invited.clone()],
+    );
+    assert_eq!(
+        setup.escrow.get_allowed_contributors(&260),
+        vec![&setup.env, invited.clone()]
+    );
+
+    let result = setup.escrow.try_release_funds(&260, &setup.contributor);
+    assert_eq!(result.unwrap_err().unwrap(), Error::UnauthorizedContributor);
+
+    setup.escrow.release_funds(&260, &invited);
+    assert_eq!(setup.token.balance(&invited), 1_000);
+}
+
+#[test]
+fn test_empty_allowlist_allows_anyone() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup.escrow.lock_funds_with_allowlist(
+        &setup.depositor,
+        &261,
+        &1_000,
+        &deadline,
+        &Vec::new(&setup.env),
+    );
+
+    assert!(setup.escrow.get_allowed_contributors(&261).is_empty());
+    assert!(setup
+        .escrow
+        .get_escrow(&261)
+        .unwrap()
+        .allowed_contributors
+        .is_empty());
+    setup.escrow.release_funds(&261, &setup.contributor);
+}
+
+// =============================================================================
+// Per-escrow tokens
+// =============================================================================
+
+#[test]
+fn test_lock_with_alternate_token_settles_in_that_token() {
+    let setup = TestSetup::new();
+    let (other_token, other_admin) = create_token_contract(&setup.env, &setup._admin);
+    other_admin.mint(&setup.depositor, &5_000);
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup.escrow.add_allowed_token(&other_token.address);
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &270, &1_000, &deadline);
+    setup.escrow.lock_funds_with_token(
+        &setup.depositor,
+        &271,
+        &2_000,
+        &deadline,
+        &other_token.address,
+    );
+
+    assert_eq!(setup.escrow.get_escrow_token(&270), setup.token.address);
+    assert_eq!(setup.escrow.get_escrow_token(&271), other_token.address);
+
+    let by_token = setup
+        .escrow
+        .query_escrows_by_token(&other_token.address, &0, &10);
+    assert_eq!(by_token.len(), 1);
+    assert_eq!(by_token.get(0).unwrap().bounty_id, 271);
+
+    let stats = setup.escrow.get_aggregate_stats();
+    assert_eq!(stats.total_locked, 3_000);
+    assert_eq!(
+        stats.total_locked_by_token.get(setup.token.address.clone()),
+        Some(1_000)
+    );
+    assert_eq!(
+        stats.total_locked_by_token.get(other_token.address.clone()),
+        Some(2_000)
+    );
+
+    setup.escrow.release_funds(&271, &setup.contributor);
+    assert_eq!(other_token.balance(&setup.contributor), 2_000);
+    assert_eq!(setup.token.balance(&setup.contributor), 0);
+    assert_eq!(setup.token.balance(&setup.escrow.address), 1_000);
+}
+
+#[test]
+fn test_batch_locks_and_emergency_withdraw_take_a_token() {
+    let setup = TestSetup::new();
+    let (other_token, other_admin) = create_token_contract(&setup.env, &setup._admin);
+    other_admin.mint(&setup.depositor, &5_000);
+    let deadline = setup.env.ledger().timestamp() + 1000;
+
+    let entries = vec![&setup.env, (272_u64, 1_000_i128, deadline)];
+    let rejected = setup.escrow.try_lock_funds_batch(
+        &setup.depositor,
+        &entries,
+        &Some(other_token.address.clone()),
+    );
+    assert_eq!(rejected.unwrap_err().unwrap(), Error::TokenNotAllowed);
+
+    setup.escrow.add_allowed_token(&other_token.address);
+    setup.escrow.lock_funds_batch(
+        &setup.depositor,
+        &entries,
+        &Some(other_token.address.clone()),
+    );
+    let items = vec![
+        &setup.env,
+        LockFundsItem {
+            bounty_id: 273,
+            depositor: setup.depositor.clone(),
+            amount: 500,
+            deadline,
+        },
+    ];
+    setup
+        .escrow
+        .batch_lock_funds(&items, &Some(other_token.address.clone()));
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &274, &300, &deadline);
+
+    assert_eq!(setup.escrow.get_escrow_token(&272), other_token.address);
+    assert_eq!(setup.escrow.get_escrow_token(&273), other_token.address);
+    assert_eq!(other_token.balance(&setup.escrow.address), 1_500);
+
+    // Draining one token leaves the other untouched
+    let target = Address::generate(&setup.env);
+    setup.escrow.set_paused(&Some(true), &None, &None, &None);
+    setup
+        .escrow
+        .emergency_withdraw(&target, &Some(other_token.address.clone()));
+    assert_eq!(other_token.balance(&target), 1_500);
+    assert_eq!(setup.token.balance(&setup.escrow.address), 300);
+
+    setup.escrow.emergency_withdraw(&target, &None);
+    assert_eq!(setup.token.balance(&target), 300);
+}
+
+// =============================================================================
+// lock_funds_batch: one depositor, many bounties, one transfer
+// =============================================================================
+
+#[test]
+fn test_lock_funds_batch_creates_all_escrows() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    let entries = vec![
+        &setup.env,
+        (280_u64, 100_i128, deadline),
+        (281_u64, 200_i128, deadline),
+        (282_u64, 300_i128, deadline + 50),
+    ];
+
+    let created = setup
+        .escrow
+        .lock_funds_batch(&setup.depositor, &entries, &None);
+    assert_eq!(created, 3);
+    assert_eq!(setup.token.balance(&setup.escrow.address), 600);
+    assert_eq!(setup.escrow.get_aggregate_stats().total_locked, 600);
+    assert_eq!(
+        setup
+            .escrow
+            .query_escrows_by_depositor(&setup.depositor, &0, &10)
+            .len(),
+        3
+    );
+    assert_eq!(setup.escrow.get_escrow_info(&282).deadline, deadline + 50);
+}
+
+#[test]
+fn test_lock_funds_batch_rolls_back_on_invalid_entry() {
+    let setup = TestSetup::new();
+    setup.env.ledger().set_timestamp(5_000);
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    let balance_before = setup.token.balance(&setup.depositor);
+
+    let zero_amount = vec![
+        &setup.env,
+        (283_u64, 100_i128, deadline),
+        (284_u64, 0_i128, deadline),
+    ];
+    let result = setup
+        .escrow
+        .try_lock_funds_batch(&setup.depositor, &zero_amount, &None);
+    assert_eq!(result.unwrap_err().unwrap(), Error::InvalidAmount);
+
+    let past_deadline = vec![
+        &setup.env,
+        (283_u64, 100_i128, deadline),
+        (284_u64, 100_i128, 4_000_u64),
+    ];
+    let result = setup
+        .escrow
+        .try_lock_funds_batch(&setup.depositor, &past_deadline, &None);
+    assert_eq!(result.unwrap_err().unwrap(), Error::InvalidDeadline);
+
+    let duplicate = vec![
+        &setup.env,
+        (283_u64, 100_i128, deadline),
+        (283_u64, 100_i128, deadline),
+    ];
+    let result = setup
+        .escrow
+        .try_lock_funds_batch(&setup.depositor, &duplicate, &None);
+    assert_eq!(result.unwrap_err().unwrap(), Error::DuplicateBountyId);
+
+    // Nothing from the failed batches was persisted
+    assert_eq!(setup.escrow.get_escrow_count(), 0);
+    assert_eq!(setup.escrow.get_escrow(&283), None);
+    assert_eq!(setup.token.balance(&setup.depositor), balance_before);
+}
+
+// =============================================================================
+// Auto-release after completion
+// =============================================================================
+
+#[test]
+fn test_claim_auto_release_after_delay_pays_contributor() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 10_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &290, &1_000, &deadline);
+    setup.escrow.set_auto_release_delay(&500);
+    setup.escrow.mark_completed(&290, &setup.contributor);
+
+    let early = setup.escrow.try_claim_auto_release(&290);
+    assert_eq!(early.unwrap_err().unwrap(), Error::AutoReleaseNotReady);
+
+    setup
+        .env
+        .ledger()
+        .set_timestamp(setup.env.ledger().timestamp() + 500);
+    setup.escrow.claim_auto_release(&290);
+
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&290).status,
+        EscrowStatus::Released
+    );
+    assert_eq!(setup.escrow.get_auto_release(&290), None);
+}
+
+#[test]
+fn test_pending_auto_release_blocks_refund() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &291, &1_000, &deadline);
+    setup.escrow.set_auto_release_delay(&50);
+    setup.escrow.mark_completed(&291, &setup.contributor);
+
+    setup.env.ledger().set_timestamp(deadline + 1);
+    let result = setup.escrow.try_refund(&291);
+    assert_eq!(result.unwrap_err().unwrap(), Error::ClaimPending);
+}
+
+#[test]
+fn test_release_funds_still_works_after_mark_completed() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &292, &1_000, &deadline);
+    setup.escrow.set_auto_release_delay(&500);
+    setup.escrow.mark_completed(&292, &setup.contributor);
+
+    setup.escrow.release_funds(&292, &setup.contributor);
+    assert_eq!(setup.escrow.get_auto_release(&292), None);
+    assert!(setup.escrow.try_claim_auto_release(&292).is_err());
+}
+
+#[test]
+fn test_auto_release_cleared_when_escrow_leaves_locked() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    let arbiter = Address::generate(&setup.env);
+    setup.escrow.set_arbiter(&arbiter);
+    setup.escrow.set_auto_release_delay(&500);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &293, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &294, &1_000, &deadline);
+
+    setup.escrow.mark_completed(&293, &setup.contributor);
+    setup.escrow.release_funds_split(
+        &293,
+        &vec![
+            &setup.env,
+            (setup.contributor.clone(), 600_i128),
+            (Address::generate(&setup.env), 400_i128),
+        ],
+    );
+    assert_eq!(setup.escrow.get_auto_release(&293), None);
+
+    // A pending dispute keeps the schedule; resolving it clears it
+    setup.escrow.mark_completed(&294, &setup.contributor);
+    setup.escrow.raise_dispute(&294, &setup.depositor);
+    assert!(setup.escrow.get_auto_release(&294).is_some());
+    setup
+        .escrow
+        .resolve_dispute(&294, &setup.contributor, &0, &arbiter);
+    assert_eq!(setup.escrow.get_auto_release(&294), None);
+}
+
+// =============================================================================
+// Paged refund history
+// =============================================================================
+
+#[test]
+fn test_refund_history_paged_and_by_depositor() {
+    let setup = TestSetup::new();
+    let other = Address::generate(&setup.env);
+    setup.token_admin.mint(&other, &5_000);
+
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &300, &1_000, &deadline);
+    setup.escrow.lock_funds(&other, &301, &2_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &302, &3_000, &deadline);
+
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund(&300);
+    setup.escrow.refund(&301);
+    setup.escrow.refund(&302);
+
+    let all = setup.escrow.get_refund_history_paged(&0, &10);
+    assert_eq!(all.len(), 3);
+    let first = all.get(0).unwrap();
+    assert_eq!(first.bounty_id, 300);
+    assert_eq!(first.depositor, setup.depositor);
+    assert_eq!(first.amount, 1_000);
+    assert_eq!(first.timestamp, deadline + 1);
+
+    let page = setup.escrow.get_refund_history_paged(&1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().bounty_id, 301);
+
+    let mine = setup
+        .escrow
+        .get_refund_history_by_depositor(&setup.depositor, &0, &10);
+    assert_eq!(mine.len(), 2);
+    assert_eq!(mine.get(0).unwrap().bounty_id, 300);
+    assert_eq!(mine.get(1).unwrap().bounty_id, 302);
+
+    let theirs = setup
+        .escrow
+        .get_refund_history_by_depositor(&other, &0, &10);
+    assert_eq!(theirs.len(), 1);
+    assert_eq!(theirs.get(0).unwrap().amount, 2_000);
+}
+
+#[test]
+fn test_sum_refunds_between_excludes_out_of_range() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &303, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &304, &2_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &305, &4_000, &deadline);
+
+    setup.env.ledger().set_timestamp(200);
+    setup.escrow.refund(&303);
+    setup.env.ledger().set_timestamp(300);
+    setup.escrow.refund(&304);
+    setup.env.ledger().set_timestamp(400);
+    setup.escrow.refund(&305);
+
+    assert_eq!(setup.escrow.sum_refunds_between(&0, &u64::MAX), 7_000);
+    assert_eq!(setup.escrow.sum_refunds_between(&200, &300), 3_000);
+    assert_eq!(setup.escrow.sum_refunds_between(&201, &400), 6_000);
+    assert_eq!(setup.escrow.sum_refunds_between(&300, &300), 2_000);
+    assert_eq!(setup.escrow.sum_refunds_between(&401, &1_000), 0);
+    assert_eq!(setup.escrow.sum_refunds_between(&400, &200), 0);
+}
+
+#[test]
+fn test_refund_history_offset_past_end_is_empty() {
+    let setup = TestSetup::new();
+    assert_eq!(setup.escrow.get_refund_history_paged(&0, &10).len(), 0);
+
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &310, &1_000, &deadline);
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund(&310);
+
+    assert_eq!(setup.escrow.get_refund_history_paged(&1, &10).len(), 0);
+    assert_eq!(
+        setup
+            .escrow
+            .get_refund_history_paged(&u32::MAX, &u32::MAX)
+            .len(),
+        0
+    );
+    assert_eq!(
+        setup
+            .escrow
+            .get_refund_history_by_depositor(&setup.depositor, &5, &10)
+            .len(),
+        0
+    );
+}
+
+// =============================================================================
+// Release history
+// =============================================================================
+
+#[test]
+fn test_release_history_paged_and_by_contributor() {
+    let setup = TestSetup::new();
+    let other = Address::generate(&setup.env);
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &600, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &601, &2_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &602, &3_000, &deadline);
+
+    setup.env.ledger().set_timestamp(10);
+    setup.escrow.release_funds(&600, &setup.contributor);
+    setup.env.ledger().set_timestamp(20);
+    setup.escrow.partial_release(&601, &other, &500);
+    setup.env.ledger().set_timestamp(30);
+    setup.escrow.release_funds(&602, &setup.contributor);
+
+    let all = setup.escrow.get_release_history(&0, &10);
+    assert_eq!(all.len(), 3);
+    assert_eq!(
+        all.get(0).unwrap(),
+        ReleaseHistoryEntry {
+            bounty_id: 600,
+            contributor: setup.contributor.clone(),
+            amount: 1_000,
+            timestamp: 10,
+        }
+    );
+    assert_eq!(
+        all.get(1).unwrap(),
+        ReleaseHistoryEntry {
+            bounty_id: 601,
+            contributor: other.clone(),
+            amount: 500,
+            timestamp: 20,
+        }
+    );
+    assert_eq!(all.get(2).unwrap().bounty_id, 602);
+
+    let page = setup.escrow.get_release_history(&1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().bounty_id, 601);
+    assert_eq!(setup.escrow.get_release_history(&3, &10).len(), 0);
+
+    let mine = setup
+        .escrow
+        .get_contributor_release_history(&setup.contributor, &0, &10);
+    assert_eq!(mine.len(), 2);
+    assert_eq!(mine.get(0).unwrap().bounty_id, 600);
+    assert_eq!(mine.get(1).unwrap().bounty_id, 602);
+    assert_eq!(mine.get(1).unwrap().amount, 3_000);
+
+    let theirs = setup
+        .escrow
+        .get_contributor_release_history(&other, &0, &10);
+    assert_eq!(theirs.len(), 1);
+    assert_eq!(theirs.get(0).unwrap().amount, 500);
+}
+
+// =============================================================================
+// Per-depositor statistics
+// =============================================================================
+
+#[test]
+fn test_depositor_stats_are_isolated_per_depositor() {
+    let setup = TestSetup::new();
+    let other = Address::generate(&setup.env);
+    setup.token_admin.mint(&other, &5_000);
+
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &320, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &321, &2_000, &deadline);
+    setup.escrow.lock_funds(&other, &322, &500, &deadline);
+
+    setup.escrow.release_funds(&320, &setup.contributor);
+    setup.escrow.release_partial(&322, &setup.contributor, &200);
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund(&321);
+
+    let mine = setup.escrow.get_depositor_stats(&setup.depositor);
+    assert_eq!(mine.total_locked, 0);
+    assert_eq!(mine.total_released, 1_000);
+    assert_eq!(mine.total_refunded, 2_000);
+    assert_eq!(mine.escrow_count, 2);
+
+    let theirs = setup.escrow.get_depositor_stats(&other);
+    assert_eq!(theirs.total_locked, 300);
+    assert_eq!(theirs.total_released, 200);
+    assert_eq!(theirs.total_refunded, 0);
+    assert_eq!(theirs.escrow_count, 1);
+
+    let global = setup.escrow.get_aggregate_stats();
+    assert_eq!(global.total_locked, mine.total_locked + theirs.total_locked);
+    assert_eq!(
+        global.total_released,
+        mine.total_released + theirs.total_released
+    );
+    assert_eq!(
+        global.total_refunded,
+        mine.total_refunded + theirs.total_refunded
+    );
+}
+
+#[test]
+fn test_depositor_stats_default_and_top_up() {
+    let setup = TestSetup::new();
+    let stranger = Address::generate(&setup.env);
+    let empty = setup.escrow.get_depositor_stats(&stranger);
+    assert_eq!(empty.total_locked, 0);
+    assert_eq!(empty.escrow_count, 0);
+
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &330, &1_000, &deadline);
+    setup.escrow.top_up(&330, &250, &None);
+
+    let stats = setup.escrow.get_depositor_stats(&setup.depositor);
+    assert_eq!(stats.total_locked, 1_250);
+    assert_eq!(stats.escrow_count, 1);
+}
+
+// =============================================================================
+// Mutual-consent cancellation
+// =============================================================================
+
+#[test]
+fn test_cancel_escrow_before_deadline_refunds_depositor() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &340, &1_000, &deadline);
+    setup.escrow.assign_contributor(&340, &setup.contributor);
+
+    setup.escrow.cancel_escrow(&340);
+
+    let auths = setup.env.auths();
+    assert!(auths.iter().any(|(a, _)| *a == setup.depositor));
+    assert!(auths.iter().any(|(a, _)| *a == setup.contributor));
+
+    assert_eq!(setup.token.balance(&setup.depositor), 1_000_000);
+    let escrow = setup.escrow.get_escrow_info(&340);
+    assert_eq!(escrow.status, EscrowStatus::Refunded);
+    assert_eq!(escrow.remaining_amount, 0);
+    assert_eq!(escrow.refund_history.len(), 1);
+
+    let stats = setup.escrow.get_aggregate_stats();
+    assert_eq!(stats.total_refunded, 1_000);
+    assert_eq!(stats.total_locked, 0);
+    assert_eq!(
+        setup
+            .escrow
+            .get_depositor_stats(&setup.depositor)
+            .total_refunded,
+        1_000
+    );
+    assert_eq!(setup.escrow.get_refund_history_paged(&0, &10).len(), 1);
+}
+
+#[test]
+fn test_cancel_escrow_requires_assigned_contributor() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &341, &1_000, &deadline);
+
+    let result = setup.escrow.try_cancel_escrow(&341);
+    assert_eq!(result.unwrap_err().unwrap(), Error::ContributorNotAssigned);
+}
+
+#[test]
+fn test_cancel_escrow_rejects_released_bounty() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &342, &1_000, &deadline);
+    setup.escrow.assign_contributor(&342, &setup.contributor);
+    setup.escrow.release_funds(&342, &setup.contributor);
+
+    let result = setup.escrow.try_cancel_escrow(&342);
+    assert_eq!(result.unwrap_err().unwrap(), Error::AlreadyReleased);
+}
+
+// =============================================================================
+// Contributor pre-assignment
+// =============================================================================
+
+#[test]
+fn test_lock_for_contributor_releases_to_assigned_by_default() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup.escrow.lock_funds_for_contributor(
+        &setup.depositor,
+        &345,
+        &1_000,
+        &deadline,
+        &setup.contributor,
+    );
+    assert_eq!(
+        setup.escrow.get_assigned_contributor(&345),
+        Some(setup.contributor.clone())
+    );
+    assert_eq!(
+        setup.escrow.get_escrow(&345).unwrap().assigned_contributor,
+        Some(setup.contributor.clone())
+    );
+
+    setup.escrow.release_to_assigned(&345);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&345).status,
+        EscrowStatus::Released
+    );
+}
+
+#[test]
+fn test_depositor_cannot_replace_assigned_contributor() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    let arbiter = Address::generate(&setup.env);
+    let other = Address::generate(&setup.env);
+    setup.escrow.set_arbiter(&arbiter);
+    setup.escrow.lock_funds_for_contributor(
+        &setup.depositor,
+        &347,
+        &1_000,
+        &deadline,
+        &setup.contributor,
+    );
+
+    assert_eq!(
+        setup
+            .escrow
+            .try_assign_contributor(&347, &other)
+            .unwrap_err()
+            .unwrap(),
+        Error::Unauthorized
+    );
+    // Re-confirming the same contributor is harmless
+    setup.escrow.assign_contributor(&347, &setup.contributor);
+    assert_eq!(
+        setup.escrow.get_assigned_contributor(&347),
+        Some(setup.contributor.clone())
+    );
+
+    setup.escrow.reassign_contributor(&347, &other, &arbiter);
+    assert_eq!(setup.escrow.get_assigned_contributor(&347), Some(other));
+}
+
+#[test]
+fn test_release_must_match_assigned_contributor() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    let stranger = Address::generate(&setup.env);
+    setup.escrow.lock_funds_for_contributor(
+        &setup.depositor,
+        &346,
+        &1_000,
+        &deadline,
+        &setup.contributor,
+    );
+
+    assert_eq!(
+        setup
+            .escrow
+            .try_release_funds(&346, &stranger)
+            .unwrap_err()
+            .unwrap(),
+        Error::ContributorMismatch
+    );
+    assert_eq!(
+        setup
+            .escrow
+            .try_partial_release(&346, &stranger, &100)
+            .unwrap_err()
+            .unwrap(),
+        Error::ContributorMismatch
+    );
+
+    // An explicit recipient matching the assignment is fine
+    setup.escrow.release_funds(&346, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+    assert_eq!(setup.token.balance(&stranger), 0);
+}
+
+#[test]
+fn test_release_to_assigned_requires_assignment() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &347, &1_000, &deadline);
+    assert_eq!(
+        setup.escrow.get_escrow(&347).unwrap().assigned_contributor,
+        None
+    );
+    assert_eq!(
+        setup
+            .escrow
+            .try_release_to_assigned(&347)
+            .unwrap_err()
+            .unwrap(),
+        Error::ContributorNotAssigned
+    );
+}
+
+// =============================================================================
+// Time-locked release (release_not_before)
+// =============================================================================
+
+#[test]
+fn test_release_before_start_time_is_rejected() {
+    let setup = TestSetup::new();
+    let now = setup.env.ledger().timestamp();
+    setup.escrow.lock_funds_with_release_time(
+        &setup.depositor,
+        &350,
+        &1_000,
+        &(now + 5_000),
+        &(now + 1_000),
+    );
+
+    setup.env.ledger().set_timestamp(now + 999);
+    let result = setup.escrow.try_release_funds(&350, &setup.contributor);
+    assert_eq!(result.unwrap_err().unwrap(), Error::ReleaseTooEarly);
+    let result = setup
+        .escrow
+        .try_release_partial(&350, &setup.contributor, &100);
+    assert_eq!(result.unwrap_err().unwrap(), Error::ReleaseTooEarly);
+}
+
+#[test]
+fn test_release_at_start_time_boundary_succeeds() {
+    let setup = TestSetup::new();
+    let now = setup.env.ledger().timestamp();
+    setup.escrow.lock_funds_with_release_time(
+        &setup.depositor,
+        &351,
+        &1_000,
+        &(now + 5_000),
+        &(now + 1_000),
+    );
+    assert_eq!(
+        setup.escrow.get_escrow(&351).unwrap().release_not_before,
+        Some(now + 1_000)
+    );
+
+    setup.env.ledger().set_timestamp(now + 1_000);
+    setup.escrow.release_funds(&351, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+}
+
+#[test]
+fn test_release_time_does_not_affect_refund_deadline() {
+    let setup = TestSetup::new();
+    let now = setup.env.ledger().timestamp();
+    setup.escrow.lock_funds_with_release_time(
+        &setup.depositor,
+        &352,
+        &1_000,
+        &(now + 100),
+        &(now + 1_000),
+    );
+
+    setup.env.ledger().set_timestamp(now + 101);
+    setup.escrow.refund(&352);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&352).status,
+        EscrowStatus::Refunded
+    );
+}
+
+// =============================================================================
+// Milestone escrows
+// =============================================================================
+
+fn lock_three_milestones(setup: &TestSetup, bounty_id: u64) -> u64 {
+    let now = setup.env.ledger().timestamp();
+    let milestones = vec![
+        &setup.env,
+        (300_i128, now + 100),
+        (300_i128, now + 200),
+        (400_i128, now + 300),
+    ];
+    setup
+        .escrow
+        .lock_with_milestones(&setup.depositor, &bounty_id, &milestones);
+    now
+}
+
+#[test]
+fn test_release_milestone_partial_completion() {
+    let setup = TestSetup::new();
+    let now = lock_three_milestones(&setup, 360);
+    assert_eq!(setup.escrow.get_escrow_info(&360).amount, 1_000);
+
+    let result = setup
+        .escrow
+        .try_release_milestone(&360, &0, &setup.contributor);
+    assert_eq!(result.unwrap_err().unwrap(), Error::MilestoneLocked);
+
+    setup.env.ledger().set_timestamp(now + 100);
+    setup.escrow.release_milestone(&360, &0, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 300);
+
+    let result = setup
+        .escrow
+        .try_release_milestone(&360, &0, &setup.contributor);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        Error::MilestoneAlreadyReleased
+    );
+    let result = setup
+        .escrow
+        .try_release_milestone(&360, &5, &setup.contributor);
+    assert_eq!(result.unwrap_err().unwrap(), Error::MilestoneNotFound);
+
+    let escrow = setup.escrow.get_escrow_info(&360);
+    assert_eq!(escrow.status, EscrowStatus::Locked);
+    assert_eq!(escrow.remaining_amount, 700);
+
+    let milestones = setup.escrow.get_milestones(&360);
+    assert_eq!(milestones.len(), 3);
+    assert!(milestones.get(0).unwrap().released);
+    assert!(!milestones.get(1).unwrap().released);
+}
+
+#[test]
+fn test_release_all_milestones_marks_released() {
+    let setup = TestSetup::new();
+    let now = lock_three_milestones(&setup, 361);
+
+    setup.env.ledger().set_timestamp(now + 300);
+    setup.escrow.release_milestone(&361, &2, &setup.contributor);
+    setup.escrow.release_milestone(&361, &0, &setup.contributor);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&361).status,
+        EscrowStatus::Locked
+    );
+    setup.escrow.release_milestone(&361, &1, &setup.contributor);
+
+    let escrow = setup.escrow.get_escrow_info(&361);
+    assert_eq!(escrow.status, EscrowStatus::Released);
+    assert_eq!(escrow.remaining_amount, 0);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+}
+
+#[test]
+fn test_unreleased_milestones_refundable_after_final_deadline() {
+    let setup = TestSetup::new();
+    let now = lock_three_milestones(&setup, 362);
+
+    setup.env.ledger().set_timestamp(now + 150);
+    setup.escrow.release_milestone(&362, &0, &setup.contributor);
+    assert!(setup.escrow.try_refund(&362).is_err());
+
+    setup.env.ledger().set_timestamp(now + 301);
+    setup.escrow.refund(&362);
+
+    assert_eq!(setup.token.balance(&setup.contributor), 300);
+    assert_eq!(setup.token.balance(&setup.depositor), 1_000_000 - 300);
+    let escrow = setup.escrow.get_escrow_info(&362);
+    assert_eq!(escrow.status, EscrowStatus::Refunded);
+    assert_eq!(escrow.remaining_amount, 0);
+}
+
+#[test]
+fn test_milestone_escrow_rejects_top_up_and_partial_release() {
+    let setup = TestSetup::new();
+    let now = lock_three_milestones(&setup, 363);
+
+    assert_eq!(
+        setup
+            .escrow
+            .try_top_up(&363, &500, &None)
+            .unwrap_err()
+            .unwrap(),
+        Error::MilestoneLocked
+    );
+    assert_eq!(
+        setup
+            .escrow
+            .try_release_partial(&363, &setup.contributor, &100)
+            .unwrap_err()
+            .unwrap(),
+        Error::MilestoneLocked
+    );
+
+    // The balance still matches the milestones, so the last one pays out
+    setup.env.ledger().set_timestamp(now + 300);
+    for index in 0..3u32 {
+        setup
+            .escrow
+            .release_milestone(&363, &index, &setup.contributor);
+    }
+    let escrow = setup.escrow.get_escrow_info(&363);
+    assert_eq!(escrow.status, EscrowStatus::Released);
+    assert_eq!(escrow.remaining_amount, 0);
+}
+
+// =============================================================================
+// Alternate refund address
+// =============================================================================
+
+#[test]
+fn test_refund_goes_to_refund_address() {
+    let setup = TestSetup::new();
+    let cold_wallet = Address::generate(&setup.env);
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup.escrow.lock_funds_with_refund_address(
+        &setup.depositor,
+        &370,
+        &1_000,
+        &deadline,
+        &cold_wallet,
+    );
+    assert_eq!(
+        setup.escrow.get_escrow(&370).unwrap().refund_address,
+        Some(cold_wallet.clone())
+    );
+
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund(&370);
+
+    assert_eq!(setup.token.balance(&cold_wallet), 1_000);
+    assert_eq!(setup.token.balance(&setup.depositor), 1_000_000 - 1_000);
+    let entry = setup
+        .escrow
+        .get_refund_history_paged(&0, &1)
+        .get(0)
+        .unwrap();
+    assert_eq!(entry.depositor, setup.depositor);
+    assert_eq!(entry.recipient, cold_wallet);
+    let record = setup
+        .escrow
+        .get_escrow_info(&370)
+        .refund_history
+        .get(0)
+        .unwrap();
+    assert_eq!(record.recipient, cold_wallet);
+}
+
+#[test]
+fn test_refund_without_refund_address_goes_to_depositor() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &371, &1_000, &deadline);
+    assert_eq!(setup.escrow.get_escrow(&371).unwrap().refund_address, None);
+
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund(&371);
+
+    assert_eq!(setup.token.balance(&setup.depositor), 1_000_000);
+    let entry = setup
+        .escrow
+        .get_refund_history_paged(&0, &1)
+        .get(0)
+        .unwrap();
+    assert_eq!(entry.recipient, setup.depositor);
+}
+
+#[test]
+fn test_lock_with_refund_address_requires_depositor_auth() {
+    let setup = TestSetup::new();
+    let cold_wallet = Address::generate(&setup.env);
+    let deadline = setup.env.ledger().timestamp() + 100;
+
+    setup.env.set_auths(&[]);
+    let result = setup.escrow.try_lock_funds_with_refund_address(
+        &setup.depositor,
+        &372,
+        &1_000,
+        &deadline,
+        &cold_wallet,
+    );
+    assert!(result.is_err());
+    assert!(setup.escrow.get_escrow(&372).is_none());
+}
+
+// =============================================================================
+// Bulk refund of expired escrows
+// =============================================================================
+
+#[test]
+fn test_refund_expired_batch_respects_limit_and_policy() {
+    let setup = TestSetup::new();
+    let treasury = Address::generate(&setup.env);
+    setup.escrow.set_fee_collector(&treasury, &setup._admin);
+    let now = setup.env.ledger().timestamp();
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &380, &1_000, &(now + 100));
+    setup.escrow.lock_funds_with_refund_policy(
+        &setup.depositor,
+        &381,
+        &1_000,
+        &(now + 100),
+        &RefundPolicy::Partial(5_000),
+    );
+    setup.escrow.lock_funds_with_refund_policy(
+        &setup.depositor,
+        &382,
+        &1_000,
+        &(now + 100),
+        &RefundPolicy::None,
+    );
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &383, &1_000, &(now + 1_000));
+
+    setup.env.ledger().set_timestamp(now + 101);
+    assert_eq!(setup.escrow.refund_expired_batch(&1), 1);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&380).status,
+        EscrowStatus::Refunded
+    );
+    assert_eq!(
+        setup.escrow.get_escrow_info(&381).status,
+        EscrowStatus::Locked
+    );
+
+    // Second call drains the rest; the no-refund escrow and the one not
+    // yet expired are left untouched
+    assert_eq!(setup.escrow.refund_expired_batch(&10), 1);
+    assert_eq!(setup.escrow.refund_expired_batch(&10), 0);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&381).status,
+        EscrowStatus::Refunded
+    );
+    assert_eq!(
+        setup.escrow.get_escrow_info(&382).status,
+        EscrowStatus::Locked
+    );
+    assert_eq!(
+        setup.escrow.get_escrow_info(&383).status,
+        EscrowStatus::Locked
+    );
+
+    assert_eq!(setup.token.balance(&treasury), 500);
+    assert_eq!(
+        setup.token.balance(&setup.depositor),
+        1_000_000 - 4_000 + 1_000 + 500
+    );
+    assert_eq!(setup.escrow.get_refund_history_paged(&0, &10).len(), 2);
+    let stats = setup.escrow.get_depositor_stats(&setup.depositor);
+    assert_eq!(stats.total_refunded, 1_500);
+}
+
+#[test]
+fn test_refund_expired_batch_with_nothing_expired() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &384, &1_000, &deadline);
+
+    assert_eq!(setup.escrow.refund_expired_batch(&10), 0);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&384).status,
+        EscrowStatus::Locked
+    );
+}
+
+// =============================================================================
+// Storage TTL
+// =============================================================================
+
+fn escrow_ttl(setup: &TestSetup, bounty_id: u64) -> u32 {
+    setup.env.as_contract(&setup.escrow.address, || {
+        setup
+            .env
+            .storage()
+            .persistent()
+            .get_ttl(&DataKey::Escrow(bounty_id))
+    })
+}
+
+#[test]
+fn test_lock_sets_ttl_past_deadline() {
+    let setup = TestSetup::new();
+    let now = setup.env.ledger().timestamp();
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &390, &1_000, &(now + 100));
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &391, &1_000, &(now + 5_000_000));
+
+    let short = escrow_ttl(&setup, 390);
+    let long = escrow_ttl(&setup, 391);
+    assert!(short as u64 >= ESCROW_TTL_GRACE_LEDGERS);
+    assert!(long > short);
+}
+
+#[test]
+fn test_bump_escrow_ttl_keeps_escrow_accessible() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &392, &1_000, &deadline);
+    let initial = escrow_ttl(&setup, 392);
+
+    setup
+        .env
+        .ledger()
+        .with_mut(|li| li.sequence_number += initial - 10);
+    setup.escrow.bump_escrow_ttl(&392, &1_000_000);
+    assert!(escrow_ttl(&setup, 392) >= 1_000_000);
+
+    // Past the original expiry, the escrow is still readable
+    setup
+        .env
+        .ledger()
+        .with_mut(|li| li.sequence_number += 100_000);
+    assert_eq!(setup.escrow.get_escrow_info(&392).amount, 1_000);
+}
+
+#[test]
+fn test_bump_escrow_ttl_unknown_bounty() {
+    let setup = TestSetup::new();
+    assert_eq!(
+        setup
+            .escrow
+            .try_bump_escrow_ttl(&393, &1_000)
+            .unwrap_err()
+            .unwrap(),
+        Error::BountyNotFound
+    );
+}
+
+// =============================================================================
+// Partial refund after the deadline
+// =============================================================================
+
+#[test]
+fn test_refund_partial_until_drained() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &400, &1_000, &deadline);
+
+    assert_eq!(
+        setup
+            .escrow
+            .try_refund_partial(&400, &300)
+            .unwrap_err()
+            .unwrap(),
+        Error::DeadlineNotPassed
+    );
+
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund_partial(&400, &300);
+    let escrow = setup.escrow.get_escrow_info(&400);
+    assert_eq!(escrow.status, EscrowStatus::PartiallyRefunded);
+    assert_eq!(escrow.remaining_amount, 700);
+    assert_eq!(setup.token.balance(&setup.depositor), 1_000_000 - 700);
+    assert_eq!(
+        setup
+            .escrow
+            .get_depositor_stats(&setup.depositor)
+            .total_refunded,
+        300
+    );
+
+    assert_eq!(
+        setup
+            .escrow
+            .try_refund_partial(&400, &701)
+            .unwrap_err()
+            .unwrap(),
+        Error::InvalidAmount
+    );
+
+    setup.escrow.refund_partial(&400, &200);
+    setup.escrow.refund_partial(&400, &500);
+    let escrow = setup.escrow.get_escrow_info(&400);
+    assert_eq!(escrow.status, EscrowStatus::Refunded);
+    assert_eq!(escrow.remaining_amount, 0);
+    assert_eq!(escrow.refund_history.len(), 3);
+    assert_eq!(setup.escrow.get_refund_history_paged(&0, &10).len(), 3);
+    assert_eq!(
+        setup
+            .escrow
+            .get_depositor_stats(&setup.depositor)
+            .total_refunded,
+        1_000
+    );
+    assert_eq!(setup.token.balance(&setup.depositor), 1_000_000);
+
+    assert_eq!(
+        setup
+            .escrow
+            .try_refund_partial(&400, &1)
+            .unwrap_err()
+            .unwrap(),
+        Error::AlreadyRefunded
+    );
+}
+
+#[test]
+fn test_refund_partial_then_refund_remainder() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &401, &1_000, &deadline);
+
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund_partial(&401, &400);
+    setup.escrow.refund(&401);
+
+    let escrow = setup.escrow.get_escrow_info(&401);
+    assert_eq!(escrow.status, EscrowStatus::Refunded);
+    assert_eq!(setup.token.balance(&setup.depositor), 1_000_000);
+}
+
+#[test]
+fn test_refund_partial_requires_full_refund_policy() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup.escrow.lock_funds_with_refund_policy(
+        &setup.depositor,
+        &402,
+        &1_000,
+        &deadline,
+        &RefundPolicy::Partial(5_000),
+    );
+
+    setup.env.ledger().set_timestamp(deadline + 1);
+    assert_eq!(
+        setup
+            .escrow
+            .try_refund_partial(&402, &100)
+            .unwrap_err()
+            .unwrap(),
+        Error::RefundNotAllowed
+    );
+}
+
+// =============================================================================
+// Refund grace period
+// =============================================================================
+
+#[test]
+fn test_refund_grace_period_delays_refund() {
+    let setup = TestSetup::new();
+    assert_eq!(setup.escrow.get_refund_grace_period(), 0);
+    setup.escrow.set_refund_grace_period(&600);
+    assert_eq!(setup.escrow.get_refund_grace_period(), 600);
+
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &410, &1_000, &deadline);
+
+    // At the deadline
+    setup.env.ledger().set_timestamp(deadline);
+    let (can_refund, deadline_passed, _, _, refundable_at) =
+        setup.escrow.get_refund_eligibility(&410);
+    assert!(deadline_passed);
+    assert!(!can_refund);
+    assert_eq!(refundable_at, deadline + 600);
+    assert_eq!(
+        setup.escrow.try_refund(&410).unwrap_err().unwrap(),
+        Error::DeadlineNotPassed
+    );
+
+    // Mid-grace
+    setup.env.ledger().set_timestamp(deadline + 300);
+    assert!(!setup.escrow.get_refund_eligibility(&410).0);
+    assert_eq!(
+        setup.escrow.try_refund(&410).unwrap_err().unwrap(),
+        Error::DeadlineNotPassed
+    );
+
+    // Post-grace
+    setup.env.ledger().set_timestamp(deadline + 600);
+    assert!(setup.escrow.get_refund_eligibility(&410).0);
+    setup.escrow.refund(&410);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&410).status,
+        EscrowStatus::Refunded
+    );
+}
+
+#[test]
+fn test_release_still_allowed_during_refund_grace() {
+    let setup = TestSetup::new();
+    setup.escrow.set_refund_grace_period(&600);
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &411, &1_000, &deadline);
+
+    setup.env.ledger().set_timestamp(deadline + 300);
+    assert_eq!(setup.escrow.refund_expired_batch(&10), 0);
+    setup.escrow.release_funds(&411, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+}
+
+// =============================================================================
+// Refund eligibility reasons
+// =============================================================================
+
+#[test]
+fn test_refund_eligibility_reasons_across_lifecycle() {
+    let setup = TestSetup::new();
+    setup.escrow.set_refund_grace_period(&50);
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &420, &1_000, &deadline);
+    setup.escrow.lock_funds_with_refund_policy(
+        &setup.depositor,
+        &421,
+        &1_000,
+        &deadline,
+        &RefundPolicy::None,
+    );
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &422, &1_000, &deadline);
+
+    let details = setup.escrow.get_refund_eligibility_details(&420);
+    assert!(!details.eligible);
+    assert_eq!(details.reason, RefundEligibilityReason::NotExpired);
+    assert_eq!(details.earliest_refund_time, deadline + 50);
+    assert_eq!(details.refundable, 1_000);
+
+    setup.env.ledger().set_timestamp(deadline + 50);
+    let details = setup.escrow.get_refund_eligibility_details(&420);
+    assert!(details.eligible);
+    assert_eq!(details.reason, RefundEligibilityReason::Eligible);
+    assert!(setup.escrow.get_refund_eligibility(&420).0);
+
+    assert_eq!(
+        setup.escrow.get_refund_eligibility_details(&421).reason,
+        RefundEligibilityReason::NonRefundableMode
+    );
+
+    setup.escrow.refund(&420);
+    setup.escrow.release_funds(&422, &setup.contributor);
+    for bounty_id in [420u64, 422u64] {
+        let details = setup.escrow.get_refund_eligibility_details(&bounty_id);
+        assert!(!details.eligible);
+        assert_eq!(details.reason, RefundEligibilityReason::AlreadySettled);
+    }
+
+    assert_eq!(
+        setup
+            .escrow
+            .try_get_refund_eligibility_details(&999)
+            .unwrap_err()
+            .unwrap(),
+        Error::BountyNotFound
+    );
+}
+
+#[test]
+fn test_refund_eligibility_approval_overrides_deadline() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &423, &1_000, &deadline);
+    setup
+        .escrow
+        .approve_refund(&423, &400, &setup.depositor, &RefundMode::Partial);
+
+    let details = setup.escrow.get_refund_eligibility_details(&423);
+    assert!(details.eligible);
+    assert_eq!(details.reason, RefundEligibilityReason::Eligible);
+}
+
+// =============================================================================
+// Admin force refund
+// =============================================================================
+
+#[test]
+fn test_admin_refund_before_deadline() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 10_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &430, &1_000, &deadline);
+
+    setup.escrow.admin_refund(&430);
+
+    let escrow = setup.escrow.get_escrow_info(&430);
+    assert_eq!(escrow.status, EscrowStatus::Refunded);
+    assert_eq!(escrow.remaining_amount, 0);
+    assert_eq!(setup.token.balance(&setup.depositor), 1_000_000);
+
+    let entry = setup
+        .escrow
+        .get_refund_history_paged(&0, &1)
+        .get(0)
+        .unwrap();
+    assert_eq!(entry.bounty_id, 430);
+    assert!(entry.admin_forced);
+
+    assert_eq!(
+        setup.escrow.try_admin_refund(&430).unwrap_err().unwrap(),
+        Error::AlreadyRefunded
+    );
+}
+
+#[test]
+fn test_admin_refund_requires_admin_auth() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 10_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &431, &1_000, &deadline);
+
+    setup.env.set_auths(&[]);
+    assert!(setup.escrow.try_admin_refund(&431).is_err());
+    assert_eq!(
+        setup.escrow.get_escrow_info(&431).status,
+        EscrowStatus::Locked
+    );
+}
+
+#[test]
+fn test_admin_refund_blocked_when_refunds_paused() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 10_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &432, &1_000, &deadline);
+
+    setup.escrow.set_paused(&None, &None, &Some(true), &None);
+    assert_eq!(
+        setup.escrow.try_admin_refund(&432).unwrap_err().unwrap(),
+        Error::FundsPaused
+    );
+}
+
+#[test]
+fn test_regular_refunds_are_not_admin_forced() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &433, &1_000, &deadline);
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund(&433);
+
+    let entry = setup
+        .escrow
+        .get_refund_history_paged(&0, &1)
+        .get(0)
+        .unwrap();
+    assert!(!entry.admin_forced);
+}
+
+// =============================================================================
+// Lock rate limit
+// =============================================================================
+
+#[test]
+fn test_rate_limit_defaults_to_unlimited() {
+    let setup = TestSetup::new();
+    assert_eq!(setup.escrow.get_rate_limit(), (0, 0));
+
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    for bounty_id in 440..445u64 {
+        setup
+            .escrow
+            .lock_funds(&setup.depositor, &bounty_id, &100, &deadline);
+    }
+}
+
+#[test]
+fn test_rate_limit_rejects_lock_over_limit_until_window_passes() {
+    let setup = TestSetup::new();
+    setup.escrow.set_rate_limit(&2, &100);
+    assert_eq!(setup.escrow.get_rate_limit(), (2, 100));
+
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &450, &100, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &451, &100, &deadline);
+    assert_eq!(
+        setup
+            .escrow
+            .try_lock_funds(&setup.depositor, &452, &100, &deadline)
+            .unwrap_err()
+            .unwrap(),
+        Error::RateLimited
+    );
+    assert!(setup.escrow.get_escrow(&452).is_none());
+
+    setup.env.ledger().with_mut(|li| li.sequence_number += 100);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &452, &100, &deadline);
+
+    // Removing the limit restores unlimited locking.
+    setup.escrow.set_rate_limit(&0, &0);
+    assert_eq!(setup.escrow.get_rate_limit(), (0, 0));
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &453, &100, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &454, &100, &deadline);
+}
+
+#[test]
+fn test_rate_limit_counts_each_escrow_in_lock_funds_batch() {
+    let setup = TestSetup::new();
+    setup.escrow.set_rate_limit(&3, &100);
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+
+    let too_many = vec![
+        &setup.env,
+        (455u64, 100i128, deadline),
+        (456u64, 100i128, deadline),
+        (457u64, 100i128, deadline),
+        (458u64, 100i128, deadline),
+    ];
+    assert_eq!(
+        setup
+            .escrow
+            .try_lock_funds_batch(&setup.depositor, &too_many, &None)
+            .unwrap_err()
+            .unwrap(),
+        Error::RateLimited
+    );
+    assert!(setup.escrow.get_escrow(&455).is_none());
+
+    let entries = vec![
+        &setup.env,
+        (455u64, 100i128, deadline),
+        (456u64, 100i128, deadline),
+    ];
+    setup
+        .escrow
+        .lock_funds_batch(&setup.depositor, &entries, &None);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &457, &100, &deadline);
+    assert_eq!(
+        setup
+            .escrow
+            .try_lock_funds(&setup.depositor, &458, &100, &deadline)
+            .unwrap_err()
+            .unwrap(),
+        Error::RateLimited
+    );
+}
+
+#[test]
+fn test_rate_limit_counts_each_escrow_in_batch_lock_funds() {
+    let setup = TestSetup::new();
+    setup.escrow.set_rate_limit(&2, &100);
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    let other = Address::generate(&setup.env);
+    setup.token_admin.mint(&other, &1_000);
+
+    let item = |bounty_id: u64, depositor: &Address| LockFundsItem {
+        bounty_id,
+        depositor: depositor.clone(),
+        amount: 100,
+        deadline,
+    };
+
+    // Three escrows for one depositor exceed its limit
+    let items = vec![
+        &setup.env,
+        item(465, &setup.depositor),
+        item(466, &other),
+        item(467, &setup.depositor),
+        item(468, &setup.depositor),
+    ];
+    assert_eq!(
+        setup
+            .escrow
+            .try_batch_lock_funds(&items, &None)
+            .unwrap_err()
+            .unwrap(),
+        Error::RateLimited
+    );
+    assert!(setup.escrow.get_escrow(&466).is_none());
+
+    // Limits are per depositor
+    let items = vec![
+        &setup.env,
+        item(465, &setup.depositor),
+        item(466, &other),
+        item(467, &setup.depositor),
+        item(468, &other),
+    ];
+    assert_eq!(setup.escrow.batch_lock_funds(&items, &None), 4);
+    assert_eq!(
+        setup
+            .escrow
+            .try_lock_funds(&other, &469, &100, &deadline)
+            .unwrap_err()
+            .unwrap(),
+        Error::RateLimited
+    );
+}
+
+// =============================================================================
+// Per-escrow history
+// =============================================================================
+
+#[test]
+fn test_escrow_history_records_lifecycle_in_order() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &460, &1_000, &deadline);
+
+    setup.env.ledger().set_timestamp(50);
+    setup.escrow.release_partial(&460, &setup.contributor, &300);
+
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund(&460);
+
+    let history = setup.escrow.get_escrow_history(&460);
+    assert_eq!(
+        history,
+        vec![
+            &setup.env,
+            EscrowHistoryEntry {
+                kind: EscrowHistoryKind::Locked,
+                timestamp: 0,
+                ledger: 0,
+                amount_delta: 1_000,
+            },
+            EscrowHistoryEntry {
+                kind: EscrowHistoryKind::Released,
+                timestamp: 50,
+                ledger: 0,
+                amount_delta: -300,
+            },
+            EscrowHistoryEntry {
+                kind: EscrowHistoryKind::Refunded,
+                timestamp: deadline + 1,
+                ledger: 0,
+                amount_delta: -700,
+            },
+        ]
+    );
+
+    assert_eq!(
+        setup
+            .escrow
+            .try_get_escrow_history(&999)
+            .unwrap_err()
+            .unwrap(),
+        Error::BountyNotFound
+    );
+}
+
+// =============================================================================
+// Self-release
+// =============================================================================
+
+#[test]
+fn test_release_to_depositor_counts_as_release() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &470, &1_000, &deadline);
+    let before = setup.escrow.get_aggregate_stats();
+
+    setup.escrow.release_funds(&470, &setup.depositor);
+
+    let escrow = setup.escrow.get_escrow_info(&470);
+    assert_eq!(escrow.status, EscrowStatus::Released);
+    assert_eq!(setup.token.balance(&setup.depositor), 1_000_000);
+
+    let after = setup.escrow.get_aggregate_stats();
+    assert_eq!(after.count_released, before.count_released + 1);
+    assert_eq!(after.count_refunded, before.count_refunded);
+    assert_eq!(after.total_refunded, 0);
+
+    let stats = setup.escrow.get_depositor_stats(&setup.depositor);
+    assert_eq!(stats.total_released, 1_000);
+    assert_eq!(stats.total_refunded, 0);
+    assert_eq!(
+        setup.escrow.get_escrow(&470).unwrap().released_to,
+        Some(setup.depositor.clone())
+    );
+}
+
+// =============================================================================
+// Refund on behalf of the depositor
+// =============================================================================
+
+#[test]
+fn test_refund_on_behalf_by_third_party_after_deadline() {
+    use soroban_sdk::{
+        testutils::{MockAuth, MockAuthInvoke},
+        IntoVal,
+    };
+
+    let setup = TestSetup::new();
+    let keeper = Address::generate(&setup.env);
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &480, &1_000, &deadline);
+
+    // Only the keeper signs; the depositor's auth is never provided.
+    let invoke = MockAuthInvoke {
+        contract: &setup.escrow.address,
+        fn_name: "refund_on_behalf",
+        args: (480u64, keeper.clone()).into_val(&setup.env),
+        sub_invokes: &[],
+    };
+    let keeper_auth = MockAuth {
+        address: &keeper,
+        invoke: &invoke,
+    };
+
+    setup.env.mock_auths(&[keeper_auth]);
+    assert_eq!(
+        setup
+            .escrow
+            .try_refund_on_behalf(&480, &keeper)
+            .unwrap_err()
+            .unwrap(),
+        Error::DeadlineNotPassed
+    );
+
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.env.mock_auths(&[MockAuth {
+        address: &keeper,
+        invoke: &invoke,
+    }]);
+    setup.escrow.refund_on_behalf(&480, &keeper);
+
+    assert_eq!(
+        setup.escrow.get_escrow_info(&480).status,
+        EscrowStatus::Refunded
+    );
+    assert_eq!(setup.token.balance(&setup.depositor), 1_000_000);
+    assert_eq!(setup.token.balance(&keeper), 0);
+}
+
+#[test]
+fn test_refund_on_behalf_requires_full_refund_policy() {
+    let setup = TestSetup::new();
+    let keeper = Address::generate(&setup.env);
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup.escrow.lock_funds_with_refund_policy(
+        &setup.depositor,
+        &481,
+        &1_000,
+        &deadline,
+        &RefundPolicy::Partial(5_000),
+    );
+
+    setup.env.ledger().set_timestamp(deadline + 1);
+    assert_eq!(
+        setup
+            .escrow
+            .try_refund_on_behalf(&481, &keeper)
+            .unwrap_err()
+            .unwrap(),
+        Error::RefundNotAllowed
+    );
+}
+
+// =============================================================================
+// Minimum deadline horizon
+// =============================================================================
+
+#[test]
+fn test_min_deadline_horizon_boundary() {
+    let setup = TestSetup::new();
+    assert_eq!(setup.escrow.get_min_deadline_horizon(), 0);
+    setup.escrow.set_min_deadline_horizon(&3_600);
+    assert_eq!(setup.escrow.get_min_deadline_horizon(), 3_600);
+
+    setup.env.ledger().set_timestamp(10_000);
+    // Back-to-back locks at one timestamp would trip the lock cooldown
+    setup.escrow.set_whitelist(&setup.depositor, &true);
+    let boundary = 10_000 + 3_600;
+
+    assert_eq!(
+        setup
+            .escrow
+            .try_lock_funds(&setup.depositor, &490, &1_000, &(boundary - 1))
+            .unwrap_err()
+            .unwrap(),
+        Error::DeadlineTooSoon
+    );
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &491, &1_000, &boundary);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &492, &1_000, &(boundary + 1));
+    assert_eq!(setup.escrow.get_escrow_info(&491).deadline, boundary);
+}
+
+#[test]
+fn test_lock_rejects_past_deadline() {
+    let setup = TestSetup::new();
+    setup.env.ledger().set_timestamp(10_000);
+
+    assert_eq!(
+        setup
+            .escrow
+            .try_lock_funds(&setup.depositor, &493, &1_000, &9_999)
+            .unwrap_err()
+            .unwrap(),
+        Error::InvalidDeadline
+    );
+    // With no horizon configured a deadline of "now" is still accepted
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &494, &1_000, &10_000);
+}
+
+#[test]
+fn test_min_deadline_horizon_applies_to_batch_lock() {
+    let setup = TestSetup::new();
+    setup.escrow.set_min_deadline_horizon(&100);
+    let now = setup.env.ledger().timestamp();
+    let items = vec![
+        &setup.env,
+        LockFundsItem {
+            bounty_id: 495,
+            depositor: setup.depositor.clone(),
+            amount: 1_000,
+            deadline: now + 100,
+        },
+        LockFundsItem {
+            bounty_id: 496,
+            depositor: setup.depositor.clone(),
+            amount: 1_000,
+            deadline: now + 99,
+        },
+    ];
+    assert_eq!(
+        setup
+            .escrow
+            .try_batch_lock_funds(&items, &None)
+            .unwrap_err()
+            .unwrap(),
+        Error::DeadlineTooSoon
+    );
+}
+
+// =============================================================================
+// Release condition contracts
+// =============================================================================
+
+/// Condition checker whose answer is set by the test.
+#[soroban_sdk::contract]
+pub struct MockConditionChecker;
+
+#[soroban_sdk::contractimpl]
+impl MockConditionChecker {
+    pub fn set_met(env: Env, met: bool) {
+        env.storage().instance().set(&symbol_short!("met"), &met);
+    }
+
+    pub fn check_condition(env: Env, _bounty_id: u64) -> bool {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("met"))
+            .unwrap_or(false)
+    }
+}
+
+fn lock_with_checker(setup: &TestSetup, bounty_id: u64) -> MockConditionCheckerClient<'static> {
+    let checker_id = setup.env.register_contract(None, MockConditionChecker);
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup.escrow.lock_funds_with_condition(
+        &setup.depositor,
+        &bounty_id,
+        &1_000,
+        &deadline,
+        &checker_id,
+    );
+    MockConditionCheckerClient::new(&setup.env, &checker_id)
+}
+
+#[test]
+fn test_release_blocked_until_condition_met() {
+    let setup = TestSetup::new();
+    let checker = lock_with_checker(&setup, 500);
+    assert_eq!(
+        setup.escrow.get_escrow(&500).unwrap().release_condition,
+        Some(checker.address.clone())
+    );
+
+    assert_eq!(
+        setup
+            .escrow
+            .try_release_funds(&500, &setup.contributor)
+            .unwrap_err()
+            .unwrap(),
+        Error::ConditionNotMet
+    );
+    assert_eq!(
+        setup
+            .escrow
+            .try_partial_release(&500, &setup.contributor, &100)
+            .unwrap_err()
+            .unwrap(),
+        Error::ConditionNotMet
+    );
+    assert_eq!(setup.token.balance(&setup.contributor), 0);
+
+    checker.set_met(&true);
+    setup.escrow.release_funds(&500, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+}
+
+#[test]
+fn test_condition_failure_does_not_block_refund() {
+    let setup = TestSetup::new();
+    let checker = lock_with_checker(&setup, 501);
+    checker.set_met(&false);
+
+    let deadline = setup.escrow.get_escrow_info(&501).deadline;
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund(&501);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&501).status,
+        EscrowStatus::Refunded
+    );
+}
+
+#[test]
+fn test_escrow_without_condition_releases_as_before() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &502, &1_000, &deadline);
+    assert_eq!(
+        setup.escrow.get_escrow(&502).unwrap().release_condition,
+        None
+    );
+    setup.escrow.release_funds(&502, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+}
+
+// =============================================================================
+// Refund penalty
+// =============================================================================
+
+#[test]
+fn test_refund_penalty_credits_treasury_and_rounds_down() {
+    let setup = TestSetup::new();
+    assert_eq!(setup.escrow.get_refund_penalty_bps(), 0);
+    setup.escrow.set_refund_penalty_bps(&250);
+    assert_eq!(setup.escrow.get_refund_penalty_bps(), 250);
+
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &510, &1_001, &deadline);
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund(&510);
+
+    // 2.5% of 1_001 is 25.025; the depositor keeps the fraction
+    assert_eq!(setup.token.balance(&setup._admin), 25);
+    assert_eq!(setup.token.balance(&setup.depositor), 1_000_000 - 25);
+
+    let entry = setup
+        .escrow
+        .get_refund_history_paged(&0, &1)
+        .get(0)
+        .unwrap();
+    assert_eq!(entry.gross_amount, 1_001);
+    assert_eq!(entry.amount, 976);
+
+    let stats = setup.escrow.get_aggregate_stats();
+    assert_eq!(stats.total_refunded, 976);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&510).status,
+        EscrowStatus::Refunded
+    );
+}
+
+#[test]
+fn test_refund_penalty_below_one_unit_is_waived() {
+    let setup = TestSetup::new();
+    setup.escrow.set_refund_penalty_bps(&250);
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &511, &39, &deadline);
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund(&511);
+
+    assert_eq!(setup.token.balance(&setup._admin), 0);
+    assert_eq!(setup.token.balance(&setup.depositor), 1_000_000);
+    let entry = setup
+        .escrow
+        .get_refund_history_paged(&0, &1)
+        .get(0)
+        .unwrap();
+    assert_eq!(entry.gross_amount, entry.amount);
+}
+
+#[test]
+fn test_refund_partial_withholds_penalty() {
+    let setup = TestSetup::new();
+    setup.escrow.set_refund_penalty_bps(&250);
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &512, &1_000, &deadline);
+    setup.env.ledger().set_timestamp(deadline + 1);
+
+    // Refunding the whole balance in one partial call is penalised too
+    setup.escrow.refund_partial(&512, &400);
+    setup.escrow.refund_partial(&512, &600);
+
+    assert_eq!(setup.token.balance(&setup._admin), 10 + 15);
+    assert_eq!(setup.token.balance(&setup.depositor), 1_000_000 - 25);
+
+    let first = setup
+        .escrow
+        .get_refund_history_paged(&0, &2)
+        .get(0)
+        .unwrap();
+    assert_eq!(first.gross_amount, 400);
+    assert_eq!(first.amount, 390);
+
+    // The withheld penalty is a collected fee, not released volume
+    let stats = setup.escrow.get_aggregate_stats();
+    assert_eq!(stats.total_refunded, 975);
+    assert_eq!(stats.total_released, 0);
+    assert_eq!(stats.total_fees_collected, 25);
+    let token_stats = setup
+        .escrow
+        .get_aggregate_stats_by_token(&setup.token.address);
+    assert_eq!(token_stats.total_released, 0);
+    assert_eq!(token_stats.total_fees_collected, 25);
+    let depositor_stats = setup.escrow.get_depositor_stats(&setup.depositor);
+    assert_eq!(depositor_stats.total_locked, 0);
+    assert_eq!(depositor_stats.total_released, 0);
+    assert_eq!(depositor_stats.total_refunded, 975);
+    let escrow = setup.escrow.get_escrow_info(&512);
+    assert_eq!(escrow.status, EscrowStatus::Refunded);
+    assert_eq!(escrow.remaining_amount, 0);
+}
+
+#[test]
+fn test_refund_penalty_is_capped() {
+    let setup = TestSetup::new();
+    setup.escrow.set_refund_penalty_bps(&1_000);
+    assert_eq!(
+        setup
+            .escrow
+            .try_set_refund_penalty_bps(&1_001)
+            .unwrap_err()
+            .unwrap(),
+        Error::InvalidFeeRate
+    );
+    assert_eq!(
+        setup
+            .escrow
+            .try_set_refund_penalty_bps(&-1)
+            .unwrap_err()
+            .unwrap(),
+        Error::InvalidFeeRate
+    );
+    assert_eq!(setup.escrow.get_refund_penalty_bps(), 1_000);
+}
+
+// =============================================================================
+// Release operators
+// =============================================================================
+
+#[test]
+fn test_approved_operator_can_release() {
+    let setup = TestSetup::new();
+    let operator = Address::generate(&setup.env);
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &520, &1_000, &deadline);
+
+    assert!(!setup
+        .escrow
+        .is_release_operator(&setup.depositor, &operator));
+    setup
+        .escrow
+        .set_release_operator(&setup.depositor, &operator, &true);
+    assert!(setup
+        .escrow
+        .is_release_operator(&setup.depositor, &operator));
+
+    setup
+        .escrow
+        .release_funds_by_operator(&520, &setup.contributor, &operator);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&520).status,
+        EscrowStatus::Released
+    );
+}
+
+#[test]
+fn test_revoked_or_foreign_operator_cannot_release() {
+    let setup = TestSetup::new();
+    let operator = Address::generate(&setup.env);
+    let other_depositor = Address::generate(&setup.env);
+    setup.token_admin.mint(&other_depositor, &1_000);
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &521, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&other_depositor, &522, &1_000, &deadline);
+
+    setup
+        .escrow
+        .set_release_operator(&setup.depositor, &operator, &true);
+    // Approval is scoped to the approving depositor's escrows
+    assert_eq!(
+        setup
+            .escrow
+            .try_release_funds_by_operator(&522, &setup.contributor, &operator)
+            .unwrap_err()
+            .unwrap(),
+        Error::Unauthorized
+    );
+
+    setup
+        .escrow
+        .set_release_operator(&setup.depositor, &operator, &false);
+    assert!(!setup
+        .escrow
+        .is_release_operator(&setup.depositor, &operator));
+    assert_eq!(
+        setup
+            .escrow
+            .try_release_funds_by_operator(&521, &setup.contributor, &operator)
+            .unwrap_err()
+            .unwrap(),
+        Error::Unauthorized
+    );
+    assert_eq!(setup.token.balance(&setup.contributor), 0);
+}
+
+#[test]
+fn test_operator_cannot_release_to_depositor_or_be_depositor() {
+    let setup = TestSetup::new();
+    let operator = Address::generate(&setup.env);
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &523, &1_000, &deadline);
+
+    assert_eq!(
+        setup
+            .escrow
+            .try_set_release_operator(&setup.depositor, &setup.depositor, &true)
+            .unwrap_err()
+            .unwrap(),
+        Error::Unauthorized
+    );
+
+    setup
+        .escrow
+        .set_release_operator(&setup.depositor, &operator, &true);
+    assert_eq!(
+        setup
+            .escrow
+            .try_release_funds_by_operator(&523, &setup.depositor, &operator)
+            .unwrap_err()
+            .unwrap(),
+        Error::Unauthorized
+    );
+    assert_eq!(
+        setup.escrow.get_escrow_info(&523).status,
+        EscrowStatus::Locked
+    );
+}
+
+// =============================================================================
+// Release simulation
+// =============================================================================
+
+#[test]
+fn test_simulate_release_matches_real_release() {
+    let setup = TestSetup::new();
+    setup.escrow.set_fee_bps(&500, &setup._admin);
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &530, &1_000, &deadline);
+
+    let sim = setup.escrow.simulate_release(&530, &setup.contributor);
+    assert_eq!(
+        sim,
+        SimulationResult {
+            would_succeed: true,
+            net_amount: 950,
+            fee: 50,
+            error_reason: None,
+        }
+    );
+    // Simulating leaves the escrow untouched
+    assert_eq!(
+        setup.escrow.get_escrow_info(&530).status,
+        EscrowStatus::Locked
+    );
+    assert_eq!(setup.token.balance(&setup.contributor), 0);
+
+    setup.escrow.release_funds(&530, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), sim.net_amount);
+    assert_eq!(setup.token.balance(&setup._admin), sim.fee);
+}
+
+#[test]
+fn test_simulate_release_reports_failure_reason() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &531, &1_000, &deadline);
+    setup.escrow.release_funds(&531, &setup.contributor);
+
+    for bounty_id in [531u64, 532] {
+        let sim = setup
+            .escrow
+            .simulate_release(&bounty_id, &setup.contributor);
+        let err = setup
+            .escrow
+            .try_release_funds(&bounty_id, &setup.contributor)
+            .unwrap_err()
+            .unwrap();
+        assert!(!sim.would_succeed);
+        assert_eq!(sim.net_amount, 0);
+        assert_eq!(sim.error_reason, Some(err as u32));
+    }
+    assert_eq!(
+        setup
+            .escrow
+            .simulate_release(&531, &setup.contributor)
+            .error_reason,
+        Some(Error::AlreadyReleased as u32)
+    );
+}
+
+// =============================================================================
+// Depositor auth and relayed locks
+// =============================================================================
+
+#[test]
+fn test_lock_requires_depositor_auth() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup.env.set_auths(&[]);
+    assert!(setup
+        .escrow
+        .try_lock_funds(&setup.depositor, &540, &1_000, &deadline)
+        .is_err());
+    assert_eq!(setup.token.balance(&setup.depositor), 1_000_000);
+}
+
+#[test]
+fn test_relayer_can_lock_for_depositor() {
+    let setup = TestSetup::new();
+    let relayer = Address::generate(&setup.env);
+    setup.token_admin.mint(&relayer, &1_000);
+    assert_eq!(setup.escrow.get_relayer(), None);
+    setup.escrow.set_relayer(&Some(relayer.clone()));
+    assert_eq!(setup.escrow.get_relayer(), Some(relayer.clone()));
+
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup
+        .escrow
+        .lock_funds_by_relayer(&relayer, &setup.depositor, &541, &1_000, &deadline);
+
+    let escrow = setup.escrow.get_escrow_info(&541);
+    assert_eq!(escrow.depositor, setup.depositor);
+    assert_eq!(setup.token.balance(&relayer), 0);
+    assert_eq!(setup.token.balance(&setup.depositor), 1_000_000);
+
+    // The depositor owns the escrow, so refunds go to them
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund(&541);
+    assert_eq!(setup.token.balance(&setup.depositor), 1_001_000);
+}
+
+#[test]
+fn test_random_address_cannot_lock_for_depositor() {
+    let setup = TestSetup::new();
+    let relayer = Address::generate(&setup.env);
+    let stranger = Address::generate(&setup.env);
+    setup.token_admin.mint(&stranger, &1_000);
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+
+    // No relayer configured
+    assert_eq!(
+        setup
+            .escrow
+            .try_lock_funds_by_relayer(&stranger, &setup.depositor, &542, &1_000, &deadline)
+            .unwrap_err()
+            .unwrap(),
+        Error::Unauthorized
+    );
+
+    setup.escrow.set_relayer(&Some(relayer));
+    assert_eq!(
+        setup
+            .escrow
+            .try_lock_funds_by_relayer(&stranger, &setup.depositor, &542, &1_000, &deadline)
+            .unwrap_err()
+            .unwrap(),
+        Error::Unauthorized
+    );
+
+    setup.escrow.set_relayer(&None);
+    assert_eq!(setup.escrow.get_relayer(), None);
+    assert_eq!(setup.token.balance(&stranger), 1_000);
+}
+
+// =============================================================================
+// Maximum escrow count
+// =============================================================================
+
+#[test]
+fn test_max_escrows_rejects_lock_past_cap() {
+    let setup = TestSetup::new();
+    assert_eq!(setup.escrow.get_max_escrows(), 0);
+    setup.escrow.set_max_escrows(&2);
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &510, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &511, &1_000, &deadline);
+    assert_eq!(setup.escrow.get_escrow_count(), 2);
+    assert_eq!(
+        setup
+            .escrow
+            .try_lock_funds(&setup.depositor, &512, &1_000, &deadline)
+            .unwrap_err()
+            .unwrap(),
+        Error::MaxEscrowsReached
+    );
+}
+
+#[test]
+fn test_max_escrows_counts_settled_escrows() {
+    let setup = TestSetup::new();
+    setup.escrow.set_max_escrows(&1);
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &513, &1_000, &deadline);
+    setup.escrow.release_funds(&513, &setup.contributor);
+
+    assert_eq!(
+        setup
+            .escrow
+            .try_lock_funds(&setup.depositor, &514, &1_000, &deadline)
+            .unwrap_err()
+            .unwrap(),
+        Error::MaxEscrowsReached
+    );
+
+    // Lifting the cap allows new escrows again
+    setup.escrow.set_max_escrows(&0);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &514, &1_000, &deadline);
+}
+
+// =============================================================================
+// Release with a depositor-signed approval
+// =============================================================================
+
+/// Mock only the depositor's signature over `approval`; the submitting relayer
+/// provides no auth at all.
+fn mock_depositor_approval(setup: &TestSetup, approval: &SignedReleaseApproval) {
+    use soroban_sdk::{
+        testutils::{MockAuth, MockAuthInvoke},
+        IntoVal,
+    };
+
+    setup.env.mock_auths(&[MockAuth {
+        address: &setup.depositor,
+        invoke: &MockAuthInvoke {
+            contract: &setup.escrow.address,
+            fn_name: "release_with_approval",
+            args: (approval.clone(),).into_val(&setup.env),
+            sub_invokes: &[],
+        },
+    }]);
+}
+
+#[test]
+fn test_release_with_valid_approval() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &520, &1_000, &deadline);
+
+    let approval = SignedReleaseApproval {
+        bounty_id: 520,
+        contributor: setup.contributor.clone(),
+        nonce: setup.escrow.get_approval_nonce(&setup.depositor),
+        expires_at: deadline,
+    };
+    mock_depositor_approval(&setup, &approval);
+    setup
+        .escrow
+        .release_with_approval(&520, &setup.contributor, &approval);
+
+    assert_eq!(
+        setup.escrow.get_escrow_info(&520).status,
+        EscrowStatus::Released
+    );
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+    assert_eq!(setup.escrow.get_approval_nonce(&setup.depositor), 1);
+}
+
+#[test]
+fn test_release_with_tampered_approval_fails() {
+    let setup = TestSetup::new();
+    let attacker = Address::generate(&setup.env);
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &521, &1_000, &deadline);
+
+    let signed = SignedReleaseApproval {
+        bounty_id: 521,
+        contributor: setup.contributor.clone(),
+        nonce: 0,
+        expires_at: deadline,
+    };
+    mock_depositor_approval(&setup, &signed);
+
+    // The approval no longer matches the call's contributor.
+    assert_eq!(
+        setup
+            .escrow
+            .try_release_with_approval(&521, &attacker, &signed)
+            .unwrap_err()
+            .unwrap(),
+        Error::Unauthorized
+    );
+
+    // Rewriting the approval itself invalidates the depositor's signature.
+    let tampered = SignedReleaseApproval {
+        contributor: attacker.clone(),
+        ..signed
+    };
+    assert!(setup
+        .escrow
+        .try_release_with_approval(&521, &attacker, &tampered)
+        .is_err());
+
+    assert_eq!(
+        setup.escrow.get_escrow_info(&521).status,
+        EscrowStatus::Locked
+    );
+    assert_eq!(setup.token.balance(&attacker), 0);
+}
+
+#[test]
+fn test_release_with_replayed_approval_fails() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &522, &1_000, &deadline);
+
+    let approval = SignedReleaseApproval {
+        bounty_id: 522,
+        contributor: setup.contributor.clone(),
+        nonce: 0,
+        expires_at: deadline,
+    };
+    mock_depositor_approval(&setup, &approval);
+    setup
+        .escrow
+        .release_with_approval(&522, &setup.contributor, &approval);
+
+    mock_depositor_approval(&setup, &approval);
+    assert_eq!(
+        setup
+            .escrow
+            .try_release_with_approval(&522, &setup.contributor, &approval)
+            .unwrap_err()
+            .unwrap(),
+        Error::Unauthorized
+    );
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+}
+
+#[test]
+fn test_release_with_expired_or_self_paying_approval_fails() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &523, &1_000, &deadline);
+
+    let to_depositor = SignedReleaseApproval {
+        bounty_id: 523,
+        contributor: setup.depositor.clone(),
+        nonce: 0,
+        expires_at: deadline,
+    };
+    mock_depositor_approval(&setup, &to_depositor);
+    assert_eq!(
+        setup
+            .escrow
+            .try_release_with_approval(&523, &setup.depositor, &to_depositor)
+            .unwrap_err()
+            .unwrap(),
+        Error::Unauthorized
+    );
+
+    let approval = SignedReleaseApproval {
+        bounty_id: 523,
+        contributor: setup.contributor.clone(),
+        nonce: 0,
+        expires_at: setup.env.ledger().timestamp() + 100,
+    };
+    setup.env.ledger().set_timestamp(approval.expires_at + 1);
+    mock_depositor_approval(&setup, &approval);
+    assert_eq!(
+        setup
+            .escrow
+            .try_release_with_approval(&523, &setup.contributor, &approval)
+            .unwrap_err()
+            .unwrap(),
+        Error::Unauthorized
+    );
+    assert_eq!(setup.escrow.get_approval_nonce(&setup.depositor), 0);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&523).status,
+        EscrowStatus::Locked
+    );
+}
+
+// =============================================================================
+// Treasury
+// =============================================================================
+
+#[test]
+fn test_treasury_accrues_fees_and_penalties() {
+    let setup = TestSetup::new();
+    let treasury = Address::generate(&setup.env);
+    setup.escrow.set_treasury(&treasury);
+    assert_eq!(setup.escrow.get_treasury(), Some(treasury.clone()));
+    setup.escrow.set_fee_bps(&500, &setup._admin);
+    setup.escrow.set_refund_penalty_bps(&1_000);
+
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &530, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &531, &2_000, &deadline);
+    setup.escrow.release_funds(&530, &setup.contributor);
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund(&531);
+
+    // 5% release fee on 1_000 plus 10% refund penalty on 2_000
+    assert_eq!(
+        setup.escrow.get_treasury_balance(&setup.token.address),
+        50 + 200
+    );
+    assert_eq!(setup.token.balance(&setup._admin), 0);
+    assert_eq!(setup.token.balance(&treasury), 0);
+
+    let (balance, locked) = setup.escrow.get_solvency();
+    assert_eq!(locked, 0);
+    assert_eq!(balance, 250);
+}
+
+#[test]
+fn test_withdraw_treasury_reduces_balance_and_is_capped() {
+    let setup = TestSetup::new();
+    let treasury = Address::generate(&setup.env);
+    setup.escrow.set_treasury(&treasury);
+    setup.escrow.set_fee_bps(&1_000, &setup._admin);
+
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &532, &1_000, &deadline);
+    setup.escrow.release_funds(&532, &setup.contributor);
+    let token = setup.token.address.clone();
+    assert_eq!(setup.escrow.get_treasury_balance(&token), 100);
+
+    setup.escrow.withdraw_treasury(&token, &40, &treasury);
+    assert_eq!(setup.escrow.get_treasury_balance(&token), 60);
+    assert_eq!(setup.token.balance(&treasury), 40);
+
+    assert_eq!(
+        setup
+            .escrow
+            .try_withdraw_treasury(&token, &61, &treasury)
+            .unwrap_err()
+            .unwrap(),
+        Error::InsufficientFunds
+    );
+    assert_eq!(setup.escrow.get_treasury_balance(&token), 60);
+}
+
+#[test]
+fn test_treasury_balance_is_kept_per_token() {
+    let setup = TestSetup::new();
+    let treasury = Address::generate(&setup.env);
+    setup.escrow.set_treasury(&treasury);
+    setup.escrow.set_fee_bps(&1_000, &setup._admin);
+    let (other_token, other_admin) = create_token_contract(&setup.env, &setup._admin);
+    other_admin.mint(&setup.depositor, &5_000);
+    setup.escrow.add_allowed_token(&other_token.address);
+
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &533, &1_000, &deadline);
+    setup.escrow.lock_funds_with_token(
+        &setup.depositor,
+        &534,
+        &2_000,
+        &deadline,
+        &other_token.address,
+    );
+    setup.escrow.release_funds(&533, &setup.contributor);
+    setup.escrow.release_funds(&534, &setup.contributor);
+
+    // Each fee is booked against the token it was paid in.
+    assert_eq!(setup.escrow.get_treasury_balance(&setup.token.address), 100);
+    assert_eq!(setup.escrow.get_treasury_balance(&other_token.address), 200);
+    assert_eq!(other_token.balance(&setup._admin), 0);
+
+    // A withdrawal in one token cannot draw on the other token's credits.
+    assert_eq!(
+        setup
+            .escrow
+            .try_withdraw_treasury(&setup.token.address, &150, &treasury)
+            .unwrap_err()
+            .unwrap(),
+        Error::InsufficientFunds
+    );
+    setup
+        .escrow
+        .withdraw_treasury(&other_token.address, &150, &treasury);
+    assert_eq!(other_token.balance(&treasury), 150);
+    assert_eq!(setup.token.balance(&treasury), 0);
+    assert_eq!(setup.escrow.get_treasury_balance(&other_token.address), 50);
+    assert_eq!(setup.escrow.get_treasury_balance(&setup.token.address), 100);
+}
+
+// =============================================================================
+// Depositor-namespaced bounty ids
+// =============================================================================
+
+#[test]
+fn test_namespaced_ids_do_not_collide_across_depositors() {
+    let setup = TestSetup::new();
+    let other = Address::generate(&setup.env);
+    setup.token_admin.mint(&other, &5_000);
+    let deadline = setup.env.ledger().timestamp() + 100;
+
+    let mine = setup
+        .escrow
+        .lock_funds_namespaced(&setup.depositor, &7, &1_000, &deadline);
+    let theirs = setup
+        .escrow
+        .lock_funds_namespaced(&other, &7, &3_000, &deadline);
+    assert_ne!(mine, theirs);
+    // The flat id space is untouched
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &7, &500, &deadline);
+
+    assert_eq!(
+        setup
+            .escrow
+            .get_escrow_namespaced(&setup.depositor, &7)
+            .amount,
+        1_000
+    );
+    assert_eq!(setup.escrow.get_escrow_namespaced(&other, &7).amount, 3_000);
+    assert_eq!(setup.escrow.get_namespaced_id(&setup.depositor, &7), mine);
+
+    setup
+        .escrow
+        .release_funds_namespaced(&setup.depositor, &7, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+    assert_eq!(
+        setup.escrow.get_escrow_namespaced(&other, &7).status,
+        EscrowStatus::Locked
+    );
+    assert_eq!(
+        setup.escrow.get_escrow_info(&7).status,
+        EscrowStatus::Locked
+    );
+
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund_namespaced(&other, &7);
+    assert_eq!(setup.token.balance(&other), 5_000);
+    assert_eq!(
+        setup
+            .escrow
+            .get_escrow_namespaced(&setup.depositor, &7)
+            .status,
+        EscrowStatus::Released
+    );
+}
+
+#[test]
+fn test_namespaced_id_reuse_by_same_depositor_fails() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds_namespaced(&setup.depositor, &8, &1_000, &deadline);
+
+    assert_eq!(
+        setup
+            .escrow
+            .try_lock_funds_namespaced(&setup.depositor, &8, &1_000, &deadline)
+            .unwrap_err()
+            .unwrap(),
+        Error::DuplicateBountyId
+    );
+    assert_eq!(
+        setup
+            .escrow
+            .try_get_escrow_namespaced(&setup.contributor, &8)
+            .unwrap_err()
+            .unwrap(),
+        Error::BountyNotFound
+    );
+}
+
+// =============================================================================
+// Release notifier
+// =============================================================================
+
+/// Notifier that remembers the last payout it was told about, or traps
+/// once `set_failing(true)` is called.
+#[soroban_sdk::contract]
+pub struct MockReleaseNotifier;
+
+#[soroban_sdk::contractimpl]
+impl MockReleaseNotifier {
+    pub fn set_failing(env: Env, failing: bool) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("failing"), &failing);
+    }
+
+    pub fn on_release(env: Env, bounty_id: u64, contributor: Address, amount: i128) {
+        if env
+            .storage()
+            .instance()
+            .get(&symbol_short!("failing"))
+            .unwrap_or(false)
+        {
+            panic!("notifier unavailable");
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("last"), &(bounty_id, contributor, amount));
+    }
+
+    pub fn last(env: Env) -> Option<(u64, Address, i128)> {
+        env.storage().instance().get(&symbol_short!("last"))
+    }
+}
+
+#[test]
+fn test_release_calls_notifier() {
+    let setup = TestSetup::new();
+    let notifier_id = setup.env.register_contract(None, MockReleaseNotifier);
+    let notifier = MockReleaseNotifierClient::new(&setup.env, &notifier_id);
+    setup.escrow.set_notifier(&Some(notifier_id.clone()));
+    assert_eq!(setup.escrow.get_notifier(), Some(notifier_id));
+
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &540, &1_000, &deadline);
+    setup.escrow.release_funds(&540, &setup.contributor);
+
+    assert_eq!(
+        notifier.last(),
+        Some((540, setup.contributor.clone(), 1_000))
+    );
+}
+
+#[test]
+fn test_panicking_notifier_does_not_revert_release() {
+    use soroban_sdk::{testutils::Events, TryFromVal};
+
+    let setup = TestSetup::new();
+    let notifier_id = setup.env.register_contract(None, MockReleaseNotifier);
+    MockReleaseNotifierClient::new(&setup.env, &notifier_id).set_failing(&true);
+    setup.escrow.set_notifier(&Some(notifier_id.clone()));
+
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &541, &1_000, &deadline);
+    setup.escrow.release_funds(&541, &setup.contributor);
+
+    assert_eq!(
+        setup.escrow.get_escrow_info(&541).status,
+        EscrowStatus::Released
+    );
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+
+    let warning = setup
+        .env
+        .events()
+        .all()
+        .iter()
+        .find(|(contract, topics, _)| {
+            *contract == setup.escrow.address
+                && Symbol::try_from_val(&setup.env, &topics.get(0).unwrap())
+                    == Ok(symbol_short!("notif_err"))
+        })
+        .map(|(_, _, data)| events::NotifierFailed::try_from_val(&setup.env, &data).unwrap())
+        .expect("NotifierFailed not emitted");
+    assert_eq!(warning.notifier, notifier_id);
+    assert_eq!(warning.bounty_id, 541);
+    assert_eq!(warning.amount, 1_000);
+}
+
+// ============================================================================
+// Time-to-release metric
+// ============================================================================
+
+#[test]
+fn test_avg_time_to_release() {
+    let setup = TestSetup::new();
+    assert_eq!(setup.escrow.get_avg_time_to_release(), 0);
+
+    let start = setup.env.ledger().timestamp();
+    let deadline = start + 10_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &550, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &551, &1_000, &deadline);
+    assert_eq!(setup.escrow.get_avg_time_to_release(), 0);
+
+    setup.env.ledger().set_timestamp(start + 100);
+    setup.escrow.release_funds(&550, &setup.contributor);
+    assert_eq!(setup.escrow.get_avg_time_to_release(), 100);
+
+    setup.env.ledger().set_timestamp(start + 300);
+    setup.escrow.release_funds(&551, &setup.contributor);
+    assert_eq!(setup.escrow.get_avg_time_to_release(), 200);
+}
+
+#[test]
+fn test_avg_time_to_release_skips_escrows_without_lock_time() {
+    let setup = TestSetup::new();
+    let start = setup.env.ledger().timestamp();
+    let deadline = start + 10_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &552, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &553, &1_000, &deadline);
+
+    // Escrows locked before the lock time was recorded have no stamp.
+    setup.env.as_contract(&setup.escrow.address, || {
+        setup
+            .env
+            .storage()
+            .persistent()
+            .remove(&RecordKey::CreatedAt(552));
+    });
+
+    setup.env.ledger().set_timestamp(start + 500);
+    setup.escrow.release_funds(&552, &setup.contributor);
+    assert_eq!(setup.escrow.get_avg_time_to_release(), 0);
+    setup.escrow.release_funds(&553, &setup.contributor);
+    assert_eq!(setup.escrow.get_avg_time_to_release(), 500);
+}
+
+// ============================================================================
+// Typed error codes
+// ============================================================================
+
+#[test]
+fn test_error_codes_are_stable() {
+    assert_eq!(Error::NotInitialized as u32, 2);
+    assert_eq!(Error::BountyExists as u32, 3);
+    assert_eq!(Error::FundsNotLocked as u32, 5);
+    assert_eq!(Error::DeadlineNotPassed as u32, 6);
+    assert_eq!(Error::Unauthorized as u32, 7);
+    assert_eq!(Error::InvalidAmount as u32, 13);
+    assert_eq!(Error::AmountBelowMinimum as u32, 19);
+    assert_eq!(Error::RateLimited as u32, 42);
+}
+
+#[test]
+fn test_lifecycle_failures_return_typed_errors() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+
+    assert_eq!(
+        setup
+            .escrow
+            .try_release_funds(&560, &setup.contributor)
+            .unwrap_err()
+            .unwrap(),
+        Error::BountyNotFound
+    );
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &560, &1_000, &deadline);
+    assert_eq!(
+        setup
+            .escrow
+            .try_lock_funds(&setup.depositor, &560, &1_000, &deadline)
+            .unwrap_err()
+            .unwrap(),
+        Error::DuplicateBountyId
+    );
+    assert_eq!(
+        setup.escrow.try_refund(&560).unwrap_err().unwrap(),
+        Error::DeadlineNotPassed
+    );
+
+    setup.escrow.release_funds(&560, &setup.contributor);
+    assert_eq!(
+        setup
+            .escrow
+            .try_release_funds(&560, &setup.contributor)
+            .unwrap_err()
+            .unwrap(),
+        Error::AlreadyReleased
+    );
+}
+
+#[test]
+fn test_cooldown_violation_returns_rate_limited() {
+    let setup = TestSetup::new();
+    setup.env.ledger().set_timestamp(1_000);
+    let deadline = 5_000;
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &561, &100, &deadline);
+    assert_eq!(
+        setup
+            .escrow
+            .try_lock_funds(&setup.depositor, &562, &100, &deadline)
+            .unwrap_err()
+            .unwrap(),
+        Error::RateLimited
+    );
+}
+
+#[test]
+fn test_invalid_amount_policy_returns_invalid_amount() {
+    let setup = TestSetup::new();
+    assert_eq!(
+        setup
+            .escrow
+            .try_set_amount_policy(&setup._admin, &5_000, &100)
+            .unwrap_err()
+            .unwrap(),
+        Error::InvalidAmount
+    );
+}
+
+// ============================================================================
+// Multi-admin actions
+// ============================================================================
+
+#[test]
+fn test_admin_action_executes_only_after_threshold() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin_a = Address::generate(&env);
+    let admin_b = Address::generate(&env);
+    let admin_c = Address::generate(&env);
+    let (token, _) = create_token_contract(&env, &admin_a);
+    let escrow = create_escrow_contract(&env);
+    escrow.init_with_admins(
+        &vec![&env, admin_a.clone(), admin_b.clone(), admin_c.clone()],
+        &2,
+        &token.address,
+    );
+    assert_eq!(escrow.get_admin_threshold(), 2);
+
+    // Direct fee changes are gated behind the admin threshold.
+    assert_eq!(
+        escrow
+            .try_update_fee_config(&Some(100), &None, &None, &Some(true))
+            .unwrap_err()
+            .unwrap(),
+        Error::Unauthorized
+    );
+
+    let fee_config = FeeConfig {
+        lock_fee_rate: 100,
+        release_fee_rate: 50,
+        fee_recipient: admin_c.clone(),
+        fee_enabled: true,
+    };
+    let action_id =
+        escrow.propose_action(&admin_a, &AdminAction::UpdateFeeConfig(fee_config.clone()));
+
+    assert_eq!(
+        escrow.try_execute_action(&action_id).unwrap_err().unwrap(),
+        Error::InsufficientApprovals
+    );
+    // A repeated approval from the proposer does not count twice.
+    escrow.approve_action(&admin_a, &action_id);
+    assert_eq!(
+        escrow.try_execute_action(&action_id).unwrap_err().unwrap(),
+        Error::InsufficientApprovals
+    );
+    assert_ne!(escrow.get_fee_config(), fee_config);
+
+    escrow.approve_action(&admin_b, &action_id);
+    escrow.execute_action(&action_id);
+    assert_eq!(escrow.get_fee_config(), fee_config);
+
+    // Executed actions are consumed.
+    assert!(escrow.get_admin_action(&action_id).is_none());
+    assert_eq!(
+        escrow.try_execute_action(&action_id).unwrap_err().unwrap(),
+        Error::InsufficientApprovals
+    );
+}
+
+#[test]
+fn test_multi_admin_gates_fund_and_fee_entrypoints() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin_a = Address::generate(&env);
+    let admin_b = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin_a);
+    token_admin.mint(&depositor, &10_000);
+    let escrow = create_escrow_contract(&env);
+    escrow.init_with_admins(
+        &vec![&env, admin_a.clone(), admin_b.clone()],
+        &2,
+        &token.address,
+    );
+
+    let run = |action: AdminAction| {
+        let action_id = escrow.propose_action(&admin_a, &action);
+        escrow.approve_action(&admin_b, &action_id);
+        escrow.execute_action(&action_id);
+    };
+
+    // Every direct fund-moving or fee-changing call is refused.
+    let errors = [
+        escrow.try_set_fee_bps(&100, &admin_a).unwrap_err().unwrap(),
+        escrow
+            .try_set_fee_collector(&treasury, &admin_a)
+            .unwrap_err()
+            .unwrap(),
+        escrow
+            .try_set_refund_penalty_bps(&100)
+            .unwrap_err()
+            .unwrap(),
+        escrow.try_set_treasury(&treasury).unwrap_err().unwrap(),
+        escrow
+            .try_withdraw_treasury(&token.address, &1, &treasury)
+            .unwrap_err()
+            .unwrap(),
+        escrow
+            .try_emergency_withdraw(&treasury, &None)
+            .unwrap_err()
+            .unwrap(),
+        escrow.try_migrate().unwrap_err().unwrap(),
+    ];
+    for error in errors {
+        assert_eq!(error, Error::Unauthorized);
+    }
+
+    run(AdminAction::SetFeeBps(1_000));
+    run(AdminAction::SetFeeCollector(admin_b.clone()));
+    run(AdminAction::SetRefundPenaltyBps(500));
+    run(AdminAction::SetTreasury(treasury.clone()));
+    run(AdminAction::Migrate);
+    assert_eq!(escrow.get_fee_config().release_fee_rate, 1_000);
+    assert_eq!(escrow.get_fee_config().fee_recipient, admin_b);
+    assert_eq!(escrow.get_refund_penalty_bps(), 500);
+    assert_eq!(escrow.get_treasury(), Some(treasury.clone()));
+
+    let deadline = env.ledger().timestamp() + 1_000;
+    escrow.lock_funds(&depositor, &1, &1_000, &deadline);
+    escrow.lock_funds(&depositor, &2, &2_000, &deadline);
+    escrow.release_funds(&1, &contributor);
+    assert_eq!(escrow.get_treasury_balance(&token.address), 100);
+
+    run(AdminAction::WithdrawTreasury(
+        token.address.clone(),
+        100,
+        treasury.clone(),
+    ));
+    assert_eq!(token.balance(&treasury), 100);
+    assert_eq!(escrow.get_treasury_balance(&token.address), 0);
+
+    escrow.set_paused(&Some(true), &None, &None, &None);
+    let recovery = Address::generate(&env);
+    run(AdminAction::EmergencyWithdraw(recovery.clone(), None));
+    assert_eq!(token.balance(&recovery), 2_000);
+}
+
+#[test]
+fn test_first_admin_alone_cannot_redirect_escrow_funds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin_a = Address::generate(&env);
+    let admin_b = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin_a);
+    token_admin.mint(&depositor, &10_000);
+    let escrow = create_escrow_contract(&env);
+    escrow.init_with_admins(
+        &vec![&env, admin_a.clone(), admin_b.clone()],
+        &2,
+        &token.address,
+    );
+    let deadline = env.ledger().timestamp() + 1_000;
+    escrow.lock_funds(&depositor, &1, &1_000, &deadline);
+    escrow.lock_funds(&depositor, &2, &1_000, &deadline);
+    escrow.lock_funds(&depositor, &3, &1_000, &deadline);
+
+    let errors = [
+        escrow
+            .try_approve_refund(&1, &1_000, &attacker, &RefundMode::Full)
+            .unwrap_err()
+            .unwrap(),
+        escrow.try_admin_refund(&1).unwrap_err().unwrap(),
+        escrow.try_set_arbiter(&attacker).unwrap_err().unwrap(),
+        escrow
+            .try_grant_role(&Role::Arbiter, &attacker)
+            .unwrap_err()
+            .unwrap(),
+        escrow
+            .try_revoke_role(&Role::Pauser, &admin_b)
+            .unwrap_err()
+            .unwrap(),
+    ];
+    for error in errors {
+        assert_eq!(error, Error::Unauthorized);
+    }
+
+    // Nor can it settle a dispute through the implicit arbiter role
+    escrow.raise_dispute(&2, &depositor);
+    assert!(!escrow.has_role(&Role::Arbiter, &admin_a));
+    assert_eq!(
+        escrow
+            .try_resolve_dispute(&2, &attacker, &1_000, &admin_a)
+            .unwrap_err()
+            .unwrap(),
+        Error::Unauthorized
+    );
+
+    // Early refunds to the depositor's own address need no action
+    escrow.approve_refund(&3, &1_000, &depositor, &RefundMode::Full);
+    escrow.refund(&3);
+    assert_eq!(token.balance(&depositor), 8_000);
+
+    let run = |action: AdminAction| {
+        let action_id = escrow.propose_action(&admin_a, &action);
+        assert_eq!(
+            escrow.try_execute_action(&action_id).unwrap_err().unwrap(),
+            Error::InsufficientApprovals
+        );
+        escrow.approve_action(&admin_b, &action_id);
+        escrow.execute_action(&action_id);
+    };
+    let arbiter = Address::generate(&env);
+    run(AdminAction::SetArbiter(arbiter.clone()));
+    run(AdminAction::SetRole(Role::Pauser, arbiter.clone(), true));
+    assert_eq!(escrow.get_arbiter(), Some(arbiter.clone()));
+    assert!(escrow.has_role(&Role::Pauser, &arbiter));
+
+    let recipient = Address::generate(&env);
+    run(AdminAction::ApproveRefund(
+        1,
+        1_000,
+        recipient.clone(),
+        RefundMode::Full,
+    ));
+    escrow.refund(&1);
+    assert_eq!(token.balance(&recipient), 1_000);
+
+    escrow.resolve_dispute(&2, &depositor, &0, &arbiter);
+    escrow.lock_funds(&depositor, &4, &1_000, &deadline);
+    run(AdminAction::AdminRefund(4));
+    assert_eq!(escrow.get_escrow_info(&4).status, EscrowStatus::Refunded);
+    assert_eq!(token.balance(&depositor), 9_000);
+}
+
+#[test]
+fn test_admin_action_rejects_non_admins() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin_a = Address::generate(&env);
+    let admin_b = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let (token, _) = create_token_contract(&env, &admin_a);
+    let escrow = create_escrow_contract(&env);
+    escrow.init_with_admins(
+        &vec![&env, admin_a.clone(), admin_b.clone()],
+        &2,
+        &token.address,
+    );
+
+    let action = AdminAction::UpdateFeeConfig(escrow.get_fee_config());
+    assert_eq!(
+        escrow
+            .try_propose_action(&outsider, &action)
+            .unwrap_err()
+            .unwrap(),
+        Error::Unauthorized
+    );
+    let action_id = escrow.propose_action(&admin_a, &action);
+    assert_eq!(
+        escrow
+            .try_approve_action(&outsider, &action_id)
+            .unwrap_err()
+            .unwrap(),
+        Error::Unauthorized
+    );
+}
+
+#[test]
+fn test_single_admin_keeps_direct_calls() {
+    let setup = TestSetup::new();
+    assert_eq!(
+        setup.escrow.get_admins(),
+        vec![&setup.env, setup._admin.clone()]
+    );
+    assert_eq!(setup.escrow.get_admin_threshold(), 1);
+
+    setup
+        .escrow
+        .update_fee_config(&Some(100), &None, &None, &Some(true));
+    assert_eq!(setup.escrow.get_fee_config().lock_fee_rate, 100);
+
+    // A lone admin's proposal already meets the threshold.
+    let mut fee_config = setup.escrow.get_fee_config();
+    fee_config.lock_fee_rate = 200;
+    let action_id = setup
+        .escrow
+        .propose_action(&setup._admin, &AdminAction::UpdateFeeConfig(fee_config));
+    setup.escrow.execute_action(&action_id);
+    assert_eq!(setup.escrow.get_fee_config().lock_fee_rate, 200);
+}
+
+#[test]
+fn test_init_with_admins_rejects_bad_threshold() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin_a = Address::generate(&env);
+    let admin_b = Address::generate(&env);
+    let (token, _) = create_token_contract(&env, &admin_a);
+    let escrow = create_escrow_contract(&env);
+
+    let admins = vec![&env, admin_a.clone(), admin_b.clone()];
+    assert_eq!(
+        escrow
+            .try_init_with_admins(&admins, &3, &token.address)
+            .unwrap_err()
+            .unwrap(),
+        Error::InvalidBatchSize
+    );
+    assert_eq!(
+        escrow
+            .try_init_with_admins(
+                &vec![&env, admin_a.clone(), admin_a.clone()],
+                &2,
+                &token.address
+            )
+            .unwrap_err()
+            .unwrap(),
+        Error::InvalidBatchSize
+    );
+    assert_eq!(
+        escrow
+            .try_init_with_admins(&vec![&env, admin_a.clone()], &0, &token.address)
+            .unwrap_err()
+            .unwrap(),
+        Error::InvalidBatchSize
+    );
+    assert_eq!(
+        escrow
+            .try_init_with_admins(&Vec::new(&env), &1, &token.address)
+            .unwrap_err()
+            .unwrap(),
+        Error::InvalidBatchSize
+    );
+    assert_eq!(
+        escrow.try_get_admins().unwrap_err().unwrap(),
+        Error::NotInitialized
+    );
+}
+
+// ============================================================================
+// Data migration
+// ============================================================================
+
+#[test]
+fn test_migrate_backfills_refund_totals_idempotently() {
+    let setup = TestSetup::new();
+    assert_eq!(setup.escrow.get_data_version(), DATA_VERSION);
+    assert_eq!(setup.escrow.migrate(), DATA_VERSION);
+
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &570, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &571, &2_000, &deadline);
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund(&570);
+    setup.escrow.refund(&571);
+
+    // Simulate state written by version 1, which kept no version marker
+    // and no refund running totals.
+    setup.env.as_contract(&setup.escrow.address, || {
+        let storage = setup.env.storage();
+        storage.instance().remove(&ConfigKey::DataVersion);
+        storage.persistent().remove(&RecordKey::RefundLogTotal(1));
+        storage.persistent().remove(&RecordKey::RefundLogTotal(2));
+    });
+    assert_eq!(setup.escrow.get_data_version(), 1);
+    assert_eq!(setup.escrow.sum_refunds_between(&0, &u64::MAX), 0);
+
+    assert_eq!(setup.escrow.migrate(), DATA_VERSION);
+    assert_eq!(setup.escrow.get_data_version(), DATA_VERSION);
+    assert_eq!(setup.escrow.sum_refunds_between(&0, &u64::MAX), 3_000);
+
+    // Running again changes nothing.
+    assert_eq!(setup.escrow.migrate(), DATA_VERSION);
+    assert_eq!(setup.escrow.sum_refunds_between(&0, &u64::MAX), 3_000);
+}
+
+/// `RefundHistoryEntry` as stored before refunds recorded their recipient,
+/// admin override and penalty.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyRefundHistoryEntry {
+    pub bounty_id: u64,
+    pub depositor: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+/// Store `count` refund log entries in the oldest layout, as a version 1
+/// contract would have left them.
+fn seed_legacy_refund_log(setup: &TestSetup, count: u64) {
+    setup.env.as_contract(&setup.escrow.address, || {
+        let storage = setup.env.storage();
+        for seq in 0..count {
+            storage.persistent().set(
+                &DataKey::RefundLog(seq),
+                &LegacyRefundHistoryEntry {
+                    bounty_id: 700 + seq,
+                    depositor: setup.depositor.clone(),
+                    amount: 10,
+                    timestamp: seq,
+                },
+            );
+        }
+        storage.persistent().set(&DataKey::RefundLogCount, &count);
+        storage.instance().remove(&ConfigKey::DataVersion);
+    });
+}
+
+#[test]
+fn test_migrate_rewrites_legacy_refund_log_entries() {
+    let setup = TestSetup::new();
+    seed_legacy_refund_log(&setup, 2);
+
+    assert_eq!(setup.escrow.migrate(), DATA_VERSION);
+    let history = setup.escrow.get_refund_history_paged(&0, &10);
+    assert_eq!(history.len(), 2);
+    let entry = history.get(1).unwrap();
+    assert_eq!(entry.bounty_id, 701);
+    assert_eq!(entry.recipient, setup.depositor);
+    assert_eq!(entry.amount, 10);
+    assert_eq!(entry.gross_amount, 10);
+    assert!(!entry.admin_forced);
+    assert_eq!(setup.escrow.sum_refunds_between(&0, &u64::MAX), 20);
+}
+
+#[test]
+fn test_migrate_resumes_across_calls() {
+    let setup = TestSetup::new();
+    seed_legacy_refund_log(&setup, 60);
+
+    // The first call stops partway through the refund log.
+    assert_eq!(setup.escrow.migrate(), 1);
+    assert_eq!(setup.escrow.get_data_version(), 1);
+
+    let mut calls = 1;
+    while setup.escrow.migrate() < DATA_VERSION {
+        calls += 1;
+    }
+    assert!(calls > 1);
+    assert_eq!(setup.escrow.get_data_version(), DATA_VERSION);
+    assert_eq!(setup.escrow.sum_refunds_between(&0, &u64::MAX), 600);
+    assert_eq!(setup.escrow.sum_refunds_between(&10, &19), 100);
+    let history = setup.escrow.get_refund_history_paged(&0, &100);
+    assert_eq!(history.len(), 60);
+    assert_eq!(history.get(59).unwrap().gross_amount, 10);
+}
+
+#[test]
+fn test_migrate_recovers_lock_times_from_history() {
+    let setup = TestSetup::new();
+    let start = setup.env.ledger().timestamp();
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &720, &1_000, &(start + 10_000));
+    setup.env.as_contract(&setup.escrow.address, || {
+        let storage = setup.env.storage();
+        storage.persistent().remove(&RecordKey::CreatedAt(720));
+        storage.instance().set(&ConfigKey::DataVersion, &5u32);
+    });
+
+    assert_eq!(setup.escrow.migrate(), DATA_VERSION);
+    setup.env.ledger().set_timestamp(start + 400);
+    setup.escrow.release_funds(&720, &setup.contributor);
+    assert_eq!(setup.escrow.get_avg_time_to_release(), 400);
+}
+
+#[test]
+fn test_migrate_rekeys_legacy_treasury_balance() {
+    let setup = TestSetup::new();
+    // Simulate version 3 state, which kept one init-token treasury balance.
+    setup.env.as_contract(&setup.escrow.address, || {
+        let storage = setup.env.storage();
+        storage.instance().set(&ConfigKey::DataVersion, &3u32);
+        storage
+            .instance()
+            .set(&ConfigKey::TreasuryBalance, &250i128);
+    });
+    assert_eq!(setup.escrow.get_treasury_balance(&setup.token.address), 0);
+
+    assert_eq!(setup.escrow.migrate(), DATA_VERSION);
+    assert_eq!(setup.escrow.get_treasury_balance(&setup.token.address), 250);
+    setup.env.as_contract(&setup.escrow.address, || {
+        assert!(!setup
+            .env
+            .storage()
+            .instance()
+            .has(&ConfigKey::TreasuryBalance));
+    });
+}
+
+// ============================================================================
+// Reviewer release approvals
+// ============================================================================
+
+#[test]
+fn test_escrow_without_reviewer_releases_normally() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &580, &1_000, &deadline);
+
+    assert_eq!(setup.escrow.get_reviewer(&580), None);
+    setup.escrow.release_funds(&580, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+}
+
+#[test]
+fn test_reviewed_escrow_requires_depositor_and_reviewer() {
+    let setup = TestSetup::new();
+    let reviewer = Address::generate(&setup.env);
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup
+        .escrow
+        .lock_funds_with_reviewer(&setup.depositor, &581, &1_000, &deadline, &reviewer);
+    assert_eq!(setup.escrow.get_reviewer(&581), Some(reviewer.clone()));
+
+    assert_eq!(
+        setup
+            .escrow
+            .try_release_funds(&581, &setup.contributor)
+            .unwrap_err()
+            .unwrap(),
+        Error::InsufficientApprovals
+    );
+
+    let outsider = Address::generate(&setup.env);
+    assert_eq!(
+        setup
+            .escrow
+            .try_approve_release(&581, &outsider)
+            .unwrap_err()
+            .unwrap(),
+        Error::Unauthorized
+    );
+
+    setup.escrow.approve_release(&581, &setup.depositor);
+    assert_eq!(
+        setup
+            .escrow
+            .try_release_funds(&581, &setup.contributor)
+            .unwrap_err()
+            .unwrap(),
+        Error::InsufficientApprovals
+    );
+
+    setup.escrow.approve_release(&581, &reviewer);
+    assert_eq!(
+        setup.escrow.get_release_approvals(&581),
+        vec![&setup.env, setup.depositor.clone(), reviewer]
+    );
+    setup.escrow.release_funds(&581, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+}
+
+// ============================================================================
+// Active TVL
+// ============================================================================
+
+#[test]
+fn test_active_tvl_drops_expired_escrows() {
+    let setup = TestSetup::new();
+    let now = setup.env.ledger().timestamp();
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &590, &1_000, &(now + 100));
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &591, &2_000, &(now + 500));
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &592, &4_000, &(now + 500));
+    setup.escrow.release_funds(&592, &setup.contributor);
+    assert_eq!(setup.escrow.get_active_tvl(), 3_000);
+
+    // 590 expires but stays locked until someone refunds it.
+    setup.env.ledger().set_timestamp(now + 100);
+    assert_eq!(setup.escrow.get_active_tvl(), 2_000);
+    assert_eq!(setup.escrow.get_aggregate_stats().total_locked, 3_000);
+
+    setup.escrow.refund(&590);
+    assert_eq!(setup.escrow.get_active_tvl(), 2_000);
+
+    setup.env.ledger().set_timestamp(now + 500);
+    assert_eq!(setup.escrow.get_active_tvl(), 0);
+}
+
+#[test]
+fn test_active_tvl_follows_top_ups_and_deadline_changes() {
+    let setup = TestSetup::new();
+    let now = setup.env.ledger().timestamp();
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &593, &1_000, &(now + 100));
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &594, &2_000, &(now + 10_000));
+
+    // Topping up moves 593 to a later deadline with its new amount.
+    setup.escrow.top_up(&593, &500, &Some(now + 300));
+    setup.env.ledger().set_timestamp(now + 200);
+    assert_eq!(setup.escrow.get_active_tvl(), 3_500);
+
+    setup.env.ledger().set_timestamp(now + 300);
+    assert_eq!(setup.escrow.get_active_tvl(), 2_000);
+    assert_eq!(setup.escrow.get_status().active_tvl, 2_000);
+}
+
+#[test]
+fn test_migrate_rebuilds_active_tvl() {
+    let setup = TestSetup::new();
+    let now = setup.env.ledger().timestamp();
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &595, &1_000, &(now + 100));
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &596, &2_000, &(now + 500));
+
+    // Simulate version 6 state, which kept no TVL totals.
+    setup.env.as_contract(&setup.escrow.address, || {
+        let storage = setup.env.storage();
+        storage.instance().remove(&ConfigKey::LockedTvl);
+        storage.persistent().remove(&RecordKey::LockedByDeadline);
+        storage.instance().set(&ConfigKey::DataVersion, &6u32);
+    });
+    assert_eq!(setup.escrow.get_active_tvl(), 0);
+
+    assert_eq!(setup.escrow.migrate(), DATA_VERSION);
+    assert_eq!(setup.escrow.get_active_tvl(), 3_000);
+    setup.env.ledger().set_timestamp(now + 100);
+    assert_eq!(setup.escrow.get_active_tvl(), 2_000);
+}
+
+// ============================================================================
+// Event verbosity
+// ============================================================================
+
+fn escrow_event_topics(setup: &TestSetup) -> Vec<Symbol> {
+    escrow_event_topics_since(setup, 0)
+}
+
+/// Like `escrow_event_topics`, skipping the first `from` recorded events.
+fn escrow_event_topics_since(setup: &TestSetup, from: u32) -> Vec<Symbol> {
+    use soroban_sdk::{testutils::Events, IntoVal};
+
+    let mut topics = Vec::new(&setup.env);
+    for (contract, event_topics, _) in setup.env.events().all().slice(from..).iter() {
+        if contract == setup.escrow.address {
+            topics.push_back(event_topics.get(0).unwrap().into_val(&setup.env));
+        }
+    }
+    topics
+}
+
+#[test]
+fn test_minimal_event_level_keeps_only_core_events() {
+    use soroban_sdk::testutils::Events;
+
+    let setup = TestSetup::new();
+    assert_eq!(setup.escrow.get_event_level(), EventLevel::Verbose);
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &600_001, &1_000, &deadline);
+    let topics = escrow_event_topics(&setup);
+    assert!(topics.contains(symbol_short!("f_lock")));
+    assert!(topics.contains(symbol_short!("dep_idx")));
+
+    setup.escrow.set_event_level(&EventLevel::Minimal);
+    assert_eq!(setup.escrow.get_event_level(), EventLevel::Minimal);
+    // Recorded events accumulate, so only look at those after the switch
+    let from = setup.env.events().all().len();
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &600_002, &1_000, &deadline);
+    let topics = escrow_event_topics_since(&setup, from);
+    assert!(topics.contains(symbol_short!("f_lock")));
+    assert!(!topics.contains(symbol_short!("dep_idx")));
+
+    setup.escrow.release_funds(&600_002, &setup.contributor);
+    let topics = escrow_event_topics_since(&setup, from);
+    assert!(topics.contains(symbol_short!("f_rel")));
+    assert!(!topics.contains(symbol_short!("dep_idx")));
+
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund(&600_001);
+    let topics = escrow_event_topics_since(&setup, from);
+    assert!(topics.contains(symbol_short!("f_ref")));
+    assert!(!topics.contains(symbol_short!("dep_idx")));
+}
+
+// ============================================================================
+// Escrow existence
+// ============================================================================
+
+#[test]
+fn test_escrow_exists_covers_settled_escrows() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    assert!(!setup.escrow.escrow_exists(&620));
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &620, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &621, &1_000, &deadline);
+    assert!(setup.escrow.escrow_exists(&620));
+
+    setup.escrow.release_funds(&620, &setup.contributor);
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund(&621);
+    assert!(setup.escrow.escrow_exists(&620));
+    assert!(setup.escrow.escrow_exists(&621));
+    assert!(!setup.escrow.escrow_exists(&622));
+}
+
+// ============================================================================
+// Contract status
+// ============================================================================
+
+#[test]
+fn test_get_status_reports_health() {
+    let env = Env::default();
+    let escrow = create_escrow_contract(&env);
+    let status = escrow.get_status();
+    assert!(!status.initialized);
+    assert_eq!(status.admin, None);
+    assert_eq!(status.escrow_count, 0);
+
+    let setup = TestSetup::new();
+    assert_eq!(
+        setup.escrow.get_status(),
+        ContractStatus {
+            initialized: true,
+            paused: false,
+            admin: Some(setup._admin.clone()),
+            lock_fee_bps: 0,
+            release_fee_bps: 0,
+            escrow_count: 0,
+            active_tvl: 0,
+        }
+    );
+
+    setup.escrow.set_fee_bps(&250, &setup._admin);
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &630, &1_000, &deadline);
+    setup.escrow.pause(&setup._admin);
+
+    let status = setup.escrow.get_status();
+    assert!(status.paused);
+    assert_eq!(status.release_fee_bps, 250);
+    assert_eq!(status.escrow_count, 1);
+    assert_eq!(status.active_tvl, 1_000);
+}
+
+// ============================================================================
+// Slippage-protected release
+// ============================================================================
+
+#[test]
+fn test_fee_increase_reverts_protected_release_only() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &640, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &641, &1_000, &deadline);
+
+    // Fee raised after the contributor agreed to a 1_000 payout.
+    setup.escrow.set_fee_bps(&250, &setup._admin);
+
+    assert_eq!(
+        setup
+            .escrow
+            .try_release_funds_with_min_net(&640, &setup.contributor, &1_000)
+            .unwrap_err()
+            .unwrap(),
+        Error::SlippageExceeded
+    );
+    assert_eq!(
+        setup.escrow.get_escrow_info(&640).status,
+        EscrowStatus::Locked
+    );
+
+    setup.escrow.release_funds(&641, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 975);
+
+    // A floor the net payout still meets goes through.
+    setup
+        .escrow
+        .release_funds_with_min_net(&640, &setup.contributor, &975);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_950);
+}
+
+// ============================================================================
+// Refund anomaly auto-pause
+// ============================================================================
+
+#[test]
+fn test_refund_burst_trips_auto_pause() {
+    let setup = TestSetup::new();
+    setup.escrow.set_anomaly_threshold(&2, &100);
+    assert_eq!(setup.escrow.get_anomaly_threshold(), (2, 100));
+
+    let deadline = setup.env.ledger().timestamp() + 50;
+    for bounty_id in 650..654u64 {
+        setup
+            .escrow
+            .lock_funds(&setup.depositor, &bounty_id, &100, &deadline);
+    }
+
+    setup.env.ledger().set_timestamp(deadline);
+    setup.escrow.refund(&650);
+    setup.escrow.refund(&651);
+    assert!(!setup.escrow.is_paused());
+
+    setup.env.ledger().set_timestamp(deadline + 10);
+    setup.escrow.refund(&652);
+    assert!(setup.escrow.is_paused());
+    assert_eq!(
+        setup.escrow.try_refund(&653).unwrap_err().unwrap(),
+        Error::ContractPaused
+    );
+
+    setup.escrow.unpause(&setup._admin);
+    setup.escrow.refund(&653);
+    assert!(!setup.escrow.is_paused());
+}
+
+#[test]
+fn test_refund_expired_batch_stops_at_auto_pause() {
+    let setup = TestSetup::new();
+    setup.escrow.set_anomaly_threshold(&2, &100);
+
+    let deadline = setup.env.ledger().timestamp() + 50;
+    for bounty_id in 655..660u64 {
+        setup
+            .escrow
+            .lock_funds(&setup.depositor, &bounty_id, &100, &deadline);
+    }
+
+    setup.env.ledger().set_timestamp(deadline);
+    // The third refund trips the threshold; the last two stay locked.
+    assert_eq!(setup.escrow.refund_expired_batch(&10), 3);
+    assert!(setup.escrow.is_paused());
+    assert_eq!(
+        setup.escrow.get_escrow_info(&657).status,
+        EscrowStatus::Refunded
+    );
+    assert_eq!(
+        setup.escrow.get_escrow_info(&658).status,
+        EscrowStatus::Locked
+    );
+    assert_eq!(
+        setup.escrow.get_escrow_info(&659).status,
+        EscrowStatus::Locked
+    );
+}
+
+#[test]
+fn test_slow_refunds_do_not_trip_auto_pause() {
+    let setup = TestSetup::new();
+    setup.escrow.set_anomaly_threshold(&2, &100);
+
+    let deadline = setup.env.ledger().timestamp() + 50;
+    for bounty_id in 660..665u64 {
+        setup
+            .escrow
+            .lock_funds(&setup.depositor, &bounty_id, &100, &deadline);
+    }
+
+    for (i, bounty_id) in (660..665u64).enumerate() {
+        setup.env.ledger().set_timestamp(deadline + 60 * i as u64);
+        setup.escrow.refund(&bounty_id);
+    }
+    assert!(!setup.escrow.is_paused());
+}
+
+// ============================================================================
+// Default lock duration
+// ============================================================================
+
+#[test]
+fn test_lock_funds_simple_uses_default_duration() {
+    let setup = TestSetup::new();
+    assert_eq!(setup.escrow.get_default_duration(), 0);
+    assert_eq!(
+        setup
+            .escrow
+            .try_lock_funds_simple(&setup.depositor, &670, &1_000)
+            .unwrap_err()
+            .unwrap(),
+        Error::InvalidDeadline
+    );
+
+    setup.escrow.set_default_duration(&3_600);
+    assert_eq!(setup.escrow.get_default_duration(), 3_600);
+
+    setup.env.ledger().set_timestamp(1_000);
+    setup
+        .escrow
+        .lock_funds_simple(&setup.depositor, &670, &1_000);
+    assert_eq!(setup.escrow.get_escrow_info(&670).deadline, 4_600);
+
+    let by_deadline = setup
+        .escrow
+        .query_escrows_by_deadline(&4_600, &4_600, &0, &10);
+    assert_eq!(by_deadline.len(), 1);
+    assert_eq!(by_deadline.get(0).unwrap().bounty_id, 670);
+}
+
+// ============================================================================
+// Late release
+// ============================================================================
+
+#[test]
+fn test_release_after_deadline_is_allowed_and_flagged() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 500;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &680, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &681, &1_000, &(deadline + 10_000));
+
+    setup.escrow.release_funds(&681, &setup.contributor);
+    assert!(!escrow_event_topics(&setup).contains(symbol_short!("late_rel")));
+
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.release_funds(&680, &setup.contributor);
+    assert!(escrow_event_topics(&setup).contains(symbol_short!("late_rel")));
+    assert_eq!(
+        setup.escrow.get_escrow_info(&680).status,
+        EscrowStatus::Released
+    );
+    assert_eq!(setup.token.balance(&setup.contributor), 2_000);
+}
+
+// ============================================================================
+// Per-token aggregate stats
+// ============================================================================
+
+#[test]
+fn test_aggregate_stats_by_token_are_isolated() {
+    let setup = TestSetup::new();
+    let (other, other_admin) = create_token_contract(&setup.env, &setup._admin);
+    other_admin.mint(&setup.depositor, &10_000);
+    setup.escrow.add_allowed_token(&other.address);
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &690, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &691, &2_000, &deadline);
+    setup
+        .escrow
+        .lock_funds_with_token(&setup.depositor, &692, &5_000, &deadline, &other.address);
+
+    setup.escrow.release_funds(&690, &setup.contributor);
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund(&692);
+
+    let base = setup
+        .escrow
+        .get_aggregate_stats_by_token(&setup.token.address);
+    assert_eq!(base.total_locked, 2_000);
+    assert_eq!(base.total_released, 1_000);
+    assert_eq!(base.total_refunded, 0);
+    assert_eq!(base.count_locked, 1);
+    assert_eq!(base.count_released, 1);
+    assert_eq!(base.count_refunded, 0);
+    assert_eq!(
+        base.total_locked_by_token.get(setup.token.address.clone()),
+        Some(2_000)
+    );
+    assert_eq!(base.total_locked_by_token.len(), 1);
+
+    let alt = setup.escrow.get_aggregate_stats_by_token(&other.address);
+    assert_eq!(alt.total_locked, 0);
+    assert_eq!(alt.total_released, 0);
+    assert_eq!(alt.total_refunded, 5_000);
+    assert_eq!(alt.count_locked, 0);
+    assert_eq!(alt.count_released, 0);
+    assert_eq!(alt.count_refunded, 1);
+    assert!(alt.total_locked_by_token.is_empty());
+
+    // Counts still add up across tokens in the global view.
+    let global = setup.escrow.get_aggregate_stats();
+    assert_eq!(global.count_locked, base.count_locked + alt.count_locked);
+    assert_eq!(
+        global.count_released,
+        base.count_released + alt.count_released
+    );
+    assert_eq!(
+        global.count_refunded,
+        base.count_refunded + alt.count_refunded
+    );
+
+    let unused = Address::generate(&setup.env);
+    assert_eq!(
+        setup
+            .escrow
+            .get_aggregate_stats_by_token(&unused)
+            .count_locked,
+        0
+    );
+}
+
+// ============================================================================
+// Lock funding pre-checks
+// ============================================================================
+
+#[test]
+fn test_lock_with_insufficient_balance_returns_typed_error() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    let poor = Address::generate(&setup.env);
+    setup.token_admin.mint(&poor, &999);
+
+    assert_eq!(
+        setup
+            .escrow
+            .try_lock_funds(&poor, &700, &1_000, &deadline)
+            .unwrap_err()
+            .unwrap(),
+        Error::InsufficientFunds
+    );
+    assert!(!setup.escrow.escrow_exists(&700));
+    assert_eq!(setup.token.balance(&poor), 999);
+
+    setup.escrow.lock_funds(&poor, &700, &999, &deadline);
+    assert_eq!(setup.token.balance(&poor), 0);
+}
+
+#[test]
+fn test_lock_pulls_funds_without_an_allowance() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    assert_eq!(
+        setup
+            .token
+            .allowance(&setup.depositor, &setup.escrow.address),
+        0
+    );
+
+    // Unless allowance locks are on, the depositor authorizes the transfer
+    // itself, so a missing allowance is not a reason for a lock to fail.
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &701, &1_000, &deadline);
+    assert_eq!(setup.token.balance(&setup.escrow.address), 1_000);
+    assert_eq!(
+        setup
+            .token
+            .allowance(&setup.depositor, &setup.escrow.address),
+        0
+    );
+}
+
+#[test]
+fn test_allowance_lock_with_insufficient_allowance_returns_typed_error() {
+    let setup = TestSetup::new();
+    setup.escrow.set_allowance_locks(&true);
+    assert!(setup.escrow.get_allowance_locks());
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    let expiration = setup.env.ledger().sequence() + 1_000;
+    setup
+        .token
+        .approve(&setup.depositor, &setup.escrow.address, &999, &expiration);
+
+    assert_eq!(
+        setup
+            .escrow
+            .try_lock_funds(&setup.depositor, &702, &1_000, &deadline)
+            .unwrap_err()
+            .unwrap(),
+        Error::InsufficientAllowance
+    );
+    assert!(!setup.escrow.escrow_exists(&702));
+
+    // The lock spends the allowance rather than pulling directly.
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &702, &600, &deadline);
+    assert_eq!(setup.token.balance(&setup.escrow.address), 600);
+    assert_eq!(
+        setup
+            .token
+            .allowance(&setup.depositor, &setup.escrow.address),
+        399
+    );
+}
+
+#[test]
+fn test_batch_locks_check_funding_up_front() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    let poor = Address::generate(&setup.env);
+    setup.token_admin.mint(&poor, &1_500);
+
+    // Each item fits the balance, but together they do not.
+    let items = vec![
+        &setup.env,
+        LockFundsItem {
+            bounty_id: 703,
+            depositor: poor.clone(),
+            amount: 1_000,
+            deadline,
+        },
+        LockFundsItem {
+            bounty_id: 704,
+            depositor: poor.clone(),
+            amount: 1_000,
+            deadline,
+        },
+    ];
+    assert_eq!(
+        setup
+            .escrow
+            .try_batch_lock_funds(&items, &None)
+            .unwrap_err()
+            .unwrap(),
+        Error::InsufficientFunds
+    );
+    assert_eq!(
+        setup
+            .escrow
+            .try_lock_funds_batch(
+                &poor,
+                &vec![&setup.env, (703, 1_000, deadline), (704, 1_000, deadline)],
+                &None
+            )
+            .unwrap_err()
+            .unwrap(),
+        Error::InsufficientFunds
+    );
+
+    setup.escrow.set_allowance_locks(&true);
+    let expiration = setup.env.ledger().sequence() + 1_000;
+    setup
+        .token
+        .approve(&poor, &setup.escrow.address, &500, &expiration);
+    assert_eq!(
+        setup
+            .escrow
+            .try_lock_funds_batch(&poor, &vec![&setup.env, (703, 1_000, deadline)], &None)
+            .unwrap_err()
+            .unwrap(),
+        Error::InsufficientAllowance
+    );
+    assert_eq!(setup.token.balance(&poor), 1_500);
+}
+
+#[test]
+fn test_top_up_checks_funding_like_a_lock() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    let poor = Address::generate(&setup.env);
+    setup.token_admin.mint(&poor, &1_500);
+    setup.escrow.lock_funds(&poor, &705, &1_000, &deadline);
+
+    assert_eq!(
+        setup
+            .escrow
+            .try_top_up(&705, &501, &None)
+            .unwrap_err()
+            .unwrap(),
+        Error::InsufficientFunds
+    );
+
+    setup.escrow.set_allowance_locks(&true);
+    let expiration = setup.env.ledger().sequence() + 1_000;
+    setup
+        .token
+        .approve(&poor, &setup.escrow.address, &200, &expiration);
+    assert_eq!(
+        setup
+            .escrow
+            .try_top_up(&705, &300, &None)
+            .unwrap_err()
+            .unwrap(),
+        Error::InsufficientAllowance
+    );
+    assert_eq!(setup.escrow.get_escrow_info(&705).amount, 1_000);
+    assert_eq!(setup.token.balance(&poor), 500);
+
+    // Within the allowance, the top-up spends it
+    setup.escrow.top_up(&705, &200, &None);
+    assert_eq!(setup.escrow.get_escrow_info(&705).amount, 1_200);
+    assert_eq!(setup.token.allowance(&poor, &setup.escrow.address), 0);
+}
+
+// ============================================================================
+// Merging escrows
+// ============================================================================
+
+#[test]
+fn test_merge_three_escrows_into_one() {
+    let setup = TestSetup::new();
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &710, &1_000, &5_000);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &711, &500, &9_000);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &712, &300, &2_000);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &713, &200, &7_000);
+    setup.escrow.partial_release(&711, &setup.contributor, &100);
+    let stats_before = setup.escrow.get_aggregate_stats();
+
+    setup
+        .escrow
+        .merge_escrows(&vec![&setup.env, 711, 712, 713], &710);
+    assert!(escrow_event_topics(&setup).contains(symbol_short!("merged")));
+
+    let target = setup.escrow.get_escrow_info(&710);
+    assert_eq!(target.status, EscrowStatus::Locked);
+    assert_eq!(target.amount, 1_900);
+    assert_eq!(target.remaining_amount, 1_900);
+    assert_eq!(target.deadline, 9_000);
+    for source_id in [711u64, 712, 713] {
+        let source = setup.escrow.get_escrow_info(&source_id);
+        assert_eq!(source.status, EscrowStatus::Merged);
+        assert_eq!(source.remaining_amount, 0);
+    }
+    assert_eq!(setup.escrow.get_escrow_info(&711).amount, 100);
+
+    assert_eq!(
+        setup.escrow.count_escrows_by_status(&EscrowStatus::Locked),
+        1
+    );
+    assert_eq!(
+        setup.escrow.count_escrows_by_status(&EscrowStatus::Merged),
+        3
+    );
+    let in_range = setup
+        .escrow
+        .query_escrows_by_amount(&1_900, &1_900, &0, &10);
+    assert_eq!(in_range.len(), 1);
+    assert_eq!(in_range.get(0).unwrap().bounty_id, 710);
+
+    let stats = setup.escrow.get_aggregate_stats();
+    assert_eq!(stats.total_locked, stats_before.total_locked);
+    assert_eq!(stats.total_released, stats_before.total_released);
+    assert_eq!(stats.count_locked, 1);
+    let token_stats = setup
+        .escrow
+        .get_aggregate_stats_by_token(&setup.token.address);
+    assert_eq!(token_stats.total_locked, 1_900);
+    assert_eq!(token_stats.total_released, 100);
+
+    setup.escrow.release_funds(&710, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 2_000);
+    assert_eq!(setup.token.balance(&setup.escrow.address), 0);
+}
+
+#[test]
+fn test_merge_escrows_rejects_foreign_and_settled_sources() {
+    let setup = TestSetup::new();
+    let other = Address::generate(&setup.env);
+    setup.token_admin.mint(&other, &1_000);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &720, &1_000, &5_000);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &721, &1_000, &5_000);
+    setup.escrow.lock_funds(&other, &722, &1_000, &5_000);
+    setup.escrow.release_funds(&721, &setup.contributor);
+
+    let merge = |sources: Vec<u64>| {
+        setup
+            .escrow
+            .try_merge_escrows(&sources, &720)
+            .unwrap_err()
+            .unwrap()
+    };
+    assert_eq!(merge(vec![&setup.env, 722]), Error::Unauthorized);
+    assert_eq!(merge(vec![&setup.env, 721]), Error::AlreadyReleased);
+    assert_eq!(merge(vec![&setup.env, 720]), Error::DuplicateBountyId);
+    assert_eq!(merge(Vec::new(&setup.env)), Error::InvalidBatchSize);
+    assert_eq!(setup.escrow.get_escrow_info(&720).remaining_amount, 1_000);
+}
+
+#[test]
+fn test_merge_escrows_requires_matching_terms() {
+    let setup = TestSetup::new();
+    let reviewer = Address::generate(&setup.env);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &730, &1_000, &5_000);
+    setup.escrow.lock_funds_with_refund_policy(
+        &setup.depositor,
+        &731,
+        &1_000,
+        &5_000,
+        &RefundPolicy::None,
+    );
+    setup.escrow.lock_funds_with_allowlist(
+        &setup.depositor,
+        &732,
+        &1_000,
+        &5_000,
+        &vec![&setup.env, setup.contributor.clone()],
+    );
+    setup
+        .escrow
+        .lock_funds_with_reviewer(&setup.depositor, &733, &1_000, &5_000, &reviewer);
+    setup
+        .escrow
+        .lock_funds_with_release_time(&setup.depositor, &734, &1_000, &5_000, &4_000);
+    setup.escrow.lock_funds_with_refund_address(
+        &setup.depositor,
+        &735,
+        &1_000,
+        &5_000,
+        &Address::generate(&setup.env),
+    );
+
+    let merge = |source: u64| {
+        setup
+            .escrow
+            .try_merge_escrows(&vec![&setup.env, source], &730)
+            .unwrap_err()
+            .unwrap()
+    };
+    assert_eq!(merge(731), Error::InvalidRefundPolicy);
+    assert_eq!(merge(732), Error::ContributorMismatch);
+    assert_eq!(merge(733), Error::ConditionNotMet);
+    assert_eq!(merge(734), Error::ConditionNotMet);
+    assert_eq!(merge(735), Error::InvalidRefundPolicy);
+    assert_eq!(setup.escrow.get_escrow_info(&730).remaining_amount, 1_000);
+}
+
+#[test]
+fn test_merge_escrows_rejects_auto_release_and_milestone_escrows() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 300;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &740, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &741, &1_000, &deadline);
+    setup.escrow.mark_completed(&741, &setup.contributor);
+    lock_three_milestones(&setup, 742);
+
+    let merge = |source: u64, target: u64| {
+        setup
+            .escrow
+            .try_merge_escrows(&vec![&setup.env, source], &target)
+            .unwrap_err()
+            .unwrap()
+    };
+    // A pending auto-release on either side would pay out the merged funds
+    assert_eq!(merge(741, 740), Error::ClaimPending);
+    assert_eq!(merge(740, 741), Error::ClaimPending);
+    assert_eq!(merge(742, 740), Error::MilestoneLocked);
+    assert_eq!(merge(740, 742), Error::MilestoneLocked);
+
+    assert_eq!(setup.escrow.get_escrow_info(&740).remaining_amount, 1_000);
+    assert_eq!(setup.escrow.get_escrow_info(&741).remaining_amount, 1_000);
+    assert_eq!(setup.escrow.get_escrow_info(&742).remaining_amount, 1_000);
+}
@@ -18,10 +18,16 @@
 /// * `get_refund_history`    – history vector is populated by approved-refund path
 /// * Monitoring event emission – lock/release/refund each emit ≥ 1 event
 /// * Error flows             – failed attempts do not corrupt metrics
-use crate::{BountyEscrowContract, BountyEscrowContractClient, EscrowStatus, RefundMode};
+/// * `publish_stats_snapshot` – emits the aggregate stats as one event
+/// * `get_stats_bucket` / `list_recent_buckets` – volume lands in the right time bucket
+use crate::{
+    events::{EventLevel, StatsSnapshot},
+    BountyEscrowContract, BountyEscrowContractClient, BucketStats, EscrowStatus, RefundMode,
+};
 use soroban_sdk::{
+    symbol_short,
     testutils::{Address as _, Events, Ledger},
-    token, Address, Env,
+    token, Address, Env, IntoVal, TryFromVal, Val, Vec,
 };
 
 // ---------------------------------------------------------------------------
@@ -685,7 +691,7 @@ fn test_refund_eligibility_false_before_deadline() {
     let deadline = env.ledger().timestamp() + 2000;
     escrow.lock_funds(&depositor, &180, &1_000, &deadline);
 
-    let (can_refund, deadline_passed, remaining, approval) = escrow.get_refund_eligibility(&180);
+    let (can_refund, deadline_passed, remaining, approval, _) = escrow.get_refund_eligibility(&180);
 
     assert!(!can_refund, "should not be eligible before deadline");
     assert!(!deadline_passed);
@@ -708,7 +714,7 @@ fn test_refund_eligibility_true_after_deadline_passes() {
     escrow.lock_funds(&depositor, &181, &1_000, &deadline);
     env.ledger().set_timestamp(deadline + 1);
 
-    let (can_refund, deadline_passed, remaining, approval) = escrow.get_refund_eligibility(&181);
+    let (can_refund, deadline_passed, remaining, approval, _) = escrow.get_refund_eligibility(&181);
 
     assert!(can_refund, "should be eligible after deadline");
     assert!(deadline_passed);
@@ -733,7 +739,8 @@ fn test_refund_eligibility_false_after_release() {
     escrow.release_funds(&182, &contributor);
 
     // After release the status is Released, so can_refund must be false
-    let (can_refund, _deadline_passed, _remaining, _approval) = escrow.get_refund_eligibility(&182);
+    let (can_refund, _deadline_passed, _remaining, _approval, _) =
+        escrow.get_refund_eligibility(&182);
 
     assert!(!can_refund, "released escrow should not be refund-eligible");
 }
@@ -755,7 +762,7 @@ fn test_refund_eligibility_true_with_admin_approval_before_deadline() {
     // Admin approves a partial refund before the deadline
     escrow.approve_refund(&183, &500, &depositor, &RefundMode::Partial);
 
-    let (can_refund, deadline_passed, remaining, approval) = escrow.get_refund_eligibility(&183);
+    let (can_refund, deadline_passed, remaining, approval, _) = escrow.get_refund_eligibility(&183);
 
     // Approval present → eligible even before deadline
     assert!(can_refund, "should be eligible with admin approval");
@@ -996,7 +1003,7 @@ fn test_count_matches_query_by_status_total() {
 
     assert_eq!(
         total_count,
-        (locked.len() + released.len() + refunded.len()) as u32,
+        locked.len() + released.len() + refunded.len(),
         "get_escrow_count must equal sum of all status buckets"
     );
 }
@@ -1149,3 +1156,124 @@ fn test_get_balance_zero_after_all_escrows_settled() {
         "contract balance must be zero when all escrows are settled"
     );
 }
+
+// ===========================================================================
+// 16. Stats snapshot event
+// ===========================================================================
+
+#[test]
+fn test_publish_stats_snapshot_emits_current_stats() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+    token_admin.mint(&depositor, &10_000_000);
+
+    let deadline = env.ledger().timestamp() + 1000;
+    escrow.lock_funds(&depositor, &300, &1_000, &deadline);
+    escrow.lock_funds(&depositor, &301, &2_000, &deadline);
+    escrow.release_funds(&300, &contributor);
+    env.ledger().set_timestamp(1_234);
+
+    let returned = escrow.publish_stats_snapshot();
+
+    let (contract, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(contract, escrow.address);
+    let expected: Vec<Val> = (symbol_short!("stats"),).into_val(&env);
+    assert_eq!(topics, expected);
+    let snapshot = StatsSnapshot::try_from_val(&env, &data).unwrap();
+    assert_eq!(snapshot.timestamp, 1_234);
+    assert_eq!(snapshot.stats, escrow.get_aggregate_stats());
+    assert_eq!(snapshot.stats, returned);
+    assert_eq!(snapshot.stats.count_locked, 1);
+    assert_eq!(snapshot.stats.count_released, 1);
+    assert_eq!(snapshot.stats.total_locked, 2_000);
+
+    // Snapshots are monitoring events, so Minimal only returns the stats
+    escrow.set_event_level(&EventLevel::Minimal);
+    let from = env.events().all().len();
+    assert_eq!(escrow.publish_stats_snapshot(), returned);
+    assert_eq!(env.events().all().len(), from);
+}
+
+// ===========================================================================
+// 17. Time-bucketed volume
+// ===========================================================================
+
+#[test]
+fn test_stats_buckets_split_volume_across_boundaries() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let other = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+    token_admin.mint(&depositor, &10_000_000);
+    token_admin.mint(&other, &10_000_000);
+
+    assert_eq!(escrow.get_bucket_size(), 0);
+    assert_eq!(escrow.list_recent_buckets(&5).len(), 0);
+    escrow.set_bucket_size(&100);
+
+    // Bucket [0, 100)
+    escrow.lock_funds(&depositor, &1, &1_000, &1_000);
+    escrow.lock_funds(&depositor, &2, &2_000, &200);
+
+    // Bucket [100, 200)
+    env.ledger().set_timestamp(120);
+    escrow.lock_funds(&other, &3, &500, &1_000);
+    env.ledger().set_timestamp(199);
+    escrow.release_funds(&1, &contributor);
+
+    // Bucket [200, 300)
+    env.ledger().set_timestamp(250);
+    escrow.refund(&2);
+
+    assert_eq!(
+        escrow.get_stats_bucket(&0),
+        BucketStats {
+            bucket_start: 0,
+            locked: 3_000,
+            released: 0,
+            refunded: 0,
+        }
+    );
+    assert_eq!(
+        escrow.get_stats_bucket(&150),
+        BucketStats {
+            bucket_start: 100,
+            locked: 500,
+            released: 1_000,
+            refunded: 0,
+        }
+    );
+    assert_eq!(escrow.get_stats_bucket(&200).refunded, 2_000);
+
+    // Only three buckets exist back to time zero, oldest first
+    let recent = escrow.list_recent_buckets(&5);
+    assert_eq!(recent.len(), 3);
+    assert_eq!(recent.get(0).unwrap().bucket_start, 0);
+    assert_eq!(recent.get(1).unwrap().bucket_start, 100);
+    assert_eq!(recent.get(2).unwrap(), escrow.get_stats_bucket(&200));
+
+    // Quiet buckets are reported as zeroes
+    env.ledger().set_timestamp(450);
+    let recent = escrow.list_recent_buckets(&3);
+    assert_eq!(recent.len(), 3);
+    assert_eq!(recent.get(0).unwrap().bucket_start, 200);
+    assert_eq!(recent.get(0).unwrap().refunded, 2_000);
+    assert_eq!(
+        recent.get(2).unwrap(),
+        BucketStats {
+            bucket_start: 400,
+            ..Default::default()
+        }
+    );
+}
@@ -194,7 +194,7 @@ fn test_auto_refund_at_exact_deadline() {
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #5)")] // FundsNotLocked
+#[should_panic(expected = "Error(Contract, #41)")] // AlreadyRefunded
 fn test_auto_refund_idempotent_second_call_fails() {
     let setup = TestSetup::new();
     let bounty_id = 1;
@@ -179,7 +179,7 @@ mod test_batch_failure_modes {
         assert_eq!(result, Err(Ok(Error::DuplicateBountyId)));
     }
 
-    /// Duplicate ID that also already exists in storage → `BountyExists`.
+    /// Duplicate ID that also already exists in storage → `DuplicateBountyId`.
     #[test]
     fn batch_lock_bounty_id_already_exists_in_storage_fails() {
         let (env, client, _admin, token_admin, token_id) = setup();
@@ -196,7 +196,7 @@ mod test_batch_failure_modes {
         let items = vec![&env, lock_item(&env, 42, dep2, 500, deadline)];
 
         let result = client.try_batch_lock_funds(&items);
-        assert_eq!(result, Err(Ok(Error::BountyExists)));
+        assert_eq!(result, Err(Ok(Error::DuplicateBountyId)));
     }
 
     // -------------------------------------------------------------------------
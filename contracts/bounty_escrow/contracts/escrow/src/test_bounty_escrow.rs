@@ -1,8 +1,15 @@
-use crate::{BountyEscrowContract, BountyEscrowContractClient, Error as ContractError};
+use crate::events::{
+    DepositorIndexUpdated, EscrowLifecycleEvent, FundsLocked, FundsReleased,
+    ESCROW_EVENT_SCHEMA_VERSION,
+};
+use crate::{
+    BountyEscrowContract, BountyEscrowContractClient, Error as ContractError, EscrowStatus,
+};
 use soroban_sdk::testutils::Events;
 use soroban_sdk::{
+    symbol_short,
     testutils::{Address as _, Ledger},
-    token, Address, Env, Map, Symbol, TryFromVal, Val,
+    token, Address, Env, IntoVal, Map, Symbol, TryFromVal, Val, Vec,
 };
 
 fn create_test_env() -> (Env, BountyEscrowContractClient<'static>, Address) {
@@ -91,6 +98,270 @@ fn test_events_emit_v2_version_tags_for_all_bounty_emitters() {
     assert_current_call_has_versioned_contract_event(&env, &contract_id);
 }
 
+/// Returns the single `("escrow", action, bounty_id)` event of the current call.
+fn lifecycle_event(
+    env: &Env,
+    contract_id: &Address,
+    action: Symbol,
+    bounty_id: u64,
+) -> EscrowLifecycleEvent {
+    let expected: Vec<Val> = (symbol_short!("escrow"), action, bounty_id).into_val(env);
+    let mut found = None;
+    for (contract, topics, data) in env.events().all().iter() {
+        if contract != *contract_id || topics != expected {
+            continue;
+        }
+        assert!(found.is_none(), "lifecycle event emitted more than once");
+        found = Some(EscrowLifecycleEvent::try_from_val(env, &data).unwrap());
+    }
+    found.expect("lifecycle event not emitted")
+}
+
+/// Most recent `("dep_idx", depositor)` event published by the contract.
+fn last_depositor_index_event(
+    env: &Env,
+    contract_id: &Address,
+    depositor: &Address,
+) -> Option<DepositorIndexUpdated> {
+    let expected: Vec<Val> = (symbol_short!("dep_idx"), depositor.clone()).into_val(env);
+    let mut last = None;
+    for (contract, topics, data) in env.events().all().iter() {
+        if contract == *contract_id && topics == expected {
+            last = Some(DepositorIndexUpdated::try_from_val(env, &data).unwrap());
+        }
+    }
+    last
+}
+
+#[test]
+fn test_lifecycle_lock_event_topics_and_payload() {
+    let (env, client, contract_id) = create_test_env();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token, _token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+    client.init(&admin, &token);
+    token_admin_client.mint(&depositor, &5_000);
+
+    let deadline = env.ledger().timestamp() + 100;
+    client.lock_funds(&depositor, &7, &5_000, &deadline);
+
+    let event = lifecycle_event(&env, &contract_id, symbol_short!("lock"), 7);
+    assert_eq!(
+        event,
+        EscrowLifecycleEvent {
+            version: ESCROW_EVENT_SCHEMA_VERSION,
+            seq: event.seq,
+            bounty_id: 7,
+            depositor,
+            amount: 5_000,
+            deadline,
+            status: EscrowStatus::Locked,
+        }
+    );
+}
+
+#[test]
+fn test_lifecycle_release_event_topics_and_payload() {
+    let (env, client, contract_id) = create_test_env();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token, _token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+    client.init(&admin, &token);
+    token_admin_client.mint(&depositor, &5_000);
+
+    let deadline = env.ledger().timestamp() + 100;
+    client.lock_funds(&depositor, &8, &5_000, &deadline);
+    client.release_funds(&8, &contributor);
+
+    let event = lifecycle_event(&env, &contract_id, symbol_short!("release"), 8);
+    assert_eq!(
+        event,
+        EscrowLifecycleEvent {
+            version: ESCROW_EVENT_SCHEMA_VERSION,
+            seq: event.seq,
+            bounty_id: 8,
+            depositor: depositor.clone(),
+            amount: 5_000,
+            deadline,
+            status: EscrowStatus::Released,
+        }
+    );
+
+    // A split payout settles the escrow too and reports the total paid out.
+    token_admin_client.mint(&depositor, &3_000);
+    client.lock_funds(&depositor, &10, &3_000, &deadline);
+    let other = Address::generate(&env);
+    client.release_funds_split(
+        &10,
+        &Vec::from_array(&env, [(contributor, 2_000), (other, 1_000)]),
+    );
+
+    let event = lifecycle_event(&env, &contract_id, symbol_short!("release"), 10);
+    assert_eq!(event.amount, 3_000);
+    assert_eq!(event.status, EscrowStatus::Released);
+}
+
+/// Data of the contract event published with exactly `topics`, if any.
+fn event_with_topics(env: &Env, contract_id: &Address, topics: Vec<Val>) -> Option<Val> {
+    env.events()
+        .all()
+        .iter()
+        .find(|(contract, event_topics, _)| contract == contract_id && *event_topics == topics)
+        .map(|(_, _, data)| data)
+}
+
+#[test]
+fn test_funds_events_carry_account_topics() {
+    let (env, client, contract_id) = create_test_env();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token, _token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+    client.init(&admin, &token);
+    token_admin_client.mint(&depositor, &5_000);
+
+    let deadline = env.ledger().timestamp() + 100;
+    client.lock_funds(&depositor, &9, &5_000, &deadline);
+    let data = event_with_topics(
+        &env,
+        &contract_id,
+        (symbol_short!("f_lock"), 9u64, depositor.clone()).into_val(&env),
+    )
+    .expect("lock event not filterable by depositor");
+    let locked = FundsLocked::try_from_val(&env, &data).unwrap();
+    assert_eq!(locked.depositor, depositor);
+    assert_eq!(locked.amount, 5_000);
+
+    client.release_funds(&9, &contributor);
+    let data = event_with_topics(
+        &env,
+        &contract_id,
+        (symbol_short!("f_rel"), 9u64, contributor.clone()).into_val(&env),
+    )
+    .expect("release event not filterable by contributor");
+    let released = FundsReleased::try_from_val(&env, &data).unwrap();
+    assert_eq!(released.recipient, contributor);
+    assert_eq!(released.amount, 5_000);
+}
+
+#[test]
+fn test_lifecycle_refund_event_topics_and_payload() {
+    let (env, client, contract_id) = create_test_env();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token, _token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+    client.init(&admin, &token);
+    token_admin_client.mint(&depositor, &5_000);
+
+    let deadline = env.ledger().timestamp() + 100;
+    client.lock_funds(&depositor, &9, &5_000, &deadline);
+    env.ledger().set_timestamp(deadline + 1);
+    client.refund(&9);
+
+    let event = lifecycle_event(&env, &contract_id, symbol_short!("refund"), 9);
+    assert_eq!(
+        event,
+        EscrowLifecycleEvent {
+            version: ESCROW_EVENT_SCHEMA_VERSION,
+            seq: event.seq,
+            bounty_id: 9,
+            depositor: depositor.clone(),
+            amount: 5_000,
+            deadline,
+            status: EscrowStatus::Refunded,
+        }
+    );
+
+    // Resolving a dispute emits a release for the award and a refund for the rest.
+    let contributor = Address::generate(&env);
+    token_admin_client.mint(&depositor, &1_000);
+    let deadline = env.ledger().timestamp() + 100;
+    client.lock_funds(&depositor, &11, &1_000, &deadline);
+    client.raise_dispute(&11, &depositor);
+    client.resolve_dispute(&11, &contributor, &700, &admin);
+
+    let released = lifecycle_event(&env, &contract_id, symbol_short!("release"), 11);
+    assert_eq!(released.amount, 700);
+    assert_eq!(released.status, EscrowStatus::Released);
+    let refunded = lifecycle_event(&env, &contract_id, symbol_short!("refund"), 11);
+    assert_eq!(refunded.amount, 300);
+    assert_eq!(refunded.status, EscrowStatus::Released);
+
+    // A dispute resolved wholly for the depositor only emits the refund.
+    token_admin_client.mint(&depositor, &1_000);
+    env.ledger().set_timestamp(env.ledger().timestamp() + 60);
+    client.lock_funds(&depositor, &12, &1_000, &deadline);
+    client.raise_dispute(&12, &depositor);
+    client.resolve_dispute(&12, &contributor, &0, &admin);
+
+    let refunded = lifecycle_event(&env, &contract_id, symbol_short!("refund"), 12);
+    assert_eq!(refunded.amount, 1_000);
+    assert_eq!(refunded.status, EscrowStatus::Refunded);
+    let release_topics: Vec<Val> =
+        (symbol_short!("escrow"), symbol_short!("release"), 12u64).into_val(&env);
+    assert!(event_with_topics(&env, &contract_id, release_topics).is_none());
+}
+
+/// Append the `seq` of every event the contract published in the current call.
+fn collect_event_seqs(env: &Env, contract_id: &Address, seqs: &mut Vec<u64>) {
+    for (contract, _topics, data) in env.events().all().iter() {
+        if contract != *contract_id {
+            continue;
+        }
+        let data_map: Map<Symbol, Val> = Map::try_from_val(env, &data).unwrap();
+        let seq_val = data_map
+            .get(Symbol::new(env, "seq"))
+            .unwrap_or_else(|| panic!("event payload must contain seq field"));
+        seqs.push_back(u64::try_from_val(env, &seq_val).unwrap());
+    }
+}
+
+#[test]
+fn test_event_seq_is_contiguous_across_calls() {
+    let (env, client, contract_id) = create_test_env();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token, _token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+    token_admin_client.mint(&depositor, &10_000);
+
+    client.init(&admin, &token);
+
+    let deadline = env.ledger().timestamp() + 100;
+    client.lock_funds(&depositor, &1, &4_000, &deadline);
+    client.lock_funds(&depositor, &2, &6_000, &deadline);
+    client.release_funds(&1, &contributor);
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.refund(&2);
+
+    // The recorded events accumulate across calls, so collect them once.
+    let mut seqs: Vec<u64> = Vec::new(&env);
+    collect_event_seqs(&env, &contract_id, &mut seqs);
+
+    // init + at least one event for each of the four escrow operations.
+    assert!(seqs.len() >= 5);
+    for (expected, seq) in seqs.iter().enumerate() {
+        assert_eq!(seq, expected as u64);
+    }
+}
+
 #[test]
 fn test_lock_fund() {
     let (env, client, _contract_id) = create_test_env();
@@ -192,7 +463,7 @@ fn test_lock_funds_zero_amount_edge_case() {
 }
 
 #[test]
-#[should_panic] // Token transfer fails due to insufficient balance, protecting against overflows/invalid accounting.
+#[should_panic(expected = "Error(Contract, #16)")] // Balance is checked before the transfer, protecting against overflows/invalid accounting.
 fn test_lock_funds_insufficient_balance_rejected() {
     let (env, client, _contract_id) = create_test_env();
     let admin = Address::generate(&env);
@@ -326,12 +597,16 @@ fn test_property_fuzz_lock_release_refund_invariants() {
 
     // Lock deterministic fuzz cases.
     for (id, amount, deadline) in fuzz_cases.iter() {
+        // Each call runs in its own transaction budget, as it would on-chain.
+        env.budget().reset_default();
         client.lock_funds(&depositor, id, amount, deadline);
     }
 
     let mut expected_locked_balance = client.get_balance();
     for i in 0..40_u64 {
         let id = 2_000 + i;
+        // Each call runs in its own transaction budget, as it would on-chain.
+        env.budget().reset_default();
         if i % 3 == 0 {
             let info = client.get_escrow_info(&id);
             client.release_funds(&id, &contributor);
@@ -365,12 +640,16 @@ fn test_stress_high_load_bounty_operations() {
     for i in 0..40_u64 {
         let amount = 100 + (i as i128 % 10);
         let deadline = now + 30 + i;
+        // Each call runs in its own transaction budget, as it would on-chain.
+        env.budget().reset_default();
         client.lock_funds(&depositor, &(5_000 + i), &amount, &deadline);
     }
     assert!(client.get_balance() > 0);
 
     for i in 0..40_u64 {
         let id = 5_000 + i;
+        // Each call runs in its own transaction budget, as it would on-chain.
+        env.budget().reset_default();
         if i % 2 == 0 {
             client.release_funds(&id, &contributor);
         } else {
@@ -502,17 +781,17 @@ fn test_update_fee_config_with_max_release_fee() {
 
     client.init(&admin, &token);
 
-    // Test: Set release_fee_rate to MAX_FEE_RATE (5000 = 50%) (should succeed)
+    // Test: Set release_fee_rate to MAX_RELEASE_FEE_BPS (1000 = 10%) (should succeed)
     let result = client.try_update_fee_config(
         &None,       // lock_fee_rate: unchanged
-        &Some(5000), // release_fee_rate: 50% (MAX_FEE_RATE)
+        &Some(1000), // release_fee_rate: 10% (MAX_RELEASE_FEE_BPS)
         &Some(fee_recipient.clone()),
         &None, // fee_enabled: unchanged
     );
     assert!(result.is_ok());
 
     let config = client.get_fee_config();
-    assert_eq!(config.release_fee_rate, 5000);
+    assert_eq!(config.release_fee_rate, 1000);
     assert_eq!(config.fee_recipient, fee_recipient);
 }
 
@@ -605,7 +884,7 @@ fn test_update_fee_config_rejects_over_max_release_fee() {
     let original_config = client.get_fee_config();
 
     let result =
-        client.try_update_fee_config(&None, &Some(5001), &Some(fee_recipient.clone()), &None);
+        client.try_update_fee_config(&None, &Some(1001), &Some(fee_recipient.clone()), &None);
     assert_eq!(result, Err(Ok(ContractError::InvalidFeeRate)));
 
     let current_config = client.get_fee_config();
@@ -702,10 +981,10 @@ fn test_update_fee_config_both_rates_at_max() {
 
     client.init(&admin, &token);
 
-    // Test: Set both lock and release fees to MAX_FEE_RATE (should succeed)
+    // Test: Set both lock and release fees to their caps (should succeed)
     let result = client.try_update_fee_config(
         &Some(5000), // lock_fee_rate: 50% (MAX_FEE_RATE)
-        &Some(5000), // release_fee_rate: 50% (MAX_FEE_RATE)
+        &Some(1000), // release_fee_rate: 10% (MAX_RELEASE_FEE_BPS)
         &Some(fee_recipient.clone()),
         &None,
     );
@@ -713,7 +992,7 @@ fn test_update_fee_config_both_rates_at_max() {
 
     let config = client.get_fee_config();
     assert_eq!(config.lock_fee_rate, 5000);
-    assert_eq!(config.release_fee_rate, 5000);
+    assert_eq!(config.release_fee_rate, 1000);
 }
 
 #[test]
@@ -1149,3 +1428,137 @@ fn test_one_above_maximum_boundary_rejected() {
     // 10_001 == max(10_000) + 1 → must be rejected.
     client.lock_funds(&depositor, &10, &10_001_i128, &deadline);
 }
+
+// ── set_amount_limits / get_amount_limits ─────────────────────────────────────
+
+#[test]
+fn test_amount_limits_reject_dust_and_oversized_locks() {
+    let (env, client, _) = create_test_env();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let deadline = env.ledger().timestamp() + 100;
+
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let (token, _token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+    client.init(&admin, &token);
+    token_admin_client.mint(&depositor, &100_000);
+
+    assert_eq!(client.get_amount_limits(), (0, 0));
+    client.set_amount_limits(&100_i128, &10_000_i128);
+    assert_eq!(client.get_amount_limits(), (100, 10_000));
+
+    let below = client.try_lock_funds(&depositor, &1, &99_i128, &deadline);
+    assert_eq!(
+        below.unwrap_err().unwrap(),
+        ContractError::AmountBelowMinimum
+    );
+    let above = client.try_lock_funds(&depositor, &2, &10_001_i128, &deadline);
+    assert_eq!(
+        above.unwrap_err().unwrap(),
+        ContractError::AmountAboveMaximum
+    );
+    client.lock_funds(&depositor, &3, &10_000_i128, &deadline);
+}
+
+#[test]
+fn test_amount_limits_zero_max_means_unbounded() {
+    let (env, client, _) = create_test_env();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let deadline = env.ledger().timestamp() + 100;
+
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let (token, _token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+    client.init(&admin, &token);
+    token_admin_client.mint(&depositor, &1_000_000);
+
+    client.set_amount_limits(&100_i128, &0_i128);
+    assert_eq!(client.get_amount_limits(), (100, 0));
+    client.lock_funds(&depositor, &1, &1_000_000_i128, &deadline);
+
+    let invalid = client.try_set_amount_limits(&500_i128, &100_i128);
+    assert_eq!(invalid.unwrap_err().unwrap(), ContractError::InvalidAmount);
+}
+
+#[test]
+fn test_amount_limits_do_not_affect_existing_escrows() {
+    let (env, client, _) = create_test_env();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let deadline = env.ledger().timestamp() + 100;
+
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let (token, token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+    client.init(&admin, &token);
+    token_admin_client.mint(&depositor, &10_000);
+
+    client.lock_funds(&depositor, &1, &50_i128, &deadline);
+    client.lock_funds(&depositor, &2, &5_000_i128, &deadline);
+
+    // Tighten limits so both existing escrows would now be out of range
+    client.set_amount_limits(&100_i128, &1_000_i128);
+
+    client.release_funds(&1, &contributor);
+    env.ledger().set_timestamp(deadline + 1);
+    client.refund(&2);
+
+    assert_eq!(token_client.balance(&contributor), 50);
+    assert_eq!(token_client.balance(&depositor), 9_950);
+}
+
+// ── Depositor index events ────────────────────────────────────────────────────
+
+#[test]
+fn test_depositor_index_event_on_lock_and_release() {
+    let (env, client, contract_id) = create_test_env();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token, _token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+    client.init(&admin, &token);
+    token_admin_client.mint(&depositor, &5_000);
+
+    let deadline = env.ledger().timestamp() + 100;
+    client.lock_funds(&depositor, &9, &5_000, &deadline);
+    let locked = last_depositor_index_event(&env, &contract_id, &depositor).unwrap();
+    assert_eq!(locked.bounty_id, 9);
+    assert_eq!(locked.depositor, depositor);
+    assert_eq!(locked.status, EscrowStatus::Locked);
+
+    client.release_funds(&9, &contributor);
+    let released = last_depositor_index_event(&env, &contract_id, &depositor).unwrap();
+    assert_eq!(released.bounty_id, 9);
+    assert_eq!(released.status, EscrowStatus::Released);
+}
+
+#[test]
+fn test_depositor_index_event_on_refund() {
+    let (env, client, contract_id) = create_test_env();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token, _token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+    client.init(&admin, &token);
+    token_admin_client.mint(&depositor, &5_000);
+
+    let deadline = env.ledger().timestamp() + 100;
+    client.lock_funds(&depositor, &10, &5_000, &deadline);
+    env.ledger().set_timestamp(deadline + 1);
+    client.refund(&10);
+
+    let refunded = last_depositor_index_event(&env, &contract_id, &depositor).unwrap();
+    assert_eq!(refunded.bounty_id, 10);
+    assert_eq!(refunded.status, EscrowStatus::Refunded);
+}
@@ -1,54 +1,414 @@
-// Dispute resolution test stubs
-// These tests will be implemented once Issue 61 (dispute resolution) is complete
+// Dispute resolution tests (Issue 61)
+//
+// Covers raise_dispute / resolve_dispute / withdraw_dispute: a Disputed escrow
+// blocks both release_funds and refund until the configured arbiter splits the
+// remaining balance between the contributor and the depositor, or the dispute
+// is withdrawn and the escrow goes back to Locked.
+
+use crate::{BountyEscrowContract, BountyEscrowContractClient, Error, EscrowStatus, Role};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, vec, Address, Env,
+};
+
+struct DisputeSetup<'a> {
+    env: Env,
+    depositor: Address,
+    contributor: Address,
+    arbiter: Address,
+    token: token::Client<'a>,
+    escrow: BountyEscrowContractClient<'a>,
+}
+
+impl<'a> DisputeSetup<'a> {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let depositor = Address::generate(&env);
+        let contributor = Address::generate(&env);
+        let arbiter = Address::generate(&env);
+
+        let token_address = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        let token = token::Client::new(&env, &token_address);
+        token::StellarAssetClient::new(&env, &token_address).mint(&depositor, &10_000);
+
+        let contract_id = env.register_contract(None, BountyEscrowContract);
+        let escrow = BountyEscrowContractClient::new(&env, &contract_id);
+        escrow.init_with_arbiter(&admin, &token_address, &Some(arbiter.clone()));
+
+        Self {
+            env,
+            depositor,
+            contributor,
+            arbiter,
+            token,
+            escrow,
+        }
+    }
+
+    fn lock(&self, bounty_id: u64, amount: i128) -> u64 {
+        let deadline = self.env.ledger().timestamp() + 1000;
+        self.escrow
+            .lock_funds(&self.depositor, &bounty_id, &amount, &deadline);
+        deadline
+    }
+}
 
 #[test]
 fn test_open_dispute_blocks_release() {
-    // TODO: Once dispute resolution is implemented (Issue 61), add:
-    // 1. Lock funds for a bounty
-    // 2. Open a dispute
-    // 3. Attempt to release funds
-    // 4. Assert that release is blocked while dispute is open
+    let setup = DisputeSetup::new();
+    setup.lock(1, 1_000);
+
+    setup.escrow.raise_dispute(&1, &setup.depositor);
+
+    let result = setup.escrow.try_release_funds(&1, &setup.contributor);
+    assert_eq!(result.unwrap_err().unwrap(), Error::BountyDisputed);
 }
 
 #[test]
 fn test_open_dispute_blocks_refund() {
-    // TODO: Once dispute resolution is implemented (Issue 61), add:
-    // 1. Lock funds for a bounty
-    // 2. Wait for deadline to pass
-    // 3. Open a dispute
-    // 4. Attempt to refund
-    // 5. Assert that refund is blocked while dispute is open
+    let setup = DisputeSetup::new();
+    let deadline = setup.lock(1, 1_000);
+
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.raise_dispute(&1, &setup.depositor);
+
+    let result = setup.escrow.try_refund(&1);
+    assert_eq!(result.unwrap_err().unwrap(), Error::BountyDisputed);
 }
 
 #[test]
 fn test_resolve_dispute_in_favor_of_release() {
-    // TODO: Once dispute resolution is implemented (Issue 61), add:
-    // 1. Lock funds for a bounty
-    // 2. Open a dispute
-    // 3. Resolve dispute in favor of release
-    // 4. Verify funds are released to contributor
-    // 5. Verify escrow status is Released
-    // 6. Verify final balances are correct
+    let setup = DisputeSetup::new();
+    setup.lock(1, 1_000);
+    setup.escrow.raise_dispute(&1, &setup.depositor);
+
+    setup
+        .escrow
+        .resolve_dispute(&1, &setup.contributor, &1_000, &setup.arbiter);
+
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+    assert_eq!(setup.token.balance(&setup.escrow.address), 0);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&1).status,
+        EscrowStatus::Released
+    );
 }
 
 #[test]
 fn test_resolve_dispute_in_favor_of_refund() {
-    // TODO: Once dispute resolution is implemented (Issue 61), add:
-    // 1. Lock funds for a bounty
-    // 2. Open a dispute
-    // 3. Resolve dispute in favor of refund
-    // 4. Verify funds are refunded to depositor
-    // 5. Verify escrow status is Refunded
-    // 6. Verify final balances are correct
+    let setup = DisputeSetup::new();
+    setup.lock(1, 1_000);
+    setup.escrow.raise_dispute(&1, &setup.depositor);
+
+    setup
+        .escrow
+        .resolve_dispute(&1, &setup.contributor, &0, &setup.arbiter);
+
+    assert_eq!(setup.token.balance(&setup.depositor), 10_000);
+    assert_eq!(setup.token.balance(&setup.contributor), 0);
+    let escrow = setup.escrow.get_escrow_info(&1);
+    assert_eq!(escrow.status, EscrowStatus::Refunded);
+    assert_eq!(escrow.refund_history.len(), 1);
+}
+
+#[test]
+fn test_resolve_dispute_split_between_parties() {
+    let setup = DisputeSetup::new();
+    setup.lock(1, 1_000);
+    setup.escrow.raise_dispute(&1, &setup.depositor);
+
+    setup
+        .escrow
+        .resolve_dispute(&1, &setup.contributor, &700, &setup.arbiter);
+
+    assert_eq!(setup.token.balance(&setup.contributor), 700);
+    assert_eq!(setup.token.balance(&setup.depositor), 9_300);
+
+    let stats = setup.escrow.get_aggregate_stats();
+    assert_eq!(stats.total_released, 700);
+    assert_eq!(stats.total_refunded, 300);
 }
 
 #[test]
 fn test_dispute_status_tracking() {
-    // TODO: Once dispute resolution is implemented (Issue 61), add:
-    // 1. Lock funds for a bounty
-    // 2. Verify dispute status is not disputed
-    // 3. Open a dispute
-    // 4. Verify dispute status shows disputed with correct opener
-    // 5. Resolve dispute
-    // 6. Verify dispute status is no longer disputed
+    let setup = DisputeSetup::new();
+    setup.lock(1, 1_000);
+    assert_eq!(setup.escrow.get_dispute(&1), None);
+
+    setup.escrow.raise_dispute(&1, &setup.depositor);
+    let dispute = setup.escrow.get_dispute(&1).unwrap();
+    assert_eq!(dispute.raised_by, setup.depositor);
+    assert_eq!(
+        setup
+            .escrow
+            .query_escrows_by_status(&EscrowStatus::Disputed, &0, &10)
+            .len(),
+        1
+    );
+
+    setup
+        .escrow
+        .resolve_dispute(&1, &setup.contributor, &500, &setup.arbiter);
+    assert_eq!(setup.escrow.get_dispute(&1), None);
+    assert_eq!(setup.escrow.get_arbiter(), Some(setup.arbiter.clone()));
+}
+
+#[test]
+fn test_raise_dispute_rejects_unrelated_caller() {
+    let setup = DisputeSetup::new();
+    setup.lock(1, 1_000);
+
+    let stranger = Address::generate(&setup.env);
+    let result = setup.escrow.try_raise_dispute(&1, &stranger);
+    assert_eq!(result.unwrap_err().unwrap(), Error::Unauthorized);
+}
+
+#[test]
+fn test_claimant_raises_dispute_on_claimable_escrow() {
+    let setup = DisputeSetup::new();
+    setup.lock(1, 1_000);
+    setup.escrow.set_claim_window(&500);
+    setup.escrow.authorize_claim(&1, &setup.contributor);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&1).status,
+        EscrowStatus::Claimable
+    );
+
+    setup.escrow.raise_dispute(&1, &setup.contributor);
+    assert_eq!(
+        setup.escrow.get_dispute(&1).unwrap().raised_by,
+        setup.contributor
+    );
+    assert_eq!(
+        setup.escrow.get_escrow_info(&1).status,
+        EscrowStatus::Disputed
+    );
+
+    // The pending claim is cancelled; the arbiter decides the payout
+    assert_eq!(
+        setup.escrow.try_get_pending_claim(&1).unwrap_err().unwrap(),
+        Error::BountyNotFound
+    );
+    assert_eq!(
+        setup.escrow.try_claim(&1).unwrap_err().unwrap(),
+        Error::BountyNotFound
+    );
+
+    setup
+        .escrow
+        .resolve_dispute(&1, &setup.contributor, &1_000, &setup.arbiter);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+}
+
+#[test]
+fn test_claimant_and_depositor_withdraw_claimant_dispute() {
+    let setup = DisputeSetup::new();
+    setup.lock(1, 1_000);
+    setup.escrow.set_claim_window(&500);
+    setup.escrow.authorize_claim(&1, &setup.contributor);
+    setup.escrow.raise_dispute(&1, &setup.contributor);
+
+    setup.escrow.withdraw_dispute(&1, &setup.contributor);
+    let auths = setup.env.auths();
+    assert!(auths.iter().any(|(signer, _)| *signer == setup.depositor));
+    assert!(auths.iter().any(|(signer, _)| *signer == setup.contributor));
+    assert_eq!(
+        setup.escrow.get_escrow_info(&1).status,
+        EscrowStatus::Locked
+    );
+
+    // A new claim can be authorized once the dispute is withdrawn
+    setup.escrow.authorize_claim(&1, &setup.contributor);
+    setup.escrow.claim(&1);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+}
+
+#[test]
+fn test_resolve_requires_disputed_status() {
+    let setup = DisputeSetup::new();
+    setup.lock(1, 1_000);
+
+    let result = setup
+        .escrow
+        .try_resolve_dispute(&1, &setup.contributor, &1_000, &setup.arbiter);
+    assert_eq!(result.unwrap_err().unwrap(), Error::NotDisputed);
+}
+
+#[test]
+fn test_reassign_contributor_redirects_release() {
+    let setup = DisputeSetup::new();
+    setup.lock(1, 1_000);
+    let replacement = Address::generate(&setup.env);
+
+    setup
+        .escrow
+        .reassign_contributor(&1, &replacement, &setup.arbiter);
+    assert_eq!(
+        setup.escrow.get_assigned_contributor(&1),
+        Some(replacement.clone())
+    );
+
+    let result = setup.escrow.try_release_funds(&1, &setup.contributor);
+    assert_eq!(result.unwrap_err().unwrap(), Error::ContributorMismatch);
+
+    setup.escrow.release_funds(&1, &replacement);
+    assert_eq!(setup.token.balance(&replacement), 1_000);
+}
+
+#[test]
+fn test_reassign_contributor_while_disputed() {
+    let setup = DisputeSetup::new();
+    setup.lock(1, 1_000);
+    let replacement = Address::generate(&setup.env);
+
+    setup.escrow.raise_dispute(&1, &setup.depositor);
+    setup
+        .escrow
+        .reassign_contributor(&1, &replacement, &setup.arbiter);
+    assert_eq!(setup.escrow.get_assigned_contributor(&1), Some(replacement));
+}
+
+#[test]
+fn test_reassign_contributor_rejects_unauthorized_caller() {
+    let setup = DisputeSetup::new();
+    setup.lock(1, 1_000);
+    let stranger = Address::generate(&setup.env);
+
+    let result = setup
+        .escrow
+        .try_reassign_contributor(&1, &stranger, &stranger);
+    assert_eq!(result.unwrap_err().unwrap(), Error::Unauthorized);
+
+    let result = setup
+        .escrow
+        .try_reassign_contributor(&1, &stranger, &setup.depositor);
+    assert_eq!(result.unwrap_err().unwrap(), Error::Unauthorized);
+    assert_eq!(setup.escrow.get_assigned_contributor(&1), None);
+}
+
+#[test]
+fn test_reassign_contributor_respects_pause_and_allowlist() {
+    let setup = DisputeSetup::new();
+    let listed = Address::generate(&setup.env);
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup.escrow.lock_funds_with_allowlist(
+        &setup.depositor,
+        &1,
+        &1_000,
+        &deadline,
+        &vec![&setup.env, setup.contributor.clone(), listed.clone()],
+    );
+
+    let outsider = Address::generate(&setup.env);
+    let result = setup
+        .escrow
+        .try_reassign_contributor(&1, &outsider, &setup.arbiter);
+    assert_eq!(result.unwrap_err().unwrap(), Error::UnauthorizedContributor);
+
+    setup.escrow.grant_role(&Role::Pauser, &setup.arbiter);
+    setup.escrow.pause(&setup.arbiter);
+    let result = setup
+        .escrow
+        .try_reassign_contributor(&1, &listed, &setup.arbiter);
+    assert_eq!(result.unwrap_err().unwrap(), Error::ContractPaused);
+    assert_eq!(setup.escrow.get_assigned_contributor(&1), None);
+
+    setup.escrow.unpause(&setup.arbiter);
+    setup
+        .escrow
+        .reassign_contributor(&1, &listed, &setup.arbiter);
+    assert_eq!(setup.escrow.get_assigned_contributor(&1), Some(listed));
+}
+
+#[test]
+fn test_arbiter_withdraws_dispute_and_escrow_is_releasable() {
+    let setup = DisputeSetup::new();
+    setup.lock(1, 1_000);
+    setup.escrow.raise_dispute(&1, &setup.depositor);
+
+    setup.escrow.withdraw_dispute(&1, &setup.arbiter);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&1).status,
+        EscrowStatus::Locked
+    );
+    assert_eq!(setup.escrow.get_dispute(&1), None);
+    assert_eq!(
+        setup
+            .escrow
+            .query_escrows_by_status(&EscrowStatus::Disputed, &0, &10)
+            .len(),
+        0
+    );
+    assert_eq!(
+        setup.escrow.count_escrows_by_status(&EscrowStatus::Locked),
+        1
+    );
+
+    setup.escrow.release_funds(&1, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+}
+
+#[test]
+fn test_disputants_withdraw_dispute_and_escrow_is_refundable() {
+    let setup = DisputeSetup::new();
+    let deadline = setup.lock(1, 1_000);
+    setup.escrow.assign_contributor(&1, &setup.contributor);
+    setup.escrow.raise_dispute(&1, &setup.depositor);
+
+    setup.escrow.withdraw_dispute(&1, &setup.contributor);
+    let auths = setup.env.auths();
+    assert!(auths.iter().any(|(signer, _)| *signer == setup.depositor));
+    assert!(auths.iter().any(|(signer, _)| *signer == setup.contributor));
+    assert_eq!(
+        setup.escrow.get_escrow_info(&1).status,
+        EscrowStatus::Locked
+    );
+
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund(&1);
+    assert_eq!(setup.token.balance(&setup.depositor), 10_000);
+}
+
+#[test]
+fn test_withdraw_dispute_rejects_outsiders_and_undisputed_escrows() {
+    let setup = DisputeSetup::new();
+    setup.lock(1, 1_000);
+
+    let result = setup.escrow.try_withdraw_dispute(&1, &setup.arbiter);
+    assert_eq!(result.unwrap_err().unwrap(), Error::NotDisputed);
+
+    setup.escrow.raise_dispute(&1, &setup.depositor);
+    let stranger = Address::generate(&setup.env);
+    let result = setup.escrow.try_withdraw_dispute(&1, &stranger);
+    assert_eq!(result.unwrap_err().unwrap(), Error::Unauthorized);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&1).status,
+        EscrowStatus::Disputed
+    );
+}
+
+#[test]
+fn test_init_with_arbiter_configures_arbiter() {
+    let setup = DisputeSetup::new();
+    assert_eq!(setup.escrow.get_arbiter(), Some(setup.arbiter.clone()));
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, BountyEscrowContract);
+    let escrow = BountyEscrowContractClient::new(&env, &contract_id);
+    escrow.init_with_arbiter(&Address::generate(&env), &Address::generate(&env), &None);
+    assert_eq!(escrow.get_arbiter(), None);
+
+    let again = escrow.try_init_with_arbiter(
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &Some(Address::generate(&env)),
+    );
+    assert_eq!(again.unwrap_err().unwrap(), Error::AlreadyInitialized);
+    assert_eq!(escrow.get_arbiter(), None);
 }
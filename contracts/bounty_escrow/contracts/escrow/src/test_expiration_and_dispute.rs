@@ -98,7 +98,7 @@ fn test_pending_claim_blocks_refund() {
 
     // Verify funds were NOT refunded
     let escrow = setup.escrow.get_escrow_info(&bounty_id);
-    assert_eq!(escrow.status, EscrowStatus::Locked);
+    assert_eq!(escrow.status, EscrowStatus::Claimable);
     assert_eq!(setup.token.balance(&setup.escrow.address), amount);
 }
 
@@ -159,9 +159,9 @@ fn test_missed_claim_window_requires_admin_cancel_then_refund() {
     // Advance to after claim window but before deadline
     setup.env.ledger().set_timestamp(claim_expires_at + 1);
 
-    // Escrow is still Locked with pending claim
+    // Escrow stays Claimable, funds held, while the claim is pending
     let escrow = setup.escrow.get_escrow_info(&bounty_id);
-    assert_eq!(escrow.status, EscrowStatus::Locked);
+    assert_eq!(escrow.status, EscrowStatus::Claimable);
     assert_eq!(setup.token.balance(&setup.escrow.address), amount);
 
     // Admin cancels the expired pending claim
@@ -602,3 +602,145 @@ fn test_no_dispute_normal_refund_after_deadline() {
     assert_eq!(info.status, EscrowStatus::Refunded);
     assert_eq!(s.token.balance(&s.depositor), 10_000_000);
 }
+
+// Claimable escrows: the contributor claims in time, or the depositor
+// reclaims once the window lapses.
+#[test]
+fn test_claimable_escrow_claimed_within_window() {
+    let s = TestSetup::new();
+    let bounty_id = 110u64;
+    let amount = 1_000i128;
+    let deadline = s.env.ledger().timestamp() + 1_000;
+
+    s.escrow.set_claim_window(&300);
+    s.escrow
+        .lock_funds(&s.depositor, &bounty_id, &amount, &deadline);
+    s.escrow.authorize_claim(&bounty_id, &s.contributor);
+    assert_eq!(
+        s.escrow.get_escrow_info(&bounty_id).status,
+        EscrowStatus::Claimable
+    );
+
+    // Still inside the window: the depositor cannot reclaim yet.
+    assert_eq!(
+        s.escrow
+            .try_reclaim_unclaimed(&bounty_id)
+            .unwrap_err()
+            .unwrap(),
+        Error::DeadlineNotPassed
+    );
+
+    s.escrow.claim(&bounty_id);
+    assert_eq!(
+        s.escrow.get_escrow_info(&bounty_id).status,
+        EscrowStatus::Released
+    );
+    assert_eq!(s.token.balance(&s.contributor), amount);
+    assert_eq!(
+        s.escrow
+            .try_reclaim_unclaimed(&bounty_id)
+            .unwrap_err()
+            .unwrap(),
+        Error::AlreadyReleased
+    );
+}
+
+#[test]
+fn test_lapsed_claim_window_lets_depositor_reclaim() {
+    let s = TestSetup::new();
+    let bounty_id = 111u64;
+    let amount = 1_000i128;
+    let deadline = s.env.ledger().timestamp() + 1_000;
+
+    s.escrow.set_claim_window(&300);
+    s.escrow
+        .lock_funds(&s.depositor, &bounty_id, &amount, &deadline);
+    s.escrow.authorize_claim(&bounty_id, &s.contributor);
+    let claim = s.escrow.get_pending_claim(&bounty_id);
+
+    // Window lapses long before the escrow deadline.
+    s.env.ledger().set_timestamp(claim.expires_at + 1);
+    s.escrow.reclaim_unclaimed(&bounty_id);
+
+    let info = s.escrow.get_escrow_info(&bounty_id);
+    assert_eq!(info.status, EscrowStatus::Refunded);
+    assert_eq!(info.remaining_amount, 0);
+    assert_eq!(s.token.balance(&s.depositor), 10_000_000);
+    assert_eq!(s.token.balance(&s.contributor), 0);
+    assert!(s.escrow.try_get_pending_claim(&bounty_id).is_err());
+
+    // The forfeited claim can no longer be exercised.
+    assert!(s.escrow.try_claim(&bounty_id).is_err());
+}
+
+#[test]
+fn test_reclaim_unclaimed_requires_claimable_escrow() {
+    let s = TestSetup::new();
+    let bounty_id = 112u64;
+    let deadline = s.env.ledger().timestamp() + 1_000;
+    s.escrow
+        .lock_funds(&s.depositor, &bounty_id, &1_000, &deadline);
+
+    assert_eq!(
+        s.escrow
+            .try_reclaim_unclaimed(&bounty_id)
+            .unwrap_err()
+            .unwrap(),
+        Error::FundsNotLocked
+    );
+}
+
+#[test]
+fn test_no_deadline_escrow_never_refundable_by_time() {
+    let setup = TestSetup::new();
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &700, &1_000, &u64::MAX);
+
+    setup.env.ledger().set_timestamp(u64::MAX / 2);
+    assert_eq!(
+        setup.escrow.try_refund(&700).unwrap_err().unwrap(),
+        Error::DeadlineNotPassed
+    );
+    let (can_refund, deadline_passed, _, _, _) = setup.escrow.get_refund_eligibility(&700);
+    assert!(!can_refund);
+    assert!(!deadline_passed);
+
+    // Still releasable whenever the work is done
+    setup.escrow.release_funds(&700, &setup.contributor);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&700).status,
+        EscrowStatus::Released
+    );
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+}
+
+#[test]
+fn test_no_deadline_escrow_excluded_from_finite_deadline_ranges() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &701, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &702, &1_000, &u64::MAX);
+
+    let finite = setup
+        .escrow
+        .query_escrows_by_deadline(&0, &(u64::MAX - 1), &0, &10);
+    assert_eq!(finite.len(), 1);
+    assert_eq!(finite.get(0).unwrap().bounty_id, 701);
+
+    let all = setup
+        .escrow
+        .query_escrows_by_deadline(&0, &u64::MAX, &0, &10);
+    assert_eq!(all.len(), 2);
+    assert_eq!(
+        setup
+            .escrow
+            .get_escrows_near_deadline(&u64::MAX, &0, &10)
+            .len(),
+        1
+    );
+}
@@ -1,5 +1,8 @@
 use super::*;
-use soroban_sdk::{testutils::Address as _, token, Address, Env};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env,
+};
 
 fn create_token_contract<'a>(
     env: &Env,
@@ -22,10 +25,10 @@ fn create_escrow_contract<'a>(env: &Env) -> BountyEscrowContractClient<'a> {
 
 struct TestSetup<'a> {
     env: Env,
-    admin: Address,
+    _admin: Address,
     depositor: Address,
     token: token::Client<'a>,
-    token_admin: token::StellarAssetClient<'a>,
+    _token_admin: token::StellarAssetClient<'a>,
     escrow: BountyEscrowContractClient<'a>,
 }
 
@@ -44,10 +47,10 @@ impl<'a> TestSetup<'a> {
 
         Self {
             env,
-            admin,
+            _admin: admin,
             depositor,
             token,
-            token_admin,
+            _token_admin: token_admin,
             escrow,
         }
     }
@@ -69,7 +72,7 @@ fn test_release_race_first_recipient_wins_order_ab() {
     setup.escrow.release_funds(&bounty_id, &recipient_a);
     let second_release = setup.escrow.try_release_funds(&bounty_id, &recipient_b);
 
-    assert_eq!(second_release, Err(Ok(Error::FundsNotLocked)));
+    assert_eq!(second_release, Err(Ok(Error::AlreadyReleased)));
     assert_eq!(setup.token.balance(&recipient_a), amount);
     assert_eq!(setup.token.balance(&recipient_b), 0);
 
@@ -93,7 +96,7 @@ fn test_release_race_first_recipient_wins_order_ba() {
     setup.escrow.release_funds(&bounty_id, &recipient_b);
     let second_release = setup.escrow.try_release_funds(&bounty_id, &recipient_a);
 
-    assert_eq!(second_release, Err(Ok(Error::FundsNotLocked)));
+    assert_eq!(second_release, Err(Ok(Error::AlreadyReleased)));
     assert_eq!(setup.token.balance(&recipient_b), amount);
     assert_eq!(setup.token.balance(&recipient_a), 0);
 
@@ -129,7 +132,7 @@ fn test_authorize_claim_race_last_authorization_wins() {
     assert_eq!(setup.token.balance(&setup.escrow.address), 0);
 
     let second_claim = setup.escrow.try_claim(&bounty_id);
-    assert_eq!(second_claim, Err(Ok(Error::FundsNotLocked)));
+    assert_eq!(second_claim, Err(Ok(Error::AlreadyReleased)));
 }
 
 // Auto-refund race: multiple parties try to trigger refund after deadline
@@ -155,7 +158,7 @@ fn test_auto_refund_race_first_caller_wins() {
     setup.escrow.refund(&bounty_id);
     let second_refund = setup.escrow.try_refund(&bounty_id);
 
-    assert_eq!(second_refund, Err(Ok(Error::FundsNotLocked)));
+    assert_eq!(second_refund, Err(Ok(Error::AlreadyRefunded)));
     assert_eq!(setup.token.balance(&setup.depositor), 1_000_000);
     assert_eq!(setup.token.balance(&caller_a), 0);
     assert_eq!(setup.token.balance(&caller_b), 0);
@@ -226,7 +229,7 @@ fn test_batch_release_prevents_double_release() {
     assert_eq!(setup.token.balance(&recipient_b), 20_000);
 
     let second_batch = setup.escrow.try_batch_release_funds(&items);
-    assert_eq!(second_batch, Err(Ok(Error::FundsNotLocked)));
+    assert_eq!(second_batch, Err(Ok(Error::AlreadyReleased)));
 
     assert_eq!(setup.token.balance(&recipient_a), 10_000);
     assert_eq!(setup.token.balance(&recipient_b), 20_000);
@@ -253,7 +256,7 @@ fn test_refund_vs_release_race_first_wins() {
     setup.escrow.refund(&bounty_id);
 
     let release_attempt = setup.escrow.try_release_funds(&bounty_id, &recipient);
-    assert_eq!(release_attempt, Err(Ok(Error::FundsNotLocked)));
+    assert_eq!(release_attempt, Err(Ok(Error::AlreadyRefunded)));
 
     assert_eq!(setup.token.balance(&setup.depositor), 1_000_000);
     assert_eq!(setup.token.balance(&recipient), 0);
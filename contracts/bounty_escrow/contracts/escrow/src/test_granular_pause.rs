@@ -220,7 +220,7 @@ fn test_batch_lock_blocked_when_lock_paused() {
             deadline,
         }
     ];
-    let result = client.try_batch_lock_funds(&items);
+    let result = client.try_batch_lock_funds(&items, &None);
     assert!(result.is_err());
 }
 
@@ -631,7 +631,7 @@ fn test_batch_lock_allowed_when_release_and_refund_paused() {
             deadline,
         }
     ];
-    let count = client.batch_lock_funds(&items);
+    let count = client.batch_lock_funds(&items, &None);
     assert_eq!(count, 1);
 }
 
@@ -929,7 +929,7 @@ fn test_batch_lock_multiple_items_succeeds_when_unpaused() {
             deadline,
         }
     ];
-    let count = client.batch_lock_funds(&items);
+    let count = client.batch_lock_funds(&items, &None);
     assert_eq!(count, 3);
 }
 
@@ -951,7 +951,7 @@ fn test_batch_lock_blocked_even_with_only_lock_paused() {
             deadline,
         }
     ];
-    assert!(client.try_batch_lock_funds(&items).is_err());
+    assert!(client.try_batch_lock_funds(&items, &None).is_err());
 }
 
 #[test]
@@ -134,7 +134,7 @@ fn test_full_bounty_lifecycle_with_refund() {
     escrow_client.approve_refund(&bounty_id, &refund_amount, &depositor, &RefundMode::Partial);
 
     // Verify eligibility
-    let (can_refund, deadline_passed, remaining, approval) =
+    let (can_refund, deadline_passed, remaining, approval, _) =
         escrow_client.get_refund_eligibility(&bounty_id);
     assert!(can_refund);
     assert!(!deadline_passed);
@@ -1,9 +1,7 @@
 use super::*;
 use soroban_sdk::{
-    testutils::Address as _, // Removed unused 'Events'
-    token,
-    Address,
-    Env, // Removed unused 'String' and 'Symbol'
+    testutils::{Address as _, Events},
+    token, Address, Env, IntoVal, String,
 };
 
 fn create_token(
@@ -99,3 +97,105 @@ fn test_tagging_logic_verification() {
     // This test is for future metadata tagging functionality
     // Currently the contract doesn't support metadata/tagging
 }
+
+#[test]
+fn test_escrow_note_set_and_update() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 3600;
+    s.escrow.lock_funds(&s.depositor, &7, &1_000, &dl);
+    assert_eq!(s.escrow.get_escrow(&7).unwrap().note, None);
+
+    let first = String::from_str(&s.env, "criteria: https://example.org/7");
+    s.escrow.set_escrow_note(&7, &first);
+    assert_eq!(s.escrow.get_escrow(&7).unwrap().note, Some(first));
+
+    let second = String::from_str(&s.env, "criteria moved to issue #8");
+    s.escrow.set_escrow_note(&7, &second);
+    let (_, topics, _) = s.env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("note"), 7u64).into_val(&s.env));
+    assert_eq!(s.escrow.get_escrow(&7).unwrap().note, Some(second));
+
+    // Funds are untouched and an empty note clears it
+    s.escrow.set_escrow_note(&7, &String::from_str(&s.env, ""));
+    let record = s.escrow.get_escrow(&7).unwrap();
+    assert_eq!(record.note, None);
+    assert_eq!(record.escrow.remaining_amount, 1_000);
+}
+
+#[test]
+fn test_escrow_note_length_limit() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 3600;
+    s.escrow.lock_funds(&s.depositor, &8, &1_000, &dl);
+
+    let max = String::from_bytes(&s.env, &[b'a'; 256]);
+    s.escrow.set_escrow_note(&8, &max);
+
+    let too_long = String::from_bytes(&s.env, &[b'a'; 257]);
+    assert_eq!(
+        s.escrow
+            .try_set_escrow_note(&8, &too_long)
+            .unwrap_err()
+            .unwrap(),
+        Error::NoteTooLong
+    );
+    assert_eq!(s.escrow.get_escrow(&8).unwrap().note, Some(max));
+}
+
+#[test]
+fn test_escrow_note_requires_depositor() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 3600;
+    s.escrow.lock_funds(&s.depositor, &9, &1_000, &dl);
+    s.env.set_auths(&[]);
+    assert!(s
+        .escrow
+        .try_set_escrow_note(&9, &String::from_str(&s.env, "hi"))
+        .is_err());
+    assert_eq!(
+        s.escrow
+            .try_set_escrow_note(&99, &String::from_str(&s.env, "hi"))
+            .unwrap_err()
+            .unwrap(),
+        Error::BountyNotFound
+    );
+}
+
+#[test]
+fn test_description_hash_set_at_lock_and_updated() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 3600;
+    let original = BytesN::from_array(&s.env, &[1u8; 32]);
+    s.escrow
+        .lock_funds_with_description(&s.depositor, &10, &1_000, &dl, &original);
+    assert_eq!(
+        s.escrow.get_escrow(&10).unwrap().description_hash,
+        Some(original.clone().into())
+    );
+
+    let revised = BytesN::from_array(&s.env, &[2u8; 32]);
+    s.escrow.set_description_hash(&10, &revised);
+    let (_, topics, data) = s.env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("desc_hash"), 10u64).into_val(&s.env));
+    let event: events::DescriptionHashUpdated = data.into_val(&s.env);
+    assert_eq!(event.previous, Some(original.into()));
+    assert_eq!(event.description_hash, revised);
+    assert_eq!(event.depositor, s.depositor);
+    assert_eq!(
+        s.escrow.get_escrow(&10).unwrap().description_hash,
+        Some(revised.into())
+    );
+
+    // Plain locks have no hash, and settled escrows can no longer change it
+    s.escrow.lock_funds(&s.depositor, &11, &1_000, &dl);
+    assert_eq!(s.escrow.get_escrow(&11).unwrap().description_hash, None);
+    let contributor = Address::generate(&s.env);
+    s.escrow.release_funds(&11, &contributor);
+    assert_eq!(
+        s.escrow
+            .try_set_description_hash(&11, &BytesN::from_array(&s.env, &[3u8; 32]))
+            .unwrap_err()
+            .unwrap(),
+        Error::AlreadyReleased
+    );
+}
@@ -241,7 +241,7 @@ fn test_batch_lock_funds_while_paused_fails() {
         }
     ];
 
-    let res = escrow_client.try_batch_lock_funds(&items);
+    let res = escrow_client.try_batch_lock_funds(&items, &None);
     assert!(res.is_err());
 }
 
@@ -354,7 +354,7 @@ fn test_emergency_withdraw_non_admin_fails() {
 
     let target = Address::generate(&env);
     escrow_client.init(&admin, &token_client.address);
-    escrow_client.emergency_withdraw(&target);
+    escrow_client.emergency_withdraw(&target, &None);
 }
 
 #[test]
@@ -368,7 +368,7 @@ fn test_emergency_withdraw_unpaused_fails() {
     let target = Address::generate(&env);
 
     escrow_client.init(&admin, &token_client.address);
-    escrow_client.emergency_withdraw(&target);
+    escrow_client.emergency_withdraw(&target, &None);
 }
 
 #[test]
@@ -392,7 +392,7 @@ fn test_emergency_withdraw_succeeds() {
     let reason = soroban_sdk::String::from_str(&env, "Hacked");
     escrow_client.set_paused(&Some(true), &None, &None, &Some(reason));
 
-    escrow_client.emergency_withdraw(&target);
+    escrow_client.emergency_withdraw(&target, &None);
 
     assert_eq!(token_client.balance(&escrow_client.address), 0);
     assert_eq!(token_client.balance(&target), 500);
@@ -448,7 +448,7 @@ fn test_rbac_admin_can_emergency_withdraw_when_paused() {
 
     assert_eq!(token_client.balance(&escrow_client.address), 500);
 
-    escrow_client.emergency_withdraw(&target);
+    escrow_client.emergency_withdraw(&target, &None);
 
     assert_eq!(token_client.balance(&escrow_client.address), 0);
     assert_eq!(token_client.balance(&target), 500);
@@ -464,7 +464,7 @@ fn test_rbac_operator_cannot_emergency_withdraw() {
     let target = Address::generate(&env);
 
     escrow_client.set_paused(&Some(true), &None, &None, &None);
-    escrow_client.emergency_withdraw(&target);
+    escrow_client.emergency_withdraw(&target, &None);
 }
 
 /// emergency_withdraw FAILS even for admin when contract is NOT paused.
@@ -477,7 +477,7 @@ fn test_rbac_admin_emergency_withdraw_requires_paused_state() {
     let (_, _, _, escrow_client) = setup_rbac_env(&env);
     let target = Address::generate(&env);
 
-    escrow_client.emergency_withdraw(&target);
+    escrow_client.emergency_withdraw(&target, &None);
 }
 
 /// emergency_withdraw emits the correct event with admin address and amount.
@@ -490,10 +490,11 @@ fn test_rbac_emergency_withdraw_emits_event() {
     let target = Address::generate(&env);
 
     escrow_client.set_paused(&Some(true), &None, &None, &None);
-    escrow_client.emergency_withdraw(&target);
+    escrow_client.emergency_withdraw(&target, &None);
 
     let all_events = env.events().all();
     let last_event = all_events.last().unwrap();
+    let emitted: events::EmergencyWithdrawEvent = last_event.2.try_into_val(&env).unwrap();
 
     assert_eq!(
         vec![&env, last_event],
@@ -503,6 +504,7 @@ fn test_rbac_emergency_withdraw_emits_event() {
                 escrow_client.address.clone(),
                 (symbol_short!("em_wtd"),).into_val(&env),
                 events::EmergencyWithdrawEvent {
+                    seq: emitted.seq,
                     admin: admin.clone(),
                     recipient: target.clone(),
                     amount: 500i128,
@@ -524,8 +526,8 @@ fn test_rbac_emergency_withdraw_on_empty_contract_is_safe() {
     let target = Address::generate(&env);
 
     escrow_client.set_paused(&Some(true), &None, &None, &None);
-    escrow_client.emergency_withdraw(&target); // drains 500
-    escrow_client.emergency_withdraw(&target); // balance = 0, should NOT panic
+    escrow_client.emergency_withdraw(&target, &None); // drains 500
+    escrow_client.emergency_withdraw(&target, &None); // balance = 0, should NOT panic
 
     assert_eq!(token_client.balance(&escrow_client.address), 0);
 }
@@ -540,7 +542,7 @@ fn test_rbac_pause_state_preserved_after_emergency_withdraw() {
     let target = Address::generate(&env);
 
     escrow_client.set_paused(&Some(true), &None, &None, &None);
-    escrow_client.emergency_withdraw(&target);
+    escrow_client.emergency_withdraw(&target, &None);
 
     let depositor = Address::generate(&env);
     let deadline = env.ledger().timestamp() + 2000;
@@ -559,7 +561,7 @@ fn test_rbac_emergency_withdraw_requires_lock_paused_not_release_paused() {
     let target = Address::generate(&env);
 
     escrow_client.set_paused(&None, &Some(true), &None, &None);
-    escrow_client.emergency_withdraw(&target);
+    escrow_client.emergency_withdraw(&target, &None);
 }
 
 /// Partial pause: only refund paused — emergency_withdraw still requires lock_paused.
@@ -573,7 +575,7 @@ fn test_rbac_emergency_withdraw_requires_lock_paused_not_refund_paused() {
     let target = Address::generate(&env);
 
     escrow_client.set_paused(&None, &None, &Some(true), &None);
-    escrow_client.emergency_withdraw(&target);
+    escrow_client.emergency_withdraw(&target, &None);
 }
 
 /// Admin withdraws correct amount when multiple bounties are locked.
@@ -607,7 +609,7 @@ fn test_rbac_emergency_withdraw_drains_all_bounties() {
 
     let target = Address::generate(&env);
     escrow_client.set_paused(&Some(true), &None, &None, &None);
-    escrow_client.emergency_withdraw(&target);
+    escrow_client.emergency_withdraw(&target, &None);
 
     assert_eq!(token_client.balance(&escrow_client.address), 0);
     assert_eq!(token_client.balance(&target), 1500);
@@ -623,7 +625,7 @@ fn test_rbac_after_emergency_withdraw_can_unpause_and_reuse() {
     let target = Address::generate(&env);
 
     escrow_client.set_paused(&Some(true), &None, &None, &None);
-    escrow_client.emergency_withdraw(&target);
+    escrow_client.emergency_withdraw(&target, &None);
 
     escrow_client.set_paused(&Some(false), &None, &None, &None);
     let flags = escrow_client.get_pause_flags();
@@ -637,3 +639,97 @@ fn test_rbac_after_emergency_withdraw_can_unpause_and_reuse() {
     escrow_client.lock_funds(&new_depositor, &99u64, &200i128, &deadline);
     assert_eq!(token_client.balance(&escrow_client.address), 200);
 }
+
+#[test]
+fn test_emergency_pause_blocks_mutations_but_not_views() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+    let (escrow_client, _) = create_escrow_contract(&env);
+    escrow_client.init(&admin, &token_client.address);
+    token_admin_client.mint(&depositor, &1000);
+
+    let deadline = env.ledger().timestamp() + 1000;
+    escrow_client.lock_funds(&depositor, &1, &100, &deadline);
+
+    escrow_client.pause(&admin);
+    assert!(escrow_client.is_paused());
+
+    let lock = escrow_client.try_lock_funds(&depositor, &2, &100, &deadline);
+    assert_eq!(lock.unwrap_err().unwrap(), Error::ContractPaused);
+    let release = escrow_client.try_release_funds(&1, &contributor);
+    assert_eq!(release.unwrap_err().unwrap(), Error::ContractPaused);
+    env.ledger().set_timestamp(deadline + 1);
+    let refund = escrow_client.try_refund(&1);
+    assert_eq!(refund.unwrap_err().unwrap(), Error::ContractPaused);
+
+    // Monitoring views remain available while paused
+    assert_eq!(escrow_client.get_aggregate_stats().count_locked, 1);
+    assert_eq!(
+        escrow_client
+            .query_escrows_by_status(&EscrowStatus::Locked, &0, &10)
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn test_locked_escrow_refundable_after_unpause() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+    let (escrow_client, _) = create_escrow_contract(&env);
+    escrow_client.init(&admin, &token_client.address);
+    token_admin_client.mint(&depositor, &1000);
+
+    let deadline = env.ledger().timestamp() + 1000;
+    escrow_client.lock_funds(&depositor, &1, &400, &deadline);
+
+    escrow_client.pause(&admin);
+    env.ledger().set_timestamp(deadline + 1);
+    assert!(escrow_client.try_refund(&1).is_err());
+
+    escrow_client.unpause(&admin);
+    assert!(!escrow_client.is_paused());
+    escrow_client.refund(&1);
+
+    assert_eq!(token_client.balance(&depositor), 1000);
+    assert_eq!(
+        escrow_client.get_escrow_info(&1).status,
+        EscrowStatus::Refunded
+    );
+}
+
+#[test]
+fn test_pause_and_unpause_emit_distinct_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, _) = create_token_contract(&env, &token_admin);
+    let (escrow_client, escrow_address) = create_escrow_contract(&env);
+    escrow_client.init(&admin, &token_client.address);
+
+    escrow_client.pause(&admin);
+    let last = env.events().all().last().unwrap();
+    assert_eq!(last.0, escrow_address);
+    let topic: Symbol = last.1.get(0).unwrap().into_val(&env);
+    assert_eq!(topic, Symbol::new(&env, "paused"));
+
+    escrow_client.unpause(&admin);
+    let last = env.events().all().last().unwrap();
+    let topic: Symbol = last.1.get(0).unwrap().into_val(&env);
+    assert_eq!(topic, Symbol::new(&env, "unpaused"));
+}
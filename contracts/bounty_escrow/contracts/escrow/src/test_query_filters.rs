@@ -502,3 +502,900 @@ fn test_aggregate_stats_amounts_invariant_sum_equals_total_locked() {
     let total = stats.total_locked + stats.total_released + stats.total_refunded;
     assert_eq!(total, 1000);
 }
+
+#[test]
+fn test_query_by_status_sorted_orders_before_paginating() {
+    let s = Setup::new();
+    let now = s.env.ledger().timestamp();
+
+    s.escrow.lock_funds(&s.depositor, &3, &300, &(now + 10));
+    s.escrow.lock_funds(&s.depositor, &1, &500, &(now + 30));
+    s.escrow.lock_funds(&s.depositor, &2, &100, &(now + 20));
+
+    let by_amount = s.escrow.query_escrows_by_status_sorted(
+        &EscrowStatus::Locked,
+        &SortBy::Amount,
+        &SortOrder::Ascending,
+        &0,
+        &10,
+    );
+    assert_eq!(by_amount.get(0).unwrap().bounty_id, 2);
+    assert_eq!(by_amount.get(1).unwrap().bounty_id, 3);
+    assert_eq!(by_amount.get(2).unwrap().bounty_id, 1);
+
+    // Second page of a descending deadline sort
+    let page = s.escrow.query_escrows_by_status_sorted(
+        &EscrowStatus::Locked,
+        &SortBy::Deadline,
+        &SortOrder::Descending,
+        &1,
+        &1,
+    );
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().bounty_id, 2);
+
+    let past_end = s.escrow.query_escrows_by_status_sorted(
+        &EscrowStatus::Locked,
+        &SortBy::BountyId,
+        &SortOrder::Ascending,
+        &5,
+        &10,
+    );
+    assert_eq!(past_end.len(), 0);
+}
+
+#[test]
+fn test_query_by_status_sorted_is_stable_across_uneven_runs() {
+    let s = Setup::new();
+    let now = s.env.ledger().timestamp();
+
+    // Seven escrows (not a power of two) with tied amounts
+    let amounts = [400_i128, 100, 300, 100, 500, 300, 200];
+    for (i, amount) in amounts.iter().enumerate() {
+        s.escrow
+            .lock_funds(&s.depositor, &(10 + i as u64), amount, &(now + 100));
+    }
+
+    let sorted = s.escrow.query_escrows_by_status_sorted(
+        &EscrowStatus::Locked,
+        &SortBy::Amount,
+        &SortOrder::Ascending,
+        &0,
+        &10,
+    );
+    // Ties keep their index order
+    let expected = [11_u64, 13, 16, 12, 15, 10, 14];
+    assert_eq!(sorted.len(), expected.len() as u32);
+    for (i, bounty_id) in expected.iter().enumerate() {
+        assert_eq!(sorted.get(i as u32).unwrap().bounty_id, *bounty_id);
+    }
+
+    let descending = s.escrow.query_escrows_by_status_sorted(
+        &EscrowStatus::Locked,
+        &SortBy::Amount,
+        &SortOrder::Descending,
+        &0,
+        &3,
+    );
+    assert_eq!(descending.get(0).unwrap().bounty_id, 14);
+    assert_eq!(descending.get(1).unwrap().bounty_id, 10);
+    assert_eq!(descending.get(2).unwrap().bounty_id, 12);
+}
+
+#[test]
+fn test_query_escrows_applies_status_amount_and_deadline_together() {
+    let s = Setup::new();
+    let now = s.env.ledger().timestamp();
+
+    s.escrow.lock_funds(&s.depositor, &1, &100, &(now + 100)); // amount too low
+    s.escrow.lock_funds(&s.depositor, &2, &500, &(now + 100)); // match
+    s.escrow.lock_funds(&s.depositor, &3, &500, &(now + 900)); // deadline too late
+    s.escrow.lock_funds(&s.depositor, &4, &600, &(now + 200)); // released below
+    s.escrow.lock_funds(&s.depositor, &5, &700, &(now + 300)); // match
+    s.escrow.release_funds(&4, &s.contributor);
+
+    let filter = EscrowFilter {
+        statuses: vec![&s.env, EscrowStatus::Locked],
+        min_amount: Some(400),
+        max_amount: Some(800),
+        min_deadline: None,
+        max_deadline: Some(now + 500),
+        depositor: None,
+    };
+
+    let results = s.escrow.query_escrows(&filter, &0, &10);
+    assert_eq!(results.len(), 2);
+    assert_eq!(results.get(0).unwrap().bounty_id, 2);
+    assert_eq!(results.get(1).unwrap().bounty_id, 5);
+
+    let second_page = s.escrow.query_escrows(&filter, &1, &10);
+    assert_eq!(second_page.len(), 1);
+    assert_eq!(second_page.get(0).unwrap().bounty_id, 5);
+
+    let unconstrained = EscrowFilter {
+        statuses: Vec::new(&s.env),
+        min_amount: None,
+        max_amount: None,
+        min_deadline: None,
+        max_deadline: None,
+        depositor: Some(s.depositor.clone()),
+    };
+    assert_eq!(s.escrow.query_escrows(&unconstrained, &0, &10).len(), 5);
+}
+
+//  contributor index tests
+
+#[test]
+fn test_query_by_contributor_returns_only_their_releases() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 1000;
+    let other = Address::generate(&s.env);
+
+    s.escrow.lock_funds(&s.depositor, &1, &100, &dl);
+    s.escrow.lock_funds(&s.depositor, &2, &200, &dl);
+    s.escrow.lock_funds(&s.depositor, &3, &300, &dl);
+    s.escrow.lock_funds(&s.depositor, &4, &400, &dl);
+    s.escrow.release_funds(&3, &s.contributor);
+    s.escrow.release_funds(&2, &other);
+    s.escrow.release_funds(&1, &s.contributor);
+
+    let mine = s
+        .escrow
+        .query_escrows_by_contributor(&s.contributor, &0, &10);
+    assert_eq!(mine.len(), 2);
+    assert_eq!(mine.get(0).unwrap().bounty_id, 3);
+    assert_eq!(mine.get(1).unwrap().bounty_id, 1);
+    assert_eq!(
+        mine.get(0).unwrap().released_to,
+        Some(s.contributor.clone())
+    );
+
+    let theirs = s.escrow.query_escrows_by_contributor(&other, &0, &10);
+    assert_eq!(theirs.len(), 1);
+    assert_eq!(theirs.get(0).unwrap().escrow.amount, 200);
+
+    let page = s
+        .escrow
+        .query_escrows_by_contributor(&s.contributor, &1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().bounty_id, 1);
+
+    // Still-locked escrows have no recipient yet
+    assert_eq!(s.escrow.get_escrow(&4).unwrap().released_to, None);
+
+    // Split, partial and dispute payouts index every recipient once
+    s.escrow.lock_funds(&s.depositor, &5, &500, &dl);
+    s.escrow.lock_funds(&s.depositor, &6, &600, &dl);
+    s.escrow.lock_funds(&s.depositor, &7, &700, &dl);
+    s.escrow.release_funds_split(
+        &5,
+        &vec![
+            &s.env,
+            (s.contributor.clone(), 300i128),
+            (other.clone(), 200i128),
+        ],
+    );
+    s.escrow.release_partial(&6, &other, &100);
+    s.escrow.release_partial(&6, &other, &500);
+    let arbiter = Address::generate(&s.env);
+    s.escrow.set_arbiter(&arbiter);
+    s.escrow.raise_dispute(&7, &s.depositor);
+    s.escrow.resolve_dispute(&7, &s.contributor, &400, &arbiter);
+
+    let ids = |contributor: &Address| {
+        let mut ids = Vec::new(&s.env);
+        for record in s
+            .escrow
+            .query_escrows_by_contributor(contributor, &0, &10)
+            .iter()
+        {
+            ids.push_back(record.bounty_id);
+        }
+        ids
+    };
+    assert_eq!(ids(&s.contributor), vec![&s.env, 3u64, 1, 5, 7]);
+    assert_eq!(ids(&other), vec![&s.env, 2u64, 5, 6]);
+    assert_eq!(s.escrow.get_escrow(&6).unwrap().released_to, Some(other));
+}
+
+#[test]
+fn test_query_by_contributor_unknown_address_is_empty() {
+    let s = Setup::new();
+    let stranger = Address::generate(&s.env);
+    assert_eq!(
+        s.escrow
+            .query_escrows_by_contributor(&stranger, &0, &10)
+            .len(),
+        0
+    );
+}
+
+//  extended stats tests
+
+#[test]
+fn test_extended_stats_min_max_average() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 1000;
+
+    s.escrow.lock_funds(&s.depositor, &1, &100, &dl);
+    s.escrow.lock_funds(&s.depositor, &2, &700, &dl);
+    s.escrow.lock_funds(&s.depositor, &3, &250, &dl);
+    s.escrow.release_funds(&2, &s.contributor);
+
+    let stats = s.escrow.get_extended_stats(&100);
+    assert!(stats.complete);
+    assert_eq!(stats.scanned, 3);
+    assert_eq!(stats.total_amount, 1_050);
+    assert_eq!(stats.min_amount, Some(100));
+    assert_eq!(stats.max_amount, Some(700));
+    assert_eq!(stats.average_amount, 350);
+}
+
+#[test]
+fn test_extended_stats_respects_max_scan() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 1000;
+
+    s.escrow.lock_funds(&s.depositor, &1, &100, &dl);
+    s.escrow.lock_funds(&s.depositor, &2, &200, &dl);
+    s.escrow.lock_funds(&s.depositor, &3, &900, &dl);
+
+    let stats = s.escrow.get_extended_stats(&2);
+    assert!(!stats.complete);
+    assert_eq!(stats.scanned, 2);
+    assert_eq!(stats.max_amount, Some(200));
+    assert_eq!(stats.average_amount, 150);
+}
+
+#[test]
+fn test_extended_stats_with_no_escrows() {
+    let s = Setup::new();
+    let stats = s.escrow.get_extended_stats(&100);
+    assert!(stats.complete);
+    assert_eq!(stats.scanned, 0);
+    assert_eq!(stats.min_amount, None);
+    assert_eq!(stats.max_amount, None);
+    assert_eq!(stats.average_amount, 0);
+}
+
+//  tag / label tests
+
+#[test]
+fn test_query_by_tag_returns_labelled_escrows() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 1000;
+    let title = soroban_sdk::String::from_str(&s.env, "Fix flaky CI");
+
+    s.escrow
+        .lock_funds_with_label(&s.depositor, &1, &100, &dl, &symbol_short!("infra"), &title);
+    s.escrow.lock_funds_with_label(
+        &s.depositor,
+        &2,
+        &200,
+        &dl,
+        &symbol_short!("docs"),
+        &soroban_sdk::String::from_str(&s.env, "Write guide"),
+    );
+    s.escrow.lock_funds(&s.depositor, &3, &300, &dl);
+    s.escrow
+        .lock_funds_with_label(&s.depositor, &4, &400, &dl, &symbol_short!("infra"), &title);
+
+    let infra = s
+        .escrow
+        .query_escrows_by_tag(&symbol_short!("infra"), &0, &10);
+    assert_eq!(infra.len(), 2);
+    assert_eq!(infra.get(0).unwrap().bounty_id, 1);
+    assert_eq!(infra.get(1).unwrap().bounty_id, 4);
+    assert_eq!(
+        infra.get(0).unwrap().label_tag,
+        Some(symbol_short!("infra"))
+    );
+    assert_eq!(infra.get(0).unwrap().label_title, Some(title.clone()));
+
+    let page = s
+        .escrow
+        .query_escrows_by_tag(&symbol_short!("infra"), &1, &5);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().escrow.amount, 400);
+
+    // Unlabelled escrows still work and carry no label
+    let unlabelled = s.escrow.get_escrow(&3).unwrap();
+    assert_eq!(unlabelled.label_tag, None);
+    assert_eq!(unlabelled.label_title, None);
+    assert_eq!(
+        s.escrow
+            .query_escrows_by_tag(&symbol_short!("design"), &0, &10)
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_lock_with_label_rejects_long_title() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 1000;
+    let long_title = soroban_sdk::String::from_bytes(&s.env, &[b'a'; 129]);
+
+    let result = s.escrow.try_lock_funds_with_label(
+        &s.depositor,
+        &1,
+        &100,
+        &dl,
+        &symbol_short!("infra"),
+        &long_title,
+    );
+    assert_eq!(result.unwrap_err().unwrap(), Error::LabelTooLong);
+    assert!(s.escrow.get_escrow(&1).is_none());
+}
+
+//  near-deadline tests
+
+#[test]
+fn test_escrows_near_deadline_window_bounds() {
+    let s = Setup::new();
+
+    s.escrow.lock_funds(&s.depositor, &1, &100, &1_000); // deadline == now
+    s.escrow.lock_funds(&s.depositor, &2, &100, &1_050);
+    s.escrow.lock_funds(&s.depositor, &3, &100, &1_100); // exactly now + window
+    s.escrow.lock_funds(&s.depositor, &4, &100, &1_101);
+    s.escrow.lock_funds(&s.depositor, &5, &100, &1_020);
+    s.escrow.release_funds(&5, &s.contributor);
+    s.env.ledger().set_timestamp(1_000);
+
+    let soon = s.escrow.get_escrows_near_deadline(&100, &0, &10);
+    assert_eq!(soon.len(), 2);
+    assert_eq!(soon.get(0).unwrap().bounty_id, 2);
+    assert_eq!(soon.get(1).unwrap().bounty_id, 3);
+
+    let page = s.escrow.get_escrows_near_deadline(&100, &1, &10);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().bounty_id, 3);
+
+    assert_eq!(s.escrow.get_escrows_near_deadline(&0, &0, &10).len(), 0);
+}
+
+//  depositor + status tests
+
+#[test]
+fn test_query_by_depositor_and_status_mixed() {
+    let s = Setup::new();
+    let other = Address::generate(&s.env);
+    s.token_admin.mint(&other, &1_000);
+    let dl = s.env.ledger().timestamp() + 1000;
+
+    s.escrow.lock_funds(&s.depositor, &1, &100, &dl);
+    s.escrow.lock_funds(&s.depositor, &2, &100, &dl);
+    s.escrow.lock_funds(&s.depositor, &3, &100, &dl);
+    s.escrow.lock_funds(&s.depositor, &4, &100, &dl);
+    s.escrow.lock_funds(&other, &5, &100, &dl);
+    s.escrow.release_funds(&2, &s.contributor);
+    s.escrow.release_funds(&4, &s.contributor);
+
+    let locked =
+        s.escrow
+            .query_by_depositor_and_status(&s.depositor, &EscrowStatus::Locked, &0, &10);
+    assert_eq!(locked.len(), 2);
+    assert_eq!(locked.get(0).unwrap().bounty_id, 1);
+    assert_eq!(locked.get(1).unwrap().bounty_id, 3);
+
+    let released =
+        s.escrow
+            .query_by_depositor_and_status(&s.depositor, &EscrowStatus::Released, &0, &10);
+    assert_eq!(released.len(), 2);
+    for i in 0..released.len() {
+        let record = released.get(i).unwrap();
+        assert_eq!(record.escrow.status, EscrowStatus::Released);
+        assert_eq!(record.escrow.depositor, s.depositor);
+    }
+
+    // Offset counts matching escrows only.
+    let page =
+        s.escrow
+            .query_by_depositor_and_status(&s.depositor, &EscrowStatus::Released, &1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().bounty_id, 4);
+
+    assert_eq!(
+        s.escrow
+            .query_by_depositor_and_status(&s.depositor, &EscrowStatus::Refunded, &0, &10)
+            .len(),
+        0
+    );
+}
+
+//  depositor id list tests
+
+#[test]
+fn test_escrow_ids_by_depositor_match_full_query() {
+    let s = Setup::new();
+    let other = Address::generate(&s.env);
+    s.token_admin.mint(&other, &1_000);
+    let dl = s.env.ledger().timestamp() + 1000;
+
+    s.escrow.lock_funds(&s.depositor, &7, &100, &dl);
+    s.escrow.lock_funds(&s.depositor, &3, &100, &dl);
+    s.escrow.lock_funds(&other, &5, &100, &dl);
+    s.escrow.lock_funds(&s.depositor, &9, &100, &dl);
+    s.escrow.lock_funds(&s.depositor, &1, &100, &dl);
+    s.escrow.release_funds(&3, &s.contributor);
+
+    for (offset, limit) in [(0u32, 10u32), (1, 2), (3, 5), (4, 1)] {
+        let records = s
+            .escrow
+            .query_escrows_by_depositor(&s.depositor, &offset, &limit);
+        let mut expected = Vec::new(&s.env);
+        for record in records.iter() {
+            expected.push_back(record.bounty_id);
+        }
+        assert_eq!(
+            s.escrow
+                .get_escrow_ids_by_depositor(&s.depositor, &offset, &limit),
+            expected
+        );
+    }
+    assert_eq!(
+        s.escrow.get_escrow_ids_by_depositor(&s.depositor, &0, &10),
+        vec![&s.env, 7, 3, 9, 1]
+    );
+}
+
+//  count tests
+
+#[test]
+fn test_counts_match_full_scan() {
+    let s = Setup::new();
+    let other = Address::generate(&s.env);
+    s.token_admin.mint(&other, &1_000);
+    let dl = s.env.ledger().timestamp() + 1000;
+
+    for id in 1..=5u64 {
+        s.escrow.lock_funds(&s.depositor, &id, &100, &dl);
+    }
+    s.escrow.lock_funds(&other, &6, &100, &dl);
+    s.escrow.lock_funds(&other, &7, &100, &dl);
+    s.escrow.release_funds(&2, &s.contributor);
+    s.escrow.release_funds(&6, &s.contributor);
+
+    for status in [
+        EscrowStatus::Locked,
+        EscrowStatus::Released,
+        EscrowStatus::Refunded,
+    ] {
+        let all = s.escrow.query_escrows_by_status(&status, &0, &1_000);
+        assert_eq!(s.escrow.count_escrows_by_status(&status), all.len());
+    }
+    assert_eq!(s.escrow.count_escrows_by_status(&EscrowStatus::Locked), 5);
+
+    for depositor in [&s.depositor, &other] {
+        let all = s.escrow.query_escrows_by_depositor(depositor, &0, &1_000);
+        assert_eq!(s.escrow.count_escrows_by_depositor(depositor), all.len());
+    }
+    assert_eq!(s.escrow.count_escrows_by_depositor(&s.depositor), 5);
+    assert_eq!(
+        s.escrow
+            .count_escrows_by_depositor(&Address::generate(&s.env)),
+        0
+    );
+}
+
+//  archival tests
+
+#[test]
+fn test_archive_settled_drops_from_queries_keeps_counts() {
+    let s = Setup::new();
+    s.escrow.lock_funds(&s.depositor, &1, &100, &500);
+    s.escrow.lock_funds(&s.depositor, &2, &200, &500);
+    s.escrow.lock_funds(&s.depositor, &3, &300, &500);
+
+    s.env.ledger().with_mut(|li| {
+        li.sequence_number = 10;
+        li.timestamp = 501;
+    });
+    s.escrow.release_funds(&1, &s.contributor);
+    s.escrow.refund(&2);
+
+    let stats_before = s.escrow.get_aggregate_stats();
+    let count_before = s.escrow.get_escrow_count();
+
+    // Nothing settled before ledger 10 yet.
+    assert_eq!(s.escrow.archive_settled(&10, &10), 0);
+
+    s.env.ledger().with_mut(|li| li.sequence_number = 100);
+    assert_eq!(s.escrow.archive_settled(&50, &10), 2);
+
+    assert_eq!(
+        s.escrow
+            .query_escrows_by_status(&EscrowStatus::Released, &0, &10)
+            .len(),
+        0
+    );
+    assert_eq!(
+        s.escrow
+            .query_escrows_by_status(&EscrowStatus::Refunded, &0, &10)
+            .len(),
+        0
+    );
+    let mine = s.escrow.query_escrows_by_depositor(&s.depositor, &0, &10);
+    assert_eq!(mine.len(), 1);
+    assert_eq!(mine.get(0).unwrap().bounty_id, 3);
+
+    assert_eq!(s.escrow.get_aggregate_stats(), stats_before);
+    assert_eq!(s.escrow.get_escrow_count(), count_before);
+    assert!(s.escrow.get_escrow(&1).is_some());
+    assert_eq!(s.escrow.archive_settled(&50, &10), 0);
+}
+
+//  multi-status tests
+
+#[test]
+fn test_query_by_statuses_returns_paginated_union() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 1000;
+    for id in 1..=5u64 {
+        s.escrow.lock_funds(&s.depositor, &id, &100, &dl);
+    }
+    s.escrow.raise_dispute(&2, &s.depositor);
+    s.escrow.raise_dispute(&4, &s.depositor);
+    s.escrow.release_funds(&3, &s.contributor);
+
+    let in_progress = soroban_sdk::vec![&s.env, EscrowStatus::Locked, EscrowStatus::Disputed];
+    let all = s.escrow.query_escrows_by_statuses(&in_progress, &0, &10);
+    assert_eq!(all.len(), 4);
+    for (i, expected) in [1u64, 2, 4, 5].iter().enumerate() {
+        assert_eq!(all.get(i as u32).unwrap().bounty_id, *expected);
+    }
+
+    // Pages split the union, not each status separately.
+    let first = s.escrow.query_escrows_by_statuses(&in_progress, &0, &2);
+    let second = s.escrow.query_escrows_by_statuses(&in_progress, &2, &2);
+    assert_eq!(first.get(1).unwrap().bounty_id, 2);
+    assert_eq!(second.len(), 2);
+    assert_eq!(second.get(0).unwrap().bounty_id, 4);
+    assert_eq!(second.get(1).unwrap().bounty_id, 5);
+    assert_eq!(
+        s.escrow
+            .query_escrows_by_statuses(&in_progress, &4, &2)
+            .len(),
+        0
+    );
+
+    // An empty status list matches everything.
+    let everything = s
+        .escrow
+        .query_escrows_by_statuses(&soroban_sdk::Vec::new(&s.env), &0, &10);
+    assert_eq!(everything.len(), 5);
+}
+
+//  depositor transfer tests
+
+#[test]
+fn test_transfer_depositor_moves_index_and_refund() {
+    let s = Setup::new();
+    let sponsor = Address::generate(&s.env);
+    s.escrow.lock_funds(&s.depositor, &1, &100, &500);
+    s.escrow.lock_funds(&s.depositor, &2, &200, &500);
+
+    s.escrow.transfer_depositor(&1, &sponsor);
+
+    let old = s.escrow.query_escrows_by_depositor(&s.depositor, &0, &10);
+    assert_eq!(old.len(), 1);
+    assert_eq!(old.get(0).unwrap().bounty_id, 2);
+    let new = s.escrow.query_escrows_by_depositor(&sponsor, &0, &10);
+    assert_eq!(new.len(), 1);
+    assert_eq!(new.get(0).unwrap().bounty_id, 1);
+    assert_eq!(new.get(0).unwrap().escrow.depositor, sponsor);
+    assert_eq!(s.escrow.get_depositor_stats(&sponsor).total_locked, 100);
+    assert_eq!(s.escrow.get_depositor_stats(&s.depositor).total_locked, 200);
+
+    s.env.ledger().set_timestamp(501);
+    s.escrow.refund(&1);
+    assert_eq!(s._token.balance(&sponsor), 100);
+}
+
+//  full export tests
+
+#[test]
+fn test_get_all_escrows_traversal_is_ordered_and_complete() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 1000;
+    let ids = [5u64, 1, 9, 3, 7, 2, 8];
+    for id in ids {
+        s.escrow.lock_funds(&s.depositor, &id, &100, &dl);
+    }
+    s.escrow.release_funds(&9, &s.contributor);
+    s.escrow.raise_dispute(&3, &s.depositor);
+
+    let mut seen = soroban_sdk::Vec::<u64>::new(&s.env);
+    let mut offset = 0u32;
+    loop {
+        let page = s.escrow.get_all_escrows(&offset, &3);
+        if page.is_empty() {
+            break;
+        }
+        for record in page.iter() {
+            seen.push_back(record.bounty_id);
+        }
+        offset += page.len();
+    }
+
+    // Lock order, each escrow exactly once
+    assert_eq!(seen, soroban_sdk::vec![&s.env, 5u64, 1, 9, 3, 7, 2, 8]);
+    assert_eq!(s.escrow.get_all_escrows(&7, &3).len(), 0);
+    assert_eq!(s.escrow.get_all_escrows(&100, &3).len(), 0);
+}
+
+#[test]
+fn test_get_all_escrows_includes_archived() {
+    let s = Setup::new();
+    s.escrow.lock_funds(&s.depositor, &2, &100, &500);
+    s.escrow.lock_funds(&s.depositor, &1, &100, &500);
+    s.escrow.release_funds(&2, &s.contributor);
+    s.env.ledger().with_mut(|li| li.sequence_number = 100);
+    assert_eq!(s.escrow.archive_settled(&50, &10), 1);
+
+    s.escrow.lock_funds(&s.depositor, &3, &100, &500);
+
+    // Archived escrows come first, then live ones in lock order
+    let all = s.escrow.get_all_escrows(&0, &10);
+    assert_eq!(all.len(), 3);
+    assert_eq!(all.get(0).unwrap().bounty_id, 2);
+    assert_eq!(all.get(1).unwrap().bounty_id, 1);
+    assert_eq!(all.get(2).unwrap().bounty_id, 3);
+
+    // A page spanning both indexes
+    let page = s.escrow.get_all_escrows(&0, &2);
+    assert_eq!(page.get(1).unwrap().bounty_id, 1);
+    let tail = s.escrow.get_all_escrows(&1, &5);
+    assert_eq!(tail.len(), 2);
+    assert_eq!(tail.get(0).unwrap().bounty_id, 1);
+}
+
+//  incremental status counter tests
+
+#[test]
+fn test_status_counters_agree_with_status_queries() {
+    let s = Setup::new();
+    let dl = 500;
+    for id in 1u64..=6 {
+        s.escrow.lock_funds(&s.depositor, &id, &100, &dl);
+    }
+    s.escrow.release_funds(&1, &s.contributor);
+    s.escrow.partial_release(&2, &s.contributor, &40);
+    s.escrow.raise_dispute(&3, &s.depositor);
+    s.escrow.authorize_claim(&4, &s.contributor);
+
+    let statuses = [
+        EscrowStatus::Locked,
+        EscrowStatus::Released,
+        EscrowStatus::Refunded,
+        EscrowStatus::PartiallyRefunded,
+        EscrowStatus::Disputed,
+        EscrowStatus::Claimable,
+    ];
+    let check = |expected: [u32; 6]| {
+        for (status, want) in statuses.iter().zip(expected) {
+            let counted = s.escrow.get_escrow_count_by_status(status);
+            assert_eq!(counted, want, "{:?}", status);
+            assert_eq!(
+                s.escrow.query_escrows_by_status(status, &0, &100).len(),
+                counted
+            );
+        }
+    };
+    check([3, 1, 0, 0, 1, 1]);
+
+    // Claiming moves the escrow out of its interim state
+    s.escrow.claim(&4);
+    check([3, 2, 0, 0, 1, 0]);
+
+    s.env.ledger().set_timestamp(dl + 1);
+    s.escrow.refund(&5);
+    check([2, 2, 1, 0, 1, 0]);
+
+    // Archived escrows leave both the index and the counters
+    s.env.ledger().with_mut(|li| li.sequence_number = 100);
+    s.escrow.archive_settled(&50, &10);
+    check([2, 0, 0, 0, 1, 0]);
+}
+
+//  deadline + status filter tests
+
+#[test]
+fn test_query_by_deadline_and_status_skips_settled() {
+    let s = Setup::new();
+    s.escrow.lock_funds(&s.depositor, &1, &100, &500);
+    s.escrow.lock_funds(&s.depositor, &2, &100, &600);
+    s.escrow.lock_funds(&s.depositor, &3, &100, &700);
+    s.escrow.lock_funds(&s.depositor, &4, &100, &800);
+    s.escrow.lock_funds(&s.depositor, &5, &100, &2_000);
+    s.escrow.release_funds(&2, &s.contributor);
+    s.escrow.release_funds(&3, &s.contributor);
+
+    // A pure deadline query also returns the released escrows
+    assert_eq!(
+        s.escrow
+            .query_escrows_by_deadline(&500, &800, &0, &10)
+            .len(),
+        4
+    );
+
+    let locked = s
+        .escrow
+        .query_by_deadline_and_status(&500, &800, &EscrowStatus::Locked, &0, &10);
+    assert_eq!(locked.len(), 2);
+    assert_eq!(locked.get(0).unwrap().bounty_id, 1);
+    assert_eq!(locked.get(1).unwrap().bounty_id, 4);
+
+    // Pagination runs over the intersection
+    let page = s
+        .escrow
+        .query_by_deadline_and_status(&500, &800, &EscrowStatus::Locked, &1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().bounty_id, 4);
+
+    let released =
+        s.escrow
+            .query_by_deadline_and_status(&550, &2_000, &EscrowStatus::Released, &0, &10);
+    assert_eq!(released.len(), 2);
+    assert_eq!(released.get(0).unwrap().bounty_id, 2);
+    assert_eq!(released.get(1).unwrap().bounty_id, 3);
+}
+
+//  amount + status filter tests
+
+#[test]
+fn test_query_by_amount_and_status_skips_settled() {
+    let s = Setup::new();
+    s.escrow.lock_funds(&s.depositor, &1, &500, &1_000);
+    s.escrow.lock_funds(&s.depositor, &2, &5_000, &1_000);
+    s.escrow.lock_funds(&s.depositor, &3, &7_000, &1_000);
+    s.escrow.lock_funds(&s.depositor, &4, &9_000, &1_000);
+    s.escrow.lock_funds(&s.depositor, &5, &20_000, &1_000);
+    s.escrow.release_funds(&3, &s.contributor);
+    s.escrow.release_funds(&5, &s.contributor);
+
+    // A pure amount query also returns the released escrow
+    assert_eq!(
+        s.escrow
+            .query_escrows_by_amount(&5_000, &10_000, &0, &10)
+            .len(),
+        3
+    );
+
+    let locked =
+        s.escrow
+            .query_by_amount_and_status(&5_000, &10_000, &EscrowStatus::Locked, &0, &10);
+    assert_eq!(locked.len(), 2);
+    assert_eq!(locked.get(0).unwrap().bounty_id, 2);
+    assert_eq!(locked.get(1).unwrap().bounty_id, 4);
+
+    // Pagination runs over the intersection
+    let page = s
+        .escrow
+        .query_by_amount_and_status(&5_000, &10_000, &EscrowStatus::Locked, &1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().bounty_id, 4);
+
+    // An overlapping range picks up a different released escrow
+    let released =
+        s.escrow
+            .query_by_amount_and_status(&6_000, &50_000, &EscrowStatus::Released, &0, &10);
+    assert_eq!(released.len(), 2);
+    assert_eq!(released.get(0).unwrap().bounty_id, 3);
+    assert_eq!(released.get(1).unwrap().bounty_id, 5);
+}
+
+//  cursor pagination tests
+
+#[test]
+fn test_status_cursor_pages_without_gaps_or_duplicates() {
+    let s = Setup::new();
+    for bounty_id in [50u64, 10, 40, 20, 30] {
+        s.escrow.lock_funds(&s.depositor, &bounty_id, &100, &1_000);
+    }
+    s.escrow.release_funds(&30, &s.contributor);
+
+    let ids = |page: &EscrowCursorPage| -> Vec<u64> {
+        let mut ids = Vec::new(&s.env);
+        for record in page.records.iter() {
+            ids.push_back(record.bounty_id);
+        }
+        ids
+    };
+
+    let first = s
+        .escrow
+        .query_escrows_by_status_cursor(&EscrowStatus::Locked, &None, &2);
+    assert_eq!(ids(&first), vec![&s.env, 10, 20]);
+    assert_eq!(first.next_cursor, Some(20));
+
+    // New escrows on either side of the cursor mid-iteration
+    s.escrow.lock_funds(&s.depositor, &15, &100, &1_000);
+    s.escrow.lock_funds(&s.depositor, &35, &100, &1_000);
+
+    let second =
+        s.escrow
+            .query_escrows_by_status_cursor(&EscrowStatus::Locked, &first.next_cursor, &2);
+    assert_eq!(ids(&second), vec![&s.env, 35, 40]);
+    assert_eq!(second.next_cursor, Some(40));
+
+    let last =
+        s.escrow
+            .query_escrows_by_status_cursor(&EscrowStatus::Locked, &second.next_cursor, &2);
+    assert_eq!(ids(&last), vec![&s.env, 50]);
+    assert_eq!(last.next_cursor, None);
+
+    let released = s
+        .escrow
+        .query_escrows_by_status_cursor(&EscrowStatus::Released, &None, &10);
+    assert_eq!(ids(&released), vec![&s.env, 30]);
+    assert_eq!(released.next_cursor, None);
+}
+
+//  oldest locked escrow tests
+
+#[test]
+fn test_get_oldest_locked_escrow_tracks_earliest_deadline() {
+    let s = Setup::new();
+    assert_eq!(s.escrow.get_oldest_locked_escrow(), None);
+
+    s.escrow.lock_funds(&s.depositor, &1, &100, &900);
+    s.escrow.lock_funds(&s.depositor, &2, &200, &300);
+    s.escrow.lock_funds(&s.depositor, &3, &300, &600);
+    s.escrow.lock_funds(&s.depositor, &4, &400, &300);
+
+    let oldest = s.escrow.get_oldest_locked_escrow().unwrap();
+    assert_eq!(oldest.bounty_id, 2);
+    assert_eq!(oldest.escrow.deadline, 300);
+
+    s.escrow.release_funds(&2, &s.contributor);
+    assert_eq!(s.escrow.get_oldest_locked_escrow().unwrap().bounty_id, 4);
+
+    s.escrow.release_funds(&4, &s.contributor);
+    assert_eq!(s.escrow.get_oldest_locked_escrow().unwrap().bounty_id, 3);
+
+    s.escrow.release_funds(&1, &s.contributor);
+    s.escrow.release_funds(&3, &s.contributor);
+    assert_eq!(s.escrow.get_oldest_locked_escrow(), None);
+}
+
+//  refund eligibility filter tests
+
+#[test]
+fn test_query_refund_eligible_matches_eligibility_view() {
+    let s = Setup::new();
+    s.escrow.set_refund_grace_period(&100);
+
+    s.escrow.lock_funds(&s.depositor, &1, &100, &500);
+    s.escrow.lock_funds(&s.depositor, &2, &200, &5_000);
+    s.escrow.lock_funds(&s.depositor, &3, &300, &500);
+    s.escrow.lock_funds(&s.depositor, &4, &400, &500);
+    s.escrow
+        .lock_funds_with_refund_policy(&s.depositor, &5, &500, &500, &RefundPolicy::None);
+    s.escrow.lock_funds(&s.depositor, &6, &600, &300);
+    s.escrow.release_funds(&3, &s.contributor);
+
+    // 1 and 4 are past their deadline but still inside the grace period.
+    s.env.ledger().set_timestamp(550);
+    let eligible = s.escrow.query_refund_eligible(&0, &10);
+    assert_eq!(eligible.len(), 1);
+    assert_eq!(eligible.get(0).unwrap().bounty_id, 6);
+
+    s.escrow.refund(&6);
+    s.env.ledger().set_timestamp(700);
+    let eligible = s.escrow.query_refund_eligible(&0, &10);
+    let mut ids = Vec::new(&s.env);
+    for record in eligible.iter() {
+        ids.push_back(record.bounty_id);
+    }
+    assert_eq!(ids, vec![&s.env, 1, 4]);
+    for bounty_id in 1..=6u64 {
+        let (can_refund, _, _, _, _) = s.escrow.get_refund_eligibility(&bounty_id);
+        assert_eq!(can_refund, ids.contains(bounty_id));
+    }
+
+    let second_page = s.escrow.query_refund_eligible(&1, &1);
+    assert_eq!(second_page.len(), 1);
+    assert_eq!(second_page.get(0).unwrap().bounty_id, 4);
+    assert_eq!(s.escrow.query_refund_eligible(&2, &10).len(), 0);
+}
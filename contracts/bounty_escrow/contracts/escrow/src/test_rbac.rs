@@ -199,3 +199,105 @@ fn test_random_cannot_lock_funds_for_depositor() {
     // This should panic: no auth mocked, depositor.require_auth() will fail
     client2.lock_funds(&depositor2, &1u64, &1000i128, &3600);
 }
+
+// ─────────────────────────────────────────────────────────
+// Role Grants (Pauser / FeeManager / Arbiter)
+// ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_pauser_role_gates_pause() {
+    let setup = RbacSetup::new();
+
+    let result = setup.client.try_pause(&setup.random);
+    assert_eq!(result.unwrap_err().unwrap(), Error::Unauthorized);
+
+    setup.client.grant_role(&Role::Pauser, &setup.random);
+    assert!(setup.client.has_role(&Role::Pauser, &setup.random));
+
+    setup.client.pause(&setup.random);
+    assert!(setup.client.is_paused());
+    setup.client.unpause(&setup.random);
+    assert!(!setup.client.is_paused());
+}
+
+#[test]
+fn test_fee_manager_role_gates_fee_setters() {
+    let setup = RbacSetup::new();
+
+    let result = setup.client.try_set_fee_bps(&100, &setup.random);
+    assert_eq!(result.unwrap_err().unwrap(), Error::Unauthorized);
+    let result = setup
+        .client
+        .try_set_fee_collector(&setup.random, &setup.random);
+    assert_eq!(result.unwrap_err().unwrap(), Error::Unauthorized);
+
+    setup.client.grant_role(&Role::FeeManager, &setup.random);
+    setup.client.set_fee_bps(&100, &setup.random);
+    setup.client.set_fee_collector(&setup.random, &setup.random);
+
+    let config = setup.client.get_fee_config();
+    assert_eq!(config.release_fee_rate, 100);
+    assert_eq!(config.fee_recipient, setup.random);
+}
+
+#[test]
+fn test_arbiter_role_gates_resolve_dispute() {
+    let setup = RbacSetup::new();
+    let sac_client = token::StellarAssetClient::new(&setup.env, &setup.token_id);
+    sac_client.mint(&setup.depositor, &1_000);
+    let deadline = setup.env.ledger().timestamp() + 3600;
+    setup
+        .client
+        .lock_funds(&setup.depositor, &1, &1_000, &deadline);
+    setup.client.raise_dispute(&1, &setup.depositor);
+
+    let result = setup
+        .client
+        .try_resolve_dispute(&1, &setup.depositor, &0, &setup.random);
+    assert_eq!(result.unwrap_err().unwrap(), Error::Unauthorized);
+
+    setup.client.grant_role(&Role::Arbiter, &setup.random);
+    setup
+        .client
+        .resolve_dispute(&1, &setup.depositor, &0, &setup.random);
+    assert_eq!(
+        setup.client.get_escrow_info(&1).status,
+        EscrowStatus::Refunded
+    );
+}
+
+#[test]
+fn test_revoked_role_is_rejected() {
+    let setup = RbacSetup::new();
+
+    setup.client.grant_role(&Role::Pauser, &setup.random);
+    setup.client.revoke_role(&Role::Pauser, &setup.random);
+    assert!(!setup.client.has_role(&Role::Pauser, &setup.random));
+
+    let result = setup.client.try_pause(&setup.random);
+    assert_eq!(result.unwrap_err().unwrap(), Error::Unauthorized);
+}
+
+#[test]
+fn test_admin_implicitly_holds_every_role() {
+    let setup = RbacSetup::new();
+
+    assert!(setup.client.has_role(&Role::Pauser, &setup.admin));
+    assert!(setup.client.has_role(&Role::FeeManager, &setup.admin));
+    assert!(setup.client.has_role(&Role::Arbiter, &setup.admin));
+    assert!(!setup.client.has_role(&Role::Pauser, &setup.random));
+
+    setup.client.pause(&setup.admin);
+    assert!(setup.client.is_paused());
+}
+
+#[test]
+fn test_grant_role_requires_admin_auth() {
+    let setup = RbacSetup::new();
+
+    // Drop the blanket auth mock so admin.require_auth() is enforced
+    setup.env.set_auths(&[]);
+    let result = setup.client.try_grant_role(&Role::Pauser, &setup.random);
+    assert!(result.is_err());
+    assert!(!setup.client.has_role(&Role::Pauser, &setup.random));
+}
@@ -174,7 +174,7 @@ fn test_partially_refunded_to_refunded() {
 
 // Invalid transition: Released → Locked
 #[test]
-#[should_panic(expected = "Error(Contract, #3)")]
+#[should_panic(expected = "Error(Contract, #12)")]
 fn test_released_to_locked_fails() {
     let setup = TestSetup::new();
     let bounty_id = 1;
@@ -193,7 +193,7 @@ fn test_released_to_locked_fails() {
 
 // Invalid transition: Released → Released
 #[test]
-#[should_panic(expected = "Error(Contract, #5)")]
+#[should_panic(expected = "Error(Contract, #40)")]
 fn test_released_to_released_fails() {
     let setup = TestSetup::new();
     let bounty_id = 1;
@@ -210,7 +210,7 @@ fn test_released_to_released_fails() {
 
 // Invalid transition: Released → Refunded
 #[test]
-#[should_panic(expected = "Error(Contract, #5)")]
+#[should_panic(expected = "Error(Contract, #40)")]
 fn test_released_to_refunded_fails() {
     let setup = TestSetup::new();
     let bounty_id = 1;
@@ -231,7 +231,7 @@ fn test_released_to_refunded_fails() {
 
 // Invalid transition: Released → PartiallyRefunded
 #[test]
-#[should_panic(expected = "Error(Contract, #5)")]
+#[should_panic(expected = "Error(Contract, #40)")]
 fn test_released_to_partially_refunded_fails() {
     let setup = TestSetup::new();
     let bounty_id = 1;
@@ -254,7 +254,7 @@ fn test_released_to_partially_refunded_fails() {
 
 // Invalid transition: Refunded → Locked
 #[test]
-#[should_panic(expected = "Error(Contract, #3)")]
+#[should_panic(expected = "Error(Contract, #12)")]
 fn test_refunded_to_locked_fails() {
     let setup = TestSetup::new();
     let bounty_id = 1;
@@ -277,7 +277,7 @@ fn test_refunded_to_locked_fails() {
 
 // Invalid transition: Refunded → Released
 #[test]
-#[should_panic(expected = "Error(Contract, #5)")]
+#[should_panic(expected = "Error(Contract, #41)")]
 fn test_refunded_to_released_fails() {
     let setup = TestSetup::new();
     let bounty_id = 1;
@@ -298,7 +298,7 @@ fn test_refunded_to_released_fails() {
 
 // Invalid transition: Refunded → Refunded
 #[test]
-#[should_panic(expected = "Error(Contract, #5)")]
+#[should_panic(expected = "Error(Contract, #41)")]
 fn test_refunded_to_refunded_fails() {
     let setup = TestSetup::new();
     let bounty_id = 1;
@@ -319,7 +319,7 @@ fn test_refunded_to_refunded_fails() {
 
 // Invalid transition: Refunded → PartiallyRefunded
 #[test]
-#[should_panic(expected = "Error(Contract, #5)")]
+#[should_panic(expected = "Error(Contract, #41)")]
 fn test_refunded_to_partially_refunded_fails() {
     let setup = TestSetup::new();
     let bounty_id = 1;
@@ -342,7 +342,7 @@ fn test_refunded_to_partially_refunded_fails() {
 
 // Invalid transition: PartiallyRefunded → Locked
 #[test]
-#[should_panic(expected = "Error(Contract, #3)")]
+#[should_panic(expected = "Error(Contract, #12)")]
 fn test_partially_refunded_to_locked_fails() {
     let setup = TestSetup::new();
     let bounty_id = 1;
@@ -381,3 +381,110 @@ fn test_partially_refunded_to_released_fails() {
 
     setup.escrow.release_funds(&bounty_id, &setup.contributor);
 }
+
+// Each lifecycle error returned by the central status check
+#[test]
+fn test_lifecycle_errors_are_specific() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1, &1000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2, &1000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &3, &1000, &deadline);
+
+    // Refund before the deadline
+    assert_eq!(
+        setup.escrow.try_refund(&1).unwrap_err().unwrap(),
+        Error::DeadlineNotPassed
+    );
+
+    setup.escrow.release_funds(&1, &setup.contributor);
+    setup
+        .escrow
+        .approve_refund(&3, &500, &setup.depositor, &RefundMode::Partial);
+    setup.escrow.refund(&3);
+    setup
+        .env
+        .ledger()
+        .with_mut(|li| li.timestamp = deadline + 1);
+    setup.escrow.refund(&2);
+
+    assert_eq!(
+        setup.escrow.try_refund(&1).unwrap_err().unwrap(),
+        Error::AlreadyReleased
+    );
+    assert_eq!(
+        setup
+            .escrow
+            .try_release_funds(&2, &setup.contributor)
+            .unwrap_err()
+            .unwrap(),
+        Error::AlreadyRefunded
+    );
+    assert_eq!(
+        setup
+            .escrow
+            .try_top_up(&2, &100, &None)
+            .unwrap_err()
+            .unwrap(),
+        Error::AlreadyRefunded
+    );
+    // Partially refunded escrows are neither locked nor settled
+    assert_eq!(
+        setup
+            .escrow
+            .try_release_funds(&3, &setup.contributor)
+            .unwrap_err()
+            .unwrap(),
+        Error::FundsNotLocked
+    );
+}
+
+// A settled bounty_id can never be locked again, whichever entry point is used
+#[test]
+fn test_relock_after_settlement_is_rejected() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1, &1000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2, &1000, &deadline);
+    setup.escrow.release_funds(&1, &setup.contributor);
+    setup
+        .env
+        .ledger()
+        .with_mut(|li| li.timestamp = deadline + 1);
+    setup.escrow.refund(&2);
+
+    let new_deadline = deadline + 1000;
+    for bounty_id in [1u64, 2u64] {
+        let relock = setup
+            .escrow
+            .try_lock_funds(&setup.depositor, &bounty_id, &500, &new_deadline);
+        assert_eq!(relock.unwrap_err().unwrap(), Error::DuplicateBountyId);
+    }
+    let batch = setup.escrow.try_lock_funds_batch(
+        &setup.depositor,
+        &soroban_sdk::vec![&setup.env, (1u64, 500i128, new_deadline)],
+        &None,
+    );
+    assert_eq!(batch.unwrap_err().unwrap(), Error::DuplicateBountyId);
+
+    // The settled records are untouched
+    let released = setup.escrow.get_escrow_info(&1);
+    assert_eq!(released.status, EscrowStatus::Released);
+    assert_eq!(released.amount, 1000);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&2).status,
+        EscrowStatus::Refunded
+    );
+}
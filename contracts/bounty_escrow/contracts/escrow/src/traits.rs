@@ -1,7 +1,8 @@
-use soroban_sdk::{Address, Env, String};
+use soroban_sdk::{contractclient, Address, Env, String};
 
 /// Shared interface for escrow functionality
 /// Both bounty_escrow and program-escrow should implement this
+#[allow(dead_code)]
 pub trait EscrowInterface {
     /// Lock funds for a bounty
     fn lock_funds(
@@ -26,6 +27,7 @@ pub trait EscrowInterface {
 }
 
 /// Shared interface for contract upgrades
+#[allow(dead_code)]
 pub trait UpgradeInterface {
     /// Get contract version
     fn get_version(env: &Env) -> u32;
@@ -33,3 +35,21 @@ pub trait UpgradeInterface {
     /// Set contract version
     fn set_version(env: &Env, new_version: u32) -> Result<(), String>;
 }
+
+/// Interface a release condition contract must implement. Escrows locked
+/// with `lock_funds_with_condition` only release while it returns `true`.
+#[contractclient(name = "ReleaseConditionClient")]
+#[allow(dead_code)]
+pub trait ReleaseCondition {
+    /// Whether the condition guarding `bounty_id` is currently met
+    fn check_condition(env: Env, bounty_id: u64) -> bool;
+}
+
+/// Interface an integration contract implements to hear about payouts, e.g.
+/// to update contributor reputation. Registered with `set_notifier`.
+#[contractclient(name = "ReleaseNotifierClient")]
+#[allow(dead_code)]
+pub trait ReleaseNotifier {
+    /// Called after `amount` of `bounty_id` was paid to `contributor`
+    fn on_release(env: Env, bounty_id: u64, contributor: Address, amount: i128);
+}